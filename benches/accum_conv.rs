@@ -30,6 +30,8 @@ struct MyCircuit {
     image: ValTensor<Fr>,
     kernel: Tensor<Fr>,
     bias: Tensor<Fr>,
+    // lower the convolution to im2col + matmul rather than a dot product per output
+    im2col: bool,
 }
 
 impl Circuit<Fr> for MyCircuit {
@@ -62,17 +64,23 @@ impl Circuit<Fr> for MyCircuit {
             || "",
             |region| {
                 let mut region = region::RegionCtx::new(region, 0, 1);
+                let op = if self.im2col {
+                    PolyOp::Im2ColConv {
+                        kernel: self.kernel.clone(),
+                        bias: Some(self.bias.clone()),
+                        padding: [(0, 0); 2],
+                        stride: (1, 1),
+                    }
+                } else {
+                    PolyOp::Conv {
+                        kernel: self.kernel.clone(),
+                        bias: Some(self.bias.clone()),
+                        padding: [(0, 0); 2],
+                        stride: (1, 1),
+                    }
+                };
                 config
-                    .layout(
-                        &mut region,
-                        &[self.image.clone()],
-                        Box::new(PolyOp::Conv {
-                            kernel: self.kernel.clone(),
-                            bias: Some(self.bias.clone()),
-                            padding: [(0, 0); 2],
-                            stride: (1, 1),
-                        }),
-                    )
+                    .layout(&mut region, &[self.image.clone()], Box::new(op))
                     .unwrap();
                 Ok(())
             },
@@ -110,39 +118,54 @@ fn runcnvrl(c: &mut Criterion) {
             let mut bias = Tensor::from((0..{ OUT_CHANNELS }).map(|_| Fr::random(OsRng)));
             bias.set_visibility(&ezkl::graph::Visibility::Private);
 
-            let circuit = MyCircuit {
-                image: ValTensor::from(image),
-                kernel,
-                bias,
-            };
-
-            group.throughput(Throughput::Elements(*size as u64));
-            group.bench_with_input(BenchmarkId::new("pk", size), &size, |b, &_| {
-                b.iter(|| {
+            for im2col in [false, true] {
+                let circuit = MyCircuit {
+                    image: ValTensor::from(image.clone()),
+                    kernel: kernel.clone(),
+                    bias: bias.clone(),
+                    im2col,
+                };
+                let strategy = if im2col { "im2col" } else { "direct" };
+
+                group.throughput(Throughput::Elements(*size as u64));
+                group.bench_with_input(
+                    BenchmarkId::new(format!("pk_{}", strategy), size),
+                    &size,
+                    |b, &_| {
+                        b.iter(|| {
+                            create_keys::<KZGCommitmentScheme<Bn256>, Fr, MyCircuit>(
+                                &circuit, &params,
+                            )
+                            .unwrap();
+                        });
+                    },
+                );
+
+                let pk =
                     create_keys::<KZGCommitmentScheme<Bn256>, Fr, MyCircuit>(&circuit, &params)
                         .unwrap();
-                });
-            });
-
-            let pk = create_keys::<KZGCommitmentScheme<Bn256>, Fr, MyCircuit>(&circuit, &params)
-                .unwrap();
-
-            group.throughput(Throughput::Elements(*size as u64));
-            group.bench_with_input(BenchmarkId::new("prove", size), &size, |b, &_| {
-                b.iter(|| {
-                    let prover = create_proof_circuit_kzg(
-                        circuit.clone(),
-                        &params,
-                        None,
-                        &pk,
-                        TranscriptType::EVM,
-                        SingleStrategy::new(&params),
-                        CheckMode::UNSAFE,
-                        None,
-                    );
-                    prover.unwrap();
-                });
-            });
+
+                group.throughput(Throughput::Elements(*size as u64));
+                group.bench_with_input(
+                    BenchmarkId::new(format!("prove_{}", strategy), size),
+                    &size,
+                    |b, &_| {
+                        b.iter(|| {
+                            let prover = create_proof_circuit_kzg(
+                                circuit.clone(),
+                                &params,
+                                None,
+                                &pk,
+                                TranscriptType::EVM,
+                                SingleStrategy::new(&params),
+                                CheckMode::UNSAFE,
+                                None,
+                            );
+                            prover.unwrap();
+                        });
+                    },
+                );
+            }
         }
     }
     group.finish();
@@ -0,0 +1,259 @@
+//! Records warm-start keygen and proving times, and their peak memory, for a set of small example
+//! circuits into a json baseline, and (optionally) compares them against a previously recorded
+//! baseline. The timings are measured and reported by criterion.
+//!
+//! ```sh
+//! # record a baseline
+//! cargo bench --bench proving_baseline
+//! # compare against it, failing if any timing regressed by more than 10%
+//! EZKL_BASELINE=baseline.json EZKL_REGRESSION_THRESHOLD=10 cargo bench --bench proving_baseline
+//! ```
+//!
+//! The comparison is skipped if the baseline file doesn't exist, eg. when the baseline commit
+//! predates this bench.
+use criterion::{BenchmarkId, Criterion};
+use ezkl::circuit::poly::PolyOp;
+use ezkl::circuit::*;
+use ezkl::pfsys::create_proof_circuit_kzg;
+use ezkl::pfsys::memory;
+use ezkl::pfsys::TranscriptType;
+use ezkl::pfsys::{create_keys, srs::gen_srs};
+use ezkl::tensor::*;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use halo2_proofs::poly::kzg::strategy::SingleStrategy;
+use halo2_proofs::{
+    arithmetic::Field,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, ConstraintSystem, Error, ProvingKey},
+};
+use halo2curves::bn256::{Bn256, Fr, G1Affine};
+use rand::rngs::OsRng;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static mut LEN: usize = 4;
+const K: usize = 12;
+const DEFAULT_OUTPUT: &str = "target/proving_baseline.json";
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+#[derive(Clone)]
+struct MyCircuit {
+    inputs: [ValTensor<Fr>; 2],
+    equation: String,
+}
+
+impl Circuit<Fr> for MyCircuit {
+    type Config = BaseConfig<Fr>;
+    type FloorPlanner = SimpleFloorPlanner;
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let len = unsafe { LEN };
+
+        let a = VarTensor::new_advice(cs, K, 1, len * len);
+        let b = VarTensor::new_advice(cs, K, 1, len * len);
+        let output = VarTensor::new_advice(cs, K, 1, (len + 1) * len);
+
+        Self::Config::configure(cs, &[a, b], &output, CheckMode::UNSAFE)
+    }
+
+    fn synthesize(
+        &self,
+        mut config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "",
+            |region| {
+                let mut region = region::RegionCtx::new(region, 0, 1);
+                config
+                    .layout(
+                        &mut region,
+                        &self.inputs,
+                        Box::new(PolyOp::Einsum {
+                            equation: self.equation.clone(),
+                        }),
+                    )
+                    .unwrap();
+                Ok(())
+            },
+        )?;
+        Ok(())
+    }
+}
+
+fn random_tensor(dims: &[usize]) -> ValTensor<Fr> {
+    let mut t =
+        Tensor::from((0..dims.iter().product::<usize>()).map(|_| Value::known(Fr::random(OsRng))));
+    t.reshape(dims);
+    ValTensor::from(t)
+}
+
+type Metrics = BTreeMap<String, BTreeMap<String, f64>>;
+
+// the total time and number of iterations criterion measured for every (circuit, phase), and the
+// peak memory of every (circuit, phase)
+static TIMINGS: Mutex<BTreeMap<(String, String), (Duration, u64)>> = Mutex::new(BTreeMap::new());
+static PEAKS: Mutex<BTreeMap<(String, String), u64>> = Mutex::new(BTreeMap::new());
+
+/// times `iters` runs of `f` for criterion, recording the timing and the peak memory of `phase`
+fn measure(name: &str, phase: &str, iters: u64, mut f: impl FnMut()) -> Duration {
+    memory::reset_peak();
+    let now = Instant::now();
+    for _ in 0..iters {
+        f();
+    }
+    let elapsed = now.elapsed();
+
+    let key = (name.to_string(), phase.to_string());
+    let mut timings = TIMINGS.lock().unwrap();
+    let timing = timings.entry(key.clone()).or_default();
+    timing.0 += elapsed;
+    timing.1 += iters;
+    if let Some(peak) = memory::peak() {
+        let mut peaks = PEAKS.lock().unwrap();
+        let max = peaks.entry(key).or_default();
+        *max = (*max).max(peak);
+    }
+    elapsed
+}
+
+/// benchmarks keygen and proving of a circuit
+fn bench_circuit(c: &mut Criterion, name: &str, circuit: &MyCircuit) {
+    let params = gen_srs::<KZGCommitmentScheme<_>>(K as u32);
+
+    let keygen =
+        || create_keys::<KZGCommitmentScheme<Bn256>, Fr, MyCircuit>(circuit, &params).unwrap();
+    let prove = |pk: &ProvingKey<G1Affine>| {
+        create_proof_circuit_kzg(
+            circuit.clone(),
+            &params,
+            None,
+            pk,
+            TranscriptType::EVM,
+            SingleStrategy::new(&params),
+            CheckMode::UNSAFE,
+            None,
+        )
+        .unwrap()
+    };
+
+    // warm start: the first run pays for allocations and thread pool startup
+    let pk = keygen();
+    prove(&pk);
+
+    let mut group = c.benchmark_group("proving_baseline");
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("keygen", name), |b| {
+        b.iter_custom(|iters| {
+            measure(name, "keygen", iters, || {
+                keygen();
+            })
+        })
+    });
+    group.bench_function(BenchmarkId::new("prove", name), |b| {
+        b.iter_custom(|iters| {
+            measure(name, "prove", iters, || {
+                prove(&pk);
+            })
+        })
+    });
+    group.finish();
+}
+
+/// the mean times (in milliseconds) and peak memory (in MiB) of every circuit measured so far
+fn results() -> Metrics {
+    let mut res = Metrics::new();
+    for ((name, phase), (elapsed, iters)) in TIMINGS.lock().unwrap().iter() {
+        let mean = elapsed.as_secs_f64() * 1000.0 / *iters as f64;
+        res.entry(name.clone())
+            .or_default()
+            .insert(format!("{}_ms", phase), mean);
+    }
+    for ((name, phase), peak) in PEAKS.lock().unwrap().iter() {
+        let mib = *peak as f64 / (1024.0 * 1024.0);
+        res.entry(name.clone())
+            .or_default()
+            .insert(format!("{}_peak_mib", phase), mib);
+    }
+    res
+}
+
+/// returns every (circuit, metric) which regressed by more than `threshold` percent
+fn regressions(baseline: &Metrics, current: &Metrics, threshold: f64) -> Vec<String> {
+    let mut res = vec![];
+    for (name, metrics) in current {
+        let base_metrics = match baseline.get(name) {
+            Some(m) => m,
+            None => continue,
+        };
+        for (metric, value) in metrics {
+            if let Some(base) = base_metrics.get(metric) {
+                let change = 100.0 * (value - base) / base;
+                if change > threshold {
+                    res.push(format!(
+                        "{}/{}: {:.2} -> {:.2} (+{:.1}%)",
+                        name, metric, base, value, change
+                    ));
+                }
+            }
+        }
+    }
+    res
+}
+
+fn main() {
+    let mut c = Criterion::default().configure_from_args();
+
+    for len in [4, 8, 16] {
+        unsafe {
+            LEN = len;
+        }
+
+        let dot = MyCircuit {
+            inputs: [random_tensor(&[len]), random_tensor(&[len])],
+            equation: "i,i->".to_string(),
+        };
+        bench_circuit(&mut c, &format!("dot_{}", len), &dot);
+
+        let matmul = MyCircuit {
+            inputs: [random_tensor(&[len, len]), random_tensor(&[len, 1])],
+            equation: "ij,jk->ik".to_string(),
+        };
+        bench_circuit(&mut c, &format!("matmul_{}", len), &matmul);
+    }
+
+    c.final_summary();
+
+    let results = results();
+    let output = std::env::var("EZKL_BASELINE_OUT").unwrap_or_else(|_| DEFAULT_OUTPUT.to_string());
+    std::fs::write(&output, serde_json::to_string_pretty(&results).unwrap()).unwrap();
+
+    if let Ok(baseline_path) = std::env::var("EZKL_BASELINE") {
+        if !std::path::Path::new(&baseline_path).exists() {
+            println!(
+                "no baseline at {}, skipping the regression check",
+                baseline_path
+            );
+            return;
+        }
+        let threshold = std::env::var("EZKL_REGRESSION_THRESHOLD")
+            .map(|t| t.parse::<f64>().unwrap())
+            .unwrap_or(DEFAULT_THRESHOLD);
+        let baseline: Metrics =
+            serde_json::from_str(&std::fs::read_to_string(baseline_path).unwrap()).unwrap();
+
+        let regressed = regressions(&baseline, &results, threshold);
+        assert!(
+            regressed.is_empty(),
+            "regressions above {}%:\n{}",
+            threshold,
+            regressed.join("\n")
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use criterion::measurement::{Measurement, ValueFormatter};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ezkl::circuit::poly::PolyOp;
+use ezkl::circuit::*;
+use ezkl::pfsys::create_proof_circuit_kzg;
+use ezkl::pfsys::memory;
+use ezkl::pfsys::TranscriptType;
+use ezkl::pfsys::{create_keys, srs::gen_srs};
+use ezkl::tensor::*;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use halo2_proofs::poly::kzg::strategy::SingleStrategy;
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+use halo2curves::bn256::{Bn256, Fr};
+use rand::Rng;
+use std::marker::PhantomData;
+
+static mut LEN: usize = 4;
+// tiny circuits (linear / logistic heads) fit comfortably in 2^8 rows
+const K: usize = 8;
+
+#[derive(Clone)]
+struct MyCircuit {
+    inputs: [ValTensor<Fr>; 2],
+    _marker: PhantomData<Fr>,
+}
+
+impl Circuit<Fr> for MyCircuit {
+    type Config = BaseConfig<Fr>;
+    type FloorPlanner = SimpleFloorPlanner;
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let len = unsafe { LEN };
+
+        let a = VarTensor::new_advice(cs, K, 1, len);
+        let b = VarTensor::new_advice(cs, K, 1, len);
+        let output = VarTensor::new_advice(cs, K, 1, len);
+
+        Self::Config::configure(cs, &[a, b], &output, CheckMode::UNSAFE)
+    }
+
+    fn synthesize(
+        &self,
+        mut config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "",
+            |region| {
+                let mut region = region::RegionCtx::new(region, 0, 1);
+                config
+                    .layout(
+                        &mut region,
+                        &self.inputs,
+                        Box::new(PolyOp::Einsum {
+                            equation: "i,i->".to_string(),
+                        }),
+                    )
+                    .unwrap();
+                Ok(())
+            },
+        )?;
+        Ok(())
+    }
+}
+
+/// The peak resident memory of the process while running the benchmarked routine, in bytes
+struct PeakMemory;
+
+impl Measurement for PeakMemory {
+    type Intermediate = ();
+    type Value = u64;
+
+    fn start(&self) -> Self::Intermediate {
+        memory::reset_peak();
+    }
+
+    fn end(&self, _: Self::Intermediate) -> Self::Value {
+        memory::peak().unwrap_or(0)
+    }
+
+    fn add(&self, v1: &Self::Value, v2: &Self::Value) -> Self::Value {
+        v1 + v2
+    }
+
+    fn zero(&self) -> Self::Value {
+        0
+    }
+
+    fn to_f64(&self, value: &Self::Value) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &MemoryFormatter
+    }
+}
+
+/// Runs `f` `iters` times and sums the peaks of the single runs, so that criterion (which divides the
+/// measured value by the number of iterations) reports the mean peak memory of a run
+fn sum_of_peaks(iters: u64, mut f: impl FnMut()) -> u64 {
+    (0..iters).fold(PeakMemory.zero(), |total, _| {
+        let start = PeakMemory.start();
+        f();
+        PeakMemory.add(&total, &PeakMemory.end(start))
+    })
+}
+
+struct MemoryFormatter;
+
+impl ValueFormatter for MemoryFormatter {
+    fn scale_values(&self, _: f64, values: &mut [f64]) -> &'static str {
+        for v in values {
+            *v /= 1024.0 * 1024.0;
+        }
+        "MiB"
+    }
+
+    fn scale_throughputs(&self, _: f64, _: &Throughput, _: &mut [f64]) -> &'static str {
+        "B"
+    }
+
+    fn scale_for_machines(&self, _: &mut [f64]) -> &'static str {
+        "B"
+    }
+}
+
+fn tiny_linear(len: usize) -> MyCircuit {
+    let mut rng = rand::thread_rng();
+    let a: Tensor<Value<Fr>> = Tensor::<i32>::from((0..len).map(|_| rng.gen_range(0..10))).into();
+    let b: Tensor<Value<Fr>> = Tensor::<i32>::from((0..len).map(|_| rng.gen_range(0..10))).into();
+
+    MyCircuit {
+        inputs: [ValTensor::from(a), ValTensor::from(b)],
+        _marker: PhantomData,
+    }
+}
+
+/// Proves a linear head on a single thread, this approximates the footprint of proving tiny models on embedded / mobile devices
+fn runtinylinear(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tiny_linear_single_thread");
+    // pin all of the prover's parallel iterators to a single worker
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+    let params = gen_srs::<KZGCommitmentScheme<_>>(K as u32);
+    for &len in [4, 16, 64].iter() {
+        unsafe {
+            LEN = len;
+        };
+
+        let circuit = tiny_linear(len);
+
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("pk", len), &len, |b, &_| {
+            b.iter(|| {
+                pool.install(|| {
+                    create_keys::<KZGCommitmentScheme<Bn256>, Fr, MyCircuit>(&circuit, &params)
+                        .unwrap();
+                })
+            });
+        });
+
+        let pk = pool.install(|| {
+            create_keys::<KZGCommitmentScheme<Bn256>, Fr, MyCircuit>(&circuit, &params).unwrap()
+        });
+
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("prove", len), &len, |b, &_| {
+            b.iter(|| {
+                pool.install(|| {
+                    let prover = create_proof_circuit_kzg(
+                        circuit.clone(),
+                        &params,
+                        None,
+                        &pk,
+                        TranscriptType::EVM,
+                        SingleStrategy::new(&params),
+                        CheckMode::UNSAFE,
+                        None,
+                    );
+                    prover.unwrap();
+                })
+            });
+        });
+    }
+    group.finish();
+}
+
+/// The peak memory of single threaded keygen and proving of a linear head, which should stay
+/// within a few hundred MiB
+fn runtinylinearmemory(c: &mut Criterion<PeakMemory>) {
+    let mut group = c.benchmark_group("tiny_linear_single_thread_memory");
+    // the peak barely varies between runs
+    group.sample_size(10);
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+    let params = gen_srs::<KZGCommitmentScheme<_>>(K as u32);
+    for &len in [4, 16, 64].iter() {
+        unsafe {
+            LEN = len;
+        };
+
+        let circuit = tiny_linear(len);
+        let pk = pool.install(|| {
+            create_keys::<KZGCommitmentScheme<Bn256>, Fr, MyCircuit>(&circuit, &params).unwrap()
+        });
+
+        group.bench_with_input(BenchmarkId::new("pk", len), &len, |b, &_| {
+            b.iter_custom(|iters| {
+                sum_of_peaks(iters, || {
+                    pool.install(|| {
+                        create_keys::<KZGCommitmentScheme<Bn256>, Fr, MyCircuit>(&circuit, &params)
+                            .unwrap();
+                    })
+                })
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("prove", len), &len, |b, &_| {
+            b.iter_custom(|iters| {
+                sum_of_peaks(iters, || {
+                    pool.install(|| {
+                        create_proof_circuit_kzg(
+                            circuit.clone(),
+                            &params,
+                            None,
+                            &pk,
+                            TranscriptType::EVM,
+                            SingleStrategy::new(&params),
+                            CheckMode::UNSAFE,
+                            None,
+                        )
+                        .unwrap();
+                    })
+                })
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+  name = benches;
+  config = Criterion::default().with_plots();
+  targets = runtinylinear
+}
+criterion_group! {
+  name = memory_benches;
+  config = Criterion::default().with_measurement(PeakMemory);
+  targets = runtinylinearmemory
+}
+criterion_main!(benches, memory_benches);
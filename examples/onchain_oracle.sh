@@ -0,0 +1,43 @@
+#! /bin/bash
+# End-to-end walkthrough of an on-chain inference oracle:
+# import model -> kzg keys -> solidity verifier -> prove inference -> verify on a local anvil node.
+# Run from the repository root with `anvil` on the PATH, eg. `bash examples/onchain_oracle.sh 1l_mlp`.
+# Set EZKL to use an already built binary, and ANVIL_PORT to run the local node on another port.
+set -e
+
+EXAMPLE=${1:-1l_mlp}
+ANVIL_PORT=${ANVIL_PORT:-8545}
+RPC_URL=${RPC_URL:-http://127.0.0.1:$ANVIL_PORT}
+OUT=$(mktemp -d)
+
+if [ -z "$EZKL" ]; then
+    cargo build --release --bin ezkl
+    EZKL=./target/release/ezkl
+fi
+
+anvil -p $ANVIL_PORT --code-size-limit=41943040 --disable-block-gas-limit > /dev/null &
+ANVIL_PID=$!
+trap "kill $ANVIL_PID" EXIT
+# give the node time to start listening
+sleep 3
+
+# import and calibrate the model, outputs are public so the verifier exposes the inference result
+$EZKL gen-settings -M examples/onnx/$EXAMPLE/network.onnx -O $OUT/settings.json --output-visibility=public
+$EZKL calibrate-settings -M examples/onnx/$EXAMPLE/network.onnx -D examples/onnx/$EXAMPLE/input.json --settings-path=$OUT/settings.json
+$EZKL compile-circuit -M examples/onnx/$EXAMPLE/network.onnx --compiled-circuit $OUT/network.ezkl -S $OUT/settings.json
+
+# kzg keys
+$EZKL get-srs --srs-path=$OUT/kzg.srs -S $OUT/settings.json
+$EZKL setup -M $OUT/network.ezkl --srs-path=$OUT/kzg.srs --vk-path=$OUT/key.vk --pk-path=$OUT/key.pk
+
+# solidity verifier, deployed to the local node
+$EZKL create-evm-verifier --srs-path=$OUT/kzg.srs --vk-path=$OUT/key.vk -S $OUT/settings.json --sol-code-path=$OUT/verifier.sol
+$EZKL deploy-evm-verifier --sol-code-path=$OUT/verifier.sol --rpc-url=$RPC_URL --addr-path=$OUT/addr.txt
+
+# prove the inference
+$EZKL gen-witness -D examples/onnx/$EXAMPLE/input.json -M $OUT/network.ezkl -O $OUT/witness.json
+$EZKL prove -W $OUT/witness.json -M $OUT/network.ezkl --pk-path=$OUT/key.pk --proof-path=$OUT/proof.pf --srs-path=$OUT/kzg.srs
+
+# submit the calldata to the verifier and read back the (now verified) result
+$EZKL verify-evm --proof-path=$OUT/proof.pf --addr-verifier=$(cat $OUT/addr.txt) --rpc-url=$RPC_URL
+$EZKL print-proof-hex --proof-path=$OUT/proof.pf
@@ -0,0 +1,43 @@
+/* C api of ezkl, built with `cargo build --release --features ffi`.
+ *
+ * Every function takes paths as nul terminated utf-8 strings and returns an
+ * ezkl_status. On failure the reason can be read with ezkl_last_error(), the
+ * returned string is owned by ezkl and stays valid until the next call on the
+ * same thread.
+ */
+#ifndef EZKL_H
+#define EZKL_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum {
+  EZKL_OK = 0,
+  EZKL_INVALID_ARGUMENT = 1,
+  EZKL_FAILED = 2,
+} ezkl_status;
+
+const char *ezkl_last_error(void);
+
+/* run_args_json may be NULL to use the cli defaults */
+ezkl_status ezkl_load_model(const char *model, const char *run_args_json,
+                            const char *settings_out, const char *compiled_out);
+
+ezkl_status ezkl_gen_witness(const char *compiled_circuit, const char *data,
+                             const char *witness_out);
+
+ezkl_status ezkl_setup(const char *compiled_circuit, const char *srs,
+                       const char *vk_out, const char *pk_out);
+
+ezkl_status ezkl_prove(const char *witness, const char *compiled_circuit,
+                       const char *pk, const char *srs, const char *proof_out);
+
+ezkl_status ezkl_verify(const char *proof, const char *settings,
+                        const char *vk, const char *srs);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* EZKL_H */
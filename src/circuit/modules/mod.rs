@@ -7,6 +7,9 @@ pub mod elgamal;
 ///
 pub mod kzg;
 
+///
+pub mod prf;
+
 ///
 pub mod planner;
 use halo2_proofs::{
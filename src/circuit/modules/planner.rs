@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt;
 use std::marker::PhantomData;
@@ -17,6 +18,17 @@ use halo2_proofs::{
 };
 use log::{trace, warn};
 
+thread_local! {
+    /// The index of the last region with each name laid out by a [ModuleLayouter] on this thread
+    static REGION_INDICES: RefCell<HashMap<String, usize>> = RefCell::new(HashMap::new());
+}
+
+/// The index of the last region named `name` laid out on the current thread, as reported by the
+/// [halo2_proofs::dev::MockProver] (which counts tables as regions too)
+pub fn region_index(name: &str) -> Option<usize> {
+    REGION_INDICES.with(|r| r.borrow().get(name).copied())
+}
+
 /// A simple [`FloorPlanner`] that performs minimal optimizations.
 #[derive(Debug)]
 pub struct ModulePlanner;
@@ -54,6 +66,8 @@ pub struct ModuleLayouter<'a, F: Field, CS: Assignment<F> + 'a> {
     current_module: usize,
     /// num_constants
     total_constants: usize,
+    /// the number of regions (and tables) entered so far
+    num_regions: usize,
 }
 
 impl<'a, F: Field, CS: Assignment<F> + 'a> fmt::Debug for ModuleLayouter<'a, F, CS> {
@@ -78,8 +92,10 @@ impl<'a, F: Field, CS: Assignment<F>> ModuleLayouter<'a, F, CS> {
             table_columns: vec![],
             current_module: 0,
             total_constants: 0,
+            num_regions: 0,
             _marker: PhantomData,
         };
+        REGION_INDICES.with(|r| r.borrow_mut().clear());
         Ok(ret)
     }
 
@@ -161,6 +177,9 @@ impl<'a, F: Field, CS: Assignment<F> + 'a + SyncDeps> Layouter<F> for ModuleLayo
         }
 
         // Assign region cells.
+        let num_regions = self.num_regions;
+        REGION_INDICES.with(|r| r.borrow_mut().insert(name().into(), num_regions));
+        self.num_regions += 1;
         self.cs.enter_region(name);
         let mut region = ModuleLayouterRegion::new(self, region_index.into());
         let result = {
@@ -232,6 +251,7 @@ impl<'a, F: Field, CS: Assignment<F> + 'a + SyncDeps> Layouter<F> for ModuleLayo
     {
         // Maintenance hazard: there is near-duplicate code in `v1::AssignmentPass::assign_table`.
         // Assign table cells.
+        self.num_regions += 1;
         self.cs.enter_region(name);
         let mut table =
             halo2_proofs::circuit::SimpleTableLayouter::new(self.cs, &self.table_columns);
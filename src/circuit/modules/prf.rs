@@ -0,0 +1,381 @@
+/*
+A keyed Poseidon PRF to select data augmentations (eg. the `iff(bit, flip(x), x)` of a random flip) in a way the
+prover can't choose. The prover commits to a secret key once, as `Poseidon(key, 0)`, and the PRF value for the
+augmentation with nonce `i` (eg. the index of the sample in the dataset) is `Poseidon(key, i + 1)`. Both are
+constrained to the instance column, so a verifier checks that the commitment is the same across the proofs of a
+dataset, and the key itself is never revealed.
+
+The selection bit of a PRF value is whether it is a quadratic non-residue, which is cheap to constrain: a gate
+checks that the bit is boolean and that `root^2 = prf * g^bit` for a witnessed `root`, where `g` is a
+non-residue. Exactly one of `prf` and `g * prf` is a square (for a non zero `prf`), so the bit is determined by
+the PRF value. The assigned bits are then used as the masks of the augmentations, see [random_flip].
+*/
+
+use halo2_proofs::halo2curves::bn256::Fr as Fp;
+use halo2_proofs::poly::Rotation;
+use halo2_proofs::{circuit::*, plonk::*};
+use halo2curves::ff::{Field, PrimeField};
+use log::error;
+
+use crate::circuit::ops::layouts;
+use crate::circuit::ops::region::RegionCtx;
+use crate::circuit::BaseConfig;
+use crate::tensor::{Tensor, ValTensor, ValType};
+
+use super::poseidon::spec::{PoseidonSpec, POSEIDON_RATE, POSEIDON_WIDTH};
+use super::poseidon::{PoseidonChip, PoseidonConfig};
+use super::Module;
+
+/// The Poseidon chip hashing the (key, nonce) pairs
+type PrfPoseidon = PoseidonChip<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 2>;
+
+/// The number of instance columns used by the PRF
+pub const NUM_INSTANCE_COLUMNS: usize = 1;
+
+/// Configuration of the [PrfChip]
+#[derive(Debug, Clone)]
+pub struct PrfConfig {
+    /// the Poseidon hash of the PRF, which constrains its outputs to an instance column
+    pub poseidon: PoseidonConfig<POSEIDON_WIDTH, POSEIDON_RATE>,
+    /// enables the gate of the selection bits, over the (prf, bit, root) hash input columns
+    pub selection: Selector,
+}
+
+/// Evaluates a keyed Poseidon PRF in-circuit, see the module docs
+#[derive(Debug, Clone)]
+pub struct PrfChip {
+    config: PrfConfig,
+    poseidon: PrfPoseidon,
+}
+
+/// The hashed nonces: 0 for the commitment, then `nonce + 1` for each of the `nonces`
+fn prf_nonces(nonces: &[u64]) -> Result<Vec<u64>, String> {
+    std::iter::once(Some(0))
+        .chain(nonces.iter().map(|n| n.checked_add(1)))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| format!("prf nonces must be below {}", u64::MAX))
+}
+
+impl PrfChip {
+    /// Configures the PRF, which constrains its outputs to a new instance column
+    pub fn configure(meta: &mut ConstraintSystem<Fp>) -> PrfConfig {
+        let poseidon = PrfPoseidon::configure(meta, ());
+        let selection = meta.selector();
+        let [prf, bit, root]: [Column<Advice>; 3] = poseidon.hash_inputs[..3]
+            .try_into()
+            .expect("poseidon has a width of at least 3");
+        meta.create_gate("prf selection bit", |meta| {
+            let s = meta.query_selector(selection);
+            let prf = meta.query_advice(prf, Rotation::cur());
+            let bit = meta.query_advice(bit, Rotation::cur());
+            let root = meta.query_advice(root, Rotation::cur());
+            let one = Expression::Constant(Fp::ONE);
+            let non_residue = Expression::Constant(Fp::MULTIPLICATIVE_GENERATOR - Fp::ONE);
+            vec![
+                s.clone() * bit.clone() * (one.clone() - bit.clone()),
+                s * (root.clone() * root - prf * (one + bit * non_residue)),
+            ]
+        });
+        PrfConfig {
+            poseidon,
+            selection,
+        }
+    }
+
+    /// Constructs a new PrfChip
+    pub fn new(config: PrfConfig) -> Self {
+        Self {
+            poseidon: PrfPoseidon::new(config.poseidon.clone()),
+            config,
+        }
+    }
+
+    /// The commitment to `key`, followed by the PRF values of `key` for each of the `nonces`: the instances
+    /// checked by [PrfChip::layout]
+    pub fn run(key: Fp, nonces: &[u64]) -> Result<Vec<Fp>, Box<dyn std::error::Error>> {
+        let mut res = vec![];
+        for nonce in prf_nonces(nonces)? {
+            res.extend(PrfPoseidon::run(vec![key, Fp::from(nonce)])?.remove(0));
+        }
+        Ok(res)
+    }
+
+    /// Lays out the commitment to the (single element) `key` and its PRF values for each of the `nonces`,
+    /// constraining them to the instances starting at `row_offset`. Returns the assigned selection bit of
+    /// each PRF value.
+    pub fn layout(
+        &self,
+        layouter: &mut impl Layouter<Fp>,
+        key: &ValTensor<Fp>,
+        nonces: &[u64],
+        row_offset: usize,
+    ) -> Result<ValTensor<Fp>, Error> {
+        self.layout_hashes(layouter, key, nonces, row_offset)?;
+        let bits = (0..nonces.len())
+            .map(|i| self.assign_bit(layouter, row_offset + i + 1, None))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Tensor::from(bits.into_iter()).into())
+    }
+
+    /// Lays out the hashes of [PrfChip::layout]
+    fn layout_hashes(
+        &self,
+        layouter: &mut impl Layouter<Fp>,
+        key: &ValTensor<Fp>,
+        nonces: &[u64],
+        row_offset: usize,
+    ) -> Result<(), Error> {
+        if key.len() != 1 {
+            return Err(Error::Synthesis);
+        }
+        let nonces = prf_nonces(nonces).map_err(|e| {
+            error!("{}", e);
+            Error::Synthesis
+        })?;
+        // every hash copies the same assigned key
+        let (key, _) = self.poseidon.layout_inputs(layouter, &[key.clone()])?;
+        let key = ValType::PrevAssigned(key[0].clone());
+
+        for (i, nonce) in nonces.iter().enumerate() {
+            let message: Tensor<ValType<Fp>> =
+                vec![key.clone(), ValType::Constant(Fp::from(*nonce))]
+                    .into_iter()
+                    .into();
+            self.poseidon
+                .layout(layouter, &[message.into()], row_offset + i)?;
+        }
+        Ok(())
+    }
+
+    /// Assigns the PRF value at `row` of the instance column and its selection bit, which is `bit` if given
+    /// rather than the one computed from the PRF value
+    fn assign_bit(
+        &self,
+        layouter: &mut impl Layouter<Fp>,
+        row: usize,
+        bit: Option<bool>,
+    ) -> Result<ValType<Fp>, Error> {
+        let instance = self.config.poseidon.instance.ok_or(Error::Synthesis)?;
+        let columns = &self.config.poseidon.hash_inputs;
+        layouter.assign_region(
+            || "prf selection bit",
+            |mut region| {
+                self.config.selection.enable(&mut region, 0)?;
+                let prf =
+                    region.assign_advice_from_instance(|| "prf", instance, row, columns[0], 0)?;
+                let bit = prf
+                    .value()
+                    .map(|prf| bit.unwrap_or_else(|| selection_bit(prf)));
+                let root = prf.value().zip(bit).map(|(prf, bit)| {
+                    let square = if bit {
+                        *prf * Fp::MULTIPLICATIVE_GENERATOR
+                    } else {
+                        *prf
+                    };
+                    // a forged bit has no root
+                    square.sqrt().unwrap_or(Fp::ZERO)
+                });
+                let bit = region.assign_advice(
+                    || "bit",
+                    columns[1],
+                    0,
+                    || bit.map(|b| Fp::from(b as u64)),
+                )?;
+                region.assign_advice(|| "root", columns[2], 0, || root)?;
+                Ok(ValType::PrevAssigned(bit))
+            },
+        )
+    }
+
+    /// Number of rows used by the PRF for `num_nonces` nonces
+    pub fn num_rows(num_nonces: usize) -> usize {
+        (num_nonces + 1) * PrfPoseidon::num_rows(2) + num_nonces
+    }
+}
+
+/// The selection bit of a PRF value: whether it is a quadratic non-residue, see the module docs
+pub fn selection_bit(prf: &Fp) -> bool {
+    bool::from(prf.sqrt().is_none())
+}
+
+/// Lays out the random flip `iff(bit, flip(x), x)` of `x` along `axis`, where `bit` is a (single element)
+/// selection bit assigned by [PrfChip::layout]
+pub fn random_flip(
+    config: &BaseConfig<Fp>,
+    region: &mut RegionCtx<Fp>,
+    bit: &ValTensor<Fp>,
+    x: &ValTensor<Fp>,
+    axis: usize,
+) -> Result<ValTensor<Fp>, Box<dyn std::error::Error>> {
+    let bit = bit.get_inner_tensor()?;
+    if bit.len() != 1 {
+        return Err("a random flip is selected by a single bit".into());
+    }
+    let mut mask: Tensor<ValType<Fp>> = vec![bit[0].clone(); x.len()].into_iter().into();
+    mask.reshape(x.dims());
+    let flipped = layouts::flip(&[x.clone()], axis)?;
+    layouts::iff(config, region, &[mask.into(), flipped, x.clone()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::modules::ModulePlanner;
+    use crate::circuit::CheckMode;
+    use crate::tensor::VarTensor;
+    use halo2_proofs::dev::MockProver;
+
+    const K: usize = 10;
+    const NONCES: [u64; 3] = [0, 1, 7];
+
+    struct PrfCircuit {
+        key: ValTensor<Fp>,
+        /// the sample flipped with the first selection bit
+        sample: ValTensor<Fp>,
+        /// the selection bits the prover claims, rather than those of the prf values
+        forged: Option<Vec<bool>>,
+        /// the augmented sample
+        expected: Vec<i128>,
+    }
+
+    impl Circuit<Fp> for PrfCircuit {
+        type Config = (PrfConfig, BaseConfig<Fp>);
+        type FloorPlanner = ModulePlanner;
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            let key: Tensor<ValType<Fp>> = vec![Value::<Fp>::unknown().into()].into_iter().into();
+            Self {
+                key: key.into(),
+                sample: self.sample.clone(),
+                forged: self.forged.clone(),
+                expected: self.expected.clone(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let prf = PrfChip::configure(meta);
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(meta, K, 1, 4))
+                .collect::<Vec<_>>();
+            VarTensor::constant_cols(meta, K, 2, false);
+            let base = BaseConfig::configure(
+                meta,
+                &[advices[0].clone(), advices[1].clone()],
+                &advices[2],
+                CheckMode::SAFE,
+            );
+            (prf, base)
+        }
+
+        fn synthesize(
+            &self,
+            (prf, base): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = PrfChip::new(prf);
+            let bits = match &self.forged {
+                None => chip.layout(&mut layouter, &self.key, &NONCES, 0)?,
+                Some(forged) => {
+                    chip.layout_hashes(&mut layouter, &self.key, &NONCES, 0)?;
+                    let bits = forged
+                        .iter()
+                        .enumerate()
+                        .map(|(i, bit)| chip.assign_bit(&mut layouter, i + 1, Some(*bit)))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Tensor::from(bits.into_iter()).into()
+                }
+            };
+            let bit: ValTensor<Fp> = Tensor::from(
+                bits.get_inner_tensor()
+                    .map_err(|_| Error::Synthesis)?
+                    .iter()
+                    .take(1)
+                    .cloned(),
+            )
+            .into();
+
+            layouter.assign_region(
+                || "augmentation",
+                |region| {
+                    let mut region = RegionCtx::new(region, 0, 1);
+                    let output = random_flip(&base, &mut region, &bit, &self.sample, 0)
+                        .map_err(|_| Error::Synthesis)?;
+                    if let Ok(evals) = output.get_int_evals() {
+                        assert_eq!(evals.to_vec(), self.expected);
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    fn circuit(key: Fp, forged: Option<Vec<bool>>) -> PrfCircuit {
+        let flip = match &forged {
+            Some(bits) => bits[0],
+            None => selection_bit(&PrfChip::run(key, &NONCES).unwrap()[1]),
+        };
+        let expected = if flip {
+            vec![3, 4, 1, 2]
+        } else {
+            vec![1, 2, 3, 4]
+        };
+        let key: Tensor<ValType<Fp>> = vec![Value::known(key).into()].into_iter().into();
+        let mut sample = Tensor::from((1..=4).map(|i| Value::known(Fp::from(i))));
+        sample.reshape(&[2, 2]);
+        PrfCircuit {
+            key: key.into(),
+            sample: sample.into(),
+            forged,
+            expected,
+        }
+    }
+
+    #[test]
+    fn prf_selection() {
+        let key = Fp::from(1234);
+        let instances = PrfChip::run(key, &NONCES).unwrap();
+        assert_eq!(instances.len(), NONCES.len() + 1);
+        // the prf is deterministic and keyed
+        assert_eq!(instances, PrfChip::run(key, &NONCES).unwrap());
+        assert_ne!(instances, PrfChip::run(Fp::from(4321), &NONCES).unwrap());
+
+        let prover =
+            MockProver::run(K as u32, &circuit(key, None), vec![instances.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // the prover can't swap the key once committed
+        let mut forged = PrfChip::run(Fp::from(4321), &NONCES).unwrap();
+        forged[0] = instances[0];
+        let prover =
+            MockProver::run(K as u32, &circuit(Fp::from(4321), None), vec![forged]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // nor pick the prf values
+        let mut forged = instances.clone();
+        forged[2] += Fp::ONE;
+        let prover = MockProver::run(K as u32, &circuit(key, None), vec![forged]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // nor the flips they select
+        let bits = instances[1..].iter().map(selection_bit).collect::<Vec<_>>();
+        let prover = MockProver::run(
+            K as u32,
+            &circuit(key, Some(bits.clone())),
+            vec![instances.clone()],
+        )
+        .unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        let mut flipped = bits;
+        flipped[0] = !flipped[0];
+        let prover =
+            MockProver::run(K as u32, &circuit(key, Some(flipped)), vec![instances]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn prf_nonce_overflow() {
+        assert!(PrfChip::run(Fp::from(1234), &[u64::MAX]).is_err());
+        assert!(PrfChip::run(Fp::from(1234), &[u64::MAX - 1]).is_ok());
+    }
+}
@@ -42,6 +42,15 @@ pub enum CircuitError {
     /// This operation is unsupported
     #[error("unsupported operation in graph")]
     UnsupportedOp,
+    /// A lookup input falls outside of the range of the table
+    #[error("lookup input {0} is outside of the table range {1:?}")]
+    LookupRangeExceeded(i128, (i128, i128)),
+    /// The layout of an op depends on weights that aren't fixed or public
+    #[error("the layout of {0} depends on its weights, which must be fixed or public")]
+    PrivateStructuralWeights(String),
+    /// The breakpoints of a piecewise linear op aren't strictly ascending at the scale of its input
+    #[error("piecewise linear breakpoints {0:?} must be strictly ascending at the input scale")]
+    UnsortedBreakpoints(Vec<f32>),
 }
 
 #[allow(missing_docs)]
@@ -338,26 +347,33 @@ impl<F: PrimeField + TensorType + PartialOrd> BaseConfig<F> {
         F: Field,
     {
         let mut selectors = BTreeMap::new();
+        // layers computing the same function (at the same range) share a single table
+        let nl = &nl.canonical();
 
         // we borrow mutably twice so we need to do this dance
 
-        let table = if !self.tables.contains_key(nl) {
-            // as all tables have the same input we see if there's another table who's input we can reuse
-            let table = if let Some(table) = self.tables.values().next() {
-                Table::<F>::configure(
-                    cs,
-                    lookup_range,
-                    logrows,
-                    nl,
-                    Some(table.table_inputs.clone()),
-                )
-            } else {
-                Table::<F>::configure(cs, lookup_range, logrows, nl, None)
-            };
-            self.tables.insert(nl.clone(), table.clone());
-            table
-        } else {
-            return Ok(());
+        let table = match self.tables.get(nl) {
+            // the table was shared from another config, so we only need to configure the lookups into it
+            Some(table) if !self.lookup_selectors.contains_key(&(nl.clone(), 0, 0)) => {
+                table.clone()
+            }
+            Some(_) => return Ok(()),
+            None => {
+                // as all tables have the same input we see if there's another table who's input we can reuse
+                let table = if let Some(table) = self.tables.values().next() {
+                    Table::<F>::configure(
+                        cs,
+                        lookup_range,
+                        logrows,
+                        nl,
+                        Some(table.table_inputs.clone()),
+                    )
+                } else {
+                    Table::<F>::configure(cs, lookup_range, logrows, nl, None)
+                };
+                self.tables.insert(nl.clone(), table.clone());
+                table
+            }
         };
 
         for x in 0..input.num_blocks() {
@@ -458,19 +474,33 @@ impl<F: PrimeField + TensorType + PartialOrd> BaseConfig<F> {
         Ok(())
     }
 
+    /// Shares the lookup tables of `other`, which has to be configured on the same [ConstraintSystem], with this config.
+    /// Lookups configured on this config then reuse the columns of these tables rather than adding new ones, and
+    /// the tables are only laid out once (by `other`), however many configs (eg. those of the sub-circuits of a
+    /// larger circuit) use them. Successive syntheses of the same circuit (eg. a batch of proofs) instead reuse the
+    /// evaluations cached in [crate::circuit::table].
+    pub fn share_tables(&mut self, other: &BaseConfig<F>) {
+        for (nl, table) in other.tables.iter() {
+            self.tables.entry(nl.clone()).or_insert_with(|| {
+                let mut table = table.clone();
+                table.is_assigned = true;
+                table
+            });
+        }
+    }
+
     /// layout_tables must be called before layout.
     pub fn layout_tables(&mut self, layouter: &mut impl Layouter<F>) -> Result<(), Box<dyn Error>> {
-        for (i, table) in self.tables.values_mut().enumerate() {
+        // all tables share the input columns of the first table, which are laid out with it
+        let mut inputs_assigned = self.tables.values().any(|table| table.is_assigned);
+        for table in self.tables.values_mut() {
             if !table.is_assigned {
                 debug!(
                     "laying out table for {}",
                     crate::circuit::ops::Op::<F>::as_string(&table.nonlinearity)
                 );
-                if i == 0 {
-                    table.layout(layouter, false)?;
-                } else {
-                    table.layout(layouter, true)?;
-                }
+                table.layout(layouter, inputs_assigned)?;
+                inputs_assigned = true;
             }
         }
         Ok(())
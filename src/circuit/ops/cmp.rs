@@ -0,0 +1,87 @@
+use std::error::Error;
+
+use halo2curves::ff::PrimeField;
+
+use super::{
+    base::BaseOp,
+    layouts::{nonlinearity, pairwise},
+    lookup::LookupOp,
+    region::RegionCtx,
+};
+use crate::{
+    circuit::{utils, BaseConfig},
+    tensor::{TensorType, ValTensor},
+};
+
+// Each comparison looks up the (broadcast) difference of its operands, so outputs are 0 or 1 by the
+// construction of the tables and need no separate boolean constraint. The difference must lie in the
+// lookup range, which is also what range checks it. The matching `*_lookup` functions return the table
+// a comparison needs, so that ops can report their `required_lookups` consistently.
+
+/// The lookup table used by [gt] and [lt]
+pub fn gt_lookup() -> LookupOp {
+    LookupOp::GreaterThan { a: utils::F32(0.) }
+}
+
+/// The lookup table used by [gte] and [lte]
+pub fn gte_lookup() -> LookupOp {
+    LookupOp::GreaterThanEqual { a: utils::F32(0.) }
+}
+
+/// The lookup table used by [eq]
+pub fn eq_lookup() -> LookupOp {
+    LookupOp::KroneckerDelta
+}
+
+/// Elementwise `a == b`
+pub fn eq<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    a: &ValTensor<F>,
+    b: &ValTensor<F>,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let diff = pairwise(config, region, &[a.clone(), b.clone()], BaseOp::Sub)?;
+    nonlinearity(config, region, &[diff], &eq_lookup())
+}
+
+/// Elementwise `a > b`
+pub fn gt<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    a: &ValTensor<F>,
+    b: &ValTensor<F>,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let diff = pairwise(config, region, &[a.clone(), b.clone()], BaseOp::Sub)?;
+    nonlinearity(config, region, &[diff], &gt_lookup())
+}
+
+/// Elementwise `a >= b`
+pub fn gte<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    a: &ValTensor<F>,
+    b: &ValTensor<F>,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let diff = pairwise(config, region, &[a.clone(), b.clone()], BaseOp::Sub)?;
+    nonlinearity(config, region, &[diff], &gte_lookup())
+}
+
+/// Elementwise `a < b`
+pub fn lt<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    a: &ValTensor<F>,
+    b: &ValTensor<F>,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    gt(config, region, b, a)
+}
+
+/// Elementwise `a <= b`
+pub fn lte<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    a: &ValTensor<F>,
+    b: &ValTensor<F>,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    gte(config, region, b, a)
+}
@@ -0,0 +1,143 @@
+use std::error::Error;
+
+use halo2_proofs::circuit::Value;
+use halo2curves::ff::PrimeField;
+
+use super::{
+    base::BaseOp,
+    cmp,
+    layouts::{enforce_equality, nonlinearity, pairwise},
+    lookup::LookupOp,
+    region::RegionCtx,
+};
+use crate::{
+    circuit::BaseConfig,
+    fieldutils::i128_to_felt,
+    tensor::{Tensor, TensorType, ValTensor, ValType},
+};
+
+// The quotient and remainder are witnessed and constrained by `x = quotient * divisor + remainder`,
+// with `0 <= remainder < divisor` checked through the comparison lookup. The quotient is range checked
+// by the same lookup: otherwise any remainder could be matched by the field element `(x - r) / divisor`.
+// This rounds towards negative infinity (euclidean division) and needs no table per divisor, unlike
+// [LookupOp::Div].
+
+/// The lookup tables used by [div_rem]
+pub fn div_rem_lookups() -> Vec<LookupOp> {
+    vec![cmp::gte_lookup()]
+}
+
+/// Witnesses `f(x, divisor)` elementwise, unknown if `x` is
+fn witness<F: PrimeField + TensorType + PartialOrd>(
+    x: &ValTensor<F>,
+    evals: &Option<Tensor<i128>>,
+    f: impl Fn(i128) -> i128,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let mut witness: Tensor<Value<F>> = match evals {
+        Some(evals) => evals.map(|v| Value::known(i128_to_felt(f(v)))),
+        None => Tensor::new(Some(&vec![Value::unknown(); x.len()]), &[x.len()])?,
+    };
+    witness.reshape(x.dims());
+    Ok(witness.into())
+}
+
+/// The elementwise quotient and remainder of the integer division of `x` by a constant `divisor`
+pub fn div_rem<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    x: &ValTensor<F>,
+    divisor: u64,
+) -> Result<[ValTensor<F>; 2], Box<dyn Error>> {
+    if divisor == 0 {
+        return Err("integer division by zero".into());
+    }
+    let d = divisor as i128;
+
+    // this is safe because we later constrain it
+    let evals = x.get_int_evals().ok().filter(|e| e.len() == x.len());
+    let quotient = witness(x, &evals, |v| v.div_euclid(d))?;
+    let remainder = witness(x, &evals, |v| v.rem_euclid(d))?;
+
+    constrain_div_rem(config, region, x, divisor, &quotient, &remainder)
+}
+
+/// Assigns the witnessed `quotient` and `remainder` of the division of `x` by `divisor` and constrains them,
+/// see [div_rem]
+pub(crate) fn constrain_div_rem<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    x: &ValTensor<F>,
+    divisor: u64,
+    quotient: &ValTensor<F>,
+    remainder: &ValTensor<F>,
+) -> Result<[ValTensor<F>; 2], Box<dyn Error>> {
+    let d = divisor as i128;
+
+    let mut x = x.clone();
+    if !x.all_prev_assigned() {
+        x = region.assign(&config.inputs[0], &x)?;
+        region.increment(x.len());
+    }
+
+    let quotient = region.assign(&config.inputs[0], quotient)?;
+    let remainder = region.assign(&config.inputs[1], remainder)?;
+    region.increment(x.len());
+
+    let constant = |c: i128| -> ValTensor<F> {
+        Tensor::from([ValType::Constant(i128_to_felt::<F>(c))].into_iter()).into()
+    };
+
+    // x = quotient * divisor + remainder
+    let scaled = pairwise(
+        config,
+        region,
+        &[quotient.clone(), constant(d)],
+        BaseOp::Mult,
+    )?;
+    let recomposed = pairwise(config, region, &[scaled, remainder.clone()], BaseOp::Add)?;
+    enforce_equality(config, region, &[x, recomposed])?;
+
+    // remainder >= 0 and divisor - 1 - remainder >= 0
+    let upper = pairwise(
+        config,
+        region,
+        &[constant(d - 1), remainder.clone()],
+        BaseOp::Sub,
+    )?;
+    let mut ones = Tensor::new(
+        Some(&vec![ValType::Constant(F::ONE); remainder.len()]),
+        &[remainder.len()],
+    )?;
+    ones.reshape(remainder.dims());
+    let ones: ValTensor<F> = ones.into();
+    for bound in [remainder.clone(), upper] {
+        let in_range = nonlinearity(config, region, &[bound], &cmp::gte_lookup())?;
+        enforce_equality(config, region, &[ones.clone(), in_range])?;
+    }
+    // the lookup fails for quotients outside of the lookup range
+    nonlinearity(config, region, &[quotient.clone()], &cmp::gte_lookup())?;
+
+    Ok([quotient, remainder])
+}
+
+/// The elementwise quotient of the integer division of `x` by a constant `divisor`, see [div_rem]
+pub fn div<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    x: &ValTensor<F>,
+    divisor: u64,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let [quotient, _] = div_rem(config, region, x, divisor)?;
+    Ok(quotient)
+}
+
+/// The elementwise remainder of the integer division of `x` by a constant `divisor`, see [div_rem]
+pub fn rem<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    x: &ValTensor<F>,
+    divisor: u64,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let [_, remainder] = div_rem(config, region, x, divisor)?;
+    Ok(remainder)
+}
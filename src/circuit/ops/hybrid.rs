@@ -35,6 +35,10 @@ pub enum HybridOp {
         axes: Vec<usize>,
     },
     RangeCheck(Tolerance),
+    AbsRangeCheck {
+        tol: utils::F32,
+        scale: utils::F32,
+    },
     Greater,
     GreaterEqual,
     Less,
@@ -60,6 +64,127 @@ pub enum HybridOp {
         dim: usize,
         constant_idx: Option<Tensor<usize>>,
     },
+    ScatterND {
+        indices: Tensor<usize>,
+    },
+    SparseAffine,
+    ClassifierHead {
+        pool_dims: (usize, usize),
+    },
+    ResizeBilinear {
+        scale_factor: Vec<usize>,
+    },
+    ChannelRescale {
+        axis: usize,
+        multipliers: Vec<u64>,
+        denom: utils::F32,
+    },
+    HardSigmoid {
+        scale: utils::F32,
+        alpha: utils::F32,
+        beta: utils::F32,
+    },
+    HardSwish {
+        scale: utils::F32,
+    },
+    PiecewiseLinear {
+        scale: utils::F32,
+        breakpoints: Vec<utils::F32>,
+        slopes: Vec<utils::F32>,
+        intercepts: Vec<utils::F32>,
+    },
+}
+
+impl HybridOp {
+    /// Requantizes each slice of a tensor along `axis` by its own denominator, eg. the channels of a conv
+    /// whose kernel was quantized at a scale per channel. Each slice is multiplied by an integer constant and
+    /// the whole tensor divided by the largest denominator, so that a single [LookupOp::Div] table is shared
+    /// by all the channels. The division is exact when the denominators divide the largest one, as the
+    /// (power of two) multipliers of per channel scales do.
+    pub fn channel_rescale(axis: usize, denoms: &[f64]) -> Self {
+        let denom = denoms.iter().cloned().fold(1.0, f64::max);
+        HybridOp::ChannelRescale {
+            axis,
+            multipliers: denoms.iter().map(|d| (denom / d).round() as u64).collect(),
+            denom: utils::F32(denom as f32),
+        }
+    }
+
+    /// A piecewise linear activation of inputs at `scale`, with one slope and intercept for each of the segments
+    /// delimited by the strictly ascending `breakpoints`
+    pub fn piecewise_linear(
+        scale: f32,
+        breakpoints: &[f32],
+        slopes: &[f32],
+        intercepts: &[f32],
+    ) -> Result<Self, circuit::CircuitError> {
+        let to_f32 = |v: &[f32]| v.iter().map(|v| utils::F32(*v)).collect_vec();
+        let op = HybridOp::PiecewiseLinear {
+            scale: utils::F32(scale),
+            breakpoints: to_f32(breakpoints),
+            slopes: to_f32(slopes),
+            intercepts: to_f32(intercepts),
+        };
+        check_piecewise_linear(&op)?;
+        Ok(op)
+    }
+}
+
+/// Checks that a [HybridOp::PiecewiseLinear] has a slope and intercept per segment, and that its breakpoints
+/// don't collapse or cross once quantized, which would silently pick the wrong segments
+fn check_piecewise_linear(op: &HybridOp) -> Result<(), circuit::CircuitError> {
+    if let HybridOp::PiecewiseLinear {
+        scale,
+        breakpoints,
+        slopes,
+        intercepts,
+    } = op
+    {
+        if slopes.len() != breakpoints.len() + 1 || intercepts.len() != slopes.len() {
+            return Err(circuit::CircuitError::DimMismatch(
+                "piecewise_linear".to_string(),
+            ));
+        }
+        let quantized = breakpoints
+            .iter()
+            .map(|b| quantize_bound(*b, *scale))
+            .collect_vec();
+        if quantized.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(circuit::CircuitError::UnsortedBreakpoints(
+                breakpoints.iter().map(|b| b.0).collect(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The fixed point representation of a breakpoint of [HybridOp::PiecewiseLinear]
+fn quantize_bound(bound: utils::F32, scale: utils::F32) -> i128 {
+    (bound.0 as f64 * scale.0 as f64).round() as i128
+}
+
+/// The fixed point representation of the tolerance of [HybridOp::AbsRangeCheck], rounded down so that
+/// the check is never looser than the tolerance given
+fn quantize_tolerance(tol: utils::F32, scale: utils::F32) -> i128 {
+    (tol.0 as f64 * scale.0 as f64).floor() as i128
+}
+
+/// The inputs of the two comparisons [layouts::clip] looks up
+fn clip_intermediates(x: &Tensor<i128>, min: i128, max: i128) -> Vec<Tensor<i128>> {
+    vec![x.map(|v| v - min), x.map(|v| v.max(min) - max)]
+}
+
+/// The inputs of the comparisons [layouts::hard_sigmoid] looks up
+fn hard_sigmoid_intermediates(
+    x: &Tensor<i128>,
+    scale: utils::F32,
+    alpha: utils::F32,
+    beta: utils::F32,
+) -> Vec<Tensor<i128>> {
+    let s = scale.0 as f64;
+    let slope = (alpha.0 as f64 * s).round() as i128;
+    let offset = (beta.0 as f64 * s * s).round() as i128;
+    clip_intermediates(&x.map(|v| slope * v + offset), 0, (s * s).round() as i128)
 }
 
 impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
@@ -68,6 +193,7 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
         match self {
             HybridOp::Greater | HybridOp::Less | HybridOp::Equals => vec![0, 1],
             HybridOp::ScatterElements { .. } => vec![0, 2],
+            HybridOp::ScatterND { .. } => vec![0, 1],
             _ => vec![],
         }
     }
@@ -196,6 +322,99 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
                     (res.clone(), inter_equals)
                 }
             }
+            HybridOp::ScatterND { indices } => {
+                let updates = inputs[1].clone().map(|x| felt_to_i128(x));
+                (tensor::ops::scatter_nd(&x, indices, &updates)?, vec![])
+            }
+            HybridOp::SparseAffine => {
+                let idx = inputs[1].clone().map(|x| felt_to_i128(x) as usize);
+                let mut vals = inputs[2].clone().map(|x| felt_to_i128(x));
+                vals.flatten();
+                let indices = Tensor::from(0..x.dims()[1] as i128);
+                let inter_equals: Vec<Tensor<i128>> = vec![indices.clone(), -indices];
+                let mut gathered = tensor::ops::gather(&x, &idx, 1)?;
+                gathered.reshape(&[x.dims()[0], vals.len()]);
+                let res = tensor::ops::einsum("ij,j->i", &[gathered, vals])?;
+                (res, inter_equals)
+            }
+            HybridOp::ResizeBilinear { scale_factor } => {
+                let (new_shape, denom, coeffs) =
+                    tensor::ops::bilinear_resize_coeffs(x.dims(), scale_factor)?;
+                let numerators = Tensor::from(
+                    coeffs
+                        .iter()
+                        .map(|c| c.iter().map(|(idx, w)| x[*idx] * w).sum::<i128>()),
+                );
+                let mut res = tensor::ops::nonlinearities::const_div(&numerators, denom as f64);
+                res.reshape(&new_shape);
+                (res, vec![numerators])
+            }
+            HybridOp::HardSigmoid { scale, alpha, beta } => (
+                tensor::ops::nonlinearities::hard_sigmoid(
+                    &x,
+                    scale.0 as f64,
+                    alpha.0 as f64,
+                    beta.0 as f64,
+                ),
+                hard_sigmoid_intermediates(&x, *scale, *alpha, *beta),
+            ),
+            HybridOp::HardSwish { scale } => (
+                tensor::ops::nonlinearities::hard_swish(&x, scale.0 as f64),
+                hard_sigmoid_intermediates(&x, *scale, utils::F32(1.0 / 6.0), utils::F32(0.5)),
+            ),
+            HybridOp::PiecewiseLinear {
+                scale,
+                breakpoints,
+                slopes,
+                intercepts,
+            } => {
+                let to_f64 = |v: &[utils::F32]| v.iter().map(|v| v.0 as f64).collect_vec();
+                let res = tensor::ops::nonlinearities::piecewise_linear(
+                    &x,
+                    scale.0 as f64,
+                    &to_f64(breakpoints),
+                    &to_f64(slopes),
+                    &to_f64(intercepts),
+                )?;
+                let intermediates = breakpoints
+                    .iter()
+                    .map(|b| {
+                        let b = quantize_bound(*b, *scale);
+                        x.map(|v| v - b)
+                    })
+                    .collect();
+                (res, intermediates)
+            }
+            HybridOp::ChannelRescale {
+                axis,
+                multipliers,
+                denom,
+            } => {
+                let scaled = tensor::ops::nonlinearities::channel_mult(&x, *axis, multipliers)?;
+                let res = tensor::ops::nonlinearities::const_div(&scaled, denom.0 as f64);
+                (res, vec![scaled])
+            }
+            HybridOp::ClassifierHead { pool_dims } => {
+                let dims = x.dims().to_vec();
+                layouts::classifier_head_dims(
+                    &inputs.iter().map(|i| i.dims().to_vec()).collect_vec(),
+                    *pool_dims,
+                )?;
+                let summed = tensor::ops::sum_axes(&x, &[2, 3])?;
+                let mut pooled = tensor::ops::nonlinearities::const_div(
+                    &summed,
+                    (pool_dims.0 * pool_dims.1) as f64,
+                );
+                pooled.reshape(&[dims[0], dims[1]]);
+                let weights = inputs[1].clone().map(|x| felt_to_i128(x));
+                let mut res = tensor::ops::einsum("bc,oc->bo", &[pooled, weights])?;
+                if inputs.len() == 3 {
+                    let mut bias = inputs[2].clone().map(|x| felt_to_i128(x));
+                    bias.reshape(&[1, bias.len()]);
+                    res = (res + bias)?;
+                }
+                (res, vec![summed])
+            }
             HybridOp::MaxPool2d {
                 padding,
                 stride,
@@ -225,6 +444,15 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
                     vec![],
                 )
             }
+            HybridOp::AbsRangeCheck { tol, scale } => {
+                let y = inputs[1].clone().map(|x| felt_to_i128(x));
+                let tol = quantize_tolerance(*tol, *scale);
+                let diff = (x.clone() - y.clone())?;
+                (
+                    tensor::ops::nonlinearities::range_check_abs(&[x, y], tol),
+                    vec![diff.map(|v| v - tol), diff.map(|v| -tol - v)],
+                )
+            }
             HybridOp::Greater => {
                 let y = inputs[1].clone().map(|x| felt_to_i128(x));
                 tensor::ops::greater(&x, &y)?
@@ -274,6 +502,7 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
                 format!("SOFTMAX (scale={}, axes={:?})", scale, axes)
             }
             HybridOp::RangeCheck(p) => format!("RANGECHECK (tol={:?})", p),
+            HybridOp::AbsRangeCheck { tol, .. } => format!("ABSRANGECHECK (tol={})", tol),
             HybridOp::Greater => "GREATER".into(),
             HybridOp::GreaterEqual => "GREATEREQUAL".into(),
             HybridOp::Less => "LESS".into(),
@@ -283,9 +512,32 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
             HybridOp::TopK { k, dim } => format!("TOPK (k={}, dim={})", k, dim),
             HybridOp::GatherElements { dim, .. } => format!("GATHERELEMENTS (dim={})", dim),
             HybridOp::ScatterElements { dim, .. } => format!("SCATTERELEMENTS (dim={})", dim),
+            HybridOp::ScatterND { .. } => "SCATTERND".into(),
+            HybridOp::SparseAffine => "SPARSEAFFINE".into(),
+            HybridOp::ClassifierHead { pool_dims } => {
+                format!("CLASSIFIERHEAD (pool_dims={:?})", pool_dims)
+            }
+            HybridOp::ResizeBilinear { scale_factor } => {
+                format!("RESIZEBILINEAR (scale_factor={:?})", scale_factor)
+            }
             HybridOp::OneHot { dim, num_classes } => {
                 format!("ONEHOT (dim={}, num_classes={})", dim, num_classes)
             }
+            HybridOp::ChannelRescale {
+                axis,
+                multipliers,
+                denom,
+            } => format!(
+                "CHANNELRESCALE (axis={}, multipliers={:?}, denom={})",
+                axis, multipliers, denom
+            ),
+            HybridOp::HardSigmoid { alpha, beta, .. } => {
+                format!("HARDSIGMOID (alpha={}, beta={})", alpha, beta)
+            }
+            HybridOp::HardSwish { .. } => "HARDSWISH".into(),
+            HybridOp::PiecewiseLinear { breakpoints, .. } => {
+                format!("PIECEWISELINEAR (breakpoints={:?})", breakpoints)
+            }
         }
     }
 
@@ -323,6 +575,22 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
                     layouts::scatter_elements(config, region, values[..].try_into()?, *dim)?
                 }
             }
+            // the indices are constant so this is pure rewiring of the assigned cells
+            HybridOp::ScatterND { indices } => tensor::ops::scatter_nd(
+                values[0].get_inner_tensor()?,
+                indices,
+                values[1].get_inner_tensor()?,
+            )?
+            .into(),
+            HybridOp::SparseAffine => {
+                layouts::sparse_affine(config, region, values[..].try_into()?)?
+            }
+            HybridOp::ClassifierHead { pool_dims } => {
+                layouts::classifier_head(config, region, values, *pool_dims)?
+            }
+            HybridOp::ResizeBilinear { scale_factor } => {
+                layouts::resize_bilinear(config, region, values[..].try_into()?, scale_factor)?
+            }
             HybridOp::MaxPool2d {
                 padding,
                 stride,
@@ -357,6 +625,12 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
                 tol.scale,
                 tol.val,
             )?,
+            HybridOp::AbsRangeCheck { tol, scale } => layouts::range_check_abs(
+                config,
+                region,
+                values[..].try_into()?,
+                quantize_tolerance(*tol, *scale),
+            )?,
             HybridOp::Greater => layouts::greater(config, region, values[..].try_into()?)?,
             HybridOp::GreaterEqual => {
                 layouts::greater_equal(config, region, values[..].try_into()?)?
@@ -370,6 +644,46 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
             HybridOp::OneHot { dim, num_classes } => {
                 layouts::one_hot_axis(config, region, values[..].try_into()?, *num_classes, *dim)?
             }
+            HybridOp::ChannelRescale {
+                axis,
+                multipliers,
+                denom,
+            } => layouts::channel_rescale(
+                config,
+                region,
+                values[..].try_into()?,
+                *axis,
+                multipliers,
+                *denom,
+            )?,
+            HybridOp::HardSigmoid { scale, alpha, beta } => layouts::hard_sigmoid(
+                config,
+                region,
+                values[..].try_into()?,
+                *scale,
+                *alpha,
+                *beta,
+            )?,
+            HybridOp::HardSwish { scale } => {
+                layouts::hard_swish(config, region, values[..].try_into()?, *scale)?
+            }
+            HybridOp::PiecewiseLinear {
+                scale,
+                breakpoints,
+                slopes,
+                intercepts,
+            } => {
+                check_piecewise_linear(self)?;
+                layouts::piecewise_linear(
+                    config,
+                    region,
+                    values[..].try_into()?,
+                    *scale,
+                    breakpoints,
+                    slopes,
+                    intercepts,
+                )?
+            }
         }))
     }
 
@@ -382,7 +696,12 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
             | HybridOp::ReduceArgMax { .. }
             | HybridOp::OneHot { .. }
             | HybridOp::ReduceArgMin { .. } => 0,
-            HybridOp::Softmax { .. } => 2 * in_scales[0],
+            HybridOp::Softmax { .. }
+            | HybridOp::HardSigmoid { .. }
+            | HybridOp::PiecewiseLinear { .. } => 2 * in_scales[0],
+            HybridOp::HardSwish { .. } => 3 * in_scales[0],
+            HybridOp::SparseAffine => in_scales[0] + in_scales[2],
+            HybridOp::ClassifierHead { .. } => in_scales[0] + in_scales[1],
             _ => in_scales[0],
         }
     }
@@ -394,10 +713,8 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
             | HybridOp::MaxPool2d { .. } => Op::<F>::required_lookups(&LookupOp::ReLU),
             HybridOp::Softmax { scale, .. } => {
                 vec![
-                    LookupOp::Exp { scale: *scale },
-                    LookupOp::Recip {
-                        scale: scale.0.powf(2.0).into(),
-                    },
+                    mathops::exp_lookup(*scale),
+                    mathops::recip_lookup(*scale, *scale),
                 ]
             }
             HybridOp::RangeCheck(tol) => {
@@ -405,9 +722,7 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
                 if tol.val > 0.0 {
                     let scale_squared = tol.scale.0.powf(2.0);
                     lookups.extend([
-                        LookupOp::Recip {
-                            scale: scale_squared.into(),
-                        },
+                        mathops::recip_lookup(tol.scale, tol.scale),
                         LookupOp::GreaterThan {
                             a: circuit::utils::F32((tol.val * scale_squared) / 100.0),
                         },
@@ -415,24 +730,22 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
                 }
                 lookups
             }
-            HybridOp::Greater { .. } | HybridOp::Less { .. } => {
-                vec![LookupOp::GreaterThan {
-                    a: circuit::utils::F32(0.),
-                }]
+            HybridOp::AbsRangeCheck { tol, .. } => {
+                if tol.0 > 0.0 {
+                    vec![cmp::gt_lookup()]
+                } else {
+                    vec![]
+                }
             }
+            HybridOp::Greater { .. }
+            | HybridOp::Less { .. }
+            | HybridOp::HardSigmoid { .. }
+            | HybridOp::HardSwish { .. }
+            | HybridOp::PiecewiseLinear { .. } => vec![cmp::gt_lookup()],
             HybridOp::GreaterEqual { .. } | HybridOp::LessEqual { .. } => {
-                vec![LookupOp::GreaterThanEqual {
-                    a: circuit::utils::F32(0.),
-                }]
-            }
-            HybridOp::TopK { .. } => {
-                vec![
-                    LookupOp::GreaterThan {
-                        a: circuit::utils::F32(0.),
-                    },
-                    LookupOp::KroneckerDelta,
-                ]
+                vec![cmp::gte_lookup()]
             }
+            HybridOp::TopK { .. } => vec![cmp::gt_lookup(), cmp::eq_lookup()],
             HybridOp::Gather {
                 constant_idx: None, ..
             }
@@ -443,12 +756,31 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for HybridOp {
             | HybridOp::ScatterElements {
                 constant_idx: None, ..
             }
+            | HybridOp::SparseAffine
             | HybridOp::Equals { .. } => {
                 vec![LookupOp::KroneckerDelta]
             }
             HybridOp::ReduceArgMax { .. } | HybridOp::ReduceArgMin { .. } => {
                 vec![LookupOp::ReLU, LookupOp::KroneckerDelta]
             }
+            HybridOp::ClassifierHead { pool_dims } => {
+                vec![LookupOp::Div {
+                    denom: circuit::utils::F32((pool_dims.0 * pool_dims.1) as f32),
+                }]
+            }
+            HybridOp::ResizeBilinear { scale_factor } => {
+                // the weights of each upsampled axis are in units of 1 / (2 * scale)
+                let denom = scale_factor
+                    .iter()
+                    .map(|s| if *s == 1 { 1 } else { 2 * s })
+                    .product::<usize>();
+                vec![LookupOp::Div {
+                    denom: circuit::utils::F32(denom as f32),
+                }]
+            }
+            HybridOp::ChannelRescale { denom, .. } if denom.0 != 1.0 => {
+                vec![LookupOp::Div { denom: *denom }]
+            }
             _ => vec![],
         }
     }
@@ -17,11 +17,12 @@ use rayon::{
 
 use super::{
     chip::{BaseConfig, CircuitError},
+    cmp, mathops,
     region::RegionCtx,
 };
 use crate::{
     circuit::{ops::base::BaseOp, utils},
-    fieldutils::i128_to_felt,
+    fieldutils::{felt_to_i128, i128_to_felt},
     tensor::{
         get_broadcasted_shape,
         ops::{accumulated, add, mult, sub},
@@ -53,7 +54,27 @@ pub fn overflowed_len(starting_idx: usize, mut total_len: usize, column_len: usi
     total_len
 }
 
-/// Dot product accumulated layout
+/// The number of gates enabled by the accumulated layouts for `len` outputs, which skip the
+/// duplicated outputs at the start of each column
+fn accumulated_gates<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &RegionCtx<F>,
+    len: usize,
+    block_width: usize,
+) -> usize {
+    (0..len)
+        .filter(|i| {
+            let (_, _, z) = config
+                .output
+                .cartesian_coord(region.linear_coord() + i * block_width);
+            z != 0 || *i == 0
+        })
+        .count()
+}
+
+/// Dot product accumulated layout. Rather than one wide expression per output, the products
+/// are folded into a running sum, `num_inner_cols` multiply-accumulates per row, so the gate
+/// degree stays constant however long the inputs are.
 pub fn dot<F: PrimeField + TensorType + PartialOrd>(
     config: &BaseConfig<F>,
     region: &mut RegionCtx<F>,
@@ -144,6 +165,9 @@ pub fn dot<F: PrimeField + TensorType + PartialOrd>(
             };
             region.enable(selector, z).unwrap();
         });
+    } else {
+        let gates = accumulated_gates(config, region, output_assigned_len, block_width);
+        region.increment_gates(gates);
     }
 
     let last_elem = output
@@ -265,7 +289,8 @@ pub fn einsum<F: PrimeField + TensorType + PartialOrd>(
 
     let inner_loop_function = |i: usize, region: &mut RegionCtx<'_, F>| -> ValType<F> {
         let coord = cartesian_coord[i].clone();
-        // Compute the slice of each input tensor given the current coordinate of the output tensor
+        // Compute the slice of each input tensor given the current coordinate of the output tensor,
+        // one coordinate at a time so that only the slices of the output being assigned are held in memory
         let inputs = (0..inputs.len())
             .map(|idx| {
                 let mut slice = vec![];
@@ -620,6 +645,8 @@ fn one_hot<F: PrimeField + TensorType + PartialOrd>(
             let selector = config.selectors.get(&(BaseOp::IsBoolean, x, y));
             region.enable(selector, z)?;
         }
+    } else {
+        region.increment_gates(assigned_output.len());
     }
     region.increment(std::cmp::max(assigned_output.len(), assigned_input.len()));
 
@@ -792,6 +819,177 @@ pub fn gather<F: PrimeField + TensorType + PartialOrd>(
     Ok(output.into())
 }
 
+/// Sparse affine layout: multiplies a dense weight matrix by a sparse input vector given as (index, value) pairs.
+/// Only the nonzero entries of the input are witnessed, and the gather constrains the indices to valid columns.
+pub fn sparse_affine<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 3],
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let (weights, indices, mut input) = (values[0].clone(), values[1].clone(), values[2].clone());
+    if weights.dims().len() != 2 {
+        return Err(Box::new(TensorError::DimMismatch(
+            "sparse_affine".to_string(),
+        )));
+    }
+    input.flatten();
+
+    let mut gathered = gather(config, region, &[weights.clone(), indices], 1)?;
+    // gather drops the axis for singleton indices
+    gathered.reshape(&[weights.dims()[0], input.len()])?;
+
+    einsum(config, region, &[gathered, input], "ij,j->i")
+}
+
+/// The layouts of [sparse_linear] and [ternary_linear] (and so the verifying key) depend on which
+/// weights are zero, which would leak the structure of private weights
+fn check_structural_weights<F: PrimeField + TensorType + PartialOrd>(
+    weights: &Tensor<F>,
+    op: &str,
+) -> Result<(), Box<dyn Error>> {
+    match weights.visibility() {
+        Some(v) if v.is_fixed() || v.is_public() => Ok(()),
+        _ => Err(Box::new(CircuitError::PrivateStructuralWeights(
+            op.to_string(),
+        ))),
+    }
+}
+
+/// Sparse linear layout: multiplies a (pruned) weight matrix by an input vector, allocating cells
+/// and terms only for the nonzero weights. The sparsity pattern is read off the weights, which are
+/// known when the circuit is configured, so only the values of the nonzero weights are witnessed.
+/// The weights must be fixed or public, as the layout depends on them.
+pub fn sparse_linear<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>],
+    weights: &Tensor<F>,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    check_structural_weights(weights, "sparse_linear")?;
+    let has_bias = values.len() == 3;
+    let mut input = values[0].clone();
+    input.flatten();
+
+    if weights.dims().len() != 2 || weights.dims()[1] != input.len() {
+        return Err(Box::new(TensorError::DimMismatch(
+            "sparse_linear".to_string(),
+        )));
+    }
+    let (out_dim, in_dim) = (weights.dims()[0], weights.dims()[1]);
+
+    // every row reads from the same input, so we assign it once and copy from there
+    if !input.all_prev_assigned() {
+        input = region.assign(&config.inputs[1], &input)?;
+        region.increment(input.len());
+    }
+    let input = input.get_inner_tensor()?;
+    let weight_values = values[1].get_inner_tensor()?;
+
+    let mut output: Tensor<ValType<F>> = Tensor::new(None, &[out_dim])?;
+    for (o, res) in output.iter_mut().enumerate() {
+        // CSR-style: the columns of the nonzero weights of this row
+        let nonzero = (0..in_dim)
+            .filter(|i| weights[o * in_dim + i] != F::ZERO)
+            .collect::<Vec<_>>();
+        *res = if nonzero.is_empty() {
+            ValType::Constant(F::ZERO)
+        } else {
+            let x: ValTensor<F> = Tensor::from(nonzero.iter().map(|i| input[*i].clone())).into();
+            let w: ValTensor<F> = Tensor::from(
+                nonzero
+                    .iter()
+                    .map(|i| weight_values[o * in_dim + i].clone()),
+            )
+            .into();
+            dot(config, region, &[x, w])?.get_inner_tensor()?[0].clone()
+        };
+    }
+
+    let mut output: ValTensor<F> = output.into();
+    if has_bias {
+        output = pairwise(config, region, &[output, values[2].clone()], BaseOp::Add)?;
+    }
+
+    Ok(output)
+}
+
+/// Ternary linear layout: multiplies a weight matrix with entries in {-1, 0, 1} by an input
+/// vector without any multiplications -- each output is the sum of the inputs with weight 1 minus
+/// the sum of the inputs with weight -1. As with [sparse_linear], the weights are read when the
+/// circuit is configured and so become part of its structure: they must be fixed or public.
+pub fn ternary_linear<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>],
+    weights: &Tensor<F>,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    check_structural_weights(weights, "ternary_linear")?;
+    let has_bias = values.len() == 2;
+    let mut input = values[0].clone();
+    input.flatten();
+
+    if weights.dims().len() != 2 || weights.dims()[1] != input.len() {
+        return Err(Box::new(TensorError::DimMismatch(
+            "ternary_linear".to_string(),
+        )));
+    }
+    if weights
+        .iter()
+        .any(|w| *w != F::ZERO && *w != F::ONE && *w != -F::ONE)
+    {
+        return Err(Box::new(CircuitError::UnsupportedOp));
+    }
+    let (out_dim, in_dim) = (weights.dims()[0], weights.dims()[1]);
+
+    // every row reads from the same input, so we assign it once and copy from there
+    if !input.all_prev_assigned() {
+        input = region.assign(&config.inputs[1], &input)?;
+        region.increment(input.len());
+    }
+    let input = input.get_inner_tensor()?;
+
+    let mut output: Tensor<ValType<F>> = Tensor::new(None, &[out_dim])?;
+    for (o, res) in output.iter_mut().enumerate() {
+        let row = &weights[o * in_dim..(o + 1) * in_dim];
+        let gather = |w: F| -> Option<ValTensor<F>> {
+            let selected = (0..in_dim)
+                .filter(|i| row[*i] == w)
+                .map(|i| input[i].clone())
+                .collect::<Vec<_>>();
+            if selected.is_empty() {
+                None
+            } else {
+                Some(Tensor::from(selected.into_iter()).into())
+            }
+        };
+
+        let summed = match (gather(F::ONE), gather(-F::ONE)) {
+            (None, None) => None,
+            (Some(pos), None) => Some(sum(config, region, &[pos])?),
+            (None, Some(neg_terms)) => {
+                let neg_sum = sum(config, region, &[neg_terms])?;
+                Some(neg(config, region, &[neg_sum])?)
+            }
+            (Some(pos), Some(neg_terms)) => {
+                let pos_sum = sum(config, region, &[pos])?;
+                let neg_sum = sum(config, region, &[neg_terms])?;
+                Some(pairwise(config, region, &[pos_sum, neg_sum], BaseOp::Sub)?)
+            }
+        };
+        *res = match summed {
+            Some(t) => t.get_inner_tensor()?[0].clone(),
+            None => ValType::Constant(F::ZERO),
+        };
+    }
+
+    let mut output: ValTensor<F> = output.into();
+    if has_bias {
+        output = pairwise(config, region, &[output, values[1].clone()], BaseOp::Add)?;
+    }
+
+    Ok(output)
+}
+
 /// Gather accumulated layout
 pub fn gather_elements<F: PrimeField + TensorType + PartialOrd>(
     config: &BaseConfig<F>,
@@ -1026,7 +1224,26 @@ pub fn sum<F: PrimeField + TensorType + PartialOrd>(
         true,
     )?;
 
-    // enable the selectors
+    enable_sum_selectors(config, region, output_assigned_len, block_width)?;
+
+    let last_elem = output
+        .get_slice(&[output.len() - 1..output.len()])
+        .expect("accum poly: failed to fetch last elem");
+
+    region.increment(assigned_len);
+
+    // last element is the result
+    Ok(last_elem)
+}
+
+/// Enables the running sum gates of the `output_assigned_len` rows assigned from the current
+/// linear coord by [sum] or [cumsum]
+fn enable_sum_selectors<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    output_assigned_len: usize,
+    block_width: usize,
+) -> Result<(), Box<dyn Error>> {
     if !region.is_dummy() {
         for i in 0..output_assigned_len {
             let (x, _, z) = config
@@ -1044,16 +1261,92 @@ pub fn sum<F: PrimeField + TensorType + PartialOrd>(
 
             region.enable(selector, z)?;
         }
+    } else {
+        let gates = accumulated_gates(config, region, output_assigned_len, block_width);
+        region.increment_gates(gates);
     }
+    Ok(())
+}
 
-    let last_elem = output
-        .get_slice(&[output.len() - 1..output.len()])
-        .expect("accum poly: failed to fetch last elem");
+/// Cumulative sum of a 1D tensor, using the running sum gate of [sum]
+fn cumsum_lane<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    lane: &ValTensor<F>,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    region.flush();
+
+    let block_width = config.output.num_inner_cols();
+
+    // each element starts a row of its own (padded with zeros), so the running sum output at every
+    // row is one element of the cumulative sum
+    let mut spread = Tensor::new(
+        Some(&vec![ValType::Constant(F::ZERO); lane.len() * block_width]),
+        &[lane.len() * block_width],
+    )?;
+    for (i, v) in lane.get_inner_tensor()?.iter().enumerate() {
+        spread[i * block_width] = v.clone();
+    }
+
+    let (res, assigned_len) = region.assign_with_duplication(
+        &config.inputs[1],
+        &spread.into(),
+        &config.check_mode,
+        false,
+    )?;
+    let input = res.get_inner()?;
+
+    let accumulated_sum = accumulated::sum(&input, block_width)?;
+
+    let (output, output_assigned_len) = region.assign_with_duplication(
+        &config.output,
+        &accumulated_sum.into(),
+        &config.check_mode,
+        true,
+    )?;
+
+    enable_sum_selectors(config, region, output_assigned_len, block_width)?;
 
     region.increment(assigned_len);
 
-    // last element is the result
-    Ok(last_elem)
+    Ok(output)
+}
+
+/// Cumulative sum along `axis` layout
+pub fn cumsum<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 1],
+    axis: usize,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let dims = values[0].dims().to_vec();
+    if axis >= dims.len() {
+        return Err(Box::new(TensorError::DimMismatch("cumsum".to_string())));
+    }
+    let last = dims.len() - 1;
+    let axis_len = dims[axis];
+
+    // make the summed axis the innermost so that every lane is contiguous
+    let mut input = values[0].clone();
+    input.move_axis(axis, last)?;
+    let moved_dims = input.dims().to_vec();
+    input.flatten();
+
+    let mut output: Vec<ValType<F>> = Vec::with_capacity(input.len());
+    for start in (0..input.len()).step_by(axis_len.max(1)) {
+        let lane = input.get_slice(&[start..start + axis_len])?;
+        output.extend(
+            cumsum_lane(config, region, &lane)?
+                .get_inner_tensor()?
+                .clone(),
+        );
+    }
+
+    let mut output: ValTensor<F> = Tensor::new(Some(&output), &[output.len()])?.into();
+    output.reshape(&moved_dims)?;
+    output.move_axis(last, axis)?;
+
+    Ok(output)
 }
 
 /// product accumulated layout
@@ -1116,6 +1409,9 @@ pub fn prod<F: PrimeField + TensorType + PartialOrd>(
 
             region.enable(selector, z).unwrap();
         });
+    } else {
+        let gates = accumulated_gates(config, region, output_assigned_len, block_width);
+        region.increment_gates(gates);
     }
 
     let last_elem = output
@@ -1217,6 +1513,23 @@ pub fn sum_axes<F: PrimeField + TensorType + PartialOrd>(
     axes_wise_op(config, region, values, axes, sum)
 }
 
+/// Mean over `axes` layout. The division by the number of reduced elements is a multiplication by
+/// `1 / n` quantized at `scale`, so the output is at the input scale + `scale` and is rescaled like
+/// any other product, without a division lookup.
+pub fn reduce_mean<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 1],
+    axes: &[usize],
+    scale: crate::Scale,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let inv_n =
+        crate::circuit::ops::poly::PolyOp::<F>::quantized_inv_len(values[0].dims(), axes, scale)?;
+    let sum = sum_axes(config, region, values, axes)?;
+    let inv_n: ValTensor<F> = Tensor::from(vec![ValType::Constant(inv_n)].into_iter()).into();
+    pairwise(config, region, &[sum, inv_n], BaseOp::Mult)
+}
+
 /// argmax layout
 pub fn argmax_axes<F: PrimeField + TensorType + PartialOrd>(
     config: &BaseConfig<F>,
@@ -1374,6 +1687,8 @@ pub fn pairwise<F: PrimeField + TensorType + PartialOrd>(
 
             region.enable(selector, z).unwrap();
         });
+    } else {
+        region.increment_gates(assigned_len);
     }
     region.increment(assigned_len);
 
@@ -1438,21 +1753,7 @@ pub fn greater<F: PrimeField + TensorType + PartialOrd>(
     region: &mut RegionCtx<F>,
     values: &[ValTensor<F>; 2],
 ) -> Result<ValTensor<F>, Box<dyn Error>> {
-    let (mut lhs, mut rhs) = (values[0].clone(), values[1].clone());
-
-    let broadcasted_shape = get_broadcasted_shape(lhs.dims(), rhs.dims())?;
-
-    lhs.expand(&broadcasted_shape)?;
-    rhs.expand(&broadcasted_shape)?;
-
-    let diff = pairwise(config, region, &[lhs, rhs], BaseOp::Sub)?;
-
-    nonlinearity(
-        config,
-        region,
-        &[diff],
-        &LookupOp::GreaterThan { a: utils::F32(0.) },
-    )
+    cmp::gt(config, region, &values[0], &values[1])
 }
 
 ///
@@ -1461,21 +1762,7 @@ pub fn greater_equal<F: PrimeField + TensorType + PartialOrd>(
     region: &mut RegionCtx<F>,
     values: &[ValTensor<F>; 2],
 ) -> Result<ValTensor<F>, Box<dyn Error>> {
-    let (mut lhs, mut rhs) = (values[0].clone(), values[1].clone());
-
-    let broadcasted_shape = get_broadcasted_shape(lhs.dims(), rhs.dims())?;
-
-    lhs.expand(&broadcasted_shape)?;
-    rhs.expand(&broadcasted_shape)?;
-
-    let diff = pairwise(config, region, &[lhs, rhs], BaseOp::Sub)?;
-
-    nonlinearity(
-        config,
-        region,
-        &[diff],
-        &LookupOp::GreaterThanEqual { a: utils::F32(0.) },
-    )
+    cmp::gte(config, region, &values[0], &values[1])
 }
 
 ///
@@ -1484,8 +1771,7 @@ pub fn less<F: PrimeField + TensorType + PartialOrd>(
     region: &mut RegionCtx<F>,
     values: &[ValTensor<F>; 2],
 ) -> Result<ValTensor<F>, Box<dyn Error>> {
-    // just flip the order and use greater
-    greater(config, region, &[values[1].clone(), values[0].clone()])
+    cmp::lt(config, region, &values[0], &values[1])
 }
 
 ///
@@ -1494,8 +1780,7 @@ pub fn less_equal<F: PrimeField + TensorType + PartialOrd>(
     region: &mut RegionCtx<F>,
     values: &[ValTensor<F>; 2],
 ) -> Result<ValTensor<F>, Box<dyn Error>> {
-    // just flip the order and use greater
-    greater_equal(config, region, &[values[1].clone(), values[0].clone()])
+    cmp::lte(config, region, &values[0], &values[1])
 }
 
 /// And boolean operation
@@ -1531,11 +1816,7 @@ pub fn equals<F: PrimeField + TensorType + PartialOrd>(
     region: &mut RegionCtx<F>,
     values: &[ValTensor<F>; 2],
 ) -> Result<ValTensor<F>, Box<dyn Error>> {
-    let diff = pairwise(config, region, values, BaseOp::Sub)?;
-
-    let res = nonlinearity(config, region, &[diff], &LookupOp::KroneckerDelta)?;
-
-    Ok(res)
+    cmp::eq(config, region, &values[0], &values[1])
 }
 
 /// Xor boolean operation
@@ -1608,6 +1889,8 @@ pub fn iff<F: PrimeField + TensorType + PartialOrd>(
             let selector = config.selectors.get(&(BaseOp::IsBoolean, x, y));
             region.enable(selector, z).unwrap();
         });
+    } else {
+        region.increment_gates(assigned_mask.len());
     }
 
     region.increment(assigned_mask.len());
@@ -1623,6 +1906,135 @@ pub fn iff<F: PrimeField + TensorType + PartialOrd>(
     Ok(res)
 }
 
+/// Clips `values[0]` to `[min, max]` with two comparisons and two selects, as opposed to a
+/// dedicated lookup table per pair of bounds
+pub fn clip<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 1],
+    min: i128,
+    max: i128,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let x = values[0].clone();
+    let min: ValTensor<F> =
+        Tensor::from(vec![ValType::Constant(i128_to_felt(min))].into_iter()).into();
+    let max: ValTensor<F> =
+        Tensor::from(vec![ValType::Constant(i128_to_felt(max))].into_iter()).into();
+
+    // x > min ? x : min
+    let above_min = greater(config, region, &[x.clone(), min.clone()])?;
+    let lower = iff(config, region, &[above_min, x, min])?;
+
+    // lower > max ? max : lower
+    let above_max = greater(config, region, &[lower.clone(), max.clone()])?;
+    iff(config, region, &[above_max, max, lower])
+}
+
+/// Hard sigmoid layout, ie. `clip(alpha * x + beta, 0, 1)`. The output is at the square of the input scale.
+pub fn hard_sigmoid<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 1],
+    scale: utils::F32,
+    alpha: utils::F32,
+    beta: utils::F32,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let scale = scale.0 as f64;
+    let slope: ValTensor<F> = Tensor::from(
+        vec![ValType::Constant(i128_to_felt(
+            (alpha.0 as f64 * scale).round() as i128,
+        ))]
+        .into_iter(),
+    )
+    .into();
+    let offset: ValTensor<F> = Tensor::from(
+        vec![ValType::Constant(i128_to_felt(
+            (beta.0 as f64 * scale * scale).round() as i128,
+        ))]
+        .into_iter(),
+    )
+    .into();
+
+    let scaled = pairwise(config, region, &[values[0].clone(), slope], BaseOp::Mult)?;
+    let shifted = pairwise(config, region, &[scaled, offset], BaseOp::Add)?;
+
+    clip(
+        config,
+        region,
+        &[shifted],
+        0,
+        (scale * scale).round() as i128,
+    )
+}
+
+/// Hard swish layout, ie. `x * hard_sigmoid(x)` with `alpha = 1 / 6` and `beta = 0.5`. The output is at the cube of the input scale.
+pub fn hard_swish<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 1],
+    scale: utils::F32,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let gate = hard_sigmoid(
+        config,
+        region,
+        values,
+        scale,
+        utils::F32(1.0 / 6.0),
+        utils::F32(0.5),
+    )?;
+    pairwise(config, region, &[values[0].clone(), gate], BaseOp::Mult)
+}
+
+/// Piecewise linear activation layout, see [crate::tensor::ops::nonlinearities::piecewise_linear].
+/// Each segment is evaluated with a multiplication and an addition, and the segment an input falls into is
+/// picked with one comparison and one select per breakpoint. The output is at the square of the input scale.
+pub fn piecewise_linear<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 1],
+    scale: utils::F32,
+    breakpoints: &[utils::F32],
+    slopes: &[utils::F32],
+    intercepts: &[utils::F32],
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    if slopes.len() != breakpoints.len() + 1 || intercepts.len() != slopes.len() {
+        return Err(Box::new(CircuitError::DimMismatch(
+            "piecewise_linear layout".to_string(),
+        )));
+    }
+    let scale = scale.0 as f64;
+    let constant = |v: f64| -> ValTensor<F> {
+        Tensor::from(vec![ValType::Constant(i128_to_felt(v.round() as i128))].into_iter()).into()
+    };
+
+    let x = values[0].clone();
+    let mut segments = vec![];
+    for (m, c) in slopes.iter().zip(intercepts) {
+        let scaled = pairwise(
+            config,
+            region,
+            &[x.clone(), constant(m.0 as f64 * scale)],
+            BaseOp::Mult,
+        )?;
+        segments.push(pairwise(
+            config,
+            region,
+            &[scaled, constant(c.0 as f64 * scale * scale)],
+            BaseOp::Add,
+        )?);
+    }
+
+    // breakpoints are ascending, so the last one that x exceeds picks its segment
+    let mut segments = segments.into_iter();
+    let mut res = segments.next().unwrap();
+    for (b, segment) in breakpoints.iter().zip(segments) {
+        let above = greater(config, region, &[x.clone(), constant(b.0 as f64 * scale)])?;
+        res = iff(config, region, &[above, segment, res])?;
+    }
+
+    Ok(res)
+}
+
 /// Negation operation accumulated layout
 pub fn neg<F: PrimeField + TensorType + PartialOrd>(
     config: &BaseConfig<F>,
@@ -1647,6 +2059,8 @@ pub fn neg<F: PrimeField + TensorType + PartialOrd>(
 
             region.enable(selector, z).unwrap();
         });
+    } else {
+        region.increment_gates(values[0].len());
     }
 
     region.increment(output.len());
@@ -1703,6 +2117,70 @@ pub fn sumpool<F: PrimeField + TensorType + PartialOrd>(
     Ok(last_elem)
 }
 
+/// Checks the shapes of the inputs of a [classifier_head]: an (N, C, H, W) image, (O, C) weights and an optional
+/// bias of O elements. The pool is global, so `pool_dims` has to cover the whole (H, W) image.
+pub fn classifier_head_dims(
+    dims: &[Vec<usize>],
+    pool_dims: (usize, usize),
+) -> Result<(), TensorError> {
+    let err = |msg: String| Err(TensorError::DimMismatch(msg));
+    if dims.len() != 2 && dims.len() != 3 {
+        return err("classifier_head".to_string());
+    }
+    let (image, weights) = (&dims[0], &dims[1]);
+    if image.len() != 4 || weights.len() != 2 || weights[1] != image[1] {
+        return err("classifier_head".to_string());
+    }
+    if dims.len() == 3 && dims[2].iter().product::<usize>() != weights[0] {
+        return err("classifier_head bias".to_string());
+    }
+    if pool_dims != (image[2], image[3]) {
+        return err(format!(
+            "classifier_head pools over the whole image, but pool_dims {:?} don't match its spatial dims {:?}",
+            pool_dims,
+            (image[2], image[3])
+        ));
+    }
+    Ok(())
+}
+
+/// Classifier head layout: a global average pool over the spatial dims of a `B x C x H x W` image,
+/// a flatten to `B x C` (pure reindexing, no constraints), and an affine layer with `O x C` weights and an optional bias of length `O`.
+pub fn classifier_head<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>],
+    pool_dims: (usize, usize),
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let dims = values.iter().map(|v| v.dims().to_vec()).collect_vec();
+    classifier_head_dims(&dims, pool_dims)?;
+    let (image, weights) = (&values[0], &values[1]);
+    let (batch, channels, height, width) = (
+        image.dims()[0],
+        image.dims()[1],
+        image.dims()[2],
+        image.dims()[3],
+    );
+    let out_features = weights.dims()[0];
+
+    let summed = sum_axes(config, region, &[image.clone()], &[2, 3])?;
+    let nl = LookupOp::Div {
+        denom: utils::F32((height * width) as f32),
+    };
+    let mut pooled = nonlinearity(config, region, &[summed], &nl)?;
+    pooled.reshape(&[batch, channels])?;
+
+    let mut res = einsum(config, region, &[pooled, weights.clone()], "bc,oc->bo")?;
+
+    if values.len() == 3 {
+        let mut bias = values[2].clone();
+        bias.reshape(&[1, out_features])?;
+        res = pairwise(config, region, &[res, bias], BaseOp::Add)?;
+    }
+
+    Ok(res)
+}
+
 /// Convolution accumulated layout
 pub fn max_pool2d<F: PrimeField + TensorType + PartialOrd>(
     config: &BaseConfig<F>,
@@ -1927,6 +2405,14 @@ pub fn conv<F: PrimeField + TensorType + PartialOrd + std::marker::Send + std::m
         return Err(Box::new(TensorError::DimMismatch("conv".to_string())));
     }
 
+    // one bias term per output channel
+    if has_bias {
+        let bias = &values[2];
+        if (bias.dims().len() != 1) || (bias.dims()[0] != kernel.dims()[0]) {
+            return Err(Box::new(TensorError::DimMismatch("conv bias".to_string())));
+        }
+    }
+
     let image_dims = image.dims();
     let kernel_dims = kernel.dims();
 
@@ -1962,27 +2448,15 @@ pub fn conv<F: PrimeField + TensorType + PartialOrd + std::marker::Send + std::m
 
     let mut output: Tensor<ValType<F>> = Tensor::new(None, &[num_outputs])?;
 
-    let cartesian_coord = [
-        (0..batch_size),
-        (0..num_groups),
-        (0..output_channels_per_group),
-        (0..vert_slides),
-        (0..horz_slides),
-    ]
-    .iter()
-    .cloned()
-    .multi_cartesian_product()
-    .collect::<Vec<_>>();
-
-    let inner_loop_function = |idx: usize, region: &mut RegionCtx<F>| -> ValType<F> {
-        let cartesian_coord_per_group = &cartesian_coord[idx];
-        let (batch, group, i, j, k) = (
-            cartesian_coord_per_group[0],
-            cartesian_coord_per_group[1],
-            cartesian_coord_per_group[2],
-            cartesian_coord_per_group[3],
-            cartesian_coord_per_group[4],
-        );
+    // the image and kernel windows (and bias index) of every output are independent of each other,
+    // an output's window is only gathered when it is about to be assigned so that we never hold the
+    // windows of the whole feature map in memory
+    let window = |idx: usize| {
+        let k = idx % horz_slides;
+        let j = (idx / horz_slides) % vert_slides;
+        let i = (idx / (horz_slides * vert_slides)) % output_channels_per_group;
+        let group = (idx / (horz_slides * vert_slides * output_channels_per_group)) % num_groups;
+        let batch = idx / (horz_slides * vert_slides * output_channels_per_group * num_groups);
         let rs = j * stride.0;
         let cs = k * stride.1;
 
@@ -2008,25 +2482,43 @@ pub fn conv<F: PrimeField + TensorType + PartialOrd + std::marker::Send + std::m
 
         local_kernel.flatten();
 
-        // this is dot product notation in einsum format
-        let mut res = einsum(config, region, &mut [local_image, local_kernel], "i,i->").unwrap();
+        (local_image, local_kernel, start_kernel_index)
+    };
 
-        if has_bias {
-            let bias = values[2].get_single_elem(start_kernel_index).unwrap();
-            res = pairwise(config, region, &[res, bias], BaseOp::Add).unwrap()
-        }
-        region.flush();
+    let assign_window =
+        |(local_image, local_kernel, start_kernel_index): (ValTensor<F>, ValTensor<F>, usize),
+         region: &mut RegionCtx<F>|
+         -> ValType<F> {
+            // this is dot product notation in einsum format
+            let mut res =
+                einsum(config, region, &mut [local_image, local_kernel], "i,i->").unwrap();
+
+            if has_bias {
+                let bias = values[2].get_single_elem(start_kernel_index).unwrap();
+                res = pairwise(config, region, &[res, bias], BaseOp::Add).unwrap()
+            }
+            region.flush();
 
-        res.get_inner_tensor().unwrap()[0].clone()
-    };
+            res.get_inner_tensor().unwrap()[0].clone()
+        };
 
     region.flush();
     if !region.is_dummy() {
-        output.iter_mut().enumerate().for_each(|(idx, o)| {
-            *o = inner_loop_function(idx, region);
-        });
+        // the windows of a row of outputs are gathered in parallel and then assigned (serially)
+        // before moving on to the next row, so peak memory scales with a row of the output
+        for (row, outputs) in output.chunks_mut(horz_slides).enumerate() {
+            let windows = (0..outputs.len())
+                .into_par_iter()
+                .map(|k| window(row * horz_slides + k))
+                .collect::<Vec<_>>();
+            for (o, w) in outputs.iter_mut().zip(windows) {
+                *o = assign_window(w, region);
+            }
+        }
     } else {
-        region.dummy_loop(&mut output, inner_loop_function)?;
+        region.dummy_loop(&mut output, |idx, region| {
+            assign_window(window(idx), region)
+        })?;
     }
 
     let reshape_output = |output: &mut Tensor<ValType<F>>| {
@@ -2048,20 +2540,191 @@ pub fn conv<F: PrimeField + TensorType + PartialOrd + std::marker::Send + std::m
     Ok(output)
 }
 
-/// Power accumulated layout
+/// Convolution layout which lowers the convolution to a single matmul: the image windows are
+/// gathered into the columns of an (im2col) matrix which is multiplied by the flattened kernel.
+/// Only ungrouped convolutions over 4D inputs are supported.
+pub fn conv_im2col<
+    F: PrimeField + TensorType + PartialOrd + std::marker::Send + std::marker::Sync,
+>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>],
+    padding: [(usize, usize); 2],
+    stride: (usize, usize),
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let has_bias = values.len() == 3;
+    let (mut image, mut kernel) = (values[0].clone(), values[1].clone());
+
+    // as in [conv], the windows overlap so we assign the image and kernel once and copy from there
+    let mut assigned_len = vec![];
+    if !kernel.all_prev_assigned() {
+        kernel = region.assign(&config.inputs[0], &kernel)?;
+        assigned_len.push(kernel.len());
+    }
+    if !image.all_prev_assigned() {
+        image = region.assign(&config.inputs[1], &image)?;
+        assigned_len.push(image.len());
+    }
+    if !assigned_len.is_empty() {
+        region.increment(*assigned_len.iter().max().unwrap());
+    }
+
+    if (image.dims().len() != 4)
+        || (kernel.dims().len() != 4)
+        || (image.dims()[1] != kernel.dims()[1])
+    {
+        return Err(Box::new(TensorError::DimMismatch(
+            "im2col conv".to_string(),
+        )));
+    }
+
+    let (batch_size, input_channels, image_height, image_width) = (
+        image.dims()[0],
+        image.dims()[1],
+        image.dims()[2],
+        image.dims()[3],
+    );
+    let (output_channels, kernel_height, kernel_width) =
+        (kernel.dims()[0], kernel.dims()[2], kernel.dims()[3]);
+
+    if has_bias {
+        let bias = &values[2];
+        if (bias.dims().len() != 1) || (bias.dims()[0] != output_channels) {
+            return Err(Box::new(TensorError::DimMismatch("conv bias".to_string())));
+        }
+    }
+
+    let vert_slides = (image_height + padding[0].0 + padding[1].0 - kernel_height) / stride.0 + 1;
+    let horz_slides = (image_width + padding[0].1 + padding[1].1 - kernel_width) / stride.1 + 1;
+
+    let mut padded_image = image.clone();
+    padded_image.pad(padding)?;
+    let padded_image = padded_image.get_inner_tensor()?;
+
+    let window_len = input_channels * kernel_height * kernel_width;
+    kernel.reshape(&[output_channels, window_len])?;
+
+    let mut batches = vec![];
+    for b in 0..batch_size {
+        // row (c, di, dj) and column (r, q) of the im2col matrix
+        let coords = [
+            (0..input_channels),
+            (0..kernel_height),
+            (0..kernel_width),
+            (0..vert_slides),
+            (0..horz_slides),
+        ]
+        .iter()
+        .cloned()
+        .multi_cartesian_product()
+        .collect::<Vec<_>>();
+
+        let mut cols: Tensor<ValType<F>> = Tensor::new(None, &[coords.len()])?;
+        cols.par_iter_mut().enumerate().for_each(|(idx, o)| {
+            let (c, di, dj, r, q) = (
+                coords[idx][0],
+                coords[idx][1],
+                coords[idx][2],
+                coords[idx][3],
+                coords[idx][4],
+            );
+            *o = padded_image.get(&[b, c, r * stride.0 + di, q * stride.1 + dj]);
+        });
+        cols.reshape(&[window_len, vert_slides * horz_slides]);
+
+        let mut res = einsum(
+            config,
+            region,
+            &mut [kernel.clone(), cols.into()],
+            "ok,kp->op",
+        )?;
+
+        if has_bias {
+            let mut bias = values[2].clone();
+            bias.reshape(&[output_channels, 1])?;
+            res = pairwise(config, region, &[res, bias], BaseOp::Add)?;
+        }
+
+        res.reshape(&[1, output_channels, vert_slides, horz_slides])?;
+        batches.push(res);
+    }
+
+    concat(&batches, &0)
+}
+
+/// Elementwise integer power layout, by square and multiply so `x^n` costs at most `2 * log2(n)`
+/// multiplication gates (and no lookups)
 pub fn pow<F: PrimeField + TensorType + PartialOrd>(
     config: &BaseConfig<F>,
     region: &mut RegionCtx<F>,
     values: &[ValTensor<F>; 1],
     exponent: u32,
 ) -> Result<ValTensor<F>, Box<dyn Error>> {
-    let mut t = values[0].clone();
+    if exponent == 0 {
+        return Err(Box::new(CircuitError::UnsupportedOp));
+    }
 
-    for _ in 1..exponent {
-        t = pairwise(config, region, &[t, values[0].clone()], BaseOp::Mult)?;
+    let mut base = values[0].clone();
+    let mut acc: Option<ValTensor<F>> = None;
+    let mut exp = exponent;
+    loop {
+        if exp & 1 == 1 {
+            acc = Some(match acc {
+                Some(acc) => pairwise(config, region, &[acc, base.clone()], BaseOp::Mult)?,
+                None => base.clone(),
+            });
+        }
+        exp >>= 1;
+        if exp == 0 {
+            break;
+        }
+        base = pairwise(config, region, &[base.clone(), base], BaseOp::Mult)?;
     }
 
-    Ok(t)
+    Ok(acc.unwrap())
+}
+
+/// Elementwise square layout
+pub fn square<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 1],
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    pow(config, region, values, 2)
+}
+
+/// Evaluates the polynomial `coeffs[0] + coeffs[1] * x + .. + coeffs[n] * x^n` using Horner's
+/// rule, purely with arithmetic gates (no lookups). The coefficients must already be quantized
+/// such that each partial sum has a homogenous scale, see [PolyOp::Polynomial](crate::circuit::ops::poly::PolyOp::Polynomial).
+pub fn polynomial<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 1],
+    coeffs: &[F],
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    if coeffs.len() < 2 {
+        return Err(Box::new(TensorError::DimMismatch(
+            "polynomial coefficients".to_string(),
+        )));
+    }
+    let constant =
+        |c: F| -> ValTensor<F> { Tensor::from(vec![ValType::Constant(c)].into_iter()).into() };
+
+    let degree = coeffs.len() - 1;
+    let mut acc = pairwise(
+        config,
+        region,
+        &[values[0].clone(), constant(coeffs[degree])],
+        BaseOp::Mult,
+    )?;
+    for k in (0..degree).rev() {
+        acc = pairwise(config, region, &[acc, constant(coeffs[k])], BaseOp::Add)?;
+        if k > 0 {
+            acc = pairwise(config, region, &[acc, values[0].clone()], BaseOp::Mult)?;
+        }
+    }
+
+    Ok(acc)
 }
 
 /// Rescaled op accumulated layout
@@ -2087,6 +2750,45 @@ pub fn rescale<F: PrimeField + TensorType + PartialOrd>(
     Ok(rescaled_inputs)
 }
 
+/// Per channel requantization layout, see [HybridOp::channel_rescale](crate::circuit::ops::hybrid::HybridOp::channel_rescale):
+/// multiplies each slice of `values[0]` along `axis` by its own constant, then divides (with rounding) the whole
+/// tensor by `denom` through a single [LookupOp::Div] table
+pub fn channel_rescale<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 1],
+    axis: usize,
+    multipliers: &[u64],
+    denom: utils::F32,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let x = &values[0];
+    let dims = x.dims().to_vec();
+    if axis >= dims.len() || dims[axis] != multipliers.len() {
+        return Err(Box::new(TensorError::DimMismatch(
+            "channel_rescale".to_string(),
+        )));
+    }
+
+    let scaled = if multipliers.iter().all(|m| *m == 1) {
+        x.clone()
+    } else {
+        // the multiplier of each element, broadcast along the other axes
+        let stride = dims[axis + 1..].iter().product::<usize>();
+        let mut m: ValTensor<F> = Tensor::from(
+            (0..x.len())
+                .map(|i| ValType::Constant(F::from(multipliers[(i / stride) % dims[axis]]))),
+        )
+        .into();
+        m.reshape(&dims)?;
+        pairwise(config, region, &[x.clone(), m], BaseOp::Mult)?
+    };
+
+    if denom.0 == 1.0 {
+        return Ok(scaled);
+    }
+    nonlinearity(config, region, &[scaled], &LookupOp::Div { denom })
+}
+
 /// Pack accumulated layout
 pub fn pack<F: PrimeField + TensorType + PartialOrd>(
     config: &BaseConfig<F>,
@@ -2123,7 +2825,7 @@ pub fn pack<F: PrimeField + TensorType + PartialOrd>(
     Ok(res)
 }
 
-/// Dummy (no contraints) reshape layout
+/// Dummy (no contraints) reshape layout, errors if the number of elements changes (e.g [C, H, W] -> [C * H * W] is fine)
 pub fn reshape<F: PrimeField + TensorType + PartialOrd>(
     values: &[ValTensor<F>; 1],
     new_dims: &[usize],
@@ -2144,6 +2846,71 @@ pub fn move_axis<F: PrimeField + TensorType + PartialOrd>(
     Ok(t)
 }
 
+/// flip layout
+pub fn flip<F: PrimeField + TensorType + PartialOrd>(
+    values: &[ValTensor<F>; 1],
+    axis: usize,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let mut t = values[0].clone();
+    t.flip(axis)?;
+    Ok(t)
+}
+
+/// Bilinear resize layout: each output element is a fixed weighted sum of (copies of) its 4 neighbouring inputs,
+/// which is then divided by the common denominator of the weights through a lookup.
+pub fn resize_bilinear<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 1],
+    scales: &[usize],
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let input = region.assign(&config.inputs[0], &values[0])?;
+    region.increment(input.len());
+    let input = input.get_inner_tensor()?;
+
+    let (new_shape, denom, coeffs) = tensor::ops::bilinear_resize_coeffs(input.dims(), scales)?;
+
+    let mut neighbours = Tensor::from(
+        coeffs
+            .iter()
+            .flat_map(|c| c.iter().map(|(idx, _)| input[*idx].clone())),
+    );
+    neighbours.reshape(&[coeffs.len(), 4]);
+
+    let mut weights = Tensor::from(
+        coeffs
+            .iter()
+            .flat_map(|c| c.iter().map(|(_, w)| i128_to_felt::<F>(*w))),
+    );
+    weights.reshape(&[coeffs.len(), 4]);
+    weights.set_visibility(&crate::graph::Visibility::Fixed);
+
+    let numerators = einsum(
+        config,
+        region,
+        &[neighbours.into(), weights.into()],
+        "ij,ij->i",
+    )?;
+
+    let nl = LookupOp::Div {
+        denom: utils::F32(denom as f32),
+    };
+    let mut output = nonlinearity(config, region, &[numerators], &nl)?;
+    output.reshape(&new_shape)?;
+
+    Ok(output)
+}
+
+/// Dummy (no contraints) permute layout
+pub fn permute<F: PrimeField + TensorType + PartialOrd>(
+    values: &[ValTensor<F>; 1],
+    perm: &[usize],
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    let mut t = values[0].clone();
+    t.permute(perm)?;
+    Ok(t)
+}
+
 /// resize layout
 pub fn resize<F: PrimeField + TensorType + PartialOrd>(
     config: &BaseConfig<F>,
@@ -2175,6 +2942,21 @@ pub fn slice<F: PrimeField + TensorType + PartialOrd>(
     Ok(output)
 }
 
+/// Strided slice layout, used for cropping feature maps or splitting tensors
+pub fn strided_slice<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 1],
+    ranges: &[(usize, usize, isize)],
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    // assigns the instance to the advice.
+    let mut output = region.assign(&config.output, &values[0])?;
+    region.increment(output.len());
+    output.strided_slice(ranges)?;
+
+    Ok(output)
+}
+
 /// Concat layout
 pub fn concat<F: PrimeField + TensorType + PartialOrd>(
     values: &[ValTensor<F>],
@@ -2202,6 +2984,40 @@ pub fn identity<F: PrimeField + TensorType + PartialOrd>(
     Ok(output)
 }
 
+/// Pads each dim of the input with the constant `value` (see [crate::tensor::ops::pad_nd]). The padded
+/// tensor is assigned, so the padding cells are constrained to `value`. Zero padding is only rewiring, as for
+/// [PolyOp::Pad](crate::circuit::ops::poly::PolyOp::Pad): the zeros are constants wherever they are assigned.
+pub fn constant_pad<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 1],
+    pads: &[(usize, usize)],
+    value: F,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    if value == F::ZERO {
+        return Ok(tensor::ops::pad_nd(
+            values[0].get_inner_tensor()?,
+            pads,
+            ValType::Constant(value),
+        )?
+        .into());
+    }
+
+    let mut input = values[0].clone();
+    if !input.all_prev_assigned() {
+        input = region.assign(&config.inputs[0], &input)?;
+        region.increment(input.len());
+    }
+
+    let padded: ValTensor<F> =
+        tensor::ops::pad_nd(input.get_inner_tensor()?, pads, ValType::Constant(value))?.into();
+
+    let output = region.assign(&config.output, &padded)?;
+    region.increment(output.len());
+
+    Ok(output)
+}
+
 /// Boolean identity constraint. Usually used to constrain an instance column to an advice so the returned cells / values can be operated upon.
 pub fn boolean_identity<F: PrimeField + TensorType + PartialOrd>(
     config: &BaseConfig<F>,
@@ -2217,6 +3033,8 @@ pub fn boolean_identity<F: PrimeField + TensorType + PartialOrd>(
 
             region.enable(selector, z).unwrap();
         });
+    } else {
+        region.increment_gates(output.len());
     }
     region.increment(output.len());
 
@@ -2271,6 +3089,8 @@ pub fn nonlinearity<F: PrimeField + TensorType + PartialOrd>(
 ) -> Result<ValTensor<F>, Box<dyn Error>> {
     // time the entire operation
     let timer = instant::Instant::now();
+    // tables and selectors are keyed by the canonical op, see [BaseConfig::configure_lookup]
+    let nl = &nl.canonical();
 
     let x = values[0].clone();
 
@@ -2298,6 +3118,23 @@ pub fn nonlinearity<F: PrimeField + TensorType + PartialOrd>(
 
     let is_dummy = region.is_dummy();
 
+    // inputs outside of the table would otherwise only surface as an unsatisfied lookup, the
+    // constants aren't looked up
+    if !is_dummy {
+        let range = config.tables.get(nl).unwrap().range;
+        for (i, e) in w.get_inner_tensor()?.iter().enumerate() {
+            if removal_indices.contains(&i) {
+                continue;
+            }
+            if let Some(f) = e.get_felt_eval() {
+                let v = felt_to_i128(f);
+                if v < range.0 || v > range.1 {
+                    return Err(Box::new(CircuitError::LookupRangeExceeded(v, range)));
+                }
+            }
+        }
+    }
+
     let table_index: ValTensor<F> = w
         .get_inner_tensor()?
         .par_enum_map(|i, e| {
@@ -2329,6 +3166,8 @@ pub fn nonlinearity<F: PrimeField + TensorType + PartialOrd>(
             let selector = config.lookup_selectors.get(&(nl.clone(), x, y));
             region.enable(selector, z).unwrap();
         });
+    } else {
+        region.increment_gates(assigned_len);
     }
 
     region.increment(assigned_len);
@@ -2487,6 +3326,8 @@ pub fn max<F: PrimeField + TensorType + PartialOrd>(
             let selector = config.selectors.get(&(BaseOp::IsBoolean, x, y));
             region.enable(selector, z).unwrap();
         });
+    } else {
+        region.increment_gates(len);
     }
 
     region.increment(len);
@@ -2562,6 +3403,8 @@ pub fn min<F: PrimeField + TensorType + PartialOrd>(
             let selector = config.selectors.get(&(BaseOp::IsBoolean, x, y));
             region.enable(selector, z).unwrap();
         });
+    } else {
+        region.increment_gates(len);
     }
 
     region.increment(len);
@@ -2727,21 +3570,12 @@ pub fn softmax<F: PrimeField + TensorType + PartialOrd>(
     scale: utils::F32,
 ) -> Result<ValTensor<F>, Box<dyn Error>> {
     // elementwise exponential
-    let ex = nonlinearity(config, region, values, &LookupOp::Exp { scale })?;
+    let ex = mathops::exp(config, region, &values[0], scale)?;
 
     // sum of exps
     let denom = sum(config, region, &[ex.clone()])?;
-    // get the inverse
-
-    let inv_denom = nonlinearity(
-        config,
-        region,
-        &[denom],
-        // we set to input scale + output_scale so the output scale is output)scale
-        &LookupOp::Recip {
-            scale: scale.0.powf(2.0).into(),
-        },
-    )?;
+    // get the inverse, at the input scale so the product below is at twice the input scale
+    let inv_denom = mathops::recip(config, region, &denom, scale, scale)?;
 
     // product of num * (1 / denom) = 2*output_scale
     let softmax = pairwise(config, region, &[ex, inv_denom], BaseOp::Mult)?;
@@ -2769,14 +3603,7 @@ pub fn range_check_percent<F: PrimeField + TensorType + PartialOrd>(
 
     let scale_squared = scale.0.powf(2.0);
     // Calculate the reciprocal of the expected output tensor, scaling by double the scaling factor
-    let recip = nonlinearity(
-        config,
-        region,
-        &[values[0].clone()],
-        &LookupOp::Recip {
-            scale: scale_squared.into(),
-        },
-    )?;
+    let recip = mathops::recip(config, region, &values[0], scale, scale)?;
     // Multiply the difference by the recip
     let product = pairwise(config, region, &[diff, recip], BaseOp::Mult)?;
 
@@ -2819,3 +3646,43 @@ pub fn range_check_percent<F: PrimeField + TensorType + PartialOrd>(
 
     Ok(sum)
 }
+
+/// Range check layout for an absolute tolerance, ie. constrains `|values[0] - values[1]| <= tol`
+/// elementwise, where `tol` is in fixed point.
+pub fn range_check_abs<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    values: &[ValTensor<F>; 2],
+    tol: i128,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    if tol == 0 {
+        // regular equality constraint
+        return enforce_equality(config, region, values);
+    }
+
+    let diff = pairwise(config, region, values, BaseOp::Sub)?;
+
+    let upper: ValTensor<F> =
+        Tensor::from(vec![ValType::Constant(i128_to_felt(tol))].into_iter()).into();
+    let lower: ValTensor<F> =
+        Tensor::from(vec![ValType::Constant(i128_to_felt(-tol))].into_iter()).into();
+
+    // diff > tol and diff < -tol are the violations of each bound
+    let upper_bound = cmp::gt(config, region, &diff, &upper)?;
+    let lower_bound = cmp::lt(config, region, &diff, &lower)?;
+
+    // Add the lower_bound and upper_bound
+    let sum = pairwise(config, region, &[lower_bound, upper_bound], BaseOp::Add)?;
+
+    // Assign the sum tensor to the inputs
+    region.assign(&config.inputs[1], &sum)?;
+
+    // Constrain the sum to be all zeros
+    let (x, y, z) = config.output.cartesian_coord(region.linear_coord());
+    let selector = config.selectors.get(&(BaseOp::IsZero, x, y));
+    region.enable(selector, z)?;
+
+    region.increment(sum.len());
+
+    Ok(sum)
+}
@@ -21,6 +21,9 @@ pub enum LookupOp {
         denom: utils::F32,
     },
     ReLU,
+    DivReLU {
+        denom: utils::F32,
+    },
     Max {
         scales: (usize, usize),
         a: utils::F32,
@@ -29,6 +32,11 @@ pub enum LookupOp {
         scales: (usize, usize),
         a: utils::F32,
     },
+    Clip {
+        scales: (usize, usize),
+        a: utils::F32,
+        b: utils::F32,
+    },
     Ceil {
         scale: utils::F32,
     },
@@ -101,6 +109,12 @@ pub enum LookupOp {
     Erf {
         scale: utils::F32,
     },
+    Gelu {
+        scale: utils::F32,
+    },
+    SiLU {
+        scale: utils::F32,
+    },
     GreaterThan {
         a: utils::F32,
     },
@@ -122,6 +136,32 @@ pub enum LookupOp {
 }
 
 impl LookupOp {
+    /// Maps ops which compute the same function onto a single representative, so that layers
+    /// using either of them share one lookup table (and one set of selectors).
+    pub fn canonical(&self) -> LookupOp {
+        match self {
+            LookupOp::LeakyReLU { slope } if slope.0 == 0.0 => LookupOp::ReLU,
+            LookupOp::Max { scales, a } if scales.0 == scales.1 && a.0 == 0.0 => LookupOp::ReLU,
+            // dividing and then multiplying by the same scale only rescales the threshold
+            LookupOp::Max { scales, a } if scales.0 == scales.1 && scales.0 != 1 => LookupOp::Max {
+                scales: (1, 1),
+                a: utils::F32(a.0 * scales.0 as f32),
+            },
+            LookupOp::Min { scales, a } if scales.0 == scales.1 && scales.0 != 1 => LookupOp::Min {
+                scales: (1, 1),
+                a: utils::F32(a.0 * scales.0 as f32),
+            },
+            LookupOp::Clip { scales, a, b } if scales.0 == scales.1 && scales.0 != 1 => {
+                LookupOp::Clip {
+                    scales: (1, 1),
+                    a: utils::F32(a.0 * scales.0 as f32),
+                    b: utils::F32(b.0 * scales.0 as f32),
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+
     /// Returns the range of values that can be represented by the table
     pub fn bit_range(max_len: usize) -> (i128, i128) {
         let range = (max_len - 1) as f64 / 2_f64;
@@ -164,6 +204,13 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for LookupOp {
                 scales.1,
                 a.0.into(),
             )),
+            LookupOp::Clip { scales, a, b } => Ok(tensor::ops::nonlinearities::clip(
+                &x,
+                scales.0,
+                scales.1,
+                a.0.into(),
+                b.0.into(),
+            )),
             LookupOp::Sign => Ok(tensor::ops::nonlinearities::sign(&x)),
             LookupOp::LessThan { a } => Ok(tensor::ops::nonlinearities::less_than(
                 &x,
@@ -186,6 +233,10 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for LookupOp {
             )),
             LookupOp::Recip { scale } => Ok(tensor::ops::nonlinearities::recip(&x, scale.into())),
             LookupOp::ReLU => Ok(tensor::ops::nonlinearities::leakyrelu(&x, 0_f64)),
+            LookupOp::DivReLU { denom } => Ok(tensor::ops::nonlinearities::leakyrelu(
+                &tensor::ops::nonlinearities::const_div(&x, f32::from(*denom).into()),
+                0_f64,
+            )),
 
             LookupOp::LeakyReLU { slope: a } => {
                 Ok(tensor::ops::nonlinearities::leakyrelu(&x, a.0.into()))
@@ -196,6 +247,8 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for LookupOp {
             LookupOp::Sqrt { scale } => Ok(tensor::ops::nonlinearities::sqrt(&x, scale.into())),
             LookupOp::Rsqrt { scale } => Ok(tensor::ops::nonlinearities::rsqrt(&x, scale.into())),
             LookupOp::Erf { scale } => Ok(tensor::ops::nonlinearities::erffunc(&x, scale.into())),
+            LookupOp::Gelu { scale } => Ok(tensor::ops::nonlinearities::gelu(&x, scale.into())),
+            LookupOp::SiLU { scale } => Ok(tensor::ops::nonlinearities::silu(&x, scale.into())),
             LookupOp::Exp { scale } => Ok(tensor::ops::nonlinearities::exp(&x, scale.into())),
             LookupOp::Ln { scale } => Ok(tensor::ops::nonlinearities::ln(&x, scale.into())),
             LookupOp::Cos { scale } => Ok(tensor::ops::nonlinearities::cos(&x, scale.into())),
@@ -232,6 +285,9 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for LookupOp {
             LookupOp::KroneckerDelta => "K_DELTA".into(),
             LookupOp::Max { scales, a } => format!("MAX(scales={:?}, a={})", scales, a),
             LookupOp::Min { scales, a } => format!("MIN(scales={:?}, a={})", scales, a),
+            LookupOp::Clip { scales, a, b } => {
+                format!("CLIP(scales={:?}, a={}, b={})", scales, a, b)
+            }
             LookupOp::Sign => "SIGN".into(),
             LookupOp::GreaterThan { .. } => "GREATER_THAN".into(),
             LookupOp::GreaterThanEqual { .. } => "GREATER_THAN_EQUAL".into(),
@@ -241,10 +297,13 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for LookupOp {
             LookupOp::Div { denom, .. } => format!("DIV(denom={})", denom),
             LookupOp::Ln { scale } => format!("LN(scale={})", scale),
             LookupOp::ReLU => "RELU".to_string(),
+            LookupOp::DivReLU { denom } => format!("DIV_RELU(denom={})", denom),
             LookupOp::LeakyReLU { slope: a } => format!("L_RELU(slope={})", a),
             LookupOp::Sigmoid { scale } => format!("SIGMOID(scale={})", scale),
             LookupOp::Sqrt { scale } => format!("SQRT(scale={})", scale),
             LookupOp::Erf { scale } => format!("ERF(scale={})", scale),
+            LookupOp::Gelu { scale } => format!("GELU(scale={})", scale),
+            LookupOp::SiLU { scale } => format!("SILU(scale={})", scale),
             LookupOp::Rsqrt { scale } => format!("RSQRT(scale={})", scale),
             LookupOp::Exp { scale } => format!("EXP(scale={})", scale),
             LookupOp::Tan { scale } => format!("TAN(scale={})", scale),
@@ -286,6 +345,8 @@ impl<F: PrimeField + TensorType + PartialOrd> Op<F> for LookupOp {
                 }
                 scale
             }
+            // the fused op rebases its input, as the division it replaces did
+            LookupOp::DivReLU { denom } => inputs_scale[0] - multiplier_to_scale(denom.0 as f64),
             LookupOp::Recip { scale } => {
                 let mut out_scale = inputs_scale[0];
                 out_scale +=
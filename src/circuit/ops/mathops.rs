@@ -0,0 +1,96 @@
+use std::error::Error;
+
+use halo2curves::ff::PrimeField;
+
+use super::{layouts::nonlinearity, lookup::LookupOp, region::RegionCtx};
+use crate::{
+    circuit::{utils, BaseConfig},
+    tensor::{TensorType, ValTensor},
+};
+
+// Each function computes `f(x / scale) * scale` elementwise through a lookup table, so the precision of the
+// result is set by the `scale` of the input. The matching `*_lookup` functions return the table a layout
+// needs, so the ops that use these functions can report their `required_lookups` consistently.
+
+/// The lookup table used by [exp]
+pub fn exp_lookup(scale: utils::F32) -> LookupOp {
+    LookupOp::Exp { scale }
+}
+
+/// The lookup table used by [ln]
+pub fn ln_lookup(scale: utils::F32) -> LookupOp {
+    LookupOp::Ln { scale }
+}
+
+/// The lookup table used by [sqrt]
+pub fn sqrt_lookup(scale: utils::F32) -> LookupOp {
+    LookupOp::Sqrt { scale }
+}
+
+/// The lookup table used by [rsqrt]
+pub fn rsqrt_lookup(scale: utils::F32) -> LookupOp {
+    LookupOp::Rsqrt { scale }
+}
+
+/// The lookup table used by [recip]
+pub fn recip_lookup(input_scale: utils::F32, output_scale: utils::F32) -> LookupOp {
+    LookupOp::Recip {
+        scale: (input_scale.0 * output_scale.0).into(),
+    }
+}
+
+/// Elementwise exponential of a tensor at fixed point `scale`
+pub fn exp<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    x: &ValTensor<F>,
+    scale: utils::F32,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    nonlinearity(config, region, &[x.clone()], &exp_lookup(scale))
+}
+
+/// Elementwise natural logarithm of a tensor at fixed point `scale`
+pub fn ln<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    x: &ValTensor<F>,
+    scale: utils::F32,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    nonlinearity(config, region, &[x.clone()], &ln_lookup(scale))
+}
+
+/// Elementwise square root of a tensor at fixed point `scale`
+pub fn sqrt<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    x: &ValTensor<F>,
+    scale: utils::F32,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    nonlinearity(config, region, &[x.clone()], &sqrt_lookup(scale))
+}
+
+/// Elementwise reciprocal square root of a tensor at fixed point `scale`
+pub fn rsqrt<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    x: &ValTensor<F>,
+    scale: utils::F32,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    nonlinearity(config, region, &[x.clone()], &rsqrt_lookup(scale))
+}
+
+/// Elementwise reciprocal of a tensor at fixed point `input_scale`, the result is at fixed point `output_scale`
+pub fn recip<F: PrimeField + TensorType + PartialOrd>(
+    config: &BaseConfig<F>,
+    region: &mut RegionCtx<F>,
+    x: &ValTensor<F>,
+    input_scale: utils::F32,
+    output_scale: utils::F32,
+) -> Result<ValTensor<F>, Box<dyn Error>> {
+    nonlinearity(
+        config,
+        region,
+        &[x.clone()],
+        &recip_lookup(input_scale, output_scale),
+    )
+}
@@ -14,12 +14,18 @@ use self::{lookup::LookupOp, region::RegionCtx};
 pub mod base;
 ///
 pub mod chip;
+/// Boolean valued comparisons (eq, gt, gte, lt, lte) of fixed point values shared by layouts
+pub mod cmp;
+/// Integer division and remainder by a constant, constrained arithmetically
+pub mod divmod;
 ///
 pub mod hybrid;
 /// Layouts for specific functions (composed of base ops)
 pub mod layouts;
 ///
 pub mod lookup;
+/// Lookup backed unary functions (exp, ln, sqrt, rsqrt, reciprocal) shared by layouts
+pub mod mathops;
 ///
 pub mod poly;
 ///
@@ -1,5 +1,7 @@
 use crate::{
-    circuit::layouts,
+    circuit::{layouts, utils, utils::CheckedI128},
+    fieldutils::{felt_to_i128, i128_to_felt},
+    graph::quantize_float,
     tensor::{self, Tensor, TensorError},
 };
 
@@ -18,6 +20,20 @@ pub enum PolyOp<F: PrimeField + TensorType + PartialOrd> {
         padding: [(usize, usize); 2],
         stride: (usize, usize),
     },
+    SparseLinear {
+        weights: Tensor<F>,
+        bias: Option<Tensor<F>>,
+    },
+    TernaryLinear {
+        weights: Tensor<F>,
+        bias: Option<Tensor<F>>,
+    },
+    Im2ColConv {
+        kernel: Tensor<F>,
+        bias: Option<Tensor<F>>,
+        padding: [(usize, usize); 2],
+        stride: (usize, usize),
+    },
     Downsample {
         axis: usize,
         stride: usize,
@@ -45,16 +61,41 @@ pub enum PolyOp<F: PrimeField + TensorType + PartialOrd> {
         source: usize,
         destination: usize,
     },
+    Flip {
+        axis: usize,
+    },
+    Permute {
+        perm: Vec<usize>,
+    },
     Flatten(Vec<usize>),
+    MultiBroadcastTo {
+        shape: Vec<usize>,
+    },
     Pad([(usize, usize); 2]),
+    ConstantPad {
+        pads: Vec<(usize, usize)>,
+        value: utils::F32,
+        scale: crate::Scale,
+    },
     Sum {
         axes: Vec<usize>,
     },
+    CumSum {
+        axis: usize,
+    },
+    Mean {
+        axes: Vec<usize>,
+        scale: crate::Scale,
+    },
     Prod {
         axes: Vec<usize>,
         len_prod: usize,
     },
     Pow(u32),
+    Polynomial {
+        coeffs: Vec<utils::F32>,
+        scale: crate::Scale,
+    },
     Pack(u32, u32),
     GlobalSumPool,
     Concat {
@@ -65,6 +106,9 @@ pub enum PolyOp<F: PrimeField + TensorType + PartialOrd> {
         start: usize,
         end: usize,
     },
+    StridedSlice {
+        ranges: Vec<(usize, usize, isize)>,
+    },
     Iff,
     Resize {
         scale_factor: Vec<usize>,
@@ -75,7 +119,120 @@ pub enum PolyOp<F: PrimeField + TensorType + PartialOrd> {
     Xor,
 }
 
-impl<F: PrimeField + TensorType + PartialOrd> PolyOp<F> {}
+impl<F: PrimeField + TensorType + PartialOrd> PolyOp<F> {
+    /// Evaluates the ops which accumulate sums and products (eg. einsums, convolutions and sums) over widened,
+    /// overflow checked, i128 integers (see [CheckedI128]) rather than the field, where an overflow would wrap
+    /// around silently. Returns `None` for the other ops, which [Op::f] evaluates exactly.
+    pub fn f_int(&self, inputs: &[Tensor<F>]) -> Result<Option<Tensor<i128>>, TensorError> {
+        let widen = |t: &Tensor<F>| t.map(|x| CheckedI128::from(felt_to_i128(x)));
+        let widened = || inputs.iter().map(widen).collect::<Vec<_>>();
+        let res = match &self {
+            PolyOp::Einsum { equation } => tensor::ops::einsum(equation, &widened())?,
+            PolyOp::SparseLinear { weights, bias } | PolyOp::TernaryLinear { weights, bias } => {
+                if 1 != inputs.len() {
+                    return Err(TensorError::DimMismatch("linear inputs".to_string()));
+                }
+                let mut input = widen(&inputs[0]);
+                input.flatten();
+                let res = tensor::ops::einsum("ij,j->i", &[widen(weights), input])?;
+                match bias {
+                    Some(b) => tensor::ops::add(&[res, widen(b)])?,
+                    None => res,
+                }
+            }
+            PolyOp::Add => tensor::ops::add(&widened())?,
+            PolyOp::Sub => tensor::ops::sub(&widened())?,
+            PolyOp::Neg => tensor::ops::neg(&widen(&inputs[0]))?,
+            PolyOp::Mult => tensor::ops::mult(&widened())?,
+            PolyOp::Conv {
+                kernel: a,
+                bias,
+                padding,
+                stride,
+            }
+            | PolyOp::Im2ColConv {
+                kernel: a,
+                bias,
+                padding,
+                stride,
+            } => {
+                let mut inputs = widened();
+                inputs.push(widen(a));
+                if let Some(b) = bias {
+                    inputs.push(widen(b));
+                }
+                tensor::ops::conv(&inputs, *padding, *stride)?
+            }
+            PolyOp::DeConv {
+                kernel: a,
+                bias,
+                padding,
+                output_padding,
+                stride,
+            } => {
+                let mut inputs = widened();
+                inputs.push(widen(a));
+                if let Some(b) = bias {
+                    inputs.push(widen(b));
+                }
+                tensor::ops::deconv(&inputs, *padding, *output_padding, *stride)?
+            }
+            PolyOp::SumPool {
+                padding,
+                stride,
+                kernel_shape,
+            } => tensor::ops::sumpool(&widen(&inputs[0]), *padding, *stride, *kernel_shape)?,
+            PolyOp::Pow(u) => widen(&inputs[0]).pow(*u)?,
+            PolyOp::Sum { axes } => tensor::ops::sum_axes(&widen(&inputs[0]), axes)?,
+            PolyOp::Prod { axes, .. } => tensor::ops::prod_axes(&widen(&inputs[0]), axes)?,
+            _ => return Ok(None),
+        };
+
+        if let Some(i) = res.iter().position(|x| x.0.is_none()) {
+            return Err(TensorError::Overflow(i));
+        }
+        Ok(Some(res.map(|x| x.0.unwrap_or_default())))
+    }
+
+    /// The "poly-ReLU" approximation `0.25x^2 + 0.5x` of ReLU, for inputs at `scale`.
+    /// Unlike [LookupOp::ReLU](crate::circuit::ops::lookup::LookupOp::ReLU) it is laid out
+    /// purely with arithmetic gates, at the cost of a larger output scale.
+    pub fn poly_relu(scale: crate::Scale) -> Self {
+        PolyOp::Polynomial {
+            coeffs: vec![utils::F32(0.), utils::F32(0.5), utils::F32(0.25)],
+            scale,
+        }
+    }
+
+    /// Quantizes polynomial coefficients, for an input at `scale`, such that every partial sum
+    /// of Horner's rule has a homogenous scale: `coeffs[k]` is quantized at `(n - k + 1) * scale`
+    /// for a polynomial of degree `n`, so the output is at `(n + 1) * scale`.
+    fn quantized_coeffs(coeffs: &[utils::F32], scale: crate::Scale) -> Result<Vec<F>, TensorError> {
+        let degree = coeffs.len() as crate::Scale - 1;
+        coeffs
+            .iter()
+            .enumerate()
+            .map(|(k, c)| {
+                let c_scale = (degree - k as crate::Scale + 1) * scale;
+                Ok(i128_to_felt(quantize_float(&(c.0 as f64), 0.0, c_scale)?))
+            })
+            .collect()
+    }
+
+    /// `1 / n` quantized at `scale`, where `n` is the number of elements of a tensor of shape `dims`
+    /// reduced over by summing along `axes`
+    pub(crate) fn quantized_inv_len(
+        dims: &[usize],
+        axes: &[usize],
+        scale: crate::Scale,
+    ) -> Result<F, TensorError> {
+        let n = axes
+            .iter()
+            .map(|a| dims.get(*a).copied().ok_or(TensorError::DimError))
+            .product::<Result<usize, _>>()?;
+        Ok(i128_to_felt(quantize_float(&(1.0 / n as f64), 0.0, scale)?))
+    }
+}
 
 impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<'de>> Op<F>
     for PolyOp<F>
@@ -88,6 +245,8 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
     fn as_string(&self) -> String {
         match &self {
             PolyOp::MoveAxis { .. } => "MOVEAXIS".into(),
+            PolyOp::Flip { axis } => format!("FLIP (axis={})", axis),
+            PolyOp::Permute { perm } => format!("PERMUTE (perm={:?})", perm),
             PolyOp::Downsample { .. } => "DOWNSAMPLE".into(),
             PolyOp::Resize { .. } => "RESIZE".into(),
             PolyOp::Iff => "IFF".into(),
@@ -95,22 +254,33 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
             PolyOp::Identity => "IDENTITY".into(),
             PolyOp::Reshape(shape) => format!("RESHAPE (shape={:?})", shape),
             PolyOp::Flatten(_) => "FLATTEN".into(),
+            PolyOp::MultiBroadcastTo { shape } => format!("MULTIBROADCASTTO (shape={:?})", shape),
             PolyOp::Pad(_) => "PAD".into(),
+            PolyOp::ConstantPad { pads, value, .. } => {
+                format!("CONSTANTPAD (pads={:?}, value={})", pads, value)
+            }
             PolyOp::Add => "ADD".into(),
             PolyOp::Mult => "MULT".into(),
             PolyOp::Sub => "SUB".into(),
             PolyOp::Sum { .. } => "SUM".into(),
+            PolyOp::CumSum { axis } => format!("CUMSUM (axis={})", axis),
+            PolyOp::Mean { axes, .. } => format!("MEAN (axes={:?})", axes),
             PolyOp::Prod { .. } => "PROD".into(),
             PolyOp::Pow(_) => "POW".into(),
+            PolyOp::Polynomial { coeffs, .. } => format!("POLYNOMIAL (coeffs={:?})", coeffs),
             PolyOp::Pack(_, _) => "PACK".into(),
             PolyOp::GlobalSumPool => "GLOBALSUMPOOL".into(),
             PolyOp::Conv { .. } => "CONV".into(),
+            PolyOp::Im2ColConv { .. } => "IM2COLCONV".into(),
+            PolyOp::SparseLinear { .. } => "SPARSELINEAR".into(),
+            PolyOp::TernaryLinear { .. } => "TERNARYLINEAR".into(),
             PolyOp::DeConv { .. } => "DECONV".into(),
             PolyOp::SumPool { .. } => "SUMPOOL".into(),
             PolyOp::Concat { axis } => format!("CONCAT (axis={})", axis),
             PolyOp::Slice { axis, start, end } => {
                 format!("SLICE (axis={}, start={}, end={})", axis, start, end)
             }
+            PolyOp::StridedSlice { ranges } => format!("STRIDEDSLICE (ranges={:?})", ranges),
             PolyOp::Neg => "NEG".into(),
             PolyOp::Not => "NOT".into(),
             PolyOp::And => "AND".into(),
@@ -135,6 +305,18 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
             PolyOp::Resize { scale_factor } => tensor::ops::resize(&inputs[0], scale_factor),
             PolyOp::Iff => tensor::ops::iff(&inputs[0], &inputs[1], &inputs[2]),
             PolyOp::Einsum { equation } => tensor::ops::einsum(equation, &inputs),
+            PolyOp::SparseLinear { weights, bias } | PolyOp::TernaryLinear { weights, bias } => {
+                if 1 != inputs.len() {
+                    return Err(TensorError::DimMismatch("linear inputs".to_string()));
+                }
+                let mut input = inputs[0].clone();
+                input.flatten();
+                let res = tensor::ops::einsum("ij,j->i", &[weights.clone(), input])?;
+                match bias {
+                    Some(b) => tensor::ops::add(&[res, b.clone()]),
+                    None => Ok(res),
+                }
+            }
             PolyOp::Identity => Ok(inputs[0].clone()),
             PolyOp::Reshape(new_dims) => {
                 let mut t = inputs[0].clone();
@@ -145,17 +327,34 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
                 source,
                 destination,
             } => inputs[0].move_axis(*source, *destination),
+            PolyOp::Flip { axis } => inputs[0].flip(*axis),
+            PolyOp::Permute { perm } => inputs[0].permute(perm),
             PolyOp::Flatten(new_dims) => {
                 let mut t = inputs[0].clone();
                 t.reshape(new_dims);
                 Ok(t)
             }
+            PolyOp::MultiBroadcastTo { shape } => {
+                if 1 != inputs.len() {
+                    return Err(TensorError::DimMismatch(
+                        "multibroadcastto inputs".to_string(),
+                    ));
+                }
+                inputs[0].expand(shape)
+            }
             PolyOp::Pad(p) => {
                 if 1 != inputs.len() {
                     return Err(TensorError::DimMismatch("pad inputs".to_string()));
                 }
                 tensor::ops::pad(&inputs[0], *p)
             }
+            PolyOp::ConstantPad { pads, value, scale } => {
+                if 1 != inputs.len() {
+                    return Err(TensorError::DimMismatch("pad inputs".to_string()));
+                }
+                let value = i128_to_felt(quantize_float(&(value.0 as f64), 0.0, *scale)?);
+                tensor::ops::pad_nd(&inputs[0], pads, value)
+            }
             PolyOp::Add => tensor::ops::add(&inputs),
             PolyOp::Neg => tensor::ops::neg(&inputs[0]),
             PolyOp::Sub => tensor::ops::sub(&inputs),
@@ -165,6 +364,12 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
                 bias,
                 padding,
                 stride,
+            }
+            | PolyOp::Im2ColConv {
+                kernel: a,
+                bias,
+                padding,
+                stride,
             } => {
                 inputs.push(a.clone());
                 if let Some(b) = bias {
@@ -203,12 +408,47 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
                 }
                 inputs[0].pow(*u)
             }
+            PolyOp::Polynomial { coeffs, scale } => {
+                if 1 != inputs.len() {
+                    return Err(TensorError::DimMismatch("polynomial inputs".to_string()));
+                }
+                if coeffs.len() < 2 {
+                    return Err(TensorError::DimMismatch(
+                        "polynomial coefficients".to_string(),
+                    ));
+                }
+                let coeffs = Self::quantized_coeffs(coeffs, *scale)?;
+                let constant = |c: F| Tensor::new(Some(&[c]), &[1]);
+                let degree = coeffs.len() - 1;
+                let mut acc = tensor::ops::mult(&[inputs[0].clone(), constant(coeffs[degree])?])?;
+                for k in (0..degree).rev() {
+                    acc = tensor::ops::add(&[acc, constant(coeffs[k])?])?;
+                    if k > 0 {
+                        acc = tensor::ops::mult(&[acc, inputs[0].clone()])?;
+                    }
+                }
+                Ok(acc)
+            }
             PolyOp::Sum { axes } => {
                 if 1 != inputs.len() {
                     return Err(TensorError::DimMismatch("sum inputs".to_string()));
                 }
                 tensor::ops::sum_axes(&inputs[0], axes)
             }
+            PolyOp::CumSum { axis } => {
+                if 1 != inputs.len() {
+                    return Err(TensorError::DimMismatch("cumsum inputs".to_string()));
+                }
+                tensor::ops::cumsum(&inputs[0], *axis)
+            }
+            PolyOp::Mean { axes, scale } => {
+                if 1 != inputs.len() {
+                    return Err(TensorError::DimMismatch("mean inputs".to_string()));
+                }
+                let inv_n = Self::quantized_inv_len(inputs[0].dims(), axes, *scale)?;
+                let sum = tensor::ops::sum_axes(&inputs[0], axes)?;
+                tensor::ops::mult(&[sum, Tensor::new(Some(&[inv_n]), &[1])?])
+            }
             PolyOp::Prod { axes, .. } => {
                 if 1 != inputs.len() {
                     return Err(TensorError::DimMismatch("prod inputs".to_string()));
@@ -225,6 +465,12 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
                 }
                 Ok(tensor::ops::slice(&inputs[0], axis, start, end)?)
             }
+            PolyOp::StridedSlice { ranges } => {
+                if 1 != inputs.len() {
+                    return Err(TensorError::DimMismatch("strided slice inputs".to_string()));
+                }
+                tensor::ops::strided_slice(&inputs[0], ranges)
+            }
         }?;
 
         Ok(ForwardResult {
@@ -250,6 +496,8 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
                 source,
                 destination,
             } => layouts::move_axis(values[..].try_into()?, *source, *destination)?,
+            PolyOp::Flip { axis } => layouts::flip(values[..].try_into()?, *axis)?,
+            PolyOp::Permute { perm } => layouts::permute(values[..].try_into()?, perm)?,
             PolyOp::Downsample {
                 axis,
                 stride,
@@ -264,6 +512,12 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
             PolyOp::Sum { axes } => {
                 layouts::sum_axes(config, region, values[..].try_into()?, axes)?
             }
+            PolyOp::CumSum { axis } => {
+                layouts::cumsum(config, region, values[..].try_into()?, *axis)?
+            }
+            PolyOp::Mean { axes, scale } => {
+                layouts::reduce_mean(config, region, values[..].try_into()?, axes, *scale)?
+            }
             PolyOp::Prod { axes, .. } => {
                 layouts::prod_axes(config, region, values[..].try_into()?, axes)?
             }
@@ -279,6 +533,32 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
                 }
                 layouts::conv(config, region, values[..].try_into()?, *padding, *stride)?
             }
+            PolyOp::SparseLinear { weights, bias } => {
+                values.push(weights.clone().into());
+                if let Some(bias) = bias {
+                    values.push(bias.clone().into());
+                }
+                layouts::sparse_linear(config, region, &values, weights)?
+            }
+            PolyOp::TernaryLinear { weights, bias } => {
+                // the weights aren't witnessed, only the input and bias are passed on
+                if let Some(bias) = bias {
+                    values.push(bias.clone().into());
+                }
+                layouts::ternary_linear(config, region, &values, weights)?
+            }
+            PolyOp::Im2ColConv {
+                kernel,
+                bias,
+                padding,
+                stride,
+            } => {
+                values.push(kernel.clone().into());
+                if let Some(bias) = bias {
+                    values.push(bias.clone().into());
+                }
+                layouts::conv_im2col(config, region, &values, *padding, *stride)?
+            }
             PolyOp::DeConv {
                 kernel,
                 bias,
@@ -318,6 +598,11 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
             }
             PolyOp::Identity => layouts::identity(config, region, values[..].try_into()?)?,
             PolyOp::Reshape(d) | PolyOp::Flatten(d) => layouts::reshape(values[..].try_into()?, d)?,
+            PolyOp::MultiBroadcastTo { shape } => {
+                let mut input = values[0].clone();
+                input.expand(shape)?;
+                input
+            }
             PolyOp::Pad(p) => {
                 if values.len() != 1 {
                     return Err(Box::new(TensorError::DimError));
@@ -326,7 +611,17 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
                 input.pad(*p)?;
                 input
             }
+            PolyOp::ConstantPad { pads, value, scale } => {
+                let value = i128_to_felt(quantize_float(&(value.0 as f64), 0.0, *scale)?);
+                layouts::constant_pad(config, region, values[..].try_into()?, pads, value)?
+            }
             PolyOp::Pow(exp) => layouts::pow(config, region, values[..].try_into()?, *exp)?,
+            PolyOp::Polynomial { coeffs, scale } => layouts::polynomial(
+                config,
+                region,
+                values[..].try_into()?,
+                &Self::quantized_coeffs(coeffs, *scale)?,
+            )?,
             PolyOp::Pack(base, scale) => {
                 layouts::pack(config, region, values[..].try_into()?, *base, *scale)?
             }
@@ -335,6 +630,9 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
             PolyOp::Slice { axis, start, end } => {
                 layouts::slice(config, region, values[..].try_into()?, axis, start, end)?
             }
+            PolyOp::StridedSlice { ranges } => {
+                layouts::strided_slice(config, region, values[..].try_into()?, ranges)?
+            }
         }))
     }
 
@@ -343,6 +641,8 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
             PolyOp::Xor | PolyOp::Or | PolyOp::And | PolyOp::Not => 0,
             PolyOp::Neg => in_scales[0],
             PolyOp::MoveAxis { .. } => in_scales[0],
+            PolyOp::Flip { .. } => in_scales[0],
+            PolyOp::Permute { .. } => in_scales[0],
             PolyOp::Downsample { .. } => in_scales[0],
             PolyOp::Resize { .. } => in_scales[0],
             PolyOp::Iff => in_scales[1],
@@ -354,8 +654,18 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
                 scale
             }
             PolyOp::Prod { len_prod, .. } => in_scales[0] * (*len_prod as crate::Scale),
-            PolyOp::Sum { .. } => in_scales[0],
-            PolyOp::Conv { kernel, bias, .. } => {
+            PolyOp::Sum { .. } | PolyOp::CumSum { .. } => in_scales[0],
+            PolyOp::Mean { scale, .. } => in_scales[0] + scale,
+            PolyOp::Conv { kernel, bias, .. }
+            | PolyOp::Im2ColConv { kernel, bias, .. }
+            | PolyOp::SparseLinear {
+                weights: kernel,
+                bias,
+            }
+            | PolyOp::TernaryLinear {
+                weights: kernel,
+                bias,
+            } => {
                 let kernel_scale = match kernel.scale() {
                     Some(s) => s,
                     None => panic!("scale must be set for conv kernel"),
@@ -400,13 +710,17 @@ impl<F: PrimeField + TensorType + PartialOrd + Serialize + for<'de> Deserialize<
                 scale
             }
             PolyOp::Identity => in_scales[0],
-            PolyOp::Reshape(_) | PolyOp::Flatten(_) => in_scales[0],
-            PolyOp::Pad(_) => in_scales[0],
+            PolyOp::Reshape(_) | PolyOp::Flatten(_) | PolyOp::MultiBroadcastTo { .. } => {
+                in_scales[0]
+            }
+            PolyOp::Pad(_) | PolyOp::ConstantPad { .. } => in_scales[0],
             PolyOp::Pow(pow) => in_scales[0] * (*pow as crate::Scale),
+            PolyOp::Polynomial { coeffs, .. } => in_scales[0] * (coeffs.len() as crate::Scale),
             PolyOp::Pack(_, _) => in_scales[0],
             PolyOp::GlobalSumPool => in_scales[0],
             PolyOp::Concat { axis: _ } => in_scales[0],
             PolyOp::Slice { .. } => in_scales[0],
+            PolyOp::StridedSlice { .. } => in_scales[0],
         }
     }
 
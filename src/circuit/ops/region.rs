@@ -10,6 +10,48 @@ use std::{
     sync::atomic::{AtomicUsize, Ordering},
 };
 
+/// An advice value assigned while the witness trace is recorded, see [start_witness_trace]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TracedAdvice {
+    /// The layer (node index) that assigned the value
+    pub layer: usize,
+    /// The index of the advice column
+    pub column: usize,
+    /// The row of the value, relative to the start of its region
+    pub row: usize,
+    /// The little endian representation of the value
+    pub value: Vec<u8>,
+}
+
+thread_local! {
+    /// The current layer and the values assigned so far, if recording
+    static WITNESS_TRACE: RefCell<Option<(usize, Vec<TracedAdvice>)>> = RefCell::new(None);
+}
+
+/// Starts recording every advice value assigned by a [RegionCtx] on the current thread
+pub fn start_witness_trace() {
+    WITNESS_TRACE.with(|t| *t.borrow_mut() = Some((0, vec![])));
+}
+
+/// Sets the layer the values assigned next are attributed to
+pub fn set_trace_layer(layer: usize) {
+    WITNESS_TRACE.with(|t| {
+        if let Some((current, _)) = &mut *t.borrow_mut() {
+            *current = layer;
+        }
+    });
+}
+
+/// Stops recording and returns the values assigned since [start_witness_trace], in order of assignment
+pub fn take_witness_trace() -> Vec<TracedAdvice> {
+    WITNESS_TRACE.with(|t| {
+        t.borrow_mut()
+            .take()
+            .map(|(_, trace)| trace)
+            .unwrap_or_default()
+    })
+}
+
 #[derive(Debug)]
 /// A context for a region
 pub struct RegionCtx<'a, F: PrimeField + TensorType + PartialOrd> {
@@ -18,6 +60,7 @@ pub struct RegionCtx<'a, F: PrimeField + TensorType + PartialOrd> {
     linear_coord: usize,
     num_inner_cols: usize,
     total_constants: usize,
+    total_gates: usize,
 }
 
 impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
@@ -32,6 +75,7 @@ impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
             row,
             linear_coord,
             total_constants: 0,
+            total_gates: 0,
         }
     }
     /// Create a new region context from a wrapped region
@@ -47,6 +91,7 @@ impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
             linear_coord,
             row,
             total_constants: 0,
+            total_gates: 0,
         }
     }
 
@@ -61,6 +106,7 @@ impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
             linear_coord,
             row,
             total_constants: 0,
+            total_gates: 0,
         }
     }
 
@@ -78,6 +124,7 @@ impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
             linear_coord,
             row,
             total_constants: constants,
+            total_gates: 0,
         }
     }
 
@@ -91,6 +138,7 @@ impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
         let row = AtomicUsize::new(self.row());
         let linear_coord = AtomicUsize::new(self.linear_coord());
         let constants = AtomicUsize::new(self.total_constants());
+        let gates = AtomicUsize::new(self.total_gates());
         *output = output.par_enum_map(|idx, _| {
             // we kick off the loop with the current offset
             let starting_offset = row.load(Ordering::Relaxed);
@@ -114,14 +162,39 @@ impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
                 local_reg.total_constants() - starting_constants,
                 Ordering::Relaxed,
             );
+            gates.fetch_add(local_reg.total_gates(), Ordering::Relaxed);
             Ok::<_, Error>(res)
         })?;
         self.total_constants = constants.into_inner();
+        self.total_gates = gates.into_inner();
         self.linear_coord = linear_coord.into_inner();
         self.row = row.into_inner();
         Ok(())
     }
 
+    /// Records the assigned cells among `values` if the witness trace is recorded
+    fn trace<'b>(values: impl IntoIterator<Item = &'b ValType<F>>)
+    where
+        F: 'b,
+    {
+        WITNESS_TRACE.with(|t| {
+            if let Some((layer, trace)) = &mut *t.borrow_mut() {
+                for v in values {
+                    if let ValType::PrevAssigned(cell) | ValType::AssignedConstant(cell, _) = v {
+                        cell.value().map(|f| {
+                            trace.push(TracedAdvice {
+                                layer: *layer,
+                                column: cell.cell().column.index(),
+                                row: cell.cell().row_offset,
+                                value: f.to_repr().as_ref().to_vec(),
+                            })
+                        });
+                    }
+                }
+            }
+        });
+    }
+
     /// Check if the region is dummy
     pub fn is_dummy(&self) -> bool {
         self.region.is_none()
@@ -135,6 +208,7 @@ impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
             num_inner_cols: self.num_inner_cols,
             row: self.row,
             total_constants: self.total_constants,
+            total_gates: self.total_gates,
         }
     }
 
@@ -153,12 +227,24 @@ impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
         self.total_constants
     }
 
+    /// The number of gates enabled so far
+    pub fn total_gates(&self) -> usize {
+        self.total_gates
+    }
+
+    /// Counts `n` gates, for the dummy layouts that skip enabling their selectors
+    pub fn increment_gates(&mut self, n: usize) {
+        self.total_gates += n;
+    }
+
     /// Assign a constant value
     pub fn assign_constant(&mut self, var: &VarTensor, value: F) -> Result<ValType<F>, Error> {
         self.total_constants += 1;
         if let Some(region) = &self.region {
             let cell = var.assign_constant(&mut region.borrow_mut(), self.linear_coord, value)?;
-            Ok(cell.into())
+            let cell: ValType<F> = cell.into();
+            Self::trace([&cell]);
+            Ok(cell)
         } else {
             Ok(value.into())
         }
@@ -170,7 +256,11 @@ impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
         values: &ValTensor<F>,
     ) -> Result<ValTensor<F>, Error> {
         if let Some(region) = &self.region {
-            var.assign(&mut region.borrow_mut(), self.linear_coord, values)
+            let res = var.assign(&mut region.borrow_mut(), self.linear_coord, values)?;
+            if let Ok(inner) = res.get_inner_tensor() {
+                Self::trace(inner.iter());
+            }
+            Ok(res)
         } else {
             self.total_constants += values.num_constants();
             Ok(values.clone())
@@ -185,12 +275,22 @@ impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
         ommissions: &HashSet<&usize>,
     ) -> Result<ValTensor<F>, Error> {
         if let Some(region) = &self.region {
-            var.assign_with_omissions(
+            let res = var.assign_with_omissions(
                 &mut region.borrow_mut(),
                 self.linear_coord,
                 values,
                 ommissions,
-            )
+            )?;
+            if let Ok(inner) = res.get_inner_tensor() {
+                Self::trace(
+                    inner
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| !ommissions.contains(&i))
+                        .map(|(_, v)| v),
+                );
+            }
+            Ok(res)
         } else {
             self.total_constants += values.num_constants();
             let inner_tensor = values.get_inner_tensor().unwrap();
@@ -211,14 +311,18 @@ impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
     ) -> Result<(ValTensor<F>, usize), Error> {
         if let Some(region) = &self.region {
             // duplicates every nth element to adjust for column overflow
-            var.assign_with_duplication(
+            let res = var.assign_with_duplication(
                 &mut region.borrow_mut(),
                 self.row,
                 self.linear_coord,
                 values,
                 check_mode,
                 single_inner_col,
-            )
+            )?;
+            if let Ok(inner) = res.0.get_inner_tensor() {
+                Self::trace(inner.iter());
+            }
+            Ok(res)
         } else {
             let (_, len, total_assigned_constants) = var.dummy_assign_with_duplication(
                 self.row,
@@ -233,6 +337,7 @@ impl<'a, F: PrimeField + TensorType + PartialOrd> RegionCtx<'a, F> {
 
     /// Enable a selector
     pub fn enable(&mut self, selector: Option<&Selector>, offset: usize) -> Result<(), Error> {
+        self.total_gates += 1;
         match &self.region {
             Some(region) => selector.unwrap().enable(&mut region.borrow_mut(), offset),
             None => Ok(()),
@@ -1,4 +1,9 @@
-use std::{error::Error, marker::PhantomData};
+use std::{
+    collections::HashMap,
+    error::Error,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
 
 use halo2curves::ff::PrimeField;
 
@@ -6,12 +11,12 @@ use halo2_proofs::{
     circuit::{Layouter, Value},
     plonk::{ConstraintSystem, Expression, TableColumn},
 };
-use log::warn;
+use log::{debug, warn};
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 
 use crate::{
     circuit::CircuitError,
-    fieldutils::i128_to_felt,
+    fieldutils::{felt_to_i128, i128_to_felt},
     tensor::{Tensor, TensorType},
 };
 
@@ -24,6 +29,69 @@ pub const RANGE_MULTIPLIER: i128 = 2;
 /// The safety factor offset for the number of rows in the lookup table.
 pub const RESERVED_BLINDING_ROWS_PAD: usize = 3;
 
+/// The most table evaluations kept in [TABLE_EVALS] (64MB worth), so that a long running process laying out
+/// the tables of many different circuits doesn't grow without bound.
+pub const MAX_CACHED_TABLE_EVALS: usize = 1 << 22;
+
+type TableCache = HashMap<(LookupOp, (i128, i128)), Arc<Vec<i128>>>;
+
+lazy_static::lazy_static! {
+    /// The evaluations of the lookup tables laid out so far, keyed by non-linearity and range. A circuit's tables
+    /// are laid out again at every synthesis (keygen, proving, each proof of a batch) so we only evaluate them once.
+    static ref TABLE_EVALS: Mutex<TableCache> = Mutex::new(HashMap::new());
+}
+
+/// Caches `evals`, first evicting the whole cache if it would hold more than `max` evaluations. Tables larger than
+/// `max` aren't cached at all.
+fn cache_table_evals(
+    cache: &mut TableCache,
+    key: (LookupOp, (i128, i128)),
+    evals: Arc<Vec<i128>>,
+    max: usize,
+) {
+    if evals.len() > max {
+        return;
+    }
+    let cached: usize = cache.values().map(|e| e.len()).sum();
+    if cached + evals.len() > max {
+        debug!("evicting {} cached table evaluations", cached);
+        cache.clear();
+    }
+    cache.insert(key, evals);
+}
+
+/// The evaluations of `nonlinearity` over every input in `range`, evaluated once and then cached
+fn table_evals<F: PrimeField + TensorType + PartialOrd>(
+    nonlinearity: &LookupOp,
+    range: (i128, i128),
+) -> Result<Arc<Vec<i128>>, Box<dyn Error>> {
+    let key = (nonlinearity.clone(), range);
+    if let Some(evals) = TABLE_EVALS.lock().unwrap().get(&key) {
+        return Ok(evals.clone());
+    }
+    let inputs = Tensor::from(range.0..=range.1).map(|x| i128_to_felt::<F>(x));
+    let evals = Op::<F>::f(nonlinearity, &[inputs])?;
+    let evals = Arc::new(
+        evals
+            .output
+            .iter()
+            .map(|x| felt_to_i128(*x))
+            .collect::<Vec<_>>(),
+    );
+    cache_table_evals(
+        &mut TABLE_EVALS.lock().unwrap(),
+        key,
+        evals.clone(),
+        MAX_CACHED_TABLE_EVALS,
+    );
+    Ok(evals)
+}
+
+/// Frees the cached evaluations of the lookup tables, eg. once all the proofs for a circuit have been generated
+pub fn clear_table_cache() {
+    TABLE_EVALS.lock().unwrap().clear();
+}
+
 #[derive(Debug, Clone)]
 ///
 pub struct SelectorConstructor<F: PrimeField> {
@@ -205,7 +273,7 @@ impl<F: PrimeField + TensorType + PartialOrd> Table<F> {
         let largest = self.range.1;
 
         let inputs = Tensor::from(smallest..=largest).map(|x| i128_to_felt(x));
-        let evals = Op::<F>::f(&self.nonlinearity, &[inputs.clone()])?;
+        let evals = table_evals::<F>(&self.nonlinearity, self.range)?;
         let chunked_inputs = inputs.chunks(self.col_size);
 
         self.is_assigned = true;
@@ -237,7 +305,7 @@ impl<F: PrimeField + TensorType + PartialOrd> Table<F> {
                                     )?;
                                 }
 
-                                let output = evals.output[row_offset];
+                                let output: F = i128_to_felt(evals[row_offset]);
 
                                 table.assign_cell(
                                     || format!("nl_o_col row {}", row_offset),
@@ -257,3 +325,25 @@ impl<F: PrimeField + TensorType + PartialOrd> Table<F> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_table_evals() {
+        let mut cache = TableCache::new();
+        let evals = |len: usize| Arc::new(vec![0; len]);
+        let key = |nl: LookupOp| (nl, (-2, 2));
+
+        cache_table_evals(&mut cache, key(LookupOp::ReLU), evals(5), 8);
+        cache_table_evals(&mut cache, key(LookupOp::Abs), evals(3), 8);
+        assert_eq!(cache.len(), 2);
+        // a third table doesn't fit, so the cache starts over
+        cache_table_evals(&mut cache, key(LookupOp::Sign), evals(5), 8);
+        assert_eq!(cache.keys().collect::<Vec<_>>(), vec![&key(LookupOp::Sign)]);
+        // and tables larger than the bound are never cached
+        cache_table_evals(&mut cache, key(LookupOp::ReLU), evals(9), 8);
+        assert!(!cache.contains_key(&key(LookupOp::ReLU)));
+    }
+}
@@ -94,6 +94,86 @@ mod matmul {
     }
 }
 
+#[cfg(test)]
+mod matmul_fixed_weights {
+
+    use super::*;
+
+    const K: usize = 9;
+    const LEN: usize = 3;
+
+    #[derive(Clone)]
+    struct MatmulCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 2],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MatmulCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN * LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN * LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN * LEN);
+            // the weights are constants, so they live in fixed columns
+            VarTensor::constant_cols(cs, K, LEN + 1, false);
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        config
+                            .layout(
+                                &mut region,
+                                &self.inputs.clone(),
+                                Box::new(PolyOp::Einsum {
+                                    equation: "ij,jk->ik".to_string(),
+                                }),
+                            )
+                            .map_err(|_| Error::Synthesis)
+                    },
+                )
+                .unwrap();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn matmulcircuitfixedweights() {
+        // parameters
+        let mut a =
+            Tensor::from((0..(LEN + 1) * LEN).map(|i| Value::known(F::from((i + 1) as u64))));
+        a.reshape(&[LEN, LEN + 1]);
+
+        let mut w = Tensor::from((0..LEN + 1).map(|i| F::from((i + 1) as u64)));
+        w.reshape(&[LEN + 1, 1]);
+        w.set_visibility(&crate::graph::Visibility::Fixed);
+
+        let circuit = MatmulCircuit::<F> {
+            inputs: [ValTensor::from(a), ValTensor::from(w)],
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
 #[cfg(test)]
 mod matmul_col_overflow_double_col {
     use super::*;
@@ -358,6 +438,173 @@ mod matmul_col_ultra_overflow_double_col {
     }
 }
 
+#[cfg(test)]
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod matmul_transcripts {
+    use halo2_proofs::poly::commitment::ParamsProver;
+
+    use super::*;
+
+    const K: usize = 9;
+    const LEN: usize = 3;
+
+    #[derive(Clone)]
+    struct MatmulCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 2],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MatmulCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN * LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN * LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN * LEN);
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        config
+                            .layout(
+                                &mut region,
+                                &self.inputs.clone(),
+                                Box::new(PolyOp::Einsum {
+                                    equation: "ij,jk->ik".to_string(),
+                                }),
+                            )
+                            .map_err(|_| Error::Synthesis)
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn matmulcircuit_transcripts() {
+        let mut a = Tensor::from((0..LEN * LEN).map(|i| Value::known(F::from((i + 1) as u64))));
+        a.reshape(&[LEN, LEN]);
+
+        let mut w = Tensor::from((0..LEN).map(|i| Value::known(F::from((i + 1) as u64))));
+        w.reshape(&[LEN, 1]);
+
+        let circuit = MatmulCircuit::<F> {
+            inputs: [ValTensor::from(a), ValTensor::from(w)],
+            _marker: PhantomData,
+        };
+
+        let params = crate::pfsys::srs::gen_srs::<
+            halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme<_>,
+        >(K as u32);
+
+        let pk = crate::pfsys::create_keys::<
+            halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme<halo2curves::bn256::Bn256>,
+            F,
+            MatmulCircuit<F>,
+        >(&circuit, &params)
+        .unwrap();
+
+        for transcript in [
+            crate::pfsys::TranscriptType::Poseidon,
+            crate::pfsys::TranscriptType::EVM,
+            crate::pfsys::TranscriptType::Blake2b,
+        ] {
+            let proof = crate::pfsys::create_proof_circuit_kzg(
+                circuit.clone(),
+                &params,
+                None,
+                &pk,
+                transcript,
+                halo2_proofs::poly::kzg::strategy::SingleStrategy::new(&params),
+                CheckMode::SAFE,
+                None,
+            )
+            .unwrap();
+            assert_eq!(proof.transcript_type, transcript);
+
+            let strategy =
+                halo2_proofs::poly::kzg::strategy::SingleStrategy::new(params.verifier_params());
+            let result = crate::pfsys::verify_proof_circuit_kzg(
+                params.verifier_params(),
+                proof,
+                pk.get_vk(),
+                strategy,
+            );
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn matmulcircuit_batch() {
+        let params = crate::pfsys::srs::gen_srs::<
+            halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme<_>,
+        >(K as u32);
+
+        let circuit = |offset: u64| {
+            let mut a =
+                Tensor::from((0..LEN * LEN).map(|i| Value::known(F::from(i as u64 + offset))));
+            a.reshape(&[LEN, LEN]);
+            let mut w = Tensor::from((0..LEN).map(|i| Value::known(F::from((i + 1) as u64))));
+            w.reshape(&[LEN, 1]);
+            MatmulCircuit::<F> {
+                inputs: [ValTensor::from(a), ValTensor::from(w)],
+                _marker: PhantomData,
+            }
+        };
+
+        let pk = crate::pfsys::create_keys::<
+            halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme<halo2curves::bn256::Bn256>,
+            F,
+            MatmulCircuit<F>,
+        >(&circuit(1), &params)
+        .unwrap();
+
+        let proofs = (1..4)
+            .map(|offset| {
+                crate::pfsys::create_proof_circuit_kzg(
+                    circuit(offset),
+                    &params,
+                    None,
+                    &pk,
+                    crate::pfsys::TranscriptType::Blake2b,
+                    halo2_proofs::poly::kzg::strategy::SingleStrategy::new(&params),
+                    CheckMode::SAFE,
+                    None,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let verify = |proofs: &[_]| {
+            crate::pfsys::verify_batch(proofs, params.verifier_params(), pk.get_vk())
+        };
+        assert!(verify(&proofs).is_ok());
+
+        // a single tampered proof fails the whole batch
+        let mut tampered = proofs.clone();
+        let len = tampered[2].proof.len();
+        tampered[2].proof[len / 2] ^= 1;
+        assert!(verify(&tampered).is_err());
+        assert!(verify(&tampered[..2]).is_ok());
+    }
+}
+
 #[cfg(test)]
 #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 mod matmul_col_ultra_overflow {
@@ -767,12 +1014,12 @@ mod sum {
 }
 
 #[cfg(test)]
-mod sum_col_overflow_double_col {
+mod cumsum {
     use super::*;
 
-    const K: usize = 4;
-    const LEN: usize = 20;
+    const K: usize = 6;
     const NUM_INNER_COLS: usize = 2;
+    const LEN: usize = 32;
 
     #[derive(Clone)]
     struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
@@ -793,6 +1040,7 @@ mod sum_col_overflow_double_col {
             let a = VarTensor::new_advice(cs, K, NUM_INNER_COLS, LEN);
             let b = VarTensor::new_advice(cs, K, NUM_INNER_COLS, LEN);
             let output = VarTensor::new_advice(cs, K, NUM_INNER_COLS, LEN);
+            VarTensor::constant_cols(cs, K, LEN, false);
 
             Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
         }
@@ -807,13 +1055,18 @@ mod sum_col_overflow_double_col {
                     || "",
                     |region| {
                         let mut region = RegionCtx::new(region, 0, NUM_INNER_COLS);
-                        config
+                        let output = config
                             .layout(
                                 &mut region,
                                 &self.inputs.clone(),
-                                Box::new(PolyOp::Sum { axes: vec![0] }),
+                                Box::new(PolyOp::CumSum { axis: 1 }),
                             )
-                            .map_err(|_| Error::Synthesis)
+                            .map_err(|_| Error::Synthesis)?
+                            .unwrap();
+                        if let Ok(evals) = output.get_int_evals() {
+                            assert_eq!(evals.to_vec(), vec![1, 3, 6, 4, 9, 15]);
+                        }
+                        Ok(())
                     },
                 )
                 .unwrap();
@@ -822,9 +1075,9 @@ mod sum_col_overflow_double_col {
     }
 
     #[test]
-    fn sumcircuit() {
-        // parameters
-        let a = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
+    fn cumsumcircuit() {
+        let mut a = Tensor::from((0..6).map(|i| Value::known(F::from(i as u64 + 1))));
+        a.reshape(&[2, 3]);
 
         let circuit = MyCircuit::<F> {
             inputs: [ValTensor::from(a)],
@@ -837,11 +1090,11 @@ mod sum_col_overflow_double_col {
 }
 
 #[cfg(test)]
-mod sum_col_overflow {
+mod mean {
     use super::*;
 
-    const K: usize = 4;
-    const LEN: usize = 20;
+    const K: usize = 6;
+    const LEN: usize = 8;
 
     #[derive(Clone)]
     struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
@@ -876,13 +1129,21 @@ mod sum_col_overflow {
                     || "",
                     |region| {
                         let mut region = RegionCtx::new(region, 0, 1);
-                        config
+                        let output = config
                             .layout(
                                 &mut region,
                                 &self.inputs.clone(),
-                                Box::new(PolyOp::Sum { axes: vec![0] }),
+                                Box::new(PolyOp::Mean {
+                                    axes: vec![1],
+                                    scale: 2,
+                                }),
                             )
-                            .map_err(|_| Error::Synthesis)
+                            .map_err(|_| Error::Synthesis)?;
+                        if let Some(Ok(evals)) = output.map(|o| o.get_int_evals()) {
+                            // the rows sum to 10 and 26, and 1 / 4 is exactly 1 at scale 2
+                            assert_eq!(evals.to_vec(), vec![10, 26]);
+                        }
+                        Ok(())
                     },
                 )
                 .unwrap();
@@ -891,9 +1152,9 @@ mod sum_col_overflow {
     }
 
     #[test]
-    fn sumcircuit() {
-        // parameters
-        let a = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
+    fn meancircuit() {
+        let mut a = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
+        a.reshape(&[2, 4]);
 
         let circuit = MyCircuit::<F> {
             inputs: [ValTensor::from(a)],
@@ -906,16 +1167,16 @@ mod sum_col_overflow {
 }
 
 #[cfg(test)]
-mod composition {
-
+mod sum_col_overflow_double_col {
     use super::*;
 
-    const K: usize = 9;
-    const LEN: usize = 4;
+    const K: usize = 4;
+    const LEN: usize = 20;
+    const NUM_INNER_COLS: usize = 2;
 
     #[derive(Clone)]
     struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
-        inputs: [ValTensor<F>; 2],
+        inputs: [ValTensor<F>; 1],
         _marker: PhantomData<F>,
     }
 
@@ -929,9 +1190,9 @@ mod composition {
         }
 
         fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
-            let a = VarTensor::new_advice(cs, K, 1, LEN);
-            let b = VarTensor::new_advice(cs, K, 1, LEN);
-            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            let a = VarTensor::new_advice(cs, K, NUM_INNER_COLS, LEN);
+            let b = VarTensor::new_advice(cs, K, NUM_INNER_COLS, LEN);
+            let output = VarTensor::new_advice(cs, K, NUM_INNER_COLS, LEN);
 
             Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
         }
@@ -941,37 +1202,16 @@ mod composition {
             mut config: Self::Config,
             mut layouter: impl Layouter<F>,
         ) -> Result<(), Error> {
-            // lots of stacked dot products
             layouter
                 .assign_region(
                     || "",
                     |region| {
-                        let mut region = RegionCtx::new(region, 0, 1);
-                        let _ = config
-                            .layout(
-                                &mut region,
-                                &self.inputs.clone(),
-                                Box::new(PolyOp::Einsum {
-                                    equation: "i,i->".to_string(),
-                                }),
-                            )
-                            .unwrap();
-                        let _ = config
-                            .layout(
-                                &mut region,
-                                &self.inputs.clone(),
-                                Box::new(PolyOp::Einsum {
-                                    equation: "i,i->".to_string(),
-                                }),
-                            )
-                            .unwrap();
+                        let mut region = RegionCtx::new(region, 0, NUM_INNER_COLS);
                         config
                             .layout(
                                 &mut region,
                                 &self.inputs.clone(),
-                                Box::new(PolyOp::Einsum {
-                                    equation: "i,i->".to_string(),
-                                }),
+                                Box::new(PolyOp::Sum { axes: vec![0] }),
                             )
                             .map_err(|_| Error::Synthesis)
                     },
@@ -982,14 +1222,12 @@ mod composition {
     }
 
     #[test]
-    fn dotcircuit() {
+    fn sumcircuit() {
         // parameters
         let a = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
 
-        let b = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
-
         let circuit = MyCircuit::<F> {
-            inputs: [ValTensor::from(a), ValTensor::from(b)],
+            inputs: [ValTensor::from(a)],
             _marker: PhantomData,
         };
 
@@ -999,11 +1237,173 @@ mod composition {
 }
 
 #[cfg(test)]
-mod conv {
-
+mod sum_col_overflow {
     use super::*;
 
-    const K: usize = 22;
+    const K: usize = 4;
+    const LEN: usize = 20;
+
+    #[derive(Clone)]
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 1],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MyCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        config
+                            .layout(
+                                &mut region,
+                                &self.inputs.clone(),
+                                Box::new(PolyOp::Sum { axes: vec![0] }),
+                            )
+                            .map_err(|_| Error::Synthesis)
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn sumcircuit() {
+        // parameters
+        let a = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
+
+        let circuit = MyCircuit::<F> {
+            inputs: [ValTensor::from(a)],
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+mod composition {
+
+    use super::*;
+
+    const K: usize = 9;
+    const LEN: usize = 4;
+
+    #[derive(Clone)]
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 2],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MyCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            // lots of stacked dot products
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let _ = config
+                            .layout(
+                                &mut region,
+                                &self.inputs.clone(),
+                                Box::new(PolyOp::Einsum {
+                                    equation: "i,i->".to_string(),
+                                }),
+                            )
+                            .unwrap();
+                        let _ = config
+                            .layout(
+                                &mut region,
+                                &self.inputs.clone(),
+                                Box::new(PolyOp::Einsum {
+                                    equation: "i,i->".to_string(),
+                                }),
+                            )
+                            .unwrap();
+                        config
+                            .layout(
+                                &mut region,
+                                &self.inputs.clone(),
+                                Box::new(PolyOp::Einsum {
+                                    equation: "i,i->".to_string(),
+                                }),
+                            )
+                            .map_err(|_| Error::Synthesis)
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn dotcircuit() {
+        // parameters
+        let a = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
+
+        let b = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
+
+        let circuit = MyCircuit::<F> {
+            inputs: [ValTensor::from(a), ValTensor::from(b)],
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+mod conv {
+
+    use super::*;
+
+    const K: usize = 22;
     const LEN: usize = 100;
 
     #[derive(Clone)]
@@ -1124,19 +1524,16 @@ mod conv {
 }
 
 #[cfg(test)]
-#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
-mod conv_col_ultra_overflow {
-    use halo2_proofs::poly::commitment::ParamsProver;
+mod conv_im2col {
 
     use super::*;
 
-    const K: usize = 4;
-    const LEN: usize = 28;
+    const K: usize = 12;
+    const LEN: usize = 40;
 
     #[derive(Clone)]
     struct ConvCircuit<F: PrimeField + TensorType + PartialOrd> {
-        image: ValTensor<F>,
-        kernel: Tensor<F>,
+        inputs: Vec<Tensor<F>>,
         _marker: PhantomData<F>,
     }
 
@@ -1150,9 +1547,9 @@ mod conv_col_ultra_overflow {
         }
 
         fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
-            let a = VarTensor::new_advice(cs, K, 1, LEN * LEN * LEN);
-            let b = VarTensor::new_advice(cs, K, 1, LEN * LEN * LEN);
-            let output = VarTensor::new_advice(cs, K, 1, LEN * LEN * LEN);
+            let a = VarTensor::new_advice(cs, K, 1, (LEN + 1) * LEN);
+            let b = VarTensor::new_advice(cs, K, 1, (LEN + 1) * LEN);
+            let output = VarTensor::new_advice(cs, K, 1, (LEN + 1) * LEN);
             Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
         }
 
@@ -1166,18 +1563,40 @@ mod conv_col_ultra_overflow {
                     || "",
                     |region| {
                         let mut region = RegionCtx::new(region, 0, 1);
-                        config
+                        let (kernel, bias) = (self.inputs[1].clone(), self.inputs.get(2).cloned());
+                        let im2col = config
                             .layout(
                                 &mut region,
-                                &[self.image.clone()],
+                                &[self.inputs[0].clone().into()],
+                                Box::new(PolyOp::Im2ColConv {
+                                    kernel: kernel.clone(),
+                                    bias: bias.clone(),
+                                    padding: [(1, 1); 2],
+                                    stride: (2, 2),
+                                }),
+                            )
+                            .map_err(|_| Error::Synthesis)?
+                            .unwrap();
+                        let direct = config
+                            .layout(
+                                &mut region,
+                                &[self.inputs[0].clone().into()],
                                 Box::new(PolyOp::Conv {
-                                    kernel: self.kernel.clone(),
-                                    bias: None,
+                                    kernel,
+                                    bias,
                                     padding: [(1, 1); 2],
                                     stride: (2, 2),
                                 }),
                             )
-                            .map_err(|_| Error::Synthesis)
+                            .map_err(|_| Error::Synthesis)?
+                            .unwrap();
+                        // both lowerings compute the same convolution
+                        assert_eq!(im2col.dims(), direct.dims());
+                        assert_eq!(
+                            im2col.get_felt_evals().unwrap(),
+                            direct.get_felt_evals().unwrap()
+                        );
+                        Ok(())
                     },
                 )
                 .unwrap();
@@ -1186,18 +1605,15 @@ mod conv_col_ultra_overflow {
     }
 
     #[test]
-    #[ignore]
-    fn conv_circuit() {
+    fn im2colconvcircuit() {
         // parameters
         let kernel_height = 2;
-        let kernel_width = 2;
-        let image_height = LEN;
-        let image_width = LEN;
+        let kernel_width = 3;
+        let image_height = 5;
+        let image_width = 7;
         let in_channels = 3;
         let out_channels = 2;
 
-        // get some logs fam
-        crate::logger::init_logger();
         let mut image =
             Tensor::from((0..in_channels * image_height * image_width).map(|i| F::from(i as u64)));
         image.reshape(&[1, in_channels, image_height, image_width]);
@@ -1210,38 +1626,169 @@ mod conv_col_ultra_overflow {
         kernels.reshape(&[out_channels, in_channels, kernel_height, kernel_width]);
         kernels.set_visibility(&crate::graph::Visibility::Private);
 
+        let mut bias = Tensor::from((0..{ out_channels }).map(|i| F::from(i as u64)));
+        bias.set_visibility(&crate::graph::Visibility::Private);
+
         let circuit = ConvCircuit::<F> {
-            image: ValTensor::from(image),
-            kernel: kernels,
+            inputs: [image, kernels, bias].to_vec(),
             _marker: PhantomData,
         };
 
-        let params = crate::pfsys::srs::gen_srs::<
-            halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme<_>,
-        >(K as u32);
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
 
-        let pk = crate::pfsys::create_keys::<
-            halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme<halo2curves::bn256::Bn256>,
-            F,
-            ConvCircuit<F>,
-        >(&circuit, &params)
-        .unwrap();
+    #[test]
+    fn im2colconvcircuitnobias() {
+        // parameters
+        let kernel_height = 2;
+        let kernel_width = 2;
+        let image_height = 4;
+        let image_width = 5;
+        let in_channels = 3;
+        let out_channels = 2;
 
-        let prover = crate::pfsys::create_proof_circuit_kzg(
-            circuit.clone(),
-            &params,
-            None,
-            &pk,
-            crate::pfsys::TranscriptType::EVM,
-            halo2_proofs::poly::kzg::strategy::SingleStrategy::new(&params),
-            // use safe mode to verify that the proof is correct
-            CheckMode::SAFE,
-            None,
-        );
+        let mut image =
+            Tensor::from((0..in_channels * image_height * image_width).map(|i| F::from(i as u64)));
+        image.reshape(&[1, in_channels, image_height, image_width]);
+        image.set_visibility(&crate::graph::Visibility::Private);
 
-        assert!(prover.is_ok());
+        let mut kernels = Tensor::from(
+            (0..{ out_channels * in_channels * kernel_height * kernel_width })
+                .map(|i| F::from(i as u64)),
+        );
+        kernels.reshape(&[out_channels, in_channels, kernel_height, kernel_width]);
+        kernels.set_visibility(&crate::graph::Visibility::Private);
 
-        let proof = prover.unwrap();
+        let circuit = ConvCircuit::<F> {
+            inputs: [image, kernels].to_vec(),
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod conv_col_ultra_overflow {
+    use halo2_proofs::poly::commitment::ParamsProver;
+
+    use super::*;
+
+    const K: usize = 4;
+    const LEN: usize = 28;
+
+    #[derive(Clone)]
+    struct ConvCircuit<F: PrimeField + TensorType + PartialOrd> {
+        image: ValTensor<F>,
+        kernel: Tensor<F>,
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for ConvCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN * LEN * LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN * LEN * LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN * LEN * LEN);
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        config
+                            .layout(
+                                &mut region,
+                                &[self.image.clone()],
+                                Box::new(PolyOp::Conv {
+                                    kernel: self.kernel.clone(),
+                                    bias: None,
+                                    padding: [(1, 1); 2],
+                                    stride: (2, 2),
+                                }),
+                            )
+                            .map_err(|_| Error::Synthesis)
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn conv_circuit() {
+        // parameters
+        let kernel_height = 2;
+        let kernel_width = 2;
+        let image_height = LEN;
+        let image_width = LEN;
+        let in_channels = 3;
+        let out_channels = 2;
+
+        // get some logs fam
+        crate::logger::init_logger();
+        let mut image =
+            Tensor::from((0..in_channels * image_height * image_width).map(|i| F::from(i as u64)));
+        image.reshape(&[1, in_channels, image_height, image_width]);
+        image.set_visibility(&crate::graph::Visibility::Private);
+
+        let mut kernels = Tensor::from(
+            (0..{ out_channels * in_channels * kernel_height * kernel_width })
+                .map(|i| F::from(i as u64)),
+        );
+        kernels.reshape(&[out_channels, in_channels, kernel_height, kernel_width]);
+        kernels.set_visibility(&crate::graph::Visibility::Private);
+
+        let circuit = ConvCircuit::<F> {
+            image: ValTensor::from(image),
+            kernel: kernels,
+            _marker: PhantomData,
+        };
+
+        let params = crate::pfsys::srs::gen_srs::<
+            halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme<_>,
+        >(K as u32);
+
+        let pk = crate::pfsys::create_keys::<
+            halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme<halo2curves::bn256::Bn256>,
+            F,
+            ConvCircuit<F>,
+        >(&circuit, &params)
+        .unwrap();
+
+        let prover = crate::pfsys::create_proof_circuit_kzg(
+            circuit.clone(),
+            &params,
+            None,
+            &pk,
+            crate::pfsys::TranscriptType::EVM,
+            halo2_proofs::poly::kzg::strategy::SingleStrategy::new(&params),
+            // use safe mode to verify that the proof is correct
+            CheckMode::SAFE,
+            None,
+        );
+
+        assert!(prover.is_ok());
+
+        let proof = prover.unwrap();
 
         let strategy =
             halo2_proofs::poly::kzg::strategy::SingleStrategy::new(params.verifier_params());
@@ -1487,12 +2034,13 @@ mod sumpool {
 mod add_w_shape_casting {
     use super::*;
 
-    const K: usize = 4;
+    const K: usize = 5;
     const LEN: usize = 4;
 
     #[derive(Clone)]
     struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
         inputs: [ValTensor<F>; 2],
+        expected: Option<Vec<i128>>,
         _marker: PhantomData<F>,
     }
 
@@ -1523,9 +2071,15 @@ mod add_w_shape_casting {
                     || "",
                     |region| {
                         let mut region = RegionCtx::new(region, 0, 1);
-                        config
+                        let output = config
                             .layout(&mut region, &self.inputs.clone(), Box::new(PolyOp::Add))
-                            .map_err(|_| Error::Synthesis)
+                            .map_err(|_| Error::Synthesis)?;
+                        if let (Some(expected), Some(Ok(evals))) =
+                            (&self.expected, output.map(|o| o.get_int_evals()))
+                        {
+                            assert_eq!(&evals.to_vec(), expected);
+                        }
+                        Ok(())
                     },
                 )
                 .unwrap();
@@ -1542,6 +2096,44 @@ mod add_w_shape_casting {
 
         let circuit = MyCircuit::<F> {
             inputs: [ValTensor::from(a), ValTensor::from(b)],
+            expected: None,
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+
+    #[test]
+    fn addcircuit_channel_bias() {
+        // a [C, 1, 1] bias over a [N, C, H, W] feature map
+        let mut a = Tensor::from((0..8).map(|i| Value::known(F::from(i as u64 + 1))));
+        a.reshape(&[1, 2, 2, 2]);
+
+        let mut b = Tensor::from((0..2).map(|i| Value::known(F::from(i as u64 + 1))));
+        b.reshape(&[2, 1, 1]);
+
+        let circuit = MyCircuit::<F> {
+            inputs: [ValTensor::from(a), ValTensor::from(b)],
+            expected: Some(vec![2, 3, 4, 5, 7, 8, 9, 10]),
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+
+    #[test]
+    fn addcircuit_row_broadcast() {
+        // a [3] vector (as the lhs) is added to each row of a [3, 3] matrix
+        let a = Tensor::from((0..3).map(|i| Value::known(F::from(i as u64 + 1))));
+
+        let mut b = Tensor::from((0..9).map(|i| Value::known(F::from(i as u64 + 1))));
+        b.reshape(&[3, 3]);
+
+        let circuit = MyCircuit::<F> {
+            inputs: [ValTensor::from(a), ValTensor::from(b)],
+            expected: Some(vec![2, 4, 6, 5, 7, 9, 8, 10, 12]),
             _marker: PhantomData,
         };
 
@@ -2020,6 +2612,80 @@ mod pow {
     }
 }
 
+#[cfg(test)]
+mod polynomial {
+    use super::*;
+
+    const K: usize = 8;
+    const LEN: usize = 4;
+
+    #[derive(Clone)]
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 1],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MyCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            let _constant = VarTensor::constant_cols(cs, K, LEN * 3, false);
+
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        config
+                            .layout(
+                                &mut region,
+                                &self.inputs.clone(),
+                                Box::new(PolyOp::poly_relu(2)),
+                            )
+                            .map_err(|_| Error::Synthesis)
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn polyrelucircuit() {
+        // inputs at scale 2, ie. multiples of 0.25
+        let a = Tensor::from(
+            [-8, -1, 2, 7]
+                .into_iter()
+                .map(|i| Value::known(crate::fieldutils::i128_to_felt::<F>(i))),
+        );
+
+        let circuit = MyCircuit::<F> {
+            inputs: [ValTensor::from(a)],
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
 #[cfg(test)]
 mod pack {
     use super::*;
@@ -2321,7 +2987,7 @@ mod rangecheckpercent {
 }
 
 #[cfg(test)]
-mod relu {
+mod rangecheckabs {
     use super::*;
     use halo2_proofs::{
         circuit::{Layouter, SimpleFloorPlanner, Value},
@@ -2329,12 +2995,20 @@ mod relu {
         plonk::{Circuit, ConstraintSystem, Error},
     };
 
+    const K: usize = 8;
+    const LEN: usize = 2;
+    // 0.05 at a scale of 32 is a tolerance of 2 in fixed point
+    const TOL: f32 = 0.05;
+    const SCALE: f32 = 32.0;
+
     #[derive(Clone)]
-    struct ReLUCircuit<F: PrimeField + TensorType + PartialOrd> {
-        pub input: ValTensor<F>,
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        input: ValTensor<F>,
+        output: ValTensor<F>,
+        _marker: PhantomData<F>,
     }
 
-    impl Circuit<F> for ReLUCircuit<F> {
+    impl Circuit<F> for MyCircuit<F> {
         type Config = BaseConfig<F>;
         type FloorPlanner = SimpleFloorPlanner;
         type Params = TestParams;
@@ -2344,16 +3018,24 @@ mod relu {
         }
 
         fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            VarTensor::constant_cols(cs, K, 2, false);
+            let mut config = Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE);
             let advices = (0..3)
-                .map(|_| VarTensor::new_advice(cs, 4, 1, 3))
+                .map(|_| VarTensor::new_advice(cs, K, 1, LEN))
                 .collect::<Vec<_>>();
-
-            let nl = LookupOp::ReLU;
-
-            let mut config = BaseConfig::default();
-
             config
-                .configure_lookup(cs, &advices[0], &advices[1], &advices[2], (-6, 6), 4, &nl)
+                .configure_lookup(
+                    cs,
+                    &advices[0],
+                    &advices[1],
+                    &advices[2],
+                    (-64, 64),
+                    K,
+                    &cmp::gt_lookup(),
+                )
                 .unwrap();
             config
         }
@@ -2361,7 +3043,7 @@ mod relu {
         fn synthesize(
             &self,
             mut config: Self::Config,
-            mut layouter: impl Layouter<F>, // layouter is our 'write buffer' for the circuit
+            mut layouter: impl Layouter<F>,
         ) -> Result<(), Error> {
             config.layout_tables(&mut layouter).unwrap();
             layouter
@@ -2370,38 +3052,54 @@ mod relu {
                     |region| {
                         let mut region = RegionCtx::new(region, 0, 1);
                         config
-                            .layout(&mut region, &[self.input.clone()], Box::new(LookupOp::ReLU))
-                            .map_err(|_| Error::Synthesis)
-                    },
+                            .layout(
+                                &mut region,
+                                &[self.output.clone(), self.input.clone()],
+                                Box::new(HybridOp::AbsRangeCheck {
+                                    tol: TOL.into(),
+                                    scale: SCALE.into(),
+                                }),
+                            )
+                            .map_err(|_| Error::Synthesis)
+                    },
                 )
                 .unwrap();
-
             Ok(())
         }
     }
 
-    #[test]
-    fn relucircuit() {
-        let input: Tensor<Value<F>> =
-            Tensor::new(Some(&[Value::<F>::known(F::from(1_u64)); 4]), &[4]).unwrap();
-
-        let circuit = ReLUCircuit::<F> {
-            input: ValTensor::from(input),
+    fn circuit(input: [i128; 2], output: [i128; 2]) -> MyCircuit<F> {
+        let tensor = |v: [i128; 2]| {
+            ValTensor::from(Tensor::from(
+                v.into_iter()
+                    .map(|x| Value::known(crate::fieldutils::i128_to_felt::<F>(x))),
+            ))
         };
+        MyCircuit {
+            input: tensor(input),
+            output: tensor(output),
+            _marker: PhantomData,
+        }
+    }
 
-        let prover = MockProver::run(4_u32, &circuit, vec![]).unwrap();
+    #[test]
+    fn test_range_check_abs() {
+        let prover = MockProver::run(K as u32, &circuit([100, 200], [101, 199]), vec![]).unwrap();
         prover.assert_satisfied_par();
+
+        // 0.05 at scale 32 is 1.6, and a difference of 2 / 32 exceeds the tolerance
+        let prover = MockProver::run(K as u32, &circuit([100, 200], [102, 200]), vec![]).unwrap();
+        assert!(prover.verify().is_err());
     }
 }
 
 #[cfg(test)]
-#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
-mod lookup_ultra_overflow {
+mod relu {
     use super::*;
     use halo2_proofs::{
         circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
         plonk::{Circuit, ConstraintSystem, Error},
-        poly::commitment::ParamsProver,
     };
 
     #[derive(Clone)]
@@ -2428,15 +3126,7 @@ mod lookup_ultra_overflow {
             let mut config = BaseConfig::default();
 
             config
-                .configure_lookup(
-                    cs,
-                    &advices[0],
-                    &advices[1],
-                    &advices[2],
-                    (-1024, 1024),
-                    4,
-                    &nl,
-                )
+                .configure_lookup(cs, &advices[0], &advices[1], &advices[2], (-6, 6), 4, &nl)
                 .unwrap();
             config
         }
@@ -2464,59 +3154,121 @@ mod lookup_ultra_overflow {
     }
 
     #[test]
-    #[ignore]
     fn relucircuit() {
-        // get some logs fam
-        crate::logger::init_logger();
-        // parameters
-        let a = Tensor::from((0..4).map(|i| Value::known(F::from(i + 1))));
+        let input: Tensor<Value<F>> =
+            Tensor::new(Some(&[Value::<F>::known(F::from(1_u64)); 4]), &[4]).unwrap();
 
         let circuit = ReLUCircuit::<F> {
-            input: ValTensor::from(a),
+            input: ValTensor::from(input),
         };
 
-        let params = crate::pfsys::srs::gen_srs::<
-            halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme<_>,
-        >(4_u32);
-
-        let pk = crate::pfsys::create_keys::<
-            halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme<halo2curves::bn256::Bn256>,
-            F,
-            ReLUCircuit<F>,
-        >(&circuit, &params)
-        .unwrap();
+        let prover = MockProver::run(4_u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
 
-        let prover = crate::pfsys::create_proof_circuit_kzg(
-            circuit.clone(),
-            &params,
-            None,
-            &pk,
-            crate::pfsys::TranscriptType::EVM,
-            halo2_proofs::poly::kzg::strategy::SingleStrategy::new(&params),
-            // use safe mode to verify that the proof is correct
-            CheckMode::SAFE,
-            None,
+    #[test]
+    fn relucircuit_constants_outside_range() {
+        use crate::tensor::ValType;
+
+        // constants are computed directly rather than looked up, so they can fall outside of the table
+        let input: Tensor<ValType<F>> = Tensor::from(
+            (0..3)
+                .map(|i| ValType::Value(Value::<F>::known(F::from(i as u64))))
+                .chain([ValType::Constant(F::from(100_u64))]),
         );
 
-        assert!(prover.is_ok());
+        let circuit = ReLUCircuit::<F> {
+            input: ValTensor::from(input),
+        };
 
-        let proof = prover.unwrap();
+        let prover = MockProver::run(4_u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
 
-        let strategy =
-            halo2_proofs::poly::kzg::strategy::SingleStrategy::new(params.verifier_params());
-        let vk = pk.get_vk();
-        let result =
-            crate::pfsys::verify_proof_circuit_kzg(params.verifier_params(), proof, vk, strategy);
+#[cfg(test)]
+mod div_relu {
+    use super::*;
+    use crate::fieldutils::{felt_to_i128, i128_to_felt};
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Circuit, ConstraintSystem, Error},
+    };
 
-        assert!(result.is_ok());
+    const NL: LookupOp = LookupOp::DivReLU {
+        denom: crate::circuit::utils::F32(2.0),
+    };
 
-        println!("done.");
+    #[derive(Clone)]
+    struct DivReLUCircuit<F: PrimeField + TensorType + PartialOrd> {
+        pub input: ValTensor<F>,
+    }
+
+    impl Circuit<F> for DivReLUCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, 4, 1, 3))
+                .collect::<Vec<_>>();
+
+            let mut config = BaseConfig::default();
+
+            config
+                .configure_lookup(cs, &advices[0], &advices[1], &advices[2], (-6, 6), 4, &NL)
+                .unwrap();
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>, // layouter is our 'write buffer' for the circuit
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        config
+                            .layout(&mut region, &[self.input.clone()], Box::new(NL))
+                            .map_err(|_| Error::Synthesis)
+                    },
+                )
+                .unwrap();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn divrelucircuit() {
+        let input = Tensor::new(Some(&[-4, -2, 2, 4]), &[4]).unwrap();
+        let res = Op::<F>::f(&NL, &[input.map(i128_to_felt)]).unwrap().output;
+        assert_eq!(
+            res.map(felt_to_i128),
+            Tensor::new(Some(&[0, 0, 1, 2]), &[4]).unwrap()
+        );
+
+        let circuit = DivReLUCircuit::<F> {
+            input: ValTensor::from(input.map(|x| Value::known(i128_to_felt::<F>(x)))),
+        };
+
+        let prover = MockProver::run(4_u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
     }
 }
 
 #[cfg(test)]
-mod softmax {
-
+mod shared_tables {
     use super::*;
     use halo2_proofs::{
         circuit::{Layouter, SimpleFloorPlanner, Value},
@@ -2524,17 +3276,26 @@ mod softmax {
         plonk::{Circuit, ConstraintSystem, Error},
     };
 
-    const K: usize = 18;
-    const LEN: usize = 3;
-    const SCALE: f32 = 128.0;
+    // all of these compute ReLU and so should share a single table
+    fn relu_aliases() -> Vec<LookupOp> {
+        vec![
+            LookupOp::ReLU,
+            LookupOp::LeakyReLU {
+                slope: crate::circuit::utils::F32(0.),
+            },
+            LookupOp::Max {
+                scales: (1, 1),
+                a: crate::circuit::utils::F32(0.),
+            },
+        ]
+    }
 
     #[derive(Clone)]
-    struct SoftmaxCircuit<F: PrimeField + TensorType + PartialOrd> {
+    struct SharedTableCircuit<F: PrimeField + TensorType + PartialOrd> {
         pub input: ValTensor<F>,
-        _marker: PhantomData<F>,
     }
 
-    impl Circuit<F> for SoftmaxCircuit<F> {
+    impl Circuit<F> for SharedTableCircuit<F> {
         type Config = BaseConfig<F>;
         type FloorPlanner = SimpleFloorPlanner;
         type Params = TestParams;
@@ -2542,65 +3303,150 @@ mod softmax {
         fn without_witnesses(&self) -> Self {
             self.clone()
         }
+
         fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
-            let a = VarTensor::new_advice(cs, K, 1, LEN);
-            let b = VarTensor::new_advice(cs, K, 1, LEN);
-            let output = VarTensor::new_advice(cs, K, 1, LEN);
-            let mut config = Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE);
             let advices = (0..3)
-                .map(|_| VarTensor::new_advice(cs, K, 1, LEN))
+                .map(|_| VarTensor::new_advice(cs, 5, 1, 3))
                 .collect::<Vec<_>>();
 
+            let mut config = BaseConfig::default();
+
+            for nl in relu_aliases() {
+                config
+                    .configure_lookup(cs, &advices[0], &advices[1], &advices[2], (-6, 6), 5, &nl)
+                    .unwrap();
+            }
+            assert_eq!(config.tables.len(), 1);
             config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>, // layouter is our 'write buffer' for the circuit
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let mut x = self.input.clone();
+                        for nl in relu_aliases() {
+                            x = config
+                                .layout(&mut region, &[x], Box::new(nl))
+                                .map_err(|_| Error::Synthesis)?
+                                .unwrap();
+                        }
+                        Ok(())
+                    },
+                )
+                .unwrap();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn sharedtablecircuit() {
+        let input: Tensor<Value<F>> = Tensor::new(
+            Some(&[-3, -1, 1, 5].map(|x| Value::known(crate::fieldutils::i128_to_felt::<F>(x)))),
+            &[4],
+        )
+        .unwrap();
+
+        let circuit = SharedTableCircuit::<F> {
+            input: ValTensor::from(input),
+        };
+
+        let prover = MockProver::run(5_u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+mod shared_table_configs {
+    use super::*;
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Circuit, ConstraintSystem, Error},
+    };
+
+    #[derive(Clone)]
+    struct SharedConfigsCircuit<F: PrimeField + TensorType + PartialOrd> {
+        pub input: ValTensor<F>,
+    }
+
+    impl Circuit<F> for SharedConfigsCircuit<F> {
+        type Config = (BaseConfig<F>, BaseConfig<F>);
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let advices = (0..6)
+                .map(|_| VarTensor::new_advice(cs, 5, 1, 3))
+                .collect::<Vec<_>>();
+
+            let mut first = BaseConfig::default();
+            first
                 .configure_lookup(
                     cs,
                     &advices[0],
                     &advices[1],
                     &advices[2],
-                    (-32768, 32768),
-                    K,
-                    &LookupOp::Exp {
-                        scale: SCALE.into(),
-                    },
+                    (-6, 6),
+                    5,
+                    &LookupOp::ReLU,
                 )
                 .unwrap();
-            config
+
+            let mut second = BaseConfig::default();
+            second.share_tables(&first);
+            second
                 .configure_lookup(
                     cs,
-                    &advices[0],
-                    &advices[1],
-                    &advices[2],
-                    (-32768, 32768),
-                    K,
-                    &LookupOp::Recip {
-                        scale: SCALE.powf(2.0).into(),
-                    },
+                    &advices[3],
+                    &advices[4],
+                    &advices[5],
+                    (-6, 6),
+                    5,
+                    &LookupOp::ReLU,
                 )
                 .unwrap();
-            config
+            assert_eq!(
+                first.tables[&LookupOp::ReLU].table_outputs,
+                second.tables[&LookupOp::ReLU].table_outputs
+            );
+            (first, second)
         }
 
         fn synthesize(
             &self,
             mut config: Self::Config,
-            mut layouter: impl Layouter<F>,
+            mut layouter: impl Layouter<F>, // layouter is our 'write buffer' for the circuit
         ) -> Result<(), Error> {
-            config.layout_tables(&mut layouter).unwrap();
+            config.0.layout_tables(&mut layouter).unwrap();
+            // the shared table is laid out by the first config
+            config.1.layout_tables(&mut layouter).unwrap();
             layouter
                 .assign_region(
                     || "",
                     |region| {
                         let mut region = RegionCtx::new(region, 0, 1);
-                        let _output = config
-                            .layout(
-                                &mut region,
-                                &[self.input.clone()],
-                                Box::new(HybridOp::Softmax {
-                                    scale: SCALE.into(),
-                                    axes: vec![0],
-                                }),
-                            )
+                        let x = config
+                            .0
+                            .layout(&mut region, &[self.input.clone()], Box::new(LookupOp::ReLU))
+                            .map_err(|_| Error::Synthesis)?
                             .unwrap();
+                        config
+                            .1
+                            .layout(&mut region, &[x], Box::new(LookupOp::ReLU))
+                            .map_err(|_| Error::Synthesis)?;
                         Ok(())
                     },
                 )
@@ -2611,14 +3457,2115 @@ mod softmax {
     }
 
     #[test]
-    fn softmax_circuit() {
-        let input = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
+    fn sharedconfigscircuit() {
+        let input: Tensor<Value<F>> = Tensor::new(
+            Some(&[-3, -1, 1, 5].map(|x| Value::known(crate::fieldutils::i128_to_felt::<F>(x)))),
+            &[4],
+        )
+        .unwrap();
 
-        let circuit = SoftmaxCircuit::<F> {
+        let circuit = SharedConfigsCircuit::<F> {
             input: ValTensor::from(input),
-            _marker: PhantomData,
         };
-        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+
+        let prover = MockProver::run(5_u32, &circuit, vec![]).unwrap();
         prover.assert_satisfied_par();
     }
 }
+
+#[cfg(test)]
+mod clip {
+    use super::*;
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Circuit, ConstraintSystem, Error},
+    };
+
+    #[derive(Clone)]
+    struct ClipCircuit<F: PrimeField + TensorType + PartialOrd> {
+        pub input: ValTensor<F>,
+    }
+
+    impl Circuit<F> for ClipCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, 4, 1, 3))
+                .collect::<Vec<_>>();
+
+            let nl = LookupOp::Clip {
+                scales: (1, 1),
+                a: crate::circuit::utils::F32(0.),
+                b: crate::circuit::utils::F32(2.),
+            };
+
+            let mut config = BaseConfig::default();
+
+            config
+                .configure_lookup(cs, &advices[0], &advices[1], &advices[2], (-6, 6), 4, &nl)
+                .unwrap();
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>, // layouter is our 'write buffer' for the circuit
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        config
+                            .layout(
+                                &mut region,
+                                &[self.input.clone()],
+                                Box::new(LookupOp::Clip {
+                                    scales: (1, 1),
+                                    a: crate::circuit::utils::F32(0.),
+                                    b: crate::circuit::utils::F32(2.),
+                                }),
+                            )
+                            .map_err(|_| Error::Synthesis)
+                    },
+                )
+                .unwrap();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn clipcircuit() {
+        let input: Tensor<Value<F>> = Tensor::new(
+            Some(&[-3, -1, 1, 5].map(|x| Value::known(crate::fieldutils::i128_to_felt::<F>(x)))),
+            &[4],
+        )
+        .unwrap();
+
+        let circuit = ClipCircuit::<F> {
+            input: ValTensor::from(input),
+        };
+
+        let prover = MockProver::run(4_u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod lookup_ultra_overflow {
+    use super::*;
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        plonk::{Circuit, ConstraintSystem, Error},
+        poly::commitment::ParamsProver,
+    };
+
+    #[derive(Clone)]
+    struct ReLUCircuit<F: PrimeField + TensorType + PartialOrd> {
+        pub input: ValTensor<F>,
+    }
+
+    impl Circuit<F> for ReLUCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, 4, 1, 3))
+                .collect::<Vec<_>>();
+
+            let nl = LookupOp::ReLU;
+
+            let mut config = BaseConfig::default();
+
+            config
+                .configure_lookup(
+                    cs,
+                    &advices[0],
+                    &advices[1],
+                    &advices[2],
+                    (-1024, 1024),
+                    4,
+                    &nl,
+                )
+                .unwrap();
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>, // layouter is our 'write buffer' for the circuit
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        config
+                            .layout(&mut region, &[self.input.clone()], Box::new(LookupOp::ReLU))
+                            .map_err(|_| Error::Synthesis)
+                    },
+                )
+                .unwrap();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn relucircuit() {
+        // get some logs fam
+        crate::logger::init_logger();
+        // parameters
+        let a = Tensor::from((0..4).map(|i| Value::known(F::from(i + 1))));
+
+        let circuit = ReLUCircuit::<F> {
+            input: ValTensor::from(a),
+        };
+
+        let params = crate::pfsys::srs::gen_srs::<
+            halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme<_>,
+        >(4_u32);
+
+        let pk = crate::pfsys::create_keys::<
+            halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme<halo2curves::bn256::Bn256>,
+            F,
+            ReLUCircuit<F>,
+        >(&circuit, &params)
+        .unwrap();
+
+        let prover = crate::pfsys::create_proof_circuit_kzg(
+            circuit.clone(),
+            &params,
+            None,
+            &pk,
+            crate::pfsys::TranscriptType::EVM,
+            halo2_proofs::poly::kzg::strategy::SingleStrategy::new(&params),
+            // use safe mode to verify that the proof is correct
+            CheckMode::SAFE,
+            None,
+        );
+
+        assert!(prover.is_ok());
+
+        let proof = prover.unwrap();
+
+        let strategy =
+            halo2_proofs::poly::kzg::strategy::SingleStrategy::new(params.verifier_params());
+        let vk = pk.get_vk();
+        let result =
+            crate::pfsys::verify_proof_circuit_kzg(params.verifier_params(), proof, vk, strategy);
+
+        assert!(result.is_ok());
+
+        println!("done.");
+    }
+}
+
+#[cfg(test)]
+mod softmax {
+
+    use super::*;
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Circuit, ConstraintSystem, Error},
+    };
+
+    const K: usize = 18;
+    const LEN: usize = 3;
+    const SCALE: f32 = 128.0;
+
+    #[derive(Clone)]
+    struct SoftmaxCircuit<F: PrimeField + TensorType + PartialOrd> {
+        pub input: ValTensor<F>,
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for SoftmaxCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            let mut config = Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE);
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, K, 1, LEN))
+                .collect::<Vec<_>>();
+
+            config
+                .configure_lookup(
+                    cs,
+                    &advices[0],
+                    &advices[1],
+                    &advices[2],
+                    (-32768, 32768),
+                    K,
+                    &LookupOp::Exp {
+                        scale: SCALE.into(),
+                    },
+                )
+                .unwrap();
+            config
+                .configure_lookup(
+                    cs,
+                    &advices[0],
+                    &advices[1],
+                    &advices[2],
+                    (-32768, 32768),
+                    K,
+                    &LookupOp::Recip {
+                        scale: SCALE.powf(2.0).into(),
+                    },
+                )
+                .unwrap();
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let _output = config
+                            .layout(
+                                &mut region,
+                                &[self.input.clone()],
+                                Box::new(HybridOp::Softmax {
+                                    scale: SCALE.into(),
+                                    axes: vec![0],
+                                }),
+                            )
+                            .unwrap();
+                        Ok(())
+                    },
+                )
+                .unwrap();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn softmax_circuit() {
+        let input = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
+
+        let circuit = SoftmaxCircuit::<F> {
+            input: ValTensor::from(input),
+            _marker: PhantomData,
+        };
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+mod mathops {
+
+    use super::*;
+    use crate::circuit::ops::mathops;
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Circuit, ConstraintSystem, Error},
+    };
+
+    const K: usize = 18;
+    const LEN: usize = 4;
+    const SCALE: f32 = 128.0;
+
+    #[derive(Clone)]
+    struct MathopsCircuit<F: PrimeField + TensorType + PartialOrd> {
+        pub input: ValTensor<F>,
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MathopsCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            let mut config = Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE);
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, K, 1, LEN))
+                .collect::<Vec<_>>();
+
+            for lookup in [
+                mathops::sqrt_lookup(SCALE.into()),
+                mathops::recip_lookup(SCALE.into(), SCALE.into()),
+            ] {
+                config
+                    .configure_lookup(
+                        cs,
+                        &advices[0],
+                        &advices[1],
+                        &advices[2],
+                        (-32768, 32768),
+                        K,
+                        &lookup,
+                    )
+                    .unwrap();
+            }
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let root =
+                            mathops::sqrt(&config, &mut region, &self.input, SCALE.into()).unwrap();
+                        let inv =
+                            mathops::recip(&config, &mut region, &root, SCALE.into(), SCALE.into())
+                                .unwrap();
+                        if let Ok(evals) = inv.get_int_evals() {
+                            // 1 / sqrt(x) for x = 1, 4, 16, 64
+                            assert_eq!(evals.to_vec(), vec![128, 64, 32, 16]);
+                        }
+                        Ok(())
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn mathops_circuit() {
+        let input = Tensor::from(
+            [1, 4, 16, 64]
+                .into_iter()
+                .map(|x: u64| Value::known(F::from(x * SCALE as u64))),
+        );
+
+        let circuit = MathopsCircuit::<F> {
+            input: ValTensor::from(input),
+            _marker: PhantomData,
+        };
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+mod divmod {
+
+    use super::*;
+    use crate::circuit::ops::divmod;
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Circuit, ConstraintSystem, Error},
+    };
+
+    const K: usize = 8;
+    const LEN: usize = 4;
+    const DIVISOR: u64 = 3;
+
+    #[derive(Clone)]
+    struct DivModCircuit<F: PrimeField + TensorType + PartialOrd> {
+        pub input: ValTensor<F>,
+        /// a quotient and remainder to witness instead of the honest ones
+        pub forged: Option<[ValTensor<F>; 2]>,
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for DivModCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            VarTensor::constant_cols(cs, K, LEN * 3, false);
+            let mut config = Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE);
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, K, 1, LEN))
+                .collect::<Vec<_>>();
+
+            for lookup in divmod::div_rem_lookups() {
+                config
+                    .configure_lookup(
+                        cs,
+                        &advices[0],
+                        &advices[1],
+                        &advices[2],
+                        (-64, 64),
+                        K,
+                        &lookup,
+                    )
+                    .unwrap();
+            }
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        if let Some([quotient, remainder]) = &self.forged {
+                            divmod::constrain_div_rem(
+                                &config,
+                                &mut region,
+                                &self.input,
+                                DIVISOR,
+                                quotient,
+                                remainder,
+                            )
+                            .unwrap();
+                            return Ok(());
+                        }
+                        let [quotient, remainder] =
+                            divmod::div_rem(&config, &mut region, &self.input, DIVISOR).unwrap();
+                        if let (Ok(q), Ok(r)) =
+                            (quotient.get_int_evals(), remainder.get_int_evals())
+                        {
+                            // rounds towards negative infinity
+                            assert_eq!(q.to_vec(), vec![0, 2, -1, -3]);
+                            assert_eq!(r.to_vec(), vec![1, 1, 2, 0]);
+                        }
+                        Ok(())
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    fn felts(values: &[i128]) -> Vec<F> {
+        values
+            .iter()
+            .map(|x| crate::fieldutils::i128_to_felt::<F>(*x))
+            .collect()
+    }
+
+    fn tensor(values: &[F]) -> ValTensor<F> {
+        ValTensor::from(Tensor::from(values.iter().map(|x| Value::known(*x))))
+    }
+
+    #[test]
+    fn divmod_circuit() {
+        let circuit = DivModCircuit::<F> {
+            input: tensor(&felts(&[1, 7, -1, -9])),
+            forged: None,
+            _marker: PhantomData,
+        };
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+
+    #[test]
+    fn divmod_forged_quotient() {
+        let input = felts(&[1, 7, -1, -9]);
+        // a zero remainder recomposes with the field quotient x / 3, which isn't an integer in range
+        let inv = F::from(DIVISOR).invert().unwrap();
+        let quotient = input.iter().map(|x| *x * inv).collect::<Vec<_>>();
+        let circuit = DivModCircuit::<F> {
+            input: tensor(&input),
+            forged: Some([tensor(&quotient), tensor(&felts(&[0, 0, 0, 0]))]),
+            _marker: PhantomData,
+        };
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
+
+#[cfg(test)]
+mod sparse_affine {
+
+    use super::*;
+
+    const K: usize = 10;
+    const LEN: usize = 8;
+
+    #[derive(Clone)]
+    struct SparseAffineCircuit<F: PrimeField + TensorType + PartialOrd> {
+        // weights, nonzero indices, nonzero values
+        pub inputs: [ValTensor<F>; 3],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for SparseAffineCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            let mut config = Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE);
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, K, 1, LEN))
+                .collect::<Vec<_>>();
+
+            config
+                .configure_lookup(
+                    cs,
+                    &advices[0],
+                    &advices[1],
+                    &advices[2],
+                    (-128, 128),
+                    K,
+                    &LookupOp::KroneckerDelta,
+                )
+                .unwrap();
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let _output = config
+                            .layout(&mut region, &self.inputs, Box::new(HybridOp::SparseAffine))
+                            .unwrap();
+                        Ok(())
+                    },
+                )
+                .unwrap();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn sparseaffinecircuit() {
+        let mut weights = Tensor::from((0..8).map(|i| Value::known(F::from(i as u64 + 1))));
+        weights.reshape(&[2, 4]);
+        let indices = Tensor::from([1u64, 3].into_iter().map(|i| Value::known(F::from(i))));
+        let values = Tensor::from([5u64, 7].into_iter().map(|i| Value::known(F::from(i))));
+
+        let circuit = SparseAffineCircuit::<F> {
+            inputs: [
+                ValTensor::from(weights),
+                ValTensor::from(indices),
+                ValTensor::from(values),
+            ],
+            _marker: PhantomData,
+        };
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+mod sparse_linear {
+
+    use super::*;
+
+    const K: usize = 9;
+    const LEN: usize = 4;
+
+    #[derive(Clone)]
+    struct SparseLinearCircuit<F: PrimeField + TensorType + PartialOrd> {
+        input: ValTensor<F>,
+        weights: Tensor<F>,
+        bias: Tensor<F>,
+    }
+
+    impl Circuit<F> for SparseLinearCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN * LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN * LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN * LEN);
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let op = PolyOp::SparseLinear {
+                weights: self.weights.clone(),
+                bias: Some(self.bias.clone()),
+            };
+            layouter.assign_region(
+                || "",
+                |region| {
+                    let mut region = RegionCtx::new(region, 0, 1);
+                    let res = config
+                        .layout(&mut region, &[self.input.clone()], Box::new(op.clone()))
+                        .map_err(|_| Error::Synthesis)?
+                        .unwrap();
+                    // row 0: 1 * 1 + 2 * 3 + 1, row 1 has no nonzero weights, row 2: 3 * 4 + 3
+                    let expected = [8, 2, 15].map(|i| F::from(i as u64));
+                    assert_eq!(res.get_felt_evals().unwrap().to_vec(), expected.to_vec());
+                    Ok(())
+                },
+            )?;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn sparselinearcircuit() {
+        let input = Tensor::from((0..LEN).map(|i| Value::known(F::from((i + 1) as u64))));
+
+        let mut weights = Tensor::from(
+            [1, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 3]
+                .map(F::from)
+                .into_iter(),
+        );
+        weights.reshape(&[3, LEN]);
+        weights.set_visibility(&crate::graph::Visibility::Fixed);
+
+        let mut bias = Tensor::from([1, 2, 3].map(F::from).into_iter());
+        bias.set_visibility(&crate::graph::Visibility::Private);
+
+        let mut circuit = SparseLinearCircuit::<F> {
+            input: ValTensor::from(input),
+            weights,
+            bias,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+
+        // the sparsity pattern of private weights would leak through the layout
+        circuit
+            .weights
+            .set_visibility(&crate::graph::Visibility::Private);
+        assert!(MockProver::run(K as u32, &circuit, vec![]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod ternary_linear {
+
+    use super::*;
+
+    const K: usize = 9;
+    const LEN: usize = 4;
+
+    #[derive(Clone)]
+    struct TernaryLinearCircuit<F: PrimeField + TensorType + PartialOrd> {
+        input: ValTensor<F>,
+        weights: Tensor<F>,
+        bias: Tensor<F>,
+    }
+
+    impl Circuit<F> for TernaryLinearCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN * LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN * LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN * LEN);
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let op = PolyOp::TernaryLinear {
+                weights: self.weights.clone(),
+                bias: Some(self.bias.clone()),
+            };
+            layouter.assign_region(
+                || "",
+                |region| {
+                    let mut region = RegionCtx::new(region, 0, 1);
+                    let res = config
+                        .layout(&mut region, &[self.input.clone()], Box::new(op.clone()))
+                        .map_err(|_| Error::Synthesis)?
+                        .unwrap();
+                    // row 0: 1 - 3 + 1, row 1 has no nonzero weights, row 2: 2 + 3 - 4 + 3
+                    let expected = [-1, 2, 4].map(crate::fieldutils::i128_to_felt::<F>);
+                    assert_eq!(res.get_felt_evals().unwrap().to_vec(), expected.to_vec());
+                    Ok(())
+                },
+            )?;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn ternarylinearcircuit() {
+        let input = Tensor::from((0..LEN).map(|i| Value::known(F::from((i + 1) as u64))));
+
+        let mut weights = Tensor::from(
+            [1, 0, -1, 0, 0, 0, 0, 0, 0, 1, 1, -1]
+                .map(crate::fieldutils::i128_to_felt::<F>)
+                .into_iter(),
+        );
+        weights.reshape(&[3, LEN]);
+        weights.set_visibility(&crate::graph::Visibility::Fixed);
+
+        let mut bias = Tensor::from([1, 2, 3].map(F::from).into_iter());
+        bias.set_visibility(&crate::graph::Visibility::Private);
+
+        let mut circuit = TernaryLinearCircuit::<F> {
+            input: ValTensor::from(input),
+            weights,
+            bias,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+
+        circuit
+            .weights
+            .set_visibility(&crate::graph::Visibility::Private);
+        assert!(MockProver::run(K as u32, &circuit, vec![]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod classifier_head {
+
+    use super::*;
+
+    const K: usize = 10;
+    const LEN: usize = 32;
+
+    #[derive(Clone)]
+    struct ClassifierHeadCircuit<F: PrimeField + TensorType + PartialOrd> {
+        // image, weights, bias
+        pub inputs: [ValTensor<F>; 3],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for ClassifierHeadCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            let mut config = Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE);
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, K, 1, LEN))
+                .collect::<Vec<_>>();
+
+            config
+                .configure_lookup(
+                    cs,
+                    &advices[0],
+                    &advices[1],
+                    &advices[2],
+                    (-128, 128),
+                    K,
+                    &LookupOp::Div {
+                        denom: crate::circuit::utils::F32(4.0),
+                    },
+                )
+                .unwrap();
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let _output = config
+                            .layout(
+                                &mut region,
+                                &self.inputs,
+                                Box::new(HybridOp::ClassifierHead { pool_dims: (2, 2) }),
+                            )
+                            .unwrap();
+                        Ok(())
+                    },
+                )
+                .unwrap();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn classifierheadcircuit() {
+        // 1 x 3 x 2 x 2 image, 2 classes
+        let mut image = Tensor::from((0..12).map(|i| Value::known(F::from(i as u64))));
+        image.reshape(&[1, 3, 2, 2]);
+        let mut weights = Tensor::from((0..6).map(|i| Value::known(F::from(i as u64 + 1))));
+        weights.reshape(&[2, 3]);
+        let bias = Tensor::from((0..2).map(|i| Value::known(F::from(i as u64))));
+
+        let circuit = ClassifierHeadCircuit::<F> {
+            inputs: [
+                ValTensor::from(image),
+                ValTensor::from(weights),
+                ValTensor::from(bias),
+            ],
+            _marker: PhantomData,
+        };
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+
+    #[test]
+    fn classifierhead_bad_dims() {
+        let felts = |dims: &[usize]| {
+            let mut t =
+                Tensor::from((0..dims.iter().product::<usize>()).map(|i| F::from(i as u64)));
+            t.reshape(dims);
+            t
+        };
+        let head = |pool_dims| HybridOp::ClassifierHead { pool_dims };
+
+        let inputs = [felts(&[1, 3, 2, 2]), felts(&[2, 3])];
+        assert!(Op::<F>::f(&head((2, 2)), &inputs).is_ok());
+        // the pool has to cover the whole image
+        assert!(Op::<F>::f(&head((1, 2)), &inputs).is_err());
+        assert!(Op::<F>::f(&head((4, 4)), &inputs).is_err());
+        // an image without spatial dims
+        assert!(Op::<F>::f(&head((2, 2)), &[felts(&[3, 4]), felts(&[2, 3])]).is_err());
+        // weights and bias that don't match the channels and classes
+        assert!(Op::<F>::f(&head((2, 2)), &[felts(&[1, 3, 2, 2]), felts(&[2, 4])]).is_err());
+        assert!(Op::<F>::f(
+            &head((2, 2)),
+            &[inputs[0].clone(), inputs[1].clone(), felts(&[3])]
+        )
+        .is_err());
+        assert!(Op::<F>::f(&head((2, 2)), &inputs[..1]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod resize_bilinear {
+
+    use super::*;
+
+    const K: usize = 10;
+    const LEN: usize = 64;
+
+    #[derive(Clone)]
+    struct ResizeBilinearCircuit<F: PrimeField + TensorType + PartialOrd> {
+        pub inputs: [ValTensor<F>; 1],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for ResizeBilinearCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            let mut config = Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE);
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, K, 1, LEN))
+                .collect::<Vec<_>>();
+            // the bilinear weights are fixed
+            VarTensor::constant_cols(cs, K, LEN, false);
+
+            config
+                .configure_lookup(
+                    cs,
+                    &advices[0],
+                    &advices[1],
+                    &advices[2],
+                    (-512, 512),
+                    K,
+                    &LookupOp::Div {
+                        denom: crate::circuit::utils::F32(16.0),
+                    },
+                )
+                .unwrap();
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let _output = config
+                            .layout(
+                                &mut region,
+                                &self.inputs,
+                                Box::new(HybridOp::ResizeBilinear {
+                                    scale_factor: vec![1, 1, 2, 2],
+                                }),
+                            )
+                            .unwrap();
+                        Ok(())
+                    },
+                )
+                .unwrap();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn resizebilinearcircuit() {
+        let mut image = Tensor::from(
+            [0u64, 3, 5, 2]
+                .into_iter()
+                .map(|i| Value::known(F::from(i))),
+        );
+        image.reshape(&[1, 1, 2, 2]);
+
+        let circuit = ResizeBilinearCircuit::<F> {
+            inputs: [ValTensor::from(image)],
+            _marker: PhantomData,
+        };
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+mod channel_rescale {
+
+    use super::*;
+
+    const K: usize = 10;
+    const LEN: usize = 6;
+
+    #[derive(Clone)]
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 1],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MyCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            let mut config = Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE);
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, K, 1, LEN))
+                .collect::<Vec<_>>();
+
+            // the channels share a single division by the largest denominator
+            config
+                .configure_lookup(
+                    cs,
+                    &advices[0],
+                    &advices[1],
+                    &advices[2],
+                    (-32, 32),
+                    K,
+                    &LookupOp::Div {
+                        denom: crate::circuit::utils::F32(4.0),
+                    },
+                )
+                .unwrap();
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let output = config
+                            .layout(
+                                &mut region,
+                                &self.inputs,
+                                Box::new(HybridOp::channel_rescale(0, &[2.0, 4.0])),
+                            )
+                            .map_err(|_| Error::Synthesis)?;
+                        if let Some(Ok(evals)) = output.map(|o| o.get_int_evals()) {
+                            assert_eq!(evals.to_vec(), vec![2, 3, 4, 3, 3, 4]);
+                        }
+                        Ok(())
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn channelrescalecircuit() {
+        let mut a = Tensor::from(
+            [4u64, 6, 8, 10, 12, 14]
+                .into_iter()
+                .map(|i| Value::known(F::from(i))),
+        );
+        a.reshape(&[2, 3]);
+
+        let circuit = MyCircuit::<F> {
+            inputs: [ValTensor::from(a)],
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+
+        // a single table whatever the number of channels
+        let op = HybridOp::channel_rescale(0, &[2.0, 4.0, 1.0, 4.0]);
+        assert_eq!(Op::<F>::required_lookups(&op).len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod hard_swish {
+
+    use super::*;
+
+    const K: usize = 10;
+    const LEN: usize = 5;
+
+    #[derive(Clone)]
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 1],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MyCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            let mut config = Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE);
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, K, 1, LEN))
+                .collect::<Vec<_>>();
+            // the slope, offset and bounds are fixed
+            VarTensor::constant_cols(cs, K, LEN, false);
+
+            config
+                .configure_lookup(
+                    cs,
+                    &advices[0],
+                    &advices[1],
+                    &advices[2],
+                    (-64, 64),
+                    K,
+                    &LookupOp::GreaterThan {
+                        a: crate::circuit::utils::F32(0.),
+                    },
+                )
+                .unwrap();
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let output = config
+                            .layout(
+                                &mut region,
+                                &self.inputs,
+                                Box::new(HybridOp::HardSwish {
+                                    scale: crate::circuit::utils::F32(4.0),
+                                }),
+                            )
+                            .map_err(|_| Error::Synthesis)?;
+                        if let Some(Ok(evals)) = output.map(|o| o.get_int_evals()) {
+                            // -1, 0 and 1 at scale 4, the output is at scale 64
+                            assert_eq!(evals.to_vec(), vec![0, -16, 0, 48, 256]);
+                        }
+                        Ok(())
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn hardswishcircuit() {
+        let a = Tensor::from(
+            [-16i128, -4, 0, 4, 16]
+                .into_iter()
+                .map(|i| Value::known(crate::fieldutils::i128_to_felt::<F>(i))),
+        );
+
+        let circuit = MyCircuit::<F> {
+            inputs: [ValTensor::from(a)],
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+mod piecewise_linear {
+
+    use super::*;
+    use crate::circuit::utils::F32;
+
+    const K: usize = 10;
+    const LEN: usize = 5;
+
+    #[derive(Clone)]
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 1],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MyCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            let mut config = Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE);
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, K, 1, LEN))
+                .collect::<Vec<_>>();
+            // the slopes, intercepts and breakpoints are fixed
+            VarTensor::constant_cols(cs, K, LEN, false);
+
+            config
+                .configure_lookup(
+                    cs,
+                    &advices[0],
+                    &advices[1],
+                    &advices[2],
+                    (-64, 64),
+                    K,
+                    &LookupOp::GreaterThan { a: F32(0.) },
+                )
+                .unwrap();
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let output = config
+                            .layout(
+                                &mut region,
+                                &self.inputs,
+                                Box::new(
+                                    HybridOp::piecewise_linear(
+                                        4.0,
+                                        &[-1.0, 1.0],
+                                        &[0.0, 1.0, 0.0],
+                                        &[-1.0, 0.0, 1.0],
+                                    )
+                                    .unwrap(),
+                                ),
+                            )
+                            .map_err(|_| Error::Synthesis)?;
+                        if let Some(Ok(evals)) = output.map(|o| o.get_int_evals()) {
+                            // hardtanh of -2, -1, 0, 1 and 2, the output is at scale 16
+                            assert_eq!(evals.to_vec(), vec![-16, -16, 0, 16, 16]);
+                        }
+                        Ok(())
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn piecewiselinearcircuit() {
+        let a = Tensor::from(
+            [-16i128, -4, 0, 4, 16]
+                .into_iter()
+                .map(|i| Value::known(crate::fieldutils::i128_to_felt::<F>(i))),
+        );
+
+        let circuit = MyCircuit::<F> {
+            inputs: [ValTensor::from(a)],
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+
+    #[test]
+    fn piecewiselinearbreakpoints() {
+        let slopes = [0.0, 1.0, 0.0];
+        let intercepts = [-1.0, 0.0, 1.0];
+        assert!(HybridOp::piecewise_linear(4.0, &[-1.0, 1.0], &slopes, &intercepts).is_ok());
+        // descending, repeated, or equal once quantized at scale 4
+        for breakpoints in [[1.0, -1.0], [1.0, 1.0], [0.1, 0.12]] {
+            assert!(matches!(
+                HybridOp::piecewise_linear(4.0, &breakpoints, &slopes, &intercepts),
+                Err(crate::circuit::CircuitError::UnsortedBreakpoints(_))
+            ));
+        }
+        // one segment too few
+        assert!(
+            HybridOp::piecewise_linear(4.0, &[-1.0, 1.0], &slopes[1..], &intercepts[1..]).is_err()
+        );
+    }
+}
+
+#[cfg(test)]
+mod scatter_nd {
+    use super::*;
+
+    const K: usize = 5;
+    const LEN: usize = 6;
+
+    #[derive(Clone)]
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 2],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MyCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        // scatter the two rows of updates to rows 2 and 0
+                        let indices = Tensor::new(Some(&[2, 0]), &[2, 1]).unwrap();
+                        let output = config
+                            .layout(
+                                &mut region,
+                                &self.inputs,
+                                Box::new(HybridOp::ScatterND { indices }),
+                            )
+                            .map_err(|_| Error::Synthesis)?;
+                        if let Some(Ok(evals)) = output.map(|o| o.get_int_evals()) {
+                            assert_eq!(evals.to_vec(), vec![9, 10, 3, 4, 7, 8]);
+                        }
+                        Ok(())
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn scatterndcircuit() {
+        let mut data = Tensor::from((1..=6).map(|i| Value::known(F::from(i as u64))));
+        data.reshape(&[3, 2]);
+        let mut updates = Tensor::from((7..=10).map(|i| Value::known(F::from(i as u64))));
+        updates.reshape(&[2, 2]);
+
+        let circuit = MyCircuit::<F> {
+            inputs: [ValTensor::from(data), ValTensor::from(updates)],
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+mod multi_broadcast_to {
+    use super::*;
+
+    const K: usize = 5;
+    const LEN: usize = 6;
+
+    #[derive(Clone)]
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 2],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MyCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        // broadcast a column to a [3, 2] tensor and add it to the second input
+                        let broadcast = config
+                            .layout(
+                                &mut region,
+                                &self.inputs[..1],
+                                Box::new(PolyOp::MultiBroadcastTo { shape: vec![3, 2] }),
+                            )
+                            .map_err(|_| Error::Synthesis)?
+                            .unwrap();
+                        assert_eq!(broadcast.dims(), &[3, 2]);
+                        let output = config
+                            .layout(
+                                &mut region,
+                                &[broadcast, self.inputs[1].clone()],
+                                Box::new(PolyOp::Add),
+                            )
+                            .map_err(|_| Error::Synthesis)?;
+                        if let Some(Ok(evals)) = output.map(|o| o.get_int_evals()) {
+                            assert_eq!(evals.to_vec(), vec![2, 2, 3, 3, 4, 4]);
+                        }
+                        Ok(())
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn multibroadcasttocircuit() {
+        let mut a = Tensor::from((1..=3).map(|i| Value::known(F::from(i as u64))));
+        a.reshape(&[3, 1]);
+        let mut b = Tensor::from((0..6).map(|_| Value::known(F::ONE)));
+        b.reshape(&[3, 2]);
+
+        let circuit = MyCircuit::<F> {
+            inputs: [ValTensor::from(a), ValTensor::from(b)],
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+mod constant_pad {
+    use super::*;
+
+    const K: usize = 5;
+    const LEN: usize = 12;
+
+    #[derive(Clone)]
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 1],
+        value: f32,
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MyCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            VarTensor::constant_cols(cs, K, LEN, false);
+
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let output = config
+                            .layout(
+                                &mut region,
+                                &self.inputs.clone(),
+                                Box::new(PolyOp::ConstantPad {
+                                    pads: vec![(1, 0), (0, 2)],
+                                    value: crate::circuit::utils::F32(self.value),
+                                    scale: 1,
+                                }),
+                            )
+                            .map_err(|_| Error::Synthesis)?;
+                        // the value is quantized at scale 1
+                        let p = (self.value * 2.0) as i128;
+                        if let Some(Ok(evals)) = output.map(|o| o.get_int_evals()) {
+                            assert_eq!(evals.to_vec(), vec![p, p, p, p, 1, 2, p, p, 3, 4, p, p]);
+                        }
+                        // zero padding is only rewiring
+                        assert_eq!(region.row() == 0, self.value == 0.0);
+                        Ok(())
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn constantpadcircuit() {
+        let mut a = Tensor::from((0..4).map(|i| Value::known(F::from(i as u64 + 1))));
+        a.reshape(&[2, 2]);
+
+        for value in [1.5, 0.0] {
+            let circuit = MyCircuit::<F> {
+                inputs: [ValTensor::from(a.clone())],
+                value,
+                _marker: PhantomData,
+            };
+
+            let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+            prover.assert_satisfied_par();
+        }
+    }
+}
+
+#[cfg(test)]
+mod flip {
+    use super::*;
+
+    const K: usize = 5;
+    const LEN: usize = 4;
+
+    #[derive(Clone)]
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 1],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MyCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let flipped = config
+                            .layout(
+                                &mut region,
+                                &self.inputs.clone(),
+                                Box::new(PolyOp::Flip { axis: 0 }),
+                            )
+                            .map_err(|_| Error::Synthesis)?
+                            .ok_or(Error::Synthesis)?;
+                        if let Ok(evals) = flipped.get_int_evals() {
+                            assert_eq!(evals.to_vec(), vec![3, 4, 1, 2]);
+                        }
+                        // the flip is only rewiring, so constrain the flipped cells against the input
+                        let output = config
+                            .layout(
+                                &mut region,
+                                &[flipped, self.inputs[0].clone()],
+                                Box::new(PolyOp::Add),
+                            )
+                            .map_err(|_| Error::Synthesis)?;
+                        if let Some(Ok(evals)) = output.map(|o| o.get_int_evals()) {
+                            assert_eq!(evals.to_vec(), vec![4, 6, 4, 6]);
+                        }
+                        Ok(())
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn flipcircuit() {
+        let mut a = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
+        a.reshape(&[2, 2]);
+
+        let circuit = MyCircuit::<F> {
+            inputs: [ValTensor::from(a)],
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+mod strided_slice {
+    use super::*;
+
+    const K: usize = 5;
+    const LEN: usize = 12;
+
+    #[derive(Clone)]
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 1],
+        ranges: Vec<(usize, usize, isize)>,
+        expected: Vec<i128>,
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MyCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let output = config
+                            .layout(
+                                &mut region,
+                                &self.inputs.clone(),
+                                Box::new(PolyOp::StridedSlice {
+                                    ranges: self.ranges.clone(),
+                                }),
+                            )
+                            .map_err(|_| Error::Synthesis)?;
+                        if let Some(Ok(evals)) = output.map(|o| o.get_int_evals()) {
+                            assert_eq!(evals.to_vec(), self.expected);
+                        }
+                        Ok(())
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn stridedslicecircuit() {
+        let mut a = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
+        a.reshape(&[2, 6]);
+
+        for (ranges, expected) in [
+            // every other element, backwards
+            (vec![(0, 2, 1), (0, 5, -2)], vec![5, 3, 1, 11, 9, 7]),
+            // every third element of the second row
+            (vec![(1, 2, 1), (1, 6, 3)], vec![8, 11]),
+        ] {
+            let circuit = MyCircuit::<F> {
+                inputs: [ValTensor::from(a.clone())],
+                ranges,
+                expected,
+                _marker: PhantomData,
+            };
+
+            let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+            prover.assert_satisfied_par();
+        }
+    }
+}
+
+#[cfg(test)]
+mod permute {
+    use super::*;
+
+    const K: usize = 5;
+    const LEN: usize = 6;
+
+    #[derive(Clone)]
+    struct MyCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 1],
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for MyCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = TestParams;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        let permuted = config
+                            .layout(
+                                &mut region,
+                                &self.inputs.clone(),
+                                Box::new(PolyOp::Permute { perm: vec![1, 0] }),
+                            )
+                            .map_err(|_| Error::Synthesis)?
+                            .ok_or(Error::Synthesis)?;
+                        assert_eq!(permuted.dims(), &[3, 2]);
+                        if let Ok(evals) = permuted.get_int_evals() {
+                            assert_eq!(evals.to_vec(), vec![1, 4, 2, 5, 3, 6]);
+                        }
+                        // the permute is only rewiring, so constrain the permuted cells
+                        let output = config
+                            .layout(
+                                &mut region,
+                                &[permuted.clone(), permuted],
+                                Box::new(PolyOp::Add),
+                            )
+                            .map_err(|_| Error::Synthesis)?;
+                        if let Some(Ok(evals)) = output.map(|o| o.get_int_evals()) {
+                            assert_eq!(evals.to_vec(), vec![2, 8, 4, 10, 6, 12]);
+                        }
+                        Ok(())
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn permutecircuit() {
+        let mut a = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
+        a.reshape(&[2, 3]);
+
+        let circuit = MyCircuit::<F> {
+            inputs: [ValTensor::from(a)],
+            _marker: PhantomData,
+        };
+
+        let prover = MockProver::run(K as u32, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod kzg_proofs {
+    use super::*;
+    use crate::pfsys::session::ProofSession;
+    use crate::pfsys::{Snark, TranscriptType};
+    use ethers::signers::{LocalWallet, Signer};
+    use halo2_proofs::plonk::ProvingKey;
+    use halo2_proofs::poly::commitment::ParamsProver;
+    use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
+    use halo2_proofs::poly::kzg::strategy::SingleStrategy;
+    use halo2curves::bn256::{Bn256, G1Affine};
+
+    const K: usize = 8;
+    const LEN: usize = 4;
+    // the first two of anvil's default accounts
+    const KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcaf784d7bf4f2ff80";
+    const OTHER_KEY: &str = "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+    #[derive(Clone)]
+    struct EinsumCircuit<F: PrimeField + TensorType + PartialOrd> {
+        inputs: [ValTensor<F>; 2],
+        equation: String,
+        _marker: PhantomData<F>,
+    }
+
+    impl Circuit<F> for EinsumCircuit<F> {
+        type Config = BaseConfig<F>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, K, 1, LEN);
+            let b = VarTensor::new_advice(cs, K, 1, LEN);
+            let output = VarTensor::new_advice(cs, K, 1, LEN);
+            Self::Config::configure(cs, &[a, b], &output, CheckMode::SAFE)
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            layouter
+                .assign_region(
+                    || "",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0, 1);
+                        config
+                            .layout(
+                                &mut region,
+                                &self.inputs.clone(),
+                                Box::new(PolyOp::Einsum {
+                                    equation: self.equation.clone(),
+                                }),
+                            )
+                            .map_err(|_| Error::Synthesis)
+                    },
+                )
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    fn einsum_circuit(equation: &str, offset: u64) -> EinsumCircuit<F> {
+        let a = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + offset))));
+        let b = Tensor::from((0..LEN).map(|i| Value::known(F::from(i as u64 + 1))));
+        EinsumCircuit {
+            inputs: [ValTensor::from(a), ValTensor::from(b)],
+            equation: equation.to_string(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn setup(circuit: &EinsumCircuit<F>) -> (ParamsKZG<Bn256>, ProvingKey<G1Affine>) {
+        let params = crate::pfsys::srs::gen_srs::<KZGCommitmentScheme<Bn256>>(K as u32);
+        let pk = crate::pfsys::create_keys::<KZGCommitmentScheme<Bn256>, F, EinsumCircuit<F>>(
+            circuit, &params,
+        )
+        .unwrap();
+        (params, pk)
+    }
+
+    fn prove(
+        circuit: &EinsumCircuit<F>,
+        params: &ParamsKZG<Bn256>,
+        pk: &ProvingKey<G1Affine>,
+        transcript: TranscriptType,
+    ) -> Snark<F, G1Affine> {
+        crate::pfsys::create_proof_circuit_kzg(
+            circuit.clone(),
+            params,
+            None,
+            pk,
+            transcript,
+            SingleStrategy::new(params),
+            // use safe mode to verify that the proof is correct
+            CheckMode::SAFE,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn key_bytes_roundtrip() {
+        let circuit = einsum_circuit("i,i->", 0);
+        let (params, pk) = setup(&circuit);
+
+        let vk_bytes =
+            crate::pfsys::vk_to_bytes::<KZGCommitmentScheme<Bn256>>(pk.get_vk()).unwrap();
+        let vk = crate::pfsys::vk_from_bytes::<KZGCommitmentScheme<Bn256>, F, EinsumCircuit<F>>(
+            &vk_bytes,
+            (),
+        )
+        .unwrap();
+        assert_eq!(
+            vk_bytes,
+            crate::pfsys::vk_to_bytes::<KZGCommitmentScheme<Bn256>>(&vk).unwrap()
+        );
+
+        let pk_bytes = crate::pfsys::pk_to_bytes::<KZGCommitmentScheme<Bn256>>(&pk).unwrap();
+        let pk = crate::pfsys::pk_from_bytes::<KZGCommitmentScheme<Bn256>, F, EinsumCircuit<F>>(
+            &pk_bytes,
+            (),
+        )
+        .unwrap();
+        assert_eq!(
+            pk_bytes,
+            crate::pfsys::pk_to_bytes::<KZGCommitmentScheme<Bn256>>(&pk).unwrap()
+        );
+
+        // the deserialized keys still prove and verify
+        let proof = prove(&circuit, &params, &pk, TranscriptType::EVM);
+        let strategy = SingleStrategy::new(params.verifier_params());
+        assert!(crate::pfsys::verify_proof_circuit_kzg(
+            params.verifier_params(),
+            proof,
+            &vk,
+            strategy
+        )
+        .is_ok());
+    }
+
+    #[tokio::test]
+    async fn proof_signature_roundtrip() {
+        let circuit = einsum_circuit("i,i->", 0);
+        let (params, pk) = setup(&circuit);
+        let proof = prove(&circuit, &params, &pk, TranscriptType::EVM);
+        let address = KEY.parse::<LocalWallet>().unwrap().address();
+
+        let signature = crate::eth::sign_proof(&proof, pk.get_vk(), KEY)
+            .await
+            .unwrap();
+        assert!(
+            crate::eth::verify_proof_signature(&proof, pk.get_vk(), &signature, address).is_ok()
+        );
+
+        // signed by someone else
+        let other_address = OTHER_KEY.parse::<LocalWallet>().unwrap().address();
+        assert!(
+            crate::eth::verify_proof_signature(&proof, pk.get_vk(), &signature, other_address)
+                .is_err()
+        );
+
+        // a tampered proof
+        let mut tampered = proof.clone();
+        tampered.proof[0] ^= 1;
+        assert!(
+            crate::eth::verify_proof_signature(&tampered, pk.get_vk(), &signature, address)
+                .is_err()
+        );
+
+        // the same proof attributed to another circuit
+        let (_, other_pk) = setup(&einsum_circuit("i,i->i", 0));
+        assert!(
+            crate::eth::verify_proof_signature(&proof, other_pk.get_vk(), &signature, address)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn proof_session_roundtrip() {
+        let circuit = einsum_circuit("i,i->", 0);
+        let (params, pk) = setup(&circuit);
+        let vks = [pk.get_vk().clone()];
+
+        let mut session = ProofSession::new(F::from(7), TranscriptType::Poseidon);
+        for offset in 0..2 {
+            session
+                .prove(
+                    einsum_circuit("i,i->", offset),
+                    vec![],
+                    &params,
+                    &pk,
+                    CheckMode::SAFE,
+                    None,
+                )
+                .unwrap();
+        }
+        assert_eq!(session.len(), 2);
+        assert!(session.verify(&params, &vks).is_ok());
+
+        // the proofs are bound to their position in the session
+        let mut swapped = session.clone();
+        swapped.proofs.swap(0, 1);
+        assert!(swapped.verify(&params, &vks).is_err());
+
+        // and to the session itself
+        let mut other = session.clone();
+        other.session_id = F::from(8);
+        assert!(other.verify(&params, &vks).is_err());
+
+        // a proof made outside of any session doesn't verify within one
+        let mut replayed = session;
+        replayed.proofs[0] = prove(&circuit, &params, &pk, TranscriptType::Poseidon);
+        assert!(replayed.verify(&params, &vks).is_err());
+    }
+}
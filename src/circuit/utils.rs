@@ -126,12 +126,62 @@ impl fmt::Display for F32 {
     }
 }
 
+// --------------------------------------------------------------------------------------------
+//
+// Widened integers for the witness math, whose overflows are caught instead of wrapping around
+//
+
+/// An i128 whose arithmetic is checked: an overflow poisons the value (as `None`) instead of wrapping
+/// around, and the poison propagates through any further arithmetic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CheckedI128(pub Option<i128>);
+
+impl From<i128> for CheckedI128 {
+    fn from(x: i128) -> Self {
+        CheckedI128(Some(x))
+    }
+}
+
+impl std::ops::Add for CheckedI128 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        CheckedI128(self.0.zip(rhs.0).and_then(|(a, b)| a.checked_add(b)))
+    }
+}
+
+impl std::ops::Sub for CheckedI128 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        CheckedI128(self.0.zip(rhs.0).and_then(|(a, b)| a.checked_sub(b)))
+    }
+}
+
+impl std::ops::Mul for CheckedI128 {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        CheckedI128(self.0.zip(rhs.0).and_then(|(a, b)| a.checked_mul(b)))
+    }
+}
+
+impl std::ops::Neg for CheckedI128 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        CheckedI128(self.0.and_then(|a| a.checked_neg()))
+    }
+}
+
+impl std::iter::Sum for CheckedI128 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(CheckedI128(Some(0)), |acc, x| acc + x)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
 
-    use super::F32;
+    use super::{CheckedI128, F32};
 
     fn calculate_hash<T: Hash>(t: &T) -> u64 {
         let mut s = DefaultHasher::new();
@@ -160,4 +210,19 @@ mod tests {
         assert!(calculate_hash(&F32(0.0)) == calculate_hash(&F32(-0.0)));
         assert!(calculate_hash(&F32(std::f32::NAN)) == calculate_hash(&F32(-std::f32::NAN)));
     }
+
+    #[test]
+    fn checked_i128_overflow() {
+        let big = CheckedI128::from(i128::MAX / 2 + 1);
+        assert_eq!(
+            big + CheckedI128::from(-1),
+            CheckedI128::from(i128::MAX / 2)
+        );
+        assert_eq!(big + big, CheckedI128(None));
+        assert_eq!(big * CheckedI128::from(2), CheckedI128(None));
+        assert_eq!(-CheckedI128::from(i128::MIN), CheckedI128(None));
+        // an overflow poisons the rest of the accumulation
+        let sum: CheckedI128 = [big, big, CheckedI128::from(-1) * big].into_iter().sum();
+        assert_eq!(sum, CheckedI128(None));
+    }
 }
@@ -34,6 +34,7 @@ impl IntoPy<PyObject> for TranscriptType {
         match self {
             TranscriptType::Poseidon => "poseidon".to_object(py),
             TranscriptType::EVM => "evm".to_object(py),
+            TranscriptType::Blake2b => "blake2b".to_object(py),
         }
     }
 }
@@ -46,6 +47,7 @@ impl<'source> FromPyObject<'source> for TranscriptType {
         match strval.to_lowercase().as_str() {
             "poseidon" => Ok(TranscriptType::Poseidon),
             "evm" => Ok(TranscriptType::EVM),
+            "blake2b" => Ok(TranscriptType::Blake2b),
             _ => Err(PyValueError::new_err("Invalid value for TranscriptType")),
         }
     }
@@ -217,6 +219,9 @@ pub enum Commands {
         /// Path to the srs file (optional - solely used to generate kzg commits)
         #[arg(short = 'P', long)]
         srs_path: Option<PathBuf>,
+        /// Path to dump every assigned advice value (layer, column, row, value) to as a binary file (optional)
+        #[arg(long)]
+        witness_trace: Option<PathBuf>,
     },
 
     /// Produces the proving hyperparameters, from run-args
@@ -284,6 +289,12 @@ pub enum Commands {
         /// Check mode for srs. verifies downloaded srs is valid. set to unsafe for speed.
         #[arg(long, default_value = "safe")]
         check: CheckMode,
+        /// The url to download the srs from, defaults to the perpetual powers of tau srs of the required size
+        #[arg(long, default_value = None)]
+        srs_url: Option<String>,
+        /// The expected keccak256 hash (hex) of the downloaded srs
+        #[arg(long, default_value = None)]
+        srs_hash: Option<String>,
     },
     /// Loads model and input and runs mock prover (for testing)
     #[command(arg_required_else_help = true)]
@@ -291,7 +302,7 @@ pub enum Commands {
         /// The path to the .json witness file
         #[arg(short = 'W', long)]
         witness: PathBuf,
-        /// The path to the .onnx model file
+        /// The path to the compiled model file (generated using the compile-circuit command)
         #[arg(short = 'M', long)]
         model: PathBuf,
     },
@@ -567,6 +578,58 @@ pub enum Commands {
         #[arg(long)]
         aggregation_settings: Vec<PathBuf>,
     },
+    /// Splits a model into two circuits at a node, which commit to the activations crossing the cut with a poseidon hash
+    #[cfg(not(target_arch = "wasm32"))]
+    #[command(arg_required_else_help = true)]
+    SplitCircuit {
+        /// The path to the .onnx model file
+        #[arg(short = 'M', long)]
+        model: PathBuf,
+        /// The last node of the first circuit
+        #[arg(long)]
+        node: usize,
+        /// The paths to output the two compiled circuits
+        #[arg(long, num_args = 2)]
+        compiled_circuits: Vec<PathBuf>,
+        /// The paths to output the settings of the two circuits
+        #[arg(long, num_args = 2)]
+        settings_paths: Vec<PathBuf>,
+        /// proving arguments
+        #[clap(flatten)]
+        args: RunArgs,
+    },
+    /// Verifies the proofs of the chunks of a split model, and that each proof consumes the activations committed to by the previous one
+    #[command(arg_required_else_help = true)]
+    VerifyChain {
+        /// The paths to the proof files, in order
+        #[arg(long)]
+        proof_paths: Vec<PathBuf>,
+        /// The paths to the settings of each proof
+        #[arg(long)]
+        settings_paths: Vec<PathBuf>,
+        /// The paths to the verification keys of each proof
+        #[arg(long)]
+        vk_paths: Vec<PathBuf>,
+        /// The kzg srs path
+        #[arg(long)]
+        srs_path: PathBuf,
+    },
+    /// Verifies several proofs of the same circuit at once, returning accept or reject for the whole batch
+    #[command(arg_required_else_help = true)]
+    VerifyBatch {
+        /// The path to load circuit params from
+        #[arg(short = 'S', long)]
+        settings_path: PathBuf,
+        /// The paths to the proof files
+        #[arg(long)]
+        proof_paths: Vec<PathBuf>,
+        /// The path to the verification key file
+        #[arg(long)]
+        vk_path: PathBuf,
+        /// The kzg srs path
+        #[arg(long)]
+        srs_path: PathBuf,
+    },
     /// Verifies a proof, returning accept or reject
     #[command(arg_required_else_help = true)]
     Verify {
@@ -660,6 +723,111 @@ pub enum Commands {
         addr_da: Option<H160>,
     },
 
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Signs a proof and its public instances with the prover's identity key, so the proof can be attributed to them
+    #[command(name = "sign-proof", arg_required_else_help = true)]
+    SignProof {
+        /// The path to the proof file
+        #[arg(long)]
+        proof_path: PathBuf,
+        /// The path to load circuit params from
+        #[arg(short = 'S', long)]
+        settings_path: PathBuf,
+        /// The path to the verification key file of the circuit
+        #[arg(long)]
+        vk_path: PathBuf,
+        /// Private secp256K1 key in hex format of the prover's identity
+        #[arg(short = 'P', long)]
+        private_key: String,
+        /// The path to output the signature
+        #[arg(long, default_value = "proof.sig")]
+        signature_path: PathBuf,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Verifies that a proof and its public instances were signed by a given prover address
+    #[command(name = "verify-proof-signature", arg_required_else_help = true)]
+    VerifyProofSignature {
+        /// The path to the proof file
+        #[arg(long)]
+        proof_path: PathBuf,
+        /// The path to load circuit params from
+        #[arg(short = 'S', long)]
+        settings_path: PathBuf,
+        /// The path to the verification key file of the circuit
+        #[arg(long)]
+        vk_path: PathBuf,
+        /// The path to the signature file
+        #[arg(long, default_value = "proof.sig")]
+        signature_path: PathBuf,
+        /// The address of the prover's identity key
+        #[arg(long)]
+        address: H160,
+    },
+
+    /// Runs the full pipeline (settings, compilation, setup, proving and verification) described by an ezkl.toml file
+    #[cfg(not(target_arch = "wasm32"))]
+    #[command(arg_required_else_help = true)]
+    Run {
+        /// The path to the pipeline configuration file
+        #[arg(short = 'C', long, default_value = "ezkl.toml")]
+        config: PathBuf,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Signs the hashes of input data with an oracle's key, attesting that proofs run on these inputs used the oracle's data
+    #[command(name = "attest-input", arg_required_else_help = true)]
+    AttestInput {
+        /// The path to the .json data file
+        #[arg(short = 'D', long)]
+        data: PathBuf,
+        /// The path to load circuit settings from
+        #[arg(short = 'S', long)]
+        settings_path: PathBuf,
+        /// Private secp256K1 key in hex format of the oracle
+        #[arg(short = 'P', long)]
+        private_key: String,
+        /// The path to output the signature
+        #[arg(long, default_value = "input.sig")]
+        signature_path: PathBuf,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Verifies that the (hashed) inputs of a proof were attested to by a given oracle address
+    #[command(name = "verify-input-attestation", arg_required_else_help = true)]
+    VerifyInputAttestation {
+        /// The path to the proof file
+        #[arg(long)]
+        proof_path: PathBuf,
+        /// The path to load circuit settings from
+        #[arg(short = 'S', long)]
+        settings_path: PathBuf,
+        /// The path to the signature file
+        #[arg(long, default_value = "input.sig")]
+        signature_path: PathBuf,
+        /// The address of the oracle's key
+        #[arg(long)]
+        address: H160,
+    },
+    /// Checks revealed outputs (eg. a generated image) against the hash of the outputs committed to in a proof
+    #[command(name = "verify-revealed-output", arg_required_else_help = true)]
+    VerifyRevealedOutput {
+        /// The path to the proof file
+        #[arg(long)]
+        proof_path: PathBuf,
+        /// The path to load circuit settings from
+        #[arg(short = 'S', long)]
+        settings_path: PathBuf,
+        /// The path to the .json data file holding the revealed outputs as its output_data
+        #[arg(short = 'D', long)]
+        data: PathBuf,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Prints a hash of the structure of a compiled circuit (its ops, shapes, scales and visibilities) that doesn't depend on its weights
+    #[command(arg_required_else_help = true)]
+    Fingerprint {
+        /// The path to the compiled model file
+        #[arg(short = 'M', long)]
+        compiled_circuit: PathBuf,
+    },
+
     /// Print the proof in hexadecimal
     #[command(name = "print-proof-hex", arg_required_else_help = true)]
     PrintProofHex {
@@ -0,0 +1,135 @@
+use crate::circuit::CircuitError;
+use crate::tensor::TensorError;
+use std::error::Error as StdError;
+use thiserror::Error;
+
+/// Errors raised while running or laying out a model, tagged with the node (layer) and op
+/// they originated from so that failures deep in a multi-layer model can be traced back.
+#[derive(Debug, Error)]
+pub enum EzklError {
+    /// The inputs or params of an op have incompatible shapes
+    #[error("shape mismatch at node {node} ({op}): {msg}")]
+    ShapeMismatch {
+        /// index of the node
+        node: usize,
+        /// the op of the node
+        op: String,
+        /// details of the mismatch
+        msg: String,
+    },
+    /// An output of a node wrapped around the field
+    #[error("output of node {node} ({op}) overflowed the fixed point range at element {element}, try reducing the scale")]
+    Overflow {
+        /// index of the node
+        node: usize,
+        /// the op of the node
+        op: String,
+        /// flat index of the first element which overflowed
+        element: usize,
+    },
+    /// The op (or the way it was configured) isn't supported
+    #[error("unsupported op at node {node} ({op})")]
+    UnsupportedOp {
+        /// index of the node
+        node: usize,
+        /// the op of the node
+        op: String,
+    },
+    /// An input to a lookup falls outside of the range of the lookup table
+    #[error("lookup input {value} at node {node} ({op}) is outside of the table range {range:?}, try increasing the lookup range")]
+    LookupRangeExceeded {
+        /// index of the node
+        node: usize,
+        /// the op of the node
+        op: String,
+        /// the offending input
+        value: i128,
+        /// the range of the lookup table
+        range: (i128, i128),
+    },
+    /// Any other error raised when laying out a node
+    #[error("failed to lay out node {node} ({op}): {source}")]
+    Layout {
+        /// index of the node
+        node: usize,
+        /// the op of the node
+        op: String,
+        /// the underlying error
+        source: Box<dyn StdError>,
+    },
+    /// An error raised by halo2
+    #[error("halo2 error: {0:?}")]
+    Halo2(#[from] halo2_proofs::plonk::Error),
+}
+
+impl EzklError {
+    /// Attaches node and op context to an error raised by an op, classifying it where possible
+    pub fn in_node(node: usize, op: String, err: Box<dyn StdError>) -> Self {
+        if let Some(TensorError::DimMismatch(msg)) = err.downcast_ref::<TensorError>() {
+            return EzklError::ShapeMismatch {
+                node,
+                op,
+                msg: msg.clone(),
+            };
+        }
+        if let Some(e) = err.downcast_ref::<CircuitError>() {
+            match e {
+                CircuitError::DimMismatch(msg) => {
+                    return EzklError::ShapeMismatch {
+                        node,
+                        op,
+                        msg: msg.clone(),
+                    }
+                }
+                CircuitError::UnsupportedOp => return EzklError::UnsupportedOp { node, op },
+                CircuitError::LookupRangeExceeded(value, range) => {
+                    return EzklError::LookupRangeExceeded {
+                        node,
+                        op,
+                        value: *value,
+                        range: *range,
+                    }
+                }
+                _ => {}
+            }
+        }
+        EzklError::Layout {
+            node,
+            op,
+            source: err,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_node_classifies_errors() {
+        let err = EzklError::in_node(
+            3,
+            "EINSUM ij,j->i".to_string(),
+            Box::new(TensorError::DimMismatch("einsum".to_string())),
+        );
+        assert!(matches!(err, EzklError::ShapeMismatch { node: 3, .. }));
+
+        let err = EzklError::in_node(
+            4,
+            "RELU".to_string(),
+            Box::new(CircuitError::LookupRangeExceeded(100, (-64, 64))),
+        );
+        assert!(matches!(
+            err,
+            EzklError::LookupRangeExceeded {
+                node: 4,
+                value: 100,
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("node 4 (RELU)"));
+
+        let err = EzklError::in_node(5, "POW".to_string(), Box::new(TensorError::WrongMethod));
+        assert!(matches!(err, EzklError::Layout { node: 5, .. }));
+    }
+}
@@ -18,6 +18,7 @@ use ethers::providers::{Http, Provider};
 use ethers::signers::Signer;
 use ethers::solc::{CompilerInput, Solc};
 use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::Signature;
 use ethers::types::TransactionRequest;
 use ethers::types::H160;
 use ethers::types::U256;
@@ -27,6 +28,7 @@ use ethers::{
     prelude::{LocalWallet, Wallet},
     utils::{Anvil, AnvilInstance},
 };
+use halo2_proofs::plonk::VerifyingKey;
 use halo2_solidity_verifier::encode_calldata;
 use halo2curves::bn256::{Fr, G1Affine};
 use halo2curves::group::ff::PrimeField;
@@ -342,6 +344,112 @@ pub async fn update_account_calls(
     Ok(())
 }
 
+/// The message a prover signs to attribute a proof to themselves: the hash of the verifying key of
+/// the circuit, the proof bytes and the byte representation of each of the proof's public instances.
+/// The vk hash binds the signature to the circuit, so it can't be replayed onto a proof of another
+/// circuit with the same instances.
+pub fn proof_signing_message(proof: &Snark<Fr, G1Affine>, vk: &VerifyingKey<G1Affine>) -> Vec<u8> {
+    let mut message = vk.transcript_repr().to_repr().as_ref().to_vec();
+    message.extend_from_slice(&proof.proof);
+    for instance in proof.instances.iter().flatten() {
+        message.extend_from_slice(instance.to_repr().as_ref());
+    }
+    message
+}
+
+/// Sign a proof (and its public instances) of the circuit of `vk` with a prover's identity key
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn sign_proof(
+    proof: &Snark<Fr, G1Affine>,
+    vk: &VerifyingKey<G1Affine>,
+    private_key: &str,
+) -> Result<Signature, Box<dyn Error>> {
+    let wallet: LocalWallet = private_key.parse()?;
+    let signature = wallet
+        .sign_message(proof_signing_message(proof, vk))
+        .await?;
+    Ok(signature)
+}
+
+/// Check that a proof (and its public instances) of the circuit of `vk` was signed by the given
+/// address
+pub fn verify_proof_signature(
+    proof: &Snark<Fr, G1Affine>,
+    vk: &VerifyingKey<G1Affine>,
+    signature: &Signature,
+    address: H160,
+) -> Result<(), Box<dyn Error>> {
+    signature.verify(proof_signing_message(proof, vk), address)?;
+    Ok(())
+}
+
+/// The poseidon hashes of the input `data`, as a proof with hashed public inputs exposes them
+/// as its first instances. These are what an oracle attests to.
+pub fn input_attestation_hashes(
+    settings: &GraphSettings,
+    data: &GraphData,
+) -> Result<Vec<Fr>, Box<dyn Error>> {
+    if !settings.run_args.input_visibility.is_hashed_public() {
+        return Err("attested inputs need a hashed/public input visibility".into());
+    }
+    let inputs = match &data.input_data {
+        DataSource::File(file) => file
+            .iter()
+            .map(|t| t.iter().map(|x| x.to_float() as f32).collect::<Vec<_>>())
+            .collect::<Vec<_>>(),
+        _ => return Err("only file sourced inputs can be attested".into()),
+    };
+    settings.input_instances(&inputs)
+}
+
+/// The message an oracle signs to attest to inputs: the byte representation of each input hash
+pub fn input_attestation_message(hashes: &[Fr]) -> Vec<u8> {
+    hashes
+        .iter()
+        .flat_map(|h| h.to_repr().as_ref().to_vec())
+        .collect()
+}
+
+/// Sign the hashes of the input `data` with an oracle's key
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn sign_input_attestation(
+    settings: &GraphSettings,
+    data: &GraphData,
+    private_key: &str,
+) -> Result<Signature, Box<dyn Error>> {
+    let hashes = input_attestation_hashes(settings, data)?;
+    let wallet: LocalWallet = private_key.parse()?;
+    let signature = wallet
+        .sign_message(input_attestation_message(&hashes))
+        .await?;
+    Ok(signature)
+}
+
+/// Check that the inputs a proof (with hashed public inputs) was run on were attested to by the
+/// oracle at `address`
+pub fn verify_input_attestation(
+    settings: &GraphSettings,
+    proof: &Snark<Fr, G1Affine>,
+    signature: &Signature,
+    address: H160,
+) -> Result<(), Box<dyn Error>> {
+    if !settings.run_args.input_visibility.is_hashed_public() {
+        return Err(
+            "the proof doesn't commit to its inputs, they need a hashed/public visibility".into(),
+        );
+    }
+    // the input hashes come ahead of any other instance
+    let instances = proof
+        .instances
+        .iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>();
+    let (hashes, _) = settings.hashed_instances(&instances)?;
+    signature.verify(input_attestation_message(hashes), address)?;
+    Ok(())
+}
+
 /// Verify a proof using a Solidity verifier contract
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn verify_proof_via_solidity(
@@ -751,3 +859,77 @@ pub fn fix_da_sol(
 
     Ok(contract)
 }
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::graph::Visibility;
+    use crate::pfsys::TranscriptType;
+
+    fn proof(instances: &[u64]) -> Snark<Fr, G1Affine> {
+        Snark {
+            protocol: None,
+            instances: vec![instances.iter().map(|i| Fr::from(*i)).collect()],
+            proof: vec![],
+            transcript_type: TranscriptType::EVM,
+            split: None,
+            header: None,
+        }
+    }
+
+    async fn sign(wallet: &LocalWallet, hashes: &[u64]) -> Signature {
+        let hashes = hashes.iter().map(|h| Fr::from(*h)).collect::<Vec<_>>();
+        wallet
+            .sign_message(input_attestation_message(&hashes))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_verify_input_attestation() {
+        // the first two anvil dev keys
+        let wallet: LocalWallet =
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+                .parse()
+                .unwrap();
+        let mut settings = GraphSettings {
+            model_input_scales: vec![0, 0],
+            model_output_scales: vec![0],
+            ..Default::default()
+        };
+        settings.run_args.input_visibility = Visibility::Hashed {
+            hash_is_public: true,
+            outlets: vec![],
+        };
+
+        // a hash per input, followed by the public output
+        let signature = sign(&wallet, &[1, 2]).await;
+        let address = wallet.address();
+        assert!(
+            verify_input_attestation(&settings, &proof(&[1, 2, 3]), &signature, address).is_ok()
+        );
+        assert!(
+            verify_input_attestation(&settings, &proof(&[1, 4, 3]), &signature, address).is_err()
+        );
+        assert!(verify_input_attestation(&settings, &proof(&[1]), &signature, address).is_err());
+        let other: LocalWallet = "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+            .parse()
+            .unwrap();
+        let other = other.address();
+        assert!(
+            verify_input_attestation(&settings, &proof(&[1, 2, 3]), &signature, other).is_err()
+        );
+
+        // a single hash of the overwritten outlet, not one per input
+        settings.run_args.input_visibility = Visibility::Hashed {
+            hash_is_public: true,
+            outlets: vec![0],
+        };
+        let signature = sign(&wallet, &[1]).await;
+        assert!(verify_input_attestation(&settings, &proof(&[1, 3]), &signature, address).is_ok());
+
+        settings.run_args.input_visibility = Visibility::Public;
+        assert!(verify_input_attestation(&settings, &proof(&[1, 3]), &signature, address).is_err());
+    }
+}
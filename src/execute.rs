@@ -7,7 +7,7 @@ use crate::eth::{deploy_da_verifier_via_solidity, deploy_verifier_via_solidity};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::eth::{fix_da_sol, get_contract_artifacts, verify_proof_via_solidity};
 use crate::graph::input::GraphData;
-use crate::graph::{GraphCircuit, GraphSettings, GraphWitness, Model};
+use crate::graph::{failure_layer, GraphCircuit, GraphSettings, GraphWitness, Model};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::graph::{TestDataSource, TestSources};
 use crate::pfsys::evm::aggregation::AggregationCircuit;
@@ -43,9 +43,7 @@ use instant::Instant;
 use itertools::Itertools;
 #[cfg(not(target_arch = "wasm32"))]
 use log::debug;
-use log::{info, trace};
-#[cfg(feature = "render")]
-use plotters::prelude::*;
+use log::{error, info, trace};
 #[cfg(not(target_arch = "wasm32"))]
 use rand::Rng;
 #[cfg(not(target_arch = "wasm32"))]
@@ -128,7 +126,9 @@ pub async fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
             settings_path,
             logrows,
             check,
-        } => get_srs_cmd(srs_path, settings_path, logrows, check).await,
+            srs_url,
+            srs_hash,
+        } => get_srs_cmd(srs_path, settings_path, logrows, check, srs_url, srs_hash).await,
         Commands::Table { model, args } => table(model, args),
         #[cfg(feature = "render")]
         Commands::RenderCircuit {
@@ -156,9 +156,21 @@ pub async fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
             output,
             vk_path,
             srs_path,
-        } => gen_witness(compiled_circuit, data, Some(output), vk_path, srs_path)
-            .await
-            .map(|_| ()),
+            witness_trace,
+        } => {
+            let witness = gen_witness(
+                compiled_circuit.clone(),
+                data,
+                Some(output),
+                vk_path,
+                srs_path,
+            )
+            .await?;
+            match witness_trace {
+                Some(path) => dump_witness_trace(compiled_circuit, &witness, path),
+                None => Ok(()),
+            }
+        }
         Commands::Mock { model, witness } => mock(model, witness).await,
         #[cfg(not(target_arch = "wasm32"))]
         Commands::CreateEVMVerifier {
@@ -305,6 +317,26 @@ pub async fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
             vk_path,
             srs_path,
         } => verify(proof_path, settings_path, vk_path, srs_path),
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::SplitCircuit {
+            model,
+            node,
+            compiled_circuits,
+            settings_paths,
+            args,
+        } => split_circuit(model, node, compiled_circuits, settings_paths, args),
+        Commands::VerifyChain {
+            proof_paths,
+            settings_paths,
+            vk_paths,
+            srs_path,
+        } => verify_chain(proof_paths, settings_paths, vk_paths, srs_path),
+        Commands::VerifyBatch {
+            proof_paths,
+            settings_path,
+            vk_path,
+            srs_path,
+        } => verify_batch(proof_paths, settings_path, vk_path, srs_path),
         Commands::VerifyAggr {
             proof_path,
             vk_path,
@@ -356,6 +388,54 @@ pub async fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
             rpc_url,
             addr_da,
         } => verify_evm(proof_path, addr_verifier, rpc_url, addr_da).await,
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::SignProof {
+            proof_path,
+            settings_path,
+            vk_path,
+            private_key,
+            signature_path,
+        } => {
+            sign_proof(
+                proof_path,
+                settings_path,
+                vk_path,
+                private_key,
+                signature_path,
+            )
+            .await
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::VerifyProofSignature {
+            proof_path,
+            settings_path,
+            vk_path,
+            signature_path,
+            address,
+        } => verify_proof_signature(proof_path, settings_path, vk_path, signature_path, address),
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::AttestInput {
+            data,
+            settings_path,
+            private_key,
+            signature_path,
+        } => attest_input(data, settings_path, private_key, signature_path).await,
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::VerifyInputAttestation {
+            proof_path,
+            settings_path,
+            signature_path,
+            address,
+        } => verify_input_attestation(proof_path, settings_path, signature_path, address),
+        Commands::VerifyRevealedOutput {
+            proof_path,
+            settings_path,
+            data,
+        } => verify_revealed_output(proof_path, settings_path, data),
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::Fingerprint { compiled_circuit } => fingerprint(compiled_circuit).map(|_| ()),
+        #[cfg(not(target_arch = "wasm32"))]
+        Commands::Run { config } => run_pipeline(config).await,
         Commands::PrintProofHex { proof_path } => print_proof_hex(proof_path),
         #[cfg(not(target_arch = "wasm32"))]
         Commands::GetHubCredentials { username, url } => {
@@ -437,6 +517,8 @@ pub(crate) async fn get_srs_cmd(
     settings_path: Option<PathBuf>,
     logrows: Option<u32>,
     check_mode: CheckMode,
+    srs_url: Option<String>,
+    srs_hash: Option<String>,
 ) -> Result<(), Box<dyn Error>> {
     let k = if let Some(settings_p) = settings_path {
         if settings_p.exists() {
@@ -457,8 +539,38 @@ pub(crate) async fn get_srs_cmd(
         return Err(err_string.into());
     };
 
-    let srs_uri = format!("{}{}", PUBLIC_SRS_URL, k);
-    let mut reader = Cursor::new(fetch_srs(&srs_uri).await?);
+    let cache_path = crate::pfsys::srs::cached_srs_path(k, srs_url.as_deref());
+    let cached = match &cache_path {
+        Some(p) if p.exists() => {
+            let srs = std::fs::read(p)?;
+            match &srs_hash {
+                Some(hash) => crate::pfsys::srs::check_srs_hash(&srs, hash)
+                    .ok()
+                    .map(|_| srs),
+                None => Some(srs),
+            }
+        }
+        _ => None,
+    };
+
+    let srs = match cached {
+        Some(srs) => {
+            info!("using cached SRS at {:?}", cache_path.as_ref().unwrap());
+            srs
+        }
+        None => {
+            let srs_uri = srs_url
+                .clone()
+                .unwrap_or_else(|| format!("{}{}", PUBLIC_SRS_URL, k));
+            let srs = fetch_srs(&srs_uri).await?;
+            if let Some(hash) = &srs_hash {
+                crate::pfsys::srs::check_srs_hash(&srs, hash)?;
+            }
+            srs
+        }
+    };
+
+    let mut reader = Cursor::new(srs);
     // check the SRS
     if matches!(check_mode, CheckMode::SAFE) {
         #[cfg(not(target_arch = "wasm32"))]
@@ -470,6 +582,17 @@ pub(crate) async fn get_srs_cmd(
         pb.finish_with_message("SRS validated");
     }
 
+    // the cache is only an optimization, failing to write it shouldn't fail the command
+    if let Some(p) = &cache_path {
+        if !p.exists() {
+            if let Err(e) = std::fs::create_dir_all(p.parent().unwrap())
+                .and_then(|_| std::fs::write(p, reader.get_ref()))
+            {
+                log::warn!("failed to cache SRS at {:?}: {}", p, e);
+            }
+        }
+    }
+
     let mut file = std::fs::File::create(srs_path)?;
     file.write_all(reader.get_ref())?;
 
@@ -480,6 +603,10 @@ pub(crate) async fn get_srs_cmd(
 pub(crate) fn table(model: PathBuf, run_args: RunArgs) -> Result<(), Box<dyn Error>> {
     let model = Model::from_run_args(&run_args, &model)?;
     info!("\n {}", model.table_nodes());
+    info!(
+        "\n PER LAYER CIRCUIT USAGE \n{}",
+        model.table_layer_reports(&run_args)?
+    );
     Ok(())
 }
 
@@ -533,12 +660,42 @@ pub(crate) async fn gen_witness(
         start_time.elapsed()
     );
 
+    if settings.run_args.input_visibility.is_hashed_public() {
+        if let Some(hashes) = witness.get_input_hashes() {
+            debug!("public input digests: {:?}", hashes);
+        }
+    }
+
     if let Some(output_path) = output {
         serde_json::to_writer(&File::create(output_path)?, &witness)?;
     }
     Ok(witness)
 }
 
+/// Lays out the circuit on a witness and dumps every advice value assigned by the model's layers
+pub(crate) fn dump_witness_trace(
+    compiled_circuit_path: PathBuf,
+    witness: &GraphWitness,
+    path: PathBuf,
+) -> Result<(), Box<dyn Error>> {
+    let mut circuit = GraphCircuit::load(compiled_circuit_path)?;
+    circuit.load_graph_witness(witness)?;
+    let public_inputs = circuit.prepare_public_inputs(witness)?;
+
+    crate::circuit::region::start_witness_trace();
+    let res = halo2_proofs::dev::MockProver::run(
+        circuit.settings().run_args.logrows,
+        &circuit,
+        vec![public_inputs],
+    );
+    let trace = crate::circuit::region::take_witness_trace();
+    res.map_err(Box::<dyn Error>::from)?;
+
+    crate::io::save_witness_trace(&path, &trace)?;
+    info!("dumped {} advice values to {}", trace.len(), path.display());
+    Ok(())
+}
+
 /// Generate a circuit settings file
 pub(crate) fn gen_circuit_settings(
     model_path: PathBuf,
@@ -587,6 +744,31 @@ pub(crate) fn init_bar(len: u64) -> ProgressBar {
     pb
 }
 
+/// A spinner that follows the [crate::pfsys::progress::Progress] of keygen and proving, so
+/// long runs don't look like they hang. Stop it with [stop_progress_spinner].
+#[cfg(not(target_arch = "wasm32"))]
+fn progress_spinner() -> ProgressBar {
+    use crate::pfsys::progress::{set_callback, Progress};
+    let pb = init_spinner();
+    let spinner = pb.clone();
+    set_callback(move |progress| match progress {
+        Progress::Started(phase) => spinner.set_message(format!("computing {}...", phase)),
+        Progress::Finished(phase, millis) => {
+            spinner.set_message(format!("{} took {}ms", phase, millis))
+        }
+        Progress::Layer { layer, total } => {
+            spinner.set_message(format!("synthesized layer {}/{}", layer + 1, total))
+        }
+    });
+    pb
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn stop_progress_spinner(pb: ProgressBar) {
+    crate::pfsys::progress::clear_callback();
+    pb.finish_and_clear();
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 use colored_json::ToColoredJson;
 
@@ -899,12 +1081,126 @@ pub(crate) async fn mock(
         vec![public_inputs],
     )
     .map_err(Box::<dyn Error>::from)?;
-    prover
-        .verify_par()
-        .map_err(|e| Box::<dyn Error>::from(ExecutionError::VerifyError(e)))?;
+    prover.verify_par().map_err(|e| {
+        // map each failure in the model region back to the layer which laid out the failing row
+        let reports = circuit
+            .model()
+            .layer_reports(&circuit.settings().run_args)
+            .unwrap_or_default();
+        for failure in &e {
+            if let Some(layer) = failure_layer(failure, &reports) {
+                error!(
+                    "{} -> in layer {} (node {}: {})",
+                    failure, layer.name, layer.idx, layer.opkind
+                );
+            }
+        }
+        Box::<dyn Error>::from(ExecutionError::VerifyError(e))
+    })?;
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sign_proof(
+    proof_path: PathBuf,
+    settings_path: PathBuf,
+    vk_path: PathBuf,
+    private_key: String,
+    signature_path: PathBuf,
+) -> Result<(), Box<dyn Error>> {
+    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path)?;
+    let settings = GraphSettings::load(&settings_path)?;
+    let vk = load_vk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(vk_path, settings)?;
+    let signature = crate::eth::sign_proof(&proof, &vk, &private_key).await?;
+    std::fs::write(&signature_path, signature.to_string())?;
+    info!("proof signature saved to {}", signature_path.display());
     Ok(())
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn verify_proof_signature(
+    proof_path: PathBuf,
+    settings_path: PathBuf,
+    vk_path: PathBuf,
+    signature_path: PathBuf,
+    address: H160,
+) -> Result<(), Box<dyn Error>> {
+    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path)?;
+    let settings = GraphSettings::load(&settings_path)?;
+    let vk = load_vk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(vk_path, settings)?;
+    let signature: ethers::types::Signature =
+        std::fs::read_to_string(&signature_path)?.trim().parse()?;
+    crate::eth::verify_proof_signature(&proof, &vk, &signature, address)?;
+    info!("proof was signed by {:?}", address);
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn attest_input(
+    data: PathBuf,
+    settings_path: PathBuf,
+    private_key: String,
+    signature_path: PathBuf,
+) -> Result<(), Box<dyn Error>> {
+    let settings = GraphSettings::load(&settings_path)?;
+    let data = GraphData::from_path(data)?;
+    let signature = crate::eth::sign_input_attestation(&settings, &data, &private_key).await?;
+    std::fs::write(&signature_path, signature.to_string())?;
+    info!("input attestation saved to {}", signature_path.display());
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn verify_input_attestation(
+    proof_path: PathBuf,
+    settings_path: PathBuf,
+    signature_path: PathBuf,
+    address: H160,
+) -> Result<(), Box<dyn Error>> {
+    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path)?;
+    let settings = GraphSettings::load(&settings_path)?;
+    let signature: ethers::types::Signature =
+        std::fs::read_to_string(&signature_path)?.trim().parse()?;
+    crate::eth::verify_input_attestation(&settings, &proof, &signature, address)?;
+    info!("proof inputs were attested to by {:?}", address);
+    Ok(())
+}
+
+pub(crate) fn verify_revealed_output(
+    proof_path: PathBuf,
+    settings_path: PathBuf,
+    data: PathBuf,
+) -> Result<(), Box<dyn Error>> {
+    let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path)?;
+    let settings = GraphSettings::load(&settings_path)?;
+    let data = GraphData::from_path(data)?;
+    let outputs = match data.output_data {
+        Some(crate::graph::DataSource::File(file)) => file
+            .iter()
+            .map(|t| t.iter().map(|x| x.to_float() as f32).collect::<Vec<_>>())
+            .collect::<Vec<_>>(),
+        _ => return Err("the data file has no file sourced output_data to check".into()),
+    };
+    let instances = proof.instances.into_iter().flatten().collect::<Vec<_>>();
+    settings.verify_revealed_outputs(&instances, &outputs)?;
+    info!("revealed outputs match the proof's commitment");
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn fingerprint(compiled_circuit: PathBuf) -> Result<String, Box<dyn Error>> {
+    let circuit = GraphCircuit::load(compiled_circuit)?;
+    let fingerprint = circuit.fingerprint()?;
+    info!("circuit fingerprint: {}", fingerprint);
+    Ok(fingerprint)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn run_pipeline(config: PathBuf) -> Result<(), Box<dyn Error>> {
+    let pipeline = crate::pipeline::Pipeline::from_config(&config)?;
+    pipeline.run().await.map(|_| ())
+}
+
 pub(crate) fn print_proof_hex(proof_path: PathBuf) -> Result<(), Box<dyn Error>> {
     let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path)?;
     for instance in proof.instances {
@@ -918,19 +1214,7 @@ pub(crate) fn print_proof_hex(proof_path: PathBuf) -> Result<(), Box<dyn Error>>
 pub(crate) fn render(model: PathBuf, output: PathBuf, args: RunArgs) -> Result<(), Box<dyn Error>> {
     let circuit = GraphCircuit::from_run_args(&args, &model)?;
     info!("Rendering circuit");
-
-    // Create the area we want to draw on.
-    // We could use SVGBackend if we want to render to .svg instead.
-    // for an overview of how to interpret these plots, see https://zcash.github.io/halo2/user/dev-tools.html
-    let root = BitMapBackend::new(&output, (512, 512)).into_drawing_area();
-    root.fill(&TRANSPARENT).unwrap();
-    let root = root.titled("Layout", ("sans-serif", 20))?;
-
-    halo2_proofs::dev::CircuitLayout::default()
-        // We hide labels, else most circuits become impossible to decipher because of overlaid text
-        .show_labels(false)
-        .render(circuit.settings().run_args.logrows, &circuit, &root)?;
-    Ok(())
+    circuit.render_layout(&output)
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -1211,8 +1495,11 @@ pub(crate) fn setup(
 
     let params = load_params_cmd(srs_path, circuit.settings().run_args.logrows)?;
 
+    let pb = progress_spinner();
     let pk = create_keys::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(&circuit, &params)
-        .map_err(Box::<dyn Error>::from)?;
+        .map_err(Box::<dyn Error>::from);
+    stop_progress_spinner(pb);
+    let pk = pk?;
 
     save_vk::<KZGCommitmentScheme<Bn256>>(&vk_path, pk.get_vk())?;
     save_pk::<KZGCommitmentScheme<Bn256>>(&pk_path, &pk)?;
@@ -1306,6 +1593,7 @@ pub(crate) async fn prove(
     let proof_split_commits: Option<ProofSplitCommit> = data.into();
 
     // creates and verifies the proof
+    let pb = progress_spinner();
     let snark = match strategy {
         StrategyType::Single => {
             let strategy = KZGSingleStrategy::new(&params);
@@ -1318,7 +1606,7 @@ pub(crate) async fn prove(
                 strategy,
                 check_mode,
                 proof_split_commits,
-            )?
+            )
         }
         StrategyType::Accum => {
             let strategy = AccumulatorStrategy::new(&params);
@@ -1331,9 +1619,11 @@ pub(crate) async fn prove(
                 strategy,
                 check_mode,
                 proof_split_commits,
-            )?
+            )
         }
     };
+    stop_progress_spinner(pb);
+    let snark = snark?;
 
     if let Some(proof_path) = proof_path {
         snark.save(&proof_path)?;
@@ -1483,6 +1773,7 @@ pub(crate) async fn fuzz(
             protocol: proof.protocol.clone(),
             transcript_type: transcript,
             split: None,
+            header: None,
         };
 
         verify_proof_circuit_kzg(
@@ -1510,6 +1801,7 @@ pub(crate) async fn fuzz(
             protocol: proof.protocol.clone(),
             transcript_type: transcript,
             split: None,
+            header: None,
         };
 
         verify_proof_circuit_kzg(
@@ -1705,6 +1997,7 @@ pub(crate) fn verify(
 
     let strategy = KZGSingleStrategy::new(params.verifier_params());
     let vk = load_vk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(vk_path, circuit_settings)?;
+    proof.check_vk(&vk)?;
     let now = Instant::now();
     let result = verify_proof_circuit_kzg(params.verifier_params(), proof, &vk, strategy);
     let elapsed = now.elapsed();
@@ -1717,6 +2010,113 @@ pub(crate) fn verify(
     result.map_err(|e| e.into())
 }
 
+pub(crate) fn verify_batch(
+    proof_paths: Vec<PathBuf>,
+    settings_path: PathBuf,
+    vk_path: PathBuf,
+    srs_path: PathBuf,
+) -> Result<(), Box<dyn Error>> {
+    let circuit_settings = GraphSettings::load(&settings_path)?;
+    let params = load_params_cmd(srs_path, circuit_settings.run_args.logrows)?;
+    let proofs = proof_paths
+        .iter()
+        .map(Snark::load::<KZGCommitmentScheme<Bn256>>)
+        .collect::<Result<Vec<_>, _>>()?;
+    // every proof is checked against the vk (see [crate::pfsys::verify_batch]) and the settings
+    let num_instances: usize = circuit_settings.total_instances().iter().sum();
+    for (proof, path) in proofs.iter().zip(&proof_paths) {
+        let proof_instances: usize = proof.instances.iter().map(|i| i.len()).sum();
+        if proof_instances != num_instances {
+            return Err(format!(
+                "proof {} has {} instances, the settings expect {}",
+                path.display(),
+                proof_instances,
+                num_instances
+            )
+            .into());
+        }
+    }
+
+    let vk = load_vk::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(vk_path, circuit_settings)?;
+    let now = Instant::now();
+    let result = crate::pfsys::verify_batch(&proofs, params.verifier_params(), &vk);
+    let elapsed = now.elapsed();
+    info!(
+        "verifying {} proofs took {}.{}",
+        proofs.len(),
+        elapsed.as_secs(),
+        elapsed.subsec_millis()
+    );
+    info!("verified: {}", result.is_ok());
+    result
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn split_circuit(
+    model_path: PathBuf,
+    node: usize,
+    compiled_circuits: Vec<PathBuf>,
+    settings_paths: Vec<PathBuf>,
+    run_args: RunArgs,
+) -> Result<(), Box<dyn Error>> {
+    if compiled_circuits.len() != 2 || settings_paths.len() != 2 {
+        return Err("expected two compiled circuit and two settings paths".into());
+    }
+    let model = Model::from_run_args(&run_args, &model_path)?;
+    for ((circuit, compiled_circuit), settings_path) in
+        GraphCircuit::split(&model, node, &run_args)?
+            .into_iter()
+            .zip(compiled_circuits)
+            .zip(settings_paths)
+    {
+        circuit.settings().save(&settings_path)?;
+        circuit.save(compiled_circuit)?;
+    }
+    info!("split model at node {}", node);
+    Ok(())
+}
+
+/// Verifies the proofs of consecutive chunks of a split model, and that the activation commitments
+/// output by each proof are the ones consumed by the next
+pub(crate) fn verify_chain(
+    proof_paths: Vec<PathBuf>,
+    settings_paths: Vec<PathBuf>,
+    vk_paths: Vec<PathBuf>,
+    srs_path: PathBuf,
+) -> Result<(), Box<dyn Error>> {
+    if proof_paths.is_empty()
+        || proof_paths.len() != settings_paths.len()
+        || proof_paths.len() != vk_paths.len()
+    {
+        return Err("expected a settings and vk path for each proof".into());
+    }
+
+    let mut proofs = vec![];
+    for ((proof_path, settings_path), vk_path) in
+        proof_paths.iter().zip(&settings_paths).zip(vk_paths)
+    {
+        verify(
+            proof_path.clone(),
+            settings_path.clone(),
+            vk_path,
+            srs_path.clone(),
+        )?;
+        let settings = GraphSettings::load(settings_path)?;
+        let proof = Snark::load::<KZGCommitmentScheme<Bn256>>(proof_path)?;
+        proofs.push((settings, proof.instances.concat()));
+    }
+
+    for (i, ((prev_settings, prev), (next_settings, next))) in
+        proofs.iter().tuple_windows().enumerate()
+    {
+        prev_settings
+            .check_chained(prev, next_settings, next)
+            .map_err(|e| format!("proofs {} and {} do not chain: {}", i, i + 1, e))?;
+    }
+    info!("verified a chain of {} proofs", proofs.len());
+    Ok(())
+}
+
 pub(crate) fn verify_aggr(
     proof_path: PathBuf,
     vk_path: PathBuf,
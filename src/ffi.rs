@@ -0,0 +1,246 @@
+use crate::circuit::CheckMode;
+use crate::execute::{compile_circuit, gen_circuit_settings, gen_witness, prove, setup, verify};
+use crate::pfsys::ProofType;
+use crate::RunArgs;
+use clap::Parser;
+use std::cell::RefCell;
+use std::error::Error;
+use std::ffi::{c_char, CStr, CString};
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+
+/// Status code returned by every function of the C api
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EzklStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A required argument was null or not valid utf-8
+    InvalidArgument = 1,
+    /// The call failed, the reason can be retrieved with [ezkl_last_error]
+    Failed = 2,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+fn set_last_error(err: impl std::fmt::Display) {
+    let msg = CString::new(err.to_string().replace('\0', ""))
+        .unwrap_or_else(|_| CString::new("unknown error").unwrap());
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(msg));
+}
+
+/// Reads a (required) path argument, returns None if it is null or not valid utf-8
+unsafe fn path_arg(ptr: *const c_char) -> Option<PathBuf> {
+    if ptr.is_null() {
+        return None;
+    }
+    CStr::from_ptr(ptr).to_str().ok().map(PathBuf::from)
+}
+
+/// Runs `f`, storing any error (or panic, which must not unwind across the ffi boundary) so it
+/// can be retrieved with [ezkl_last_error]
+fn run(f: impl FnOnce() -> Result<(), Box<dyn Error>>) -> EzklStatus {
+    match std::panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => {
+            LAST_ERROR.with(|e| *e.borrow_mut() = None);
+            EzklStatus::Ok
+        }
+        Ok(Err(e)) => {
+            set_last_error(e);
+            EzklStatus::Failed
+        }
+        Err(_) => {
+            set_last_error("ezkl panicked");
+            EzklStatus::Failed
+        }
+    }
+}
+
+fn block_on<T>(f: impl Future<Output = Result<T, Box<dyn Error>>>) -> Result<T, Box<dyn Error>> {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?
+        .block_on(f)
+}
+
+/// The cli defaults of [RunArgs]
+#[derive(Parser)]
+struct DefaultRunArgs {
+    #[clap(flatten)]
+    args: RunArgs,
+}
+
+macro_rules! path_args {
+    ($($arg:ident),*) => {
+        $(
+            let $arg = match path_arg($arg) {
+                Some(p) => p,
+                None => {
+                    set_last_error(concat!("invalid argument: ", stringify!($arg)));
+                    return EzklStatus::InvalidArgument;
+                }
+            };
+        )*
+    };
+}
+
+/// Returns the error message of the last failed call on the current thread, or null if the last
+/// call succeeded. The string is owned by ezkl and stays valid until the next call on the same thread.
+#[no_mangle]
+pub extern "C" fn ezkl_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| match &*e.borrow() {
+        Some(msg) => msg.as_ptr(),
+        None => std::ptr::null(),
+    })
+}
+
+/// Generates the circuit settings of an .onnx model and compiles it.
+/// `run_args_json` is a json encoded [RunArgs], null uses the cli defaults.
+///
+/// # Safety
+/// Every pointer argument must be null or point to a valid nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ezkl_load_model(
+    model: *const c_char,
+    run_args_json: *const c_char,
+    settings_out: *const c_char,
+    compiled_out: *const c_char,
+) -> EzklStatus {
+    path_args!(model, settings_out, compiled_out);
+    let run_args_json = if run_args_json.is_null() {
+        None
+    } else {
+        match CStr::from_ptr(run_args_json).to_str() {
+            Ok(s) => Some(s.to_string()),
+            Err(_) => {
+                set_last_error("invalid argument: run_args_json");
+                return EzklStatus::InvalidArgument;
+            }
+        }
+    };
+    run(|| {
+        let run_args = match run_args_json {
+            Some(json) => RunArgs::from_json(&json)?,
+            None => DefaultRunArgs::parse_from(["ezkl"]).args,
+        };
+        gen_circuit_settings(model.clone(), settings_out.clone(), run_args)?;
+        compile_circuit(model, compiled_out, settings_out)
+    })
+}
+
+/// Runs a forward pass of a compiled circuit on the input data, writing the witness to `witness_out`
+///
+/// # Safety
+/// Every pointer argument must be null or point to a valid nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ezkl_gen_witness(
+    compiled_circuit: *const c_char,
+    data: *const c_char,
+    witness_out: *const c_char,
+) -> EzklStatus {
+    path_args!(compiled_circuit, data, witness_out);
+    run(|| {
+        block_on(gen_witness(
+            compiled_circuit,
+            data,
+            Some(witness_out),
+            None,
+            None,
+        ))?;
+        Ok(())
+    })
+}
+
+/// Creates the verifying and proving keys of a compiled circuit
+///
+/// # Safety
+/// Every pointer argument must be null or point to a valid nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ezkl_setup(
+    compiled_circuit: *const c_char,
+    srs: *const c_char,
+    vk_out: *const c_char,
+    pk_out: *const c_char,
+) -> EzklStatus {
+    path_args!(compiled_circuit, srs, vk_out, pk_out);
+    run(|| setup(compiled_circuit, srs, vk_out, pk_out, None))
+}
+
+/// Proves a witness of a compiled circuit, writing the proof to `proof_out`
+///
+/// # Safety
+/// Every pointer argument must be null or point to a valid nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ezkl_prove(
+    witness: *const c_char,
+    compiled_circuit: *const c_char,
+    pk: *const c_char,
+    srs: *const c_char,
+    proof_out: *const c_char,
+) -> EzklStatus {
+    path_args!(witness, compiled_circuit, pk, srs, proof_out);
+    run(|| {
+        block_on(prove(
+            witness,
+            compiled_circuit,
+            pk,
+            Some(proof_out),
+            srs,
+            ProofType::Single,
+            CheckMode::UNSAFE,
+        ))?;
+        Ok(())
+    })
+}
+
+/// Verifies a proof, returns [EzklStatus::Ok] only if the proof is valid
+///
+/// # Safety
+/// Every pointer argument must be null or point to a valid nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ezkl_verify(
+    proof: *const c_char,
+    settings: *const c_char,
+    vk: *const c_char,
+    srs: *const c_char,
+) -> EzklStatus {
+    path_args!(proof, settings, vk, srs);
+    run(|| verify(proof, settings, vk, srs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffi_reports_errors() {
+        let status = unsafe {
+            ezkl_verify(
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+            )
+        };
+        assert_eq!(status, EzklStatus::InvalidArgument);
+        let msg = unsafe { CStr::from_ptr(ezkl_last_error()) };
+        assert_eq!(msg.to_str().unwrap(), "invalid argument: proof");
+
+        let missing = CString::new("missing.json").unwrap();
+        let status = unsafe {
+            ezkl_verify(
+                missing.as_ptr(),
+                missing.as_ptr(),
+                missing.as_ptr(),
+                missing.as_ptr(),
+            )
+        };
+        assert_eq!(status, EzklStatus::Failed);
+        assert!(!ezkl_last_error().is_null());
+
+        assert_eq!(DefaultRunArgs::parse_from(["ezkl"]).args.logrows, 17);
+    }
+}
@@ -65,6 +65,13 @@ pub fn felt_to_i128<F: PrimeField + PartialOrd + Field>(x: F) -> i128 {
     }
 }
 
+/// Whether a PrimeField element lies within the range that [felt_to_i128] can represent,
+/// ie. whether it hasn't wrapped around the field.
+pub fn felt_fits_i128<F: PrimeField + PartialOrd + Field>(x: F) -> bool {
+    let max = F::from_u128(i128::MAX as u128);
+    x <= max || -x <= max
+}
+
 #[cfg(test)]
 mod test {
 
@@ -95,6 +102,18 @@ mod test {
         }
     }
 
+    #[test]
+    fn feltfitsi128() {
+        assert!(felt_fits_i128::<F>(i128_to_felt(i128::MAX)));
+        assert!(felt_fits_i128::<F>(i128_to_felt(-i128::MAX)));
+        assert!(!felt_fits_i128::<F>(
+            F::from_u128(i128::MAX as u128) + F::from(1)
+        ));
+        assert!(!felt_fits_i128::<F>(
+            -F::from_u128(i128::MAX as u128) - F::from(1)
+        ));
+    }
+
     #[test]
     fn felttoi128() {
         for x in -(2i128.pow(20))..(2i128.pow(20)) {
@@ -103,4 +122,31 @@ mod test {
             assert_eq!(x, xf);
         }
     }
+
+    #[test]
+    fn felttoi128_bn256() {
+        // the circuits are proven over bn256, whose modulus the conversions must also hold for
+        use halo2curves::bn256::Fr;
+        for x in [
+            0,
+            1,
+            -1,
+            2i128.pow(64),
+            -(2i128.pow(64)),
+            i128::MAX,
+            -i128::MAX,
+        ] {
+            let fieldx: Fr = i128_to_felt::<Fr>(x);
+            assert_eq!(felt_to_i128::<Fr>(fieldx), x);
+            assert!(felt_fits_i128::<Fr>(fieldx));
+        }
+        assert_eq!(i32_to_felt::<Fr>(-15), -Fr::from(15));
+        assert_eq!(
+            felt_to_i32::<Fr>(i32_to_felt(-(2i32.pow(16)))),
+            -(2i32.pow(16))
+        );
+        assert!(!felt_fits_i128::<Fr>(
+            Fr::from_u128(i128::MAX as u128) + Fr::from(1)
+        ));
+    }
 }
@@ -0,0 +1,322 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use halo2curves::bn256::Fr as Fp;
+#[cfg(feature = "python-bindings")]
+use pyo3::{
+    exceptions::PyValueError, types::PyString, FromPyObject, IntoPy, PyAny, PyObject, PyResult,
+    PyTryFrom, Python, ToPyObject,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{quantize_float, scale_to_multiplier};
+use crate::circuit::lookup::LookupOp;
+use crate::circuit::ops::{cmp, layouts::enforce_equality, region::RegionCtx};
+use crate::circuit::BaseConfig;
+use crate::fieldutils::i128_to_felt;
+use crate::tensor::{Tensor, ValTensor, ValType};
+
+/// The comparison of an [OutputAssertion]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AssertionCmp {
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessEqual,
+}
+
+impl AssertionCmp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            AssertionCmp::Greater => ">",
+            AssertionCmp::GreaterEqual => ">=",
+            AssertionCmp::Less => "<",
+            AssertionCmp::LessEqual => "<=",
+        }
+    }
+}
+
+/// A claim about one element of a model output that is constrained in the circuit, so that eg.
+/// "the fraud score exceeds 0.9" can be proven while the score itself stays private.
+/// Written `output[index] cmp threshold` on the cli, eg. `0[3]>0.9`, where `index` is into the
+/// flattened output.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub struct OutputAssertion {
+    /// the index of the model output
+    pub output: usize,
+    /// the index of the element in the (flattened) output
+    pub index: usize,
+    /// the comparison between the element and the threshold
+    pub cmp: AssertionCmp,
+    /// the threshold, as a float that is quantized at the scale of the output
+    pub threshold: f32,
+}
+
+impl FromStr for OutputAssertion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("invalid assertion `{s}`, expected eg. `0[3]>0.9`");
+        let open = s.find('[').ok_or_else(err)?;
+        let close = s.find(']').ok_or_else(err)?;
+        let output = s[..open].trim().parse().map_err(|_| err())?;
+        let index = s[open + 1..close].trim().parse().map_err(|_| err())?;
+        let rest = s[close + 1..].trim();
+        // the two character comparisons are matched first
+        let cmp = [
+            AssertionCmp::GreaterEqual,
+            AssertionCmp::LessEqual,
+            AssertionCmp::Greater,
+            AssertionCmp::Less,
+        ]
+        .into_iter()
+        .find(|c| rest.starts_with(c.symbol()))
+        .ok_or_else(err)?;
+        let threshold = rest[cmp.symbol().len()..]
+            .trim()
+            .parse()
+            .map_err(|_| err())?;
+        Ok(OutputAssertion {
+            output,
+            index,
+            cmp,
+            threshold,
+        })
+    }
+}
+
+impl fmt::Display for OutputAssertion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}]{}{}",
+            self.output,
+            self.index,
+            self.cmp.symbol(),
+            self.threshold
+        )
+    }
+}
+
+impl OutputAssertion {
+    /// The lookup table the comparison needs
+    pub fn required_lookup(&self) -> LookupOp {
+        match self.cmp {
+            AssertionCmp::Greater | AssertionCmp::Less => cmp::gt_lookup(),
+            AssertionCmp::GreaterEqual | AssertionCmp::LessEqual => cmp::gte_lookup(),
+        }
+    }
+
+    /// The threshold quantized at `scale`, rounded so that the quantized comparison holds only for
+    /// elements whose float value satisfies the assertion
+    pub fn quantized_threshold(&self, scale: crate::Scale) -> Result<i128, Box<dyn Error>> {
+        let threshold = self.threshold as f64 * scale_to_multiplier(scale);
+        // eg. an integer x satisfies x >= t exactly when x >= ceil(t)
+        let threshold = match self.cmp {
+            AssertionCmp::GreaterEqual | AssertionCmp::Less => threshold.ceil(),
+            AssertionCmp::Greater | AssertionCmp::LessEqual => threshold.floor(),
+        };
+        Ok(quantize_float(&threshold, 0.0, 0)?)
+    }
+
+    /// Constrains the assertion to hold on the model `outputs`, which are at `output_scales`
+    pub fn layout(
+        &self,
+        config: &BaseConfig<Fp>,
+        region: &mut RegionCtx<Fp>,
+        outputs: &[ValTensor<Fp>],
+        output_scales: &[crate::Scale],
+    ) -> Result<(), Box<dyn Error>> {
+        let (output, scale) = match (outputs.get(self.output), output_scales.get(self.output)) {
+            (Some(output), Some(scale)) => (output, *scale),
+            _ => return Err(format!("assertion {}: no such output", self).into()),
+        };
+        if self.index >= output.len() {
+            return Err(format!("assertion {}: index out of bounds", self).into());
+        }
+        let mut output = output.clone();
+        output.flatten();
+        let elem = output.get_slice(&[self.index..self.index + 1])?;
+
+        let threshold = self.quantized_threshold(scale)?;
+        let constant =
+            |v: Fp| -> ValTensor<Fp> { Tensor::from([ValType::Constant(v)].into_iter()).into() };
+        let threshold = constant(i128_to_felt(threshold));
+
+        let holds = match self.cmp {
+            AssertionCmp::Greater => cmp::gt(config, region, &elem, &threshold)?,
+            AssertionCmp::GreaterEqual => cmp::gte(config, region, &elem, &threshold)?,
+            AssertionCmp::Less => cmp::lt(config, region, &elem, &threshold)?,
+            AssertionCmp::LessEqual => cmp::lte(config, region, &elem, &threshold)?,
+        };
+        enforce_equality(config, region, &[constant(i128_to_felt(1)), holds])?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "python-bindings")]
+/// Converts OutputAssertion into a PyObject (Required for OutputAssertion to be compatible with Python)
+impl IntoPy<PyObject> for OutputAssertion {
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_string().to_object(py)
+    }
+}
+
+#[cfg(feature = "python-bindings")]
+/// Obtains OutputAssertion from PyObject (Required for OutputAssertion to be compatible with Python)
+impl<'source> FromPyObject<'source> for OutputAssertion {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        let trystr = <PyString as PyTryFrom>::try_from(ob)?;
+        trystr
+            .to_string()
+            .parse()
+            .map_err(|e: String| PyValueError::new_err(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::CheckMode;
+    use crate::tensor::VarTensor;
+    use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::plonk::{Circuit, ConstraintSystem, Error as PlonkError};
+
+    const K: usize = 9;
+    const SCALE: crate::Scale = 7;
+
+    #[derive(Clone)]
+    struct AssertionCircuit {
+        output: i128,
+        assertion: OutputAssertion,
+    }
+
+    impl Circuit<Fp> for AssertionCircuit {
+        type Config = BaseConfig<Fp>;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let advices = (0..3)
+                .map(|_| VarTensor::new_advice(cs, K, 1, 4))
+                .collect::<Vec<_>>();
+            VarTensor::constant_cols(cs, K, 4, false);
+            let mut config = BaseConfig::configure(
+                cs,
+                &[advices[0].clone(), advices[1].clone()],
+                &advices[2],
+                CheckMode::SAFE,
+            );
+            for lookup in [cmp::gt_lookup(), cmp::gte_lookup()] {
+                config
+                    .configure_lookup(
+                        cs,
+                        &advices[0],
+                        &advices[1],
+                        &advices[2],
+                        (-128, 128),
+                        K,
+                        &lookup,
+                    )
+                    .unwrap();
+            }
+            config
+        }
+
+        fn synthesize(
+            &self,
+            mut config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), PlonkError> {
+            config.layout_tables(&mut layouter).unwrap();
+            layouter.assign_region(
+                || "",
+                |region| {
+                    let mut region = RegionCtx::new(region, 0, 1);
+                    let output: ValTensor<Fp> =
+                        Tensor::from([Value::known(i128_to_felt(self.output))].into_iter()).into();
+                    self.assertion
+                        .layout(&config, &mut region, &[output], &[SCALE])
+                        .map_err(|_| PlonkError::Synthesis)
+                },
+            )
+        }
+    }
+
+    fn holds(assertion: &str, output: f64) -> bool {
+        let circuit = AssertionCircuit {
+            output: quantize_float(&output, 0.0, SCALE).unwrap(),
+            assertion: assertion.parse().unwrap(),
+        };
+        MockProver::run(K as u32, &circuit, vec![])
+            .unwrap()
+            .verify()
+            .is_ok()
+    }
+
+    #[test]
+    fn test_assertion_circuit() {
+        // 0.9 is 115.2 at scale 7, the closest output below it is 115 / 128 = 0.8984
+        assert!(holds("0[0]>=0.9", 0.9063));
+        assert!(!holds("0[0]>=0.9", 0.8984));
+        assert!(holds("0[0]>0.9", 0.9063));
+        assert!(!holds("0[0]>0.9", 0.8984));
+        assert!(holds("0[0]<0.9", 0.8984));
+        assert!(!holds("0[0]<0.9", 0.9063));
+        assert!(holds("0[0]<=0.9", 0.8984));
+        assert!(!holds("0[0]<=0.9", 0.9063));
+        // thresholds that quantize exactly
+        assert!(holds("0[0]>=0.5", 0.5));
+        assert!(!holds("0[0]>0.5", 0.5));
+        assert!(holds("0[0]<=-0.5", -0.5));
+        assert!(!holds("0[0]<-0.5", -0.5));
+    }
+
+    #[test]
+    fn test_quantized_threshold() {
+        let threshold = |a: &str| {
+            a.parse::<OutputAssertion>()
+                .unwrap()
+                .quantized_threshold(SCALE)
+        };
+        assert_eq!(threshold("0[0]>=0.9").unwrap(), 116);
+        assert_eq!(threshold("0[0]<0.9").unwrap(), 116);
+        assert_eq!(threshold("0[0]>0.9").unwrap(), 115);
+        assert_eq!(threshold("0[0]<=0.9").unwrap(), 115);
+        assert_eq!(threshold("0[0]>=-0.9").unwrap(), -115);
+        assert_eq!(threshold("0[0]>-0.9").unwrap(), -116);
+    }
+
+    #[test]
+    fn test_parse_assertion() {
+        let a: OutputAssertion = "0[3]>0.9".parse().unwrap();
+        assert_eq!(
+            a,
+            OutputAssertion {
+                output: 0,
+                index: 3,
+                cmp: AssertionCmp::Greater,
+                threshold: 0.9,
+            }
+        );
+        assert_eq!(a.to_string(), "0[3]>0.9");
+
+        let a: OutputAssertion = "1[0] <= -2".parse().unwrap();
+        assert_eq!(a.cmp, AssertionCmp::LessEqual);
+        assert_eq!(a.threshold, -2.0);
+
+        assert!("0>0.9".parse::<OutputAssertion>().is_err());
+        assert!("0[3]=0.9".parse::<OutputAssertion>().is_err());
+    }
+}
@@ -450,8 +450,23 @@ impl GraphData {
         }
     }
 
-    /// Load the model input from a file
+    /// Export the model input as json
+    pub fn as_json(&self) -> Result<String, Box<dyn std::error::Error>> {
+        serde_json::to_string(&self).map_err(|e| e.into())
+    }
+
+    /// Parse the model input from a json string
+    pub fn from_json(arg_json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(arg_json)
+    }
+
+    /// Load the model input from a file, .npy and .npz files are read with [GraphData::from_npy] and [GraphData::from_npz]
     pub fn from_path(path: std::path::PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("npy") => return Self::from_npy(&[path]),
+            Some("npz") => return Self::from_npz(path),
+            _ => {}
+        }
         let mut file = std::fs::File::open(path.clone())
             .map_err(|_| format!("failed to open input at {}", path.display()))?;
         let mut data = String::new();
@@ -459,6 +474,31 @@ impl GraphData {
         serde_json::from_str(&data).map_err(|e| e.into())
     }
 
+    /// Load the model inputs from .npy files (as written by `np.save`), one file per model input
+    pub fn from_npy(paths: &[std::path::PathBuf]) -> Result<Self, Box<dyn std::error::Error>> {
+        let input_data = paths
+            .iter()
+            .map(|path| {
+                let array = crate::io::load_npy(path)
+                    .map_err(|e| format!("failed to load {}: {}", path.display(), e))?;
+                Ok(array.data.into_iter().map(FileSourceInner::Float).collect())
+            })
+            .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+        Ok(GraphData::new(DataSource::File(input_data)))
+    }
+
+    /// Load the model inputs from a .npz archive (as written by `np.savez`), one array per model input,
+    /// in the order the arrays were saved in.
+    pub fn from_npz(path: std::path::PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
+        let arrays = crate::io::load_npz(&path)
+            .map_err(|e| format!("failed to load {}: {}", path.display(), e))?;
+        let input_data = arrays
+            .into_iter()
+            .map(|(_, array)| array.data.into_iter().map(FileSourceInner::Float).collect())
+            .collect();
+        Ok(GraphData::new(DataSource::File(input_data)))
+    }
+
     /// Save the model input to a file
     pub fn save(&self, path: std::path::PathBuf) -> Result<(), Box<dyn std::error::Error>> {
         serde_json::to_writer(std::fs::File::create(path)?, &self).map_err(|e| e.into())
@@ -529,6 +569,60 @@ impl GraphData {
 
         Ok(batches)
     }
+
+    /// Stacks several single-sample inputs into one batched input, the inverse of
+    /// [GraphData::split_into_batches]. Proving the result with the `batch_size` variable set
+    /// to `samples.len()` lays every sample out at successive offsets in the same circuit, so
+    /// the parameters are only assigned once for the whole batch.
+    pub fn merge_batches(samples: &[GraphData]) -> Result<Self, Box<dyn std::error::Error>> {
+        let as_file = |source: &DataSource| match source {
+            DataSource::File(data) => Ok(data.clone()),
+            _ => Err(Box::new(GraphError::InvalidDims(
+                0,
+                "only file data sources can be batched".to_string(),
+            ))),
+        };
+
+        if samples.is_empty() {
+            return Err(Box::new(GraphError::InvalidDims(
+                0,
+                "cannot batch an empty set of samples".to_string(),
+            )));
+        }
+
+        let merge = |sources: Vec<FileSource>| -> Result<FileSource, Box<dyn std::error::Error>> {
+            let num_inputs = sources[0].len();
+            if sources.iter().any(|s| s.len() != num_inputs) {
+                return Err(Box::new(GraphError::InvalidDims(
+                    0,
+                    "all samples must have the same number of inputs".to_string(),
+                )));
+            }
+            Ok((0..num_inputs)
+                .map(|i| sources.iter().flat_map(|s| s[i].clone()).collect())
+                .collect())
+        };
+
+        let inputs = samples
+            .iter()
+            .map(|s| as_file(&s.input_data))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let output_data = if samples.iter().all(|s| s.output_data.is_some()) {
+            let outputs = samples
+                .iter()
+                .map(|s| as_file(s.output_data.as_ref().unwrap()))
+                .collect::<Result<Vec<_>, _>>()?;
+            Some(DataSource::File(merge(outputs)?))
+        } else {
+            None
+        };
+
+        Ok(GraphData {
+            input_data: DataSource::File(merge(inputs)?),
+            output_data,
+        })
+    }
 }
 
 #[cfg(feature = "python-bindings")]
@@ -632,6 +726,47 @@ mod tests {
         assert_eq!(graph_input3, file);
     }
 
+    #[test]
+    fn test_merge_batches_round_trip() {
+        let samples = vec![
+            GraphData::new(DataSource::from(vec![vec![1.0, 2.0], vec![3.0]])),
+            GraphData::new(DataSource::from(vec![vec![4.0, 5.0], vec![6.0]])),
+        ];
+
+        let batched = GraphData::merge_batches(&samples).unwrap();
+        assert_eq!(
+            batched.input_data,
+            DataSource::from(vec![vec![1.0, 2.0, 4.0, 5.0], vec![3.0, 6.0]])
+        );
+        assert_eq!(batched.output_data, None);
+
+        let split = batched
+            .split_into_batches(vec![vec![1, 2], vec![1]])
+            .unwrap();
+        assert_eq!(split, samples);
+    }
+
+    #[test]
+    // older input files also carried the input shapes, these should still load
+    fn test_graph_input_legacy_shape_field() {
+        const JSON: &str =
+            r#"{"input_data":[[1.0,2.0,3.0,4.0]],"input_shape":[[2,2]],"output_data":[[0.5]]}"#;
+
+        let graph_input = GraphData::from_json(JSON).unwrap();
+
+        assert_eq!(
+            graph_input.input_data,
+            DataSource::from(vec![vec![1.0, 2.0, 3.0, 4.0]])
+        );
+        assert_eq!(
+            graph_input.output_data,
+            Some(DataSource::from(vec![vec![0.5]]))
+        );
+
+        let round_trip = GraphData::from_json(&graph_input.as_json().unwrap()).unwrap();
+        assert_eq!(round_trip, graph_input);
+    }
+
     //  test for the compatibility with the serialized elements from the mclbn256 library
     #[test]
     fn test_python_compat() {
@@ -1,15 +1,22 @@
+/// In-circuit assertions on the model outputs
+pub mod assertion;
 /// Representations of a computational graph's inputs.
 pub mod input;
 /// Crate for defining a computational graph and building a ZK-circuit from it.
 pub mod model;
 /// Representations of a computational graph's modules.
 pub mod modules;
+/// Circuits laying out several forward passes of a model that share its weights
+pub mod multipass;
 /// Inner elements of a computational graph that represent a single operation / constraints.
 pub mod node;
+/// Circuits proving properties of a model over pairs of forward passes
+pub mod property;
 /// Helper functions
 pub mod utilities;
 /// Representations of a computational graph's variables.
 pub mod vars;
+pub use assertion::*;
 #[cfg(not(target_arch = "wasm32"))]
 use colored_json::ToColoredJson;
 use halo2_proofs::plonk::VerifyingKey;
@@ -37,7 +44,9 @@ use halo2curves::bn256::{self, Bn256, Fr as Fp, G1Affine};
 use halo2curves::ff::PrimeField;
 use log::{debug, error, info, trace};
 pub use model::*;
+pub use multipass::*;
 pub use node::*;
+pub use property::*;
 #[cfg(feature = "python-bindings")]
 use pyo3::prelude::*;
 #[cfg(feature = "python-bindings")]
@@ -171,6 +180,14 @@ impl GraphWitness {
         commitments
     }
 
+    /// Get the poseidon digests of the inputs (if the inputs are hashed), these are the values
+    /// an external system can use to bind a proof to a known data commitment
+    pub fn get_input_hashes(&self) -> Option<Vec<Fp>> {
+        self.processed_inputs
+            .as_ref()
+            .and_then(|processed_inputs| processed_inputs.poseidon_hash.clone())
+    }
+
     /// Export the ezkl witness as json
     pub fn as_json(&self) -> Result<String, Box<dyn std::error::Error>> {
         let serialized = match serde_json::to_string(&self) {
@@ -438,6 +455,192 @@ impl GraphSettings {
     pub fn uses_modules(&self) -> bool {
         !self.module_sizes.max_constraints() > 0
     }
+
+    /// The instances the claimed values of the model inputs contribute to a proof, these come first in
+    /// [Self::public_instances]
+    pub fn input_instances(
+        &self,
+        inputs: &[Vec<f32>],
+    ) -> Result<Vec<Fp>, Box<dyn std::error::Error>> {
+        visibility_instances(
+            &self.run_args.input_visibility,
+            inputs,
+            &self.model_input_scales,
+        )
+    }
+
+    /// The instances the claimed values of the model outputs contribute to a proof, these come last in
+    /// [Self::public_instances]
+    pub fn output_instances(
+        &self,
+        outputs: &[Vec<f32>],
+    ) -> Result<Vec<Fp>, Box<dyn std::error::Error>> {
+        visibility_instances(
+            &self.run_args.output_visibility,
+            outputs,
+            &self.model_output_scales,
+        )
+    }
+
+    /// Checks revealed outputs against the digest a proof committed to. With a hashed/public output
+    /// visibility a large output (eg. a generated image) is only exposed as its poseidon hash, which keeps
+    /// the instances small, and the output itself can be handed over (and checked) separately.
+    pub fn verify_revealed_outputs(
+        &self,
+        instances: &[Fp],
+        outputs: &[Vec<f32>],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.run_args.output_visibility.is_hashed_public() {
+            return Err(
+                "the proof doesn't commit to its outputs, they need a hashed/public visibility"
+                    .into(),
+            );
+        }
+        let expected = self.output_instances(outputs)?;
+        // the output hashes are the last instances of the proof
+        if instances.len() < expected.len()
+            || instances[instances.len() - expected.len()..] != expected[..]
+        {
+            return Err(
+                "the revealed outputs don't match the digest committed to in the proof".into(),
+            );
+        }
+        Ok(())
+    }
+
+    /// The poseidon hashes of the model inputs and of the model outputs among the `instances` of a proof, empty
+    /// when they aren't hashed/public. The instances of the inputs come first and those of the outputs last (see
+    /// [GraphCircuit::prepare_public_inputs]), with a hash per input or output, or per overwritten outlet.
+    pub fn hashed_instances<'a>(
+        &self,
+        instances: &'a [Fp],
+    ) -> Result<(&'a [Fp], &'a [Fp]), Box<dyn std::error::Error>> {
+        let num_hashes = |visibility: &Visibility, num_tensors: usize| {
+            if !visibility.is_hashed_public() {
+                0
+            } else if visibility.overwrites_inputs().is_empty() {
+                num_tensors
+            } else {
+                visibility.overwrites_inputs().len()
+            }
+        };
+        let num_inputs = num_hashes(
+            &self.run_args.input_visibility,
+            self.model_input_scales.len(),
+        );
+        let num_outputs = num_hashes(
+            &self.run_args.output_visibility,
+            self.model_output_scales.len(),
+        );
+        if instances.len() < num_inputs + num_outputs {
+            return Err(format!(
+                "expected {} input and {} output hashes, the proof has {} instances",
+                num_inputs,
+                num_outputs,
+                instances.len()
+            )
+            .into());
+        }
+        Ok((
+            &instances[..num_inputs],
+            &instances[instances.len() - num_outputs..],
+        ))
+    }
+
+    /// Checks that a proof of these settings, with `instances`, outputs the activations consumed by a proof of
+    /// the `next` chunk of a split model (see [Model::split_at]): the hashes of its outputs have to be the hashes
+    /// of the inputs of the next proof.
+    pub fn check_chained(
+        &self,
+        instances: &[Fp],
+        next: &GraphSettings,
+        next_instances: &[Fp],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.run_args.output_visibility.is_hashed_public()
+            || !next.run_args.input_visibility.is_hashed_public()
+        {
+            return Err("the proofs do not commit to the activations they exchange".into());
+        }
+        let (_, outputs) = self.hashed_instances(instances)?;
+        let (inputs, _) = next.hashed_instances(next_instances)?;
+        if outputs != inputs {
+            return Err("the activation commitments do not match".into());
+        }
+        Ok(())
+    }
+
+    /// Builds the instances a proof of this circuit is verified against from the claimed (floating
+    /// point) values of the model inputs and outputs, in the order the circuit lays out its instance
+    /// columns: the public inputs (or their hashes) come first and the public outputs (or their hashes) last.
+    /// Values are quantized with the scales recorded in the settings, so claims that were read back from a
+    /// witness produce exactly the instances of its proof.
+    pub fn public_instances(
+        &self,
+        inputs: &[Vec<f32>],
+        outputs: &[Vec<f32>],
+    ) -> Result<Vec<Fp>, Box<dyn std::error::Error>> {
+        if self.run_args.param_visibility.requires_processing() {
+            return Err(
+                "param hashes and commitments can only be computed from the compiled circuit"
+                    .into(),
+            );
+        }
+        let mut instances = self.input_instances(inputs)?;
+        instances.extend(self.output_instances(outputs)?);
+        debug!("public instances: {:?}", instances);
+        Ok(instances)
+    }
+}
+
+/// Mirrors [GraphCircuit::prepare_public_inputs] for a single set of claimed values
+fn visibility_instances(
+    visibility: &Visibility,
+    values: &[Vec<f32>],
+    scales: &[crate::Scale],
+) -> Result<Vec<Fp>, Box<dyn std::error::Error>> {
+    if !visibility.is_public() && !visibility.requires_processing() {
+        return Ok(vec![]);
+    }
+    if values.len() != scales.len() {
+        return Err(format!(
+            "expected {} tensors of claimed values, got {}",
+            scales.len(),
+            values.len()
+        )
+        .into());
+    }
+    let quantized = values
+        .iter()
+        .zip(scales)
+        .map(|(v, scale)| {
+            v.iter()
+                .map(|x| {
+                    quantize_float(&(*x as f64), 0.0, *scale)
+                        .map(crate::fieldutils::i128_to_felt::<Fp>)
+                })
+                .collect::<Result<Tensor<Fp>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if visibility.is_public() {
+        Ok(quantized.into_iter().flatten().collect())
+    } else if visibility.is_hashed() {
+        let outlets = visibility.overwrites_inputs();
+        let hashed = if outlets.is_empty() {
+            quantized
+        } else {
+            outlets.iter().map(|o| quantized[*o].clone()).collect()
+        };
+        let res = GraphModules::forward(&hashed, visibility.clone(), None, None)?;
+        Ok(res.get_instances().into_iter().flatten().collect())
+    } else {
+        // elgamal ciphertexts and kzg commitments depend on randomness and keys only the prover has
+        Err(format!(
+            "{} values can't be rebuilt from their claimed values",
+            visibility
+        )
+        .into())
+    }
 }
 
 /// Configuration for a computational graph / model loaded from a `.onnx` file.
@@ -480,6 +683,21 @@ impl GraphCircuit {
     pub fn model(&self) -> &Model {
         &self.core.model
     }
+
+    /// A keccak256 hash (hex encoded) of the structure of the compiled circuit: its ops, shapes, scales and
+    /// visibilities, and its settings, but not the values of its weights. Verifiers can use it to check which
+    /// model architecture a verifying key was generated for.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn fingerprint(&self) -> Result<String, Box<dyn std::error::Error>> {
+        // the version of ezkl doesn't change the circuit, the artifact headers record it
+        let settings = GraphSettings {
+            version: String::new(),
+            ..self.settings().clone()
+        };
+        let encoded = bincode::serialize(&(self.model().without_weights(), settings))?;
+        Ok(hex::encode(ethers::utils::keccak256(encoded)))
+    }
+
     ///
     pub fn save(&self, path: std::path::PathBuf) -> Result<(), Box<dyn std::error::Error>> {
         let f = std::fs::File::create(path)?;
@@ -926,6 +1144,44 @@ impl GraphCircuit {
         self.calc_min_logrows(&res, max_logrows)
     }
 
+    /// Runs a "dry run" forward pass of the model outside of the constraint system (skipping any hashing, encryption or commitments),
+    /// returning the quantized predictions. Useful to validate quantization accuracy before proving.
+    pub fn execute(
+        &self,
+        inputs: &[Tensor<Fp>],
+    ) -> Result<Vec<Tensor<i128>>, Box<dyn std::error::Error>> {
+        let model_results = self.model().forward(inputs)?;
+        Ok(model_results
+            .outputs
+            .iter()
+            .map(|t| t.map(crate::fieldutils::felt_to_i128))
+            .collect())
+    }
+
+    /// Runs a dry run of the model on `inputs` and compares each output against a floating point `reference` output.
+    pub fn compare_to_reference(
+        &self,
+        inputs: &[Tensor<Fp>],
+        reference: &[Vec<f32>],
+    ) -> Result<Vec<AccuracyReport>, Box<dyn std::error::Error>> {
+        let outputs = self.execute(inputs)?;
+        if reference.len() != outputs.len() {
+            return Err(format!(
+                "got {} reference outputs but the model has {} outputs",
+                reference.len(),
+                outputs.len()
+            )
+            .into());
+        }
+        let output_scales = self.model().graph.get_output_scales();
+        outputs
+            .iter()
+            .zip(reference)
+            .zip(output_scales)
+            .map(|((output, reference), scale)| accuracy_report(reference, output, scale))
+            .collect()
+    }
+
     /// Runs the forward pass of the model / graph of computations and any associated hashing.
     pub fn forward(
         &self,
@@ -1026,6 +1282,19 @@ impl GraphCircuit {
         Ok(witness)
     }
 
+    /// Splits `model` at node `idx` (see [Model::split_at]) and builds the circuits of the two chunks
+    pub fn split(
+        model: &Model,
+        idx: usize,
+        run_args: &RunArgs,
+    ) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
+        model
+            .split_at(idx, run_args)?
+            .into_iter()
+            .map(|(chunk, args)| Self::new(chunk, &args))
+            .collect()
+    }
+
     /// Create a new circuit from a set of input data and [RunArgs].
     #[cfg(not(target_arch = "wasm32"))]
     pub fn from_run_args(
@@ -1047,6 +1316,51 @@ impl GraphCircuit {
         Self::new_from_settings(model, params.clone(), check_mode)
     }
 
+    /// Renders the layout of the circuit to a .png file. The halo2 column / row layout is drawn on
+    /// the left and the rows occupied by each layer of the model, labeled by node and op, on the right.
+    /// For an overview of how to interpret the halo2 layout, see https://zcash.github.io/halo2/user/dev-tools.html
+    #[cfg(feature = "render")]
+    pub fn render_layout(&self, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+        use plotters::prelude::*;
+
+        let run_args = &self.settings().run_args;
+        let reports = self.model().layer_reports(run_args)?;
+
+        let root = BitMapBackend::new(path, (1024, 512)).into_drawing_area();
+        root.fill(&WHITE)?;
+        let root = root.titled("Layout", ("sans-serif", 20))?;
+        let (layout_area, layer_area) = root.split_horizontally(512);
+
+        halo2_proofs::dev::CircuitLayout::default()
+            // We hide labels, else most circuits become impossible to decipher because of overlaid text
+            .show_labels(false)
+            .render(run_args.logrows, self, &layout_area)?;
+
+        // layers are laid out one after the other in the model region, so each occupies the rows
+        // directly following those of the previous layer. we scale to the full 2^k rows so the
+        // layers line up with the halo2 layout.
+        let total_rows = 1usize << run_args.logrows;
+        let (width, height) = layer_area.dim_in_pixel();
+        let to_pixel = |row: usize| (row * height as usize / total_rows) as i32;
+        let mut start = 0;
+        for (i, report) in reports.iter().filter(|r| r.rows > 0).enumerate() {
+            let (top, bottom) = (to_pixel(start), to_pixel(start + report.rows));
+            layer_area.draw(&Rectangle::new(
+                [(0, top), (width as i32, bottom)],
+                Palette99::pick(i).mix(0.6).filled(),
+            ))?;
+            layer_area.draw(&Text::new(
+                format!("{}: {} ({})", report.idx, report.name, report.opkind),
+                (5, top),
+                ("sans-serif", 12),
+            ))?;
+            start += report.rows;
+        }
+
+        root.present()?;
+        Ok(())
+    }
+
     ///
     #[cfg(not(target_arch = "wasm32"))]
     pub async fn populate_on_chain_test_data(
@@ -1185,6 +1499,7 @@ impl Circuit<Fp> for GraphCircuit {
             params.run_args.num_inner_cols,
             params.total_const_size,
             params.uses_modules(),
+            params.run_args.unblinded_advice,
         );
 
         module_configs.configure_complex_modules(cs, visibility, params.module_sizes.clone());
@@ -1403,3 +1718,269 @@ impl Circuit<Fp> for GraphCircuit {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_public_instances() {
+        let settings = GraphSettings {
+            run_args: RunArgs {
+                input_visibility: Visibility::Public,
+                output_visibility: Visibility::Public,
+                ..Default::default()
+            },
+            model_input_scales: vec![1, 2],
+            model_output_scales: vec![0],
+            ..Default::default()
+        };
+        let instances = settings
+            .public_instances(&[vec![0.5, 1.0], vec![0.25]], &[vec![-3.0]])
+            .unwrap();
+        let expected = [1, 2, 1, -3].map(crate::fieldutils::i128_to_felt::<Fp>);
+        assert_eq!(instances, expected);
+
+        // the claimed values have to match the shapes of the model
+        assert!(settings
+            .public_instances(&[vec![0.5]], &[vec![1.0]])
+            .is_err());
+
+        let settings = GraphSettings {
+            run_args: RunArgs {
+                output_visibility: Visibility::Encrypted,
+                ..settings.run_args
+            },
+            ..settings
+        };
+        assert!(settings
+            .public_instances(&[vec![0.5, 1.0], vec![0.25]], &[vec![-3.0]])
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_revealed_outputs() {
+        let settings = GraphSettings {
+            run_args: RunArgs {
+                input_visibility: Visibility::Public,
+                output_visibility: Visibility::from("hashed"),
+                ..Default::default()
+            },
+            model_input_scales: vec![0],
+            model_output_scales: vec![2],
+            ..Default::default()
+        };
+        let image = vec![0.25, 0.5, 0.75, 1.0];
+        let instances = settings
+            .public_instances(&[vec![1.0]], &[image.clone()])
+            .unwrap();
+        // the input and a single hash for the output
+        assert_eq!(instances.len(), 2);
+
+        assert!(settings
+            .verify_revealed_outputs(&instances, &[image.clone()])
+            .is_ok());
+        let mut tampered = image;
+        tampered[0] = 0.5;
+        assert!(settings
+            .verify_revealed_outputs(&instances, &[tampered])
+            .is_err());
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let circuit = |values: &[f32], dims: &[usize]| {
+            let raw = Tensor::new(Some(values), dims).unwrap();
+            let quantized = raw.map(|x| crate::fieldutils::i128_to_felt::<Fp>(x as i128));
+            let node = Node {
+                opkind: SupportedOp::Constant(crate::circuit::Constant::new(quantized, raw)),
+                out_scale: 0,
+                inputs: vec![],
+                out_dims: dims.to_vec(),
+                idx: 0,
+                num_uses: 1,
+                name: "weights".into(),
+            };
+            let mut circuit = GraphCircuit::default();
+            circuit
+                .core
+                .model
+                .graph
+                .nodes
+                .insert(0, NodeType::Node(node));
+            circuit
+        };
+
+        let fingerprint = circuit(&[1.0, 2.0], &[2]).fingerprint().unwrap();
+        // the weights don't change the fingerprint
+        assert_eq!(
+            fingerprint,
+            circuit(&[3.0, 4.0], &[2]).fingerprint().unwrap()
+        );
+        // their shapes do
+        assert_ne!(
+            fingerprint,
+            circuit(&[1.0, 2.0], &[1, 2]).fingerprint().unwrap()
+        );
+
+        // and so does every run arg
+        let run_args: [fn(&mut RunArgs); 4] = [
+            |r| r.param_scale += 1,
+            |r| r.abs_tolerance = 0.5,
+            |r| r.unblinded_advice = true,
+            |r| r.output_assertions = vec!["0[0]>0.9".parse().unwrap()],
+        ];
+        for change in run_args {
+            let mut changed = circuit(&[1.0, 2.0], &[2]);
+            change(&mut changed.settings_mut().run_args);
+            assert_ne!(fingerprint, changed.fingerprint().unwrap());
+        }
+        let mut threshold = circuit(&[1.0, 2.0], &[2]);
+        threshold.settings_mut().run_args.output_assertions = vec!["0[0]>0.1".parse().unwrap()];
+        let mut other_threshold = threshold.clone();
+        other_threshold.settings_mut().run_args.output_assertions =
+            vec!["0[0]>0.9".parse().unwrap()];
+        assert_ne!(
+            threshold.fingerprint().unwrap(),
+            other_threshold.fingerprint().unwrap()
+        );
+        // but not the version of ezkl
+        let mut upgraded = circuit(&[1.0, 2.0], &[2]);
+        upgraded.settings_mut().version = "0.0.0".into();
+        assert_eq!(fingerprint, upgraded.fingerprint().unwrap());
+    }
+
+    #[test]
+    fn test_execute() {
+        let mut circuit = GraphCircuit::default();
+        circuit.core.model = model::tests::scaled_model(&[2.0, -3.0]);
+        let input = Tensor::new(
+            Some(&[1, 2].map(crate::fieldutils::i128_to_felt::<Fp>)),
+            &[2],
+        )
+        .unwrap();
+        let outputs = circuit.execute(&[input.clone()]).unwrap();
+        assert_eq!(outputs, vec![Tensor::new(Some(&[2, -6]), &[2]).unwrap()]);
+
+        let reports = circuit
+            .compare_to_reference(&[input.clone()], &[vec![2.5, -6.0]])
+            .unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].max_error, 0.5);
+        // every model output needs a reference
+        assert!(circuit.compare_to_reference(&[input.clone()], &[]).is_err());
+        assert!(circuit
+            .compare_to_reference(&[input], &[vec![2.0, -6.0], vec![1.0]])
+            .is_err());
+    }
+
+    #[test]
+    fn test_unblinded_advice() {
+        use crate::pfsys::TranscriptType;
+        use halo2_proofs::poly::commitment::ParamsProver;
+        use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+        use halo2_proofs::poly::kzg::strategy::SingleStrategy;
+
+        let logrows = 10;
+        let params = crate::pfsys::srs::gen_srs::<KZGCommitmentScheme<Bn256>>(logrows);
+        // returns the satisfied circuit of `input * [2, -3]` and two proofs of it
+        let prove_twice = |unblinded_advice: bool| {
+            let run_args = RunArgs {
+                logrows,
+                unblinded_advice,
+                ..Default::default()
+            };
+            let mut circuit =
+                GraphCircuit::new(model::tests::scaled_model(&[2.0, -3.0]), &run_args).unwrap();
+            let witness = circuit
+                .forward(&mut [model::tests::felts(&[1, 2])], None, None)
+                .unwrap();
+            circuit.load_graph_witness(&witness).unwrap();
+            let instances = circuit.prepare_public_inputs(&witness).unwrap();
+            halo2_proofs::dev::MockProver::run(logrows, &circuit, vec![instances.clone()])
+                .unwrap()
+                .assert_satisfied();
+
+            let pk =
+                crate::pfsys::create_keys::<KZGCommitmentScheme<Bn256>, Fp, _>(&circuit, &params)
+                    .unwrap();
+            [(); 2].map(|_| {
+                let proof = crate::pfsys::create_proof_circuit_kzg(
+                    circuit.clone(),
+                    &params,
+                    Some(instances.clone()),
+                    &pk,
+                    TranscriptType::EVM,
+                    SingleStrategy::new(&params),
+                    CheckMode::SAFE,
+                    None,
+                )
+                .unwrap();
+                assert!(crate::pfsys::verify_proof_circuit_kzg(
+                    params.verifier_params(),
+                    proof.clone(),
+                    pk.get_vk(),
+                    SingleStrategy::new(params.verifier_params()),
+                )
+                .is_ok());
+                proof.proof
+            })
+        };
+
+        // the proof starts with the commitment to the first advice column of the model, which is
+        // the same for every proof of a witness once it is unblinded
+        let [first, second] = prove_twice(true);
+        assert_eq!(first[..32], second[..32]);
+        let [first, second] = prove_twice(false);
+        assert_ne!(first[..32], second[..32]);
+    }
+
+    #[test]
+    fn test_split_chain() {
+        let model = model::tests::two_layer_model(&[2.0, -1.0], &[3.0, 3.0]);
+        let run_args = RunArgs {
+            logrows: 12,
+            ..Default::default()
+        };
+        let chunks = GraphCircuit::split(&model, 2, &run_args).unwrap();
+
+        // mock proves a chunk on `input`, returning its output and instances
+        let prove = |circuit: &GraphCircuit, input: Tensor<Fp>| {
+            let mut circuit = circuit.clone();
+            let witness = circuit.forward(&mut [input], None, None).unwrap();
+            circuit.load_graph_witness(&witness).unwrap();
+            let instances = circuit.prepare_public_inputs(&witness).unwrap();
+            let prover = halo2_proofs::dev::MockProver::run(
+                run_args.logrows,
+                &circuit,
+                vec![instances.clone()],
+            )
+            .unwrap();
+            prover.assert_satisfied();
+            (
+                Tensor::from(witness.outputs[0].clone().into_iter()),
+                instances,
+            )
+        };
+
+        let (activations, head) = prove(&chunks[0], model::tests::felts(&[1, 2]));
+        let (outputs, tail) = prove(&chunks[1], activations);
+        assert_eq!(outputs, model::tests::felts(&[6, -6]));
+        assert!(chunks[0]
+            .settings()
+            .check_chained(&head, chunks[1].settings(), &tail)
+            .is_ok());
+
+        // a tail proven on other activations doesn't chain
+        let (_, forged) = prove(&chunks[1], model::tests::felts(&[2, 2]));
+        assert!(chunks[0]
+            .settings()
+            .check_chained(&head, chunks[1].settings(), &forged)
+            .is_err());
+        // nor do proofs which don't commit to their activations
+        assert!(chunks[1]
+            .settings()
+            .check_chained(&tail, chunks[0].settings(), &head)
+            .is_err());
+    }
+}
@@ -5,14 +5,17 @@ use super::vars::*;
 use super::GraphError;
 use super::GraphSettings;
 use crate::circuit::hybrid::HybridOp;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::circuit::poly::PolyOp;
 use crate::circuit::region::RegionCtx;
 use crate::circuit::Input;
 use crate::circuit::InputType;
 use crate::circuit::Unknown;
-use crate::fieldutils::felt_to_i128;
+use crate::error::EzklError;
+use crate::fieldutils::{felt_fits_i128, felt_to_i128, i128_to_felt};
 use crate::{
     circuit::{lookup::LookupOp, BaseConfig as PolyConfig, CheckMode, Op},
-    tensor::{Tensor, ValTensor},
+    tensor::{Tensor, TensorError, ValTensor},
     RunArgs,
 };
 use halo2curves::bn256::Fr as Fp;
@@ -21,6 +24,7 @@ use halo2curves::bn256::Fr as Fp;
 use colored::Colorize;
 use halo2_proofs::{
     circuit::{Layouter, Value},
+    dev::{metadata, FailureLocation, VerifyFailure},
     plonk::ConstraintSystem,
 };
 use itertools::Itertools;
@@ -62,6 +66,118 @@ pub struct ForwardResult {
     pub min_lookup_inputs: i128,
 }
 
+/// The divergence between the outputs of the same node in two versions of a model.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LayerDivergence {
+    /// The index of the node in the graph
+    pub idx: usize,
+    /// The operation the node performs
+    pub opkind: String,
+    /// The largest absolute difference between the (dequantized) node outputs
+    pub max_abs_error: f64,
+    /// The mean absolute difference between the (dequantized) node outputs
+    pub mean_abs_error: f64,
+}
+
+/// The circuit resources used by a single (top level) node of a model, as measured by a dummy layout.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct LayerReport {
+    /// The index of the node in the graph
+    pub idx: usize,
+    /// The name of the layer the node was created from
+    pub name: String,
+    /// The operation the node performs
+    pub opkind: String,
+    /// The first row (within the model region) laid out by the node
+    pub start_row: usize,
+    /// The number of gates (selectors) enabled by the node
+    pub gates: usize,
+    /// The number of advice cells assigned by the node
+    pub advice_cells: usize,
+    /// The number of advice columns (per input / output var) the node's rows span
+    pub advice_columns: usize,
+    /// The number of rows looked up in the node's lookup tables
+    pub lookup_rows: usize,
+    /// The number of constants (fixed cells) assigned by the node
+    pub constants: usize,
+    /// The estimated height of the node's region
+    pub rows: usize,
+}
+
+impl LayerReport {
+    /// Returns true if the node laid out `row` of the model region
+    pub fn contains_row(&self, row: usize) -> bool {
+        row >= self.start_row && row < self.start_row + self.rows
+    }
+}
+
+/// The name of the region the nodes of a model are laid out in
+pub const MODEL_REGION: &str = "model";
+
+/// Finds the layer (of the model last laid out on this thread) a [halo2_proofs::dev::MockProver]
+/// failure in the model region originates from
+pub fn failure_layer<'a>(
+    failure: &VerifyFailure,
+    reports: &'a [LayerReport],
+) -> Option<&'a LayerReport> {
+    let (region, offset) = match failure {
+        VerifyFailure::CellNotAssigned { region, offset, .. } => {
+            (region, usize::try_from(*offset).ok()?)
+        }
+        VerifyFailure::ConstraintNotSatisfied { location, .. }
+        | VerifyFailure::Lookup { location, .. }
+        | VerifyFailure::Permutation { location, .. } => match location {
+            FailureLocation::InRegion { region, offset } => (region, *offset),
+            FailureLocation::OutsideRegion { .. } => return None,
+        },
+        _ => return None,
+    };
+    let model_region = crate::circuit::modules::region_index(MODEL_REGION)?;
+    if *region != metadata::Region::from((model_region, MODEL_REGION)) {
+        return None;
+    }
+    reports.iter().find(|r| r.contains_row(offset))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl tabled::Tabled for LayerReport {
+    const LENGTH: usize = 10;
+
+    fn headers() -> Vec<std::borrow::Cow<'static, str>> {
+        let mut headers = Vec::with_capacity(Self::LENGTH);
+        for i in [
+            "idx",
+            "name",
+            "opkind",
+            "start_row",
+            "gates",
+            "advice_cells",
+            "advice_columns",
+            "lookup_rows",
+            "constants",
+            "rows",
+        ] {
+            headers.push(std::borrow::Cow::Borrowed(i));
+        }
+        headers
+    }
+
+    fn fields(&self) -> Vec<std::borrow::Cow<'_, str>> {
+        let mut fields = Vec::with_capacity(Self::LENGTH);
+        fields.push(std::borrow::Cow::Owned(self.idx.to_string()));
+        fields.push(std::borrow::Cow::Owned(self.name.clone()));
+        fields.push(std::borrow::Cow::Owned(self.opkind.clone()));
+        fields.push(std::borrow::Cow::Owned(self.start_row.to_string()));
+        fields.push(std::borrow::Cow::Owned(self.gates.to_string()));
+        fields.push(std::borrow::Cow::Owned(self.advice_cells.to_string()));
+        fields.push(std::borrow::Cow::Owned(self.advice_columns.to_string()));
+        fields.push(std::borrow::Cow::Owned(self.lookup_rows.to_string()));
+        fields.push(std::borrow::Cow::Owned(self.constants.to_string()));
+        fields.push(std::borrow::Cow::Owned(self.rows.to_string()));
+        fields
+    }
+}
+
 /// A circuit configuration for the entirety of a model loaded from an Onnx file.
 #[derive(Clone, Debug)]
 pub struct ModelConfig {
@@ -173,6 +289,52 @@ fn output_state_idx(output_mappings: &[Vec<OutputMapping>]) -> Vec<usize> {
         .collect::<Vec<_>>()
 }
 
+/// The check between the computed and the claimed (public or fixed) outputs at `scale`, within the
+/// absolute tolerance if one is set and else within the percentage tolerance
+fn output_check(run_args: &RunArgs, scale: crate::Scale) -> HybridOp {
+    if run_args.abs_tolerance > 0.0 {
+        HybridOp::AbsRangeCheck {
+            tol: run_args.abs_tolerance.into(),
+            scale: scale_to_multiplier(scale).into(),
+        }
+    } else {
+        let mut tolerance = run_args.tolerance;
+        tolerance.scale = scale_to_multiplier(scale).into();
+        HybridOp::RangeCheck(tolerance)
+    }
+}
+
+/// Zeroes the weights embedded in `op` (keeping their shapes and visibilities), see [Model::without_weights]
+fn zero_weights(op: &mut SupportedOp) {
+    use crate::circuit::poly::PolyOp;
+    let zero = |t: &mut Tensor<Fp>| t.iter_mut().for_each(|x| *x = i128_to_felt(0));
+    match op {
+        SupportedOp::Linear(PolyOp::Conv { kernel, bias, .. })
+        | SupportedOp::Linear(PolyOp::Im2ColConv { kernel, bias, .. })
+        | SupportedOp::Linear(PolyOp::DeConv { kernel, bias, .. })
+        | SupportedOp::Linear(PolyOp::SparseLinear {
+            weights: kernel,
+            bias,
+        })
+        | SupportedOp::Linear(PolyOp::TernaryLinear {
+            weights: kernel,
+            bias,
+        }) => {
+            zero(kernel);
+            if let Some(bias) = bias {
+                zero(bias);
+            }
+        }
+        SupportedOp::Constant(c) => {
+            zero(&mut c.quantized_values);
+            c.raw_values.iter_mut().for_each(|x| *x = 0.0);
+        }
+        SupportedOp::RebaseScale(r) => zero_weights(&mut r.inner),
+        SupportedOp::Rescaled(r) => zero_weights(&mut r.inner),
+        _ => {}
+    }
+}
+
 /// Enables model as subnode of other models
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub enum NodeType {
@@ -251,6 +413,14 @@ impl NodeType {
         }
     }
 
+    /// Returns the name of the layer the node was created from.
+    pub fn name(&self) -> String {
+        match self {
+            NodeType::Node(n) => n.name.clone(),
+            NodeType::SubGraph { idx, .. } => format!("subgraph_{}", idx),
+        }
+    }
+
     /// Returns true if the operation is a rebase
     pub fn is_rebase(&self) -> bool {
         match self {
@@ -433,6 +603,12 @@ impl Model {
         run_args: &RunArgs,
         check_mode: CheckMode,
     ) -> Result<GraphSettings, Box<dyn Error>> {
+        if run_args.tolerance.val > 0.0 && run_args.abs_tolerance > 0.0 {
+            return Err(
+                "only one of the percentage and the absolute output tolerance can be set".into(),
+            );
+        }
+
         let instance_shapes = self.instance_shapes();
         #[cfg(not(target_arch = "wasm32"))]
         info!(
@@ -460,18 +636,23 @@ impl Model {
         // extract the requisite lookup ops from the model
         let mut lookup_ops: Vec<LookupOp> = self.required_lookups();
 
-        // if we're using percentage tolerance, we need to add the necessary range check ops for it.
+        // if we're using a (percentage or absolute) tolerance, we need to add the necessary range check ops for it.
 
-        if run_args.tolerance.val > 0.0 {
+        if run_args.tolerance.val > 0.0 || run_args.abs_tolerance > 0.0 {
             for scale in self.graph.get_output_scales() {
-                let mut tolerance = run_args.tolerance;
-                tolerance.scale = scale_to_multiplier(scale).into();
-                let opkind: Box<dyn Op<Fp>> = Box::new(HybridOp::RangeCheck(tolerance));
+                let opkind: Box<dyn Op<Fp>> = Box::new(output_check(run_args, scale));
                 lookup_ops.extend(opkind.required_lookups());
             }
         }
 
-        let set: HashSet<_> = lookup_ops.drain(..).collect(); // dedup
+        lookup_ops.extend(
+            run_args
+                .output_assertions
+                .iter()
+                .map(|a| a.required_lookup()),
+        );
+
+        let set: HashSet<_> = lookup_ops.drain(..).map(|op| op.canonical()).collect(); // dedup
         lookup_ops.extend(set.into_iter().sorted());
 
         Ok(GraphSettings {
@@ -496,6 +677,74 @@ impl Model {
     /// * `model_inputs` - A vector of [Tensor]s to use as inputs to the model.
     /// * `run_args` - [RunArgs]
     pub fn forward(&self, model_inputs: &[Tensor<Fp>]) -> Result<ForwardResult, Box<dyn Error>> {
+        let (res, _) = self.forward_with_intermediates(model_inputs)?;
+        Ok(res)
+    }
+
+    /// Runs a forward pass on sample data and compares the (dequantized) outputs of each node against those of
+    /// `other`, eg. the same network before and after a weight update.
+    /// Only nodes present in both models (same index and output shapes) are compared.
+    pub fn layer_divergence(
+        &self,
+        other: &Model,
+        model_inputs: &[Tensor<Fp>],
+    ) -> Result<Vec<LayerDivergence>, Box<dyn Error>> {
+        let (_, results) = self.forward_with_intermediates(model_inputs)?;
+        let (_, other_results) = other.forward_with_intermediates(model_inputs)?;
+
+        let mut divergence = vec![];
+        for (idx, node) in self.graph.nodes.iter() {
+            let other_node = match other.graph.nodes.get(idx) {
+                Some(n) => n,
+                None => continue,
+            };
+            if node.out_dims() != other_node.out_dims() {
+                continue;
+            }
+            let (outs, other_outs) = match (results.get(idx), other_results.get(idx)) {
+                (Some(a), Some(b)) => (a, b),
+                _ => continue,
+            };
+
+            let mut max_abs_error: f64 = 0.0;
+            let mut total_abs_error = 0.0;
+            let mut num_elements = 0;
+            for (((a, b), scale_a), scale_b) in outs
+                .iter()
+                .zip(other_outs)
+                .zip(node.out_scales())
+                .zip(other_node.out_scales())
+            {
+                for (x, y) in a.iter().zip(b.iter()) {
+                    let x = crate::fieldutils::felt_to_f64(*x) / scale_to_multiplier(scale_a);
+                    let y = crate::fieldutils::felt_to_f64(*y) / scale_to_multiplier(scale_b);
+                    let err = (x - y).abs();
+                    max_abs_error = max_abs_error.max(err);
+                    total_abs_error += err;
+                    num_elements += 1;
+                }
+            }
+
+            divergence.push(LayerDivergence {
+                idx: *idx,
+                opkind: node.as_str(),
+                max_abs_error,
+                mean_abs_error: if num_elements > 0 {
+                    total_abs_error / num_elements as f64
+                } else {
+                    0.0
+                },
+            });
+        }
+
+        Ok(divergence)
+    }
+
+    /// Runs a forward pass and returns the outputs of every node alongside the usual [ForwardResult].
+    fn forward_with_intermediates(
+        &self,
+        model_inputs: &[Tensor<Fp>],
+    ) -> Result<(ForwardResult, BTreeMap<usize, Vec<Tensor<Fp>>>), Box<dyn Error>> {
         let mut results: BTreeMap<&usize, Vec<Tensor<Fp>>> = BTreeMap::new();
         let mut max_lookup_inputs = 0;
         let mut min_lookup_inputs = 0;
@@ -545,7 +794,25 @@ impl Model {
                 NodeType::Node(n) => {
                     // execute the op
                     let start = instant::Instant::now();
-                    let res = Op::<Fp>::f(&n.opkind, &inputs)?;
+                    // sums and products are accumulated over widened integers, where overflows are exact
+                    let res = n.opkind.f_int(&inputs).and_then(|output| match output {
+                        Some(output) => Ok(crate::circuit::ForwardResult {
+                            output: output.map(i128_to_felt),
+                            intermediate_lookups: vec![],
+                        }),
+                        None => Op::<Fp>::f(&n.opkind, &inputs),
+                    });
+                    let res = match res {
+                        Ok(res) => res,
+                        Err(TensorError::Overflow(element)) => {
+                            return Err(Box::new(EzklError::Overflow {
+                                node: *idx,
+                                op: Op::<Fp>::as_string(&n.opkind),
+                                element,
+                            }))
+                        }
+                        Err(e) => return Err(Box::new(e)),
+                    };
                     let elapsed = start.elapsed();
                     trace!("op took: {:?}", elapsed);
                     // see if any of the intermediate lookup calcs are the max
@@ -569,6 +836,15 @@ impl Model {
                                 / scale_to_multiplier(n.out_scale))
                             .show()
                     );
+                    // lookup, hybrid and rebase ops do their math over i128 (see [felt_to_i128]) so
+                    // any activation that wrapped around the field must be caught before it reaches them
+                    if let Some(i) = res.output.iter().position(|x| !felt_fits_i128(*x)) {
+                        return Err(Box::new(EzklError::Overflow {
+                            node: *idx,
+                            op: Op::<Fp>::as_string(&n.opkind),
+                            element: i,
+                        }));
+                    }
                     results.insert(idx, vec![res.output]);
                 }
                 NodeType::SubGraph {
@@ -677,7 +953,9 @@ impl Model {
             min_lookup_inputs,
         };
 
-        Ok(res)
+        let results = results.into_iter().map(|(k, v)| (*k, v)).collect();
+
+        Ok((res, results))
     }
 
     /// Loads an Onnx model from a specified path.
@@ -751,7 +1029,7 @@ impl Model {
         model = model.concretize_dims(&symbol_values)?;
 
         let scales = VarScales::from_args(run_args)?;
-        let nodes = Self::nodes_from_graph(
+        let mut nodes = Self::nodes_from_graph(
             &model,
             run_args,
             &scales,
@@ -760,6 +1038,13 @@ impl Model {
             None,
             None,
         )?;
+        // public outputs can be rounded down to a coarser scale so they are readable as is
+        if let Some(scale) = run_args.public_output_scale {
+            if visibility.output.is_public() {
+                let outputs = model.outputs.iter().map(|o| o.node).collect::<HashSet<_>>();
+                Self::round_public_outputs(&mut nodes, &outputs, scale)?;
+            }
+        }
 
         debug!("\n {}", model);
 
@@ -811,6 +1096,17 @@ impl Model {
         format!("{} \n{}", string, table)
     }
 
+    /// The visibility of the params of the node named `name`: the per layer visibility given for exactly
+    /// that name, or the model wide param visibility
+    fn layer_param_visibility<'a>(run_args: &'a RunArgs, name: &str) -> &'a Visibility {
+        run_args
+            .layer_param_visibility
+            .iter()
+            .find(|(layer, _)| layer == name)
+            .map(|(_, v)| v)
+            .unwrap_or(&run_args.param_visibility)
+    }
+
     /// Creates ezkl nodes from a tract graph
     /// # Arguments
     /// * `graph` - A tract graph.
@@ -955,11 +1251,12 @@ impl Model {
                     );
                 }
                 None => {
+                    let param_visibility = Self::layer_param_visibility(run_args, &n.name);
                     let mut n = Node::new(
                         n.clone(),
                         &mut nodes,
                         scales,
-                        &run_args.param_visibility,
+                        param_visibility,
                         i,
                         symbol_values,
                     )?;
@@ -985,15 +1282,332 @@ impl Model {
                             n.out_scale = scales[&i];
                         }
                     }
+                    if !graph.outputs.iter().any(|o| o.node == i) {
+                        Self::fold_constants(&mut n, &mut nodes);
+                    }
                     nodes.insert(i, NodeType::Node(n));
                 }
             }
         }
+        let outputs = graph.outputs.iter().map(|o| o.node).collect::<HashSet<_>>();
+        Self::remove_identity_nodes(&mut nodes, &outputs);
+        if run_args.fuse_ops {
+            Self::fuse_rebased_relus(&mut nodes, &outputs);
+        }
+        if run_args.im2col_conv {
+            Self::lower_convs_to_im2col(&mut nodes);
+        }
+        if run_args.fuse_transposes {
+            Self::fuse_axis_moves(&mut nodes, &outputs);
+        }
         Self::remove_unused_nodes(&mut nodes);
 
         Ok(nodes)
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Precomputes a node whose inputs are all (non public) constants, replacing it with a constant
+    /// holding its output. Nodes are visited in topological order so whole constant subgraphs (eg. shape
+    /// computations or batchnorm math) collapse into a single constant, the constants that are no longer
+    /// used are then dropped by [Self::remove_unused_nodes].
+    fn fold_constants(n: &mut Node, nodes: &mut BTreeMap<usize, NodeType>) {
+        if n.inputs.is_empty() || n.opkind.is_input() || n.opkind.is_constant() {
+            return;
+        }
+
+        let mut inputs = vec![];
+        let mut visibility = None;
+        for (idx, outlet) in n.inputs.iter() {
+            match nodes.get(idx).map(|n| n.opkind()) {
+                Some(SupportedOp::Constant(c)) if *outlet == 0 => {
+                    // public constants have to stay in the instance column
+                    match c.quantized_values.visibility() {
+                        Some(v @ (Visibility::Private | Visibility::Fixed)) => {
+                            if visibility.is_some() && visibility != Some(v.clone()) {
+                                return;
+                            }
+                            visibility = Some(v);
+                        }
+                        _ => return,
+                    }
+                    inputs.push(c.quantized_values.clone());
+                }
+                _ => return,
+            }
+        }
+
+        // ops that can't be evaluated out of circuit (eg. unknown ops) are left to fail at layout
+        let mut quantized_values = match Op::<Fp>::f(&n.opkind, &inputs) {
+            Ok(res) => res.output,
+            Err(_) => return,
+        };
+        let multiplier = scale_to_multiplier(n.out_scale);
+        let raw_values =
+            quantized_values.map(|x| (crate::fieldutils::felt_to_f64(x) / multiplier) as f32);
+        if let Some(visibility) = visibility {
+            quantized_values.set_visibility(&visibility);
+        }
+
+        trace!(
+            "folding node {} ({}) into a constant",
+            n.idx,
+            n.opkind.as_string()
+        );
+        for (idx, _) in n.inputs.iter() {
+            if let Some(input) = nodes.get_mut(idx) {
+                input.decrement_use();
+            }
+        }
+        n.opkind =
+            SupportedOp::Constant(crate::circuit::Constant::new(quantized_values, raw_values));
+        n.inputs = vec![];
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Bypasses identity nodes (eg. dropouts and casts between float types, which are no-ops at inference
+    /// time) by pointing their users at the identity's own input. Graph outputs are kept as they are.
+    fn remove_identity_nodes(nodes: &mut BTreeMap<usize, NodeType>, outputs: &HashSet<usize>) {
+        let identities: BTreeMap<usize, (Outlet, usize)> = nodes
+            .iter()
+            .filter_map(|(idx, n)| match n {
+                NodeType::Node(n)
+                    if matches!(
+                        n.opkind,
+                        SupportedOp::Linear(crate::circuit::poly::PolyOp::Identity)
+                    ) && n.inputs.len() == 1
+                        && !outputs.contains(idx) =>
+                {
+                    Some((*idx, (n.inputs[0], n.num_uses)))
+                }
+                _ => None,
+            })
+            .collect();
+
+        // follow chains of identities back to the node that actually computes the value
+        let resolve = |mut outlet: Outlet| {
+            while let Some((input, _)) = identities.get(&outlet.0) {
+                outlet = *input;
+            }
+            outlet
+        };
+
+        for node in nodes.values_mut() {
+            let inputs = match node {
+                NodeType::Node(n) => &mut n.inputs,
+                NodeType::SubGraph { inputs, .. } => inputs,
+            };
+            for input in inputs.iter_mut() {
+                *input = resolve(*input);
+            }
+        }
+
+        // the source loses the identity as a user and gains all of the identity's users
+        for (input, num_uses) in identities.values() {
+            if let Some(NodeType::Node(n)) = nodes.get_mut(&resolve(*input).0) {
+                n.num_uses = n.num_uses + num_uses - 1;
+            }
+        }
+
+        nodes.retain(|idx, _| !identities.contains_key(idx));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Fuses the rescale of a node (eg. the output of a conv, with any batchnorm already folded into its
+    /// kernel and bias at declutter time) into the relu that consumes it. The rescale and the relu then
+    /// share a single [LookupOp::DivReLU] instead of taking up two lookups per element.
+    fn fuse_rebased_relus(nodes: &mut BTreeMap<usize, NodeType>, outputs: &HashSet<usize>) {
+        let fusable = nodes
+            .iter()
+            .filter_map(|(idx, n)| match n {
+                NodeType::Node(n)
+                    if n.opkind.get_lookup().map(|l| l.canonical()) == Some(LookupOp::ReLU)
+                        && n.inputs.len() == 1 =>
+                {
+                    let (input_idx, slot) = n.inputs[0];
+                    match nodes.get(&input_idx) {
+                        // the rescaled value must not be needed anywhere else
+                        Some(NodeType::Node(input))
+                            if slot == 0
+                                && input.num_uses == 1
+                                && !outputs.contains(&input_idx)
+                                // per channel rescales can't be fused into a single division
+                                && input
+                                    .opkind
+                                    .get_rebased()
+                                    .map(|op| op.channel_multipliers.is_none())
+                                    == Some(true) =>
+                        {
+                            Some((*idx, input_idx))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect_vec();
+
+        for (relu_idx, rebased_idx) in fusable {
+            let rebased = match nodes.get_mut(&rebased_idx) {
+                Some(NodeType::Node(n)) => n,
+                _ => continue,
+            };
+            let op = match rebased.opkind.get_rebased() {
+                Some(op) => op.clone(),
+                None => continue,
+            };
+            rebased.opkind = *op.inner;
+            rebased.out_scale = op.original_scale;
+            if let Some(NodeType::Node(relu)) = nodes.get_mut(&relu_idx) {
+                relu.opkind = SupportedOp::Nonlinear(LookupOp::DivReLU {
+                    denom: crate::circuit::utils::F32(op.multiplier as f32),
+                });
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Swaps the (ungrouped) convolutions for their im2col lowering, see [crate::circuit::ops::layouts::conv_im2col]
+    fn lower_convs_to_im2col(nodes: &mut BTreeMap<usize, NodeType>) {
+        let out_dims = nodes
+            .iter()
+            .map(|(idx, n)| (*idx, n.out_dims()))
+            .collect::<BTreeMap<_, _>>();
+
+        for node in nodes.values_mut() {
+            let n = match node {
+                NodeType::Node(n) => n,
+                _ => continue,
+            };
+            let input_dims = n
+                .inputs
+                .first()
+                .and_then(|(idx, outlet)| out_dims.get(idx).and_then(|d| d.get(*outlet)));
+            let opkind = match &mut n.opkind {
+                SupportedOp::RebaseScale(op) => op.inner.as_mut(),
+                op => op,
+            };
+            let lowered = match &*opkind {
+                // im2col doesn't support grouped convolutions
+                SupportedOp::Linear(PolyOp::Conv {
+                    kernel,
+                    bias,
+                    padding,
+                    stride,
+                }) if input_dims.map(|d| d.len() == 4 && d[1] == kernel.dims()[1])
+                    == Some(true) =>
+                {
+                    PolyOp::Im2ColConv {
+                        kernel: kernel.clone(),
+                        bias: bias.clone(),
+                        padding: *padding,
+                        stride: *stride,
+                    }
+                }
+                _ => continue,
+            };
+            *opkind = SupportedOp::Linear(lowered);
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Swaps the axis moves (which tract lowers onnx transposes to, one per moved axis) for permutes, and
+    /// collapses each chain of them into a single permute so that the assigned cells are only reindexed
+    /// once per transpose. The intermediate results of a chain must not be used elsewhere or be graph outputs.
+    fn fuse_axis_moves(nodes: &mut BTreeMap<usize, NodeType>, outputs: &HashSet<usize>) {
+        // nodes are visited in topological order, so the input of a node has already been swapped
+        let idxs = nodes.keys().cloned().collect_vec();
+        for idx in idxs {
+            let (perm, input) = match nodes.get(&idx) {
+                Some(NodeType::Node(n)) if n.inputs.len() == 1 => match &n.opkind {
+                    SupportedOp::Linear(PolyOp::MoveAxis {
+                        source,
+                        destination,
+                    }) => {
+                        let mut perm = (0..n.out_dims.len()).collect_vec();
+                        let axis = perm.remove(*source);
+                        perm.insert(*destination, axis);
+                        (perm, n.inputs[0])
+                    }
+                    SupportedOp::Linear(PolyOp::Permute { perm }) => (perm.clone(), n.inputs[0]),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+
+            let (input_idx, slot) = input;
+            let fused = match nodes.get(&input_idx) {
+                Some(NodeType::Node(prev))
+                    if slot == 0 && prev.num_uses == 1 && !outputs.contains(&input_idx) =>
+                {
+                    match &prev.opkind {
+                        // axis i of the output is axis perm[i] of the input, which is axis prev[perm[i]]
+                        // of the input of the previous permute
+                        SupportedOp::Linear(PolyOp::Permute { perm: prev_perm }) => Some((
+                            perm.iter().map(|p| prev_perm[*p]).collect_vec(),
+                            prev.inputs[0],
+                        )),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+
+            let (perm, input) = match fused {
+                Some(fused) => {
+                    if let Some(prev) = nodes.get_mut(&input_idx) {
+                        prev.decrement_use();
+                    }
+                    fused
+                }
+                None => (perm, input),
+            };
+            if let Some(NodeType::Node(n)) = nodes.get_mut(&idx) {
+                n.opkind = SupportedOp::Linear(PolyOp::Permute { perm });
+                n.inputs = vec![input];
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Rounds the graph outputs down to `scale`. Errors if `scale` is finer than the scale of an output, as
+    /// rebasing the output up would only pad it with zero bits, or if an output can't be rebased.
+    fn round_public_outputs(
+        nodes: &mut BTreeMap<usize, NodeType>,
+        outputs: &HashSet<usize>,
+        scale: crate::Scale,
+    ) -> Result<(), Box<dyn Error>> {
+        for idx in outputs {
+            let n = match nodes.get_mut(idx) {
+                Some(NodeType::Node(n)) => n,
+                _ => {
+                    return Err(
+                        format!("output {} is a subgraph, which can't be rounded", idx).into(),
+                    )
+                }
+            };
+            if scale > n.out_scale {
+                return Err(format!(
+                    "the public output scale {} is finer than the scale {} of output {}",
+                    scale, n.out_scale, idx
+                )
+                .into());
+            }
+            if scale == n.out_scale {
+                continue;
+            }
+            if n.opkind.is_input() || n.opkind.is_constant() {
+                return Err(format!(
+                    "output {} is an input or a constant, which can't be rounded",
+                    idx
+                )
+                .into());
+            }
+            n.opkind = RebaseScale::rebase(n.opkind.clone(), scale, n.out_scale, 1);
+            n.out_scale = scale;
+        }
+        Ok(())
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     /// Removes all nodes that are consts with 0 uses
     fn remove_unused_nodes(nodes: &mut BTreeMap<usize, NodeType>) {
@@ -1021,11 +1635,20 @@ impl Model {
         run_args: &RunArgs,
         model: &std::path::Path,
     ) -> Result<Self, Box<dyn Error>> {
-        Model::new(
+        let mut model = Model::new(
             &mut std::fs::File::open(model)
                 .map_err(|_| format!("failed to load model at {}", model.display()))?,
             run_args,
-        )
+        )?;
+        if let Some(weights) = &run_args.weights {
+            let replaced = model.load_weights(weights)?;
+            info!(
+                "replaced {} constants with the weights in {}",
+                replaced,
+                weights.display()
+            );
+        }
+        Ok(model)
     }
 
     /// Configures a model for the circuit
@@ -1102,30 +1725,46 @@ impl Model {
         let mut linear_coord = 0;
 
         let outputs = layouter.assign_region(
-            || "model",
+            || MODEL_REGION,
             |region| {
                 let mut thread_safe_region = RegionCtx::new(region, 0, run_args.num_inner_cols);
                 // we need to do this as this loop is called multiple times
                 vars.set_instance_idx(instance_idx);
 
                 let outputs = self
-                    .layout_nodes(&mut config, &mut thread_safe_region, &mut results)
+                    .layout_nodes(
+                        &mut config,
+                        &mut thread_safe_region,
+                        &mut results,
+                        &mut None,
+                    )
                     .map_err(|e| {
                         error!("{}", e);
                         halo2_proofs::plonk::Error::Synthesis
                     })?;
 
+                let output_scales = self.graph.get_output_scales();
+                for assertion in &run_args.output_assertions {
+                    assertion
+                        .layout(
+                            &config.base,
+                            &mut thread_safe_region,
+                            &outputs,
+                            &output_scales,
+                        )
+                        .map_err(|e| {
+                            error!("{}", e);
+                            halo2_proofs::plonk::Error::Synthesis
+                        })?;
+                }
+
                 if run_args.output_visibility == Visibility::Public
                     || run_args.output_visibility == Visibility::Fixed
                 {
-                    let output_scales = self.graph.get_output_scales();
                     let _ = outputs
                         .iter()
                         .enumerate()
                         .map(|(i, output)| {
-                            let mut tolerance = run_args.tolerance;
-                            tolerance.scale = scale_to_multiplier(output_scales[i]).into();
-
                             let comparators = if run_args.output_visibility == Visibility::Public {
                                 let res = vars.instance.as_ref().unwrap().clone();
                                 vars.increment_instance_idx();
@@ -1138,7 +1777,7 @@ impl Model {
                             config.base.layout(
                                 &mut thread_safe_region,
                                 &[output.clone(), comparators],
-                                Box::new(HybridOp::RangeCheck(tolerance)),
+                                Box::new(output_check(run_args, output_scales[i])),
                             )
                         })
                         .collect_vec();
@@ -1166,6 +1805,7 @@ impl Model {
         config: &mut ModelConfig,
         region: &mut RegionCtx<Fp>,
         results: &mut BTreeMap<usize, Vec<ValTensor<Fp>>>,
+        reports: &mut Option<Vec<LayerReport>>,
     ) -> Result<Vec<ValTensor<Fp>>, Box<dyn Error>> {
         // index over results to get original inputs
         let orig_inputs: BTreeMap<usize, _> = results
@@ -1174,7 +1814,8 @@ impl Model {
             .filter(|(idx, _)| self.graph.inputs.contains(idx))
             .collect();
 
-        for (idx, node) in self.graph.nodes.iter() {
+        let total = self.graph.nodes.len();
+        for (layer, (idx, node)) in self.graph.nodes.iter().enumerate() {
             let mut values: Vec<ValTensor<Fp>> = if !node.is_input() {
                 node.inputs()
                     .iter()
@@ -1185,15 +1826,23 @@ impl Model {
                 vec![results.get(idx).unwrap()[0].clone()]
             };
 
+            crate::circuit::region::set_trace_layer(*idx);
             debug!(
-                "laying out {}: {}, row:{}, coord:{}, total_constants: {}",
+                "laying out {} ({}): {}, row:{}, coord:{}, total_constants: {}",
                 idx,
+                node.name(),
                 node.as_str(),
                 region.row(),
                 region.linear_coord(),
                 region.total_constants()
             );
             debug!("dims: {:?}", node.out_dims());
+            let (start_row, start_coord, start_constants, start_gates) = (
+                region.row(),
+                region.linear_coord(),
+                region.total_constants(),
+                region.total_gates(),
+            );
             debug!(
                 "input_dims {:?}",
                 values.iter().map(|v| v.dims()).collect_vec()
@@ -1211,8 +1860,9 @@ impl Model {
                             .base
                             .layout(region, &values, n.opkind.clone_dyn())
                             .map_err(|e| {
+                                let e = EzklError::in_node(*idx, node.as_str(), e);
                                 error!("{}", e);
-                                halo2_proofs::plonk::Error::Synthesis
+                                e
                             })?
                     };
 
@@ -1267,7 +1917,8 @@ impl Model {
                                 .zip(values.clone().into_iter().map(|v| vec![v])),
                         );
 
-                        let res = model.layout_nodes(config, region, &mut subgraph_results)?;
+                        let res =
+                            model.layout_nodes(config, region, &mut subgraph_results, &mut None)?;
 
                         let mut outlets = BTreeMap::new();
 
@@ -1314,6 +1965,40 @@ impl Model {
                     results.insert(*idx, full_results);
                 }
             }
+
+            if let Some(reports) = reports {
+                let col_size = config.base.output.col_size();
+                let rows = region.row() - start_row;
+                let num_blocks = if rows > 0 {
+                    (region.row() - 1) / col_size - start_row / col_size + 1
+                } else {
+                    0
+                };
+                let out_len: usize = node
+                    .out_dims()
+                    .iter()
+                    .map(|d| d.iter().product::<usize>())
+                    .sum();
+                reports.push(LayerReport {
+                    idx: *idx,
+                    name: node.name(),
+                    opkind: node.as_str(),
+                    start_row,
+                    gates: region.total_gates() - start_gates,
+                    advice_cells: region.linear_coord() - start_coord,
+                    advice_columns: num_blocks * config.base.output.num_inner_cols(),
+                    lookup_rows: node.required_lookups().len() * out_len,
+                    constants: region.total_constants() - start_constants,
+                    rows,
+                });
+            }
+
+            if !region.is_dummy() {
+                crate::pfsys::progress::report(crate::pfsys::progress::Progress::Layer {
+                    layer,
+                    total,
+                });
+            }
         }
 
         // we do this so we can support multiple passes of the same model and have deterministic results (Non-assigned inputs etc... etc...)
@@ -1338,6 +2023,32 @@ impl Model {
         &self,
         run_args: &RunArgs,
         input_shapes: &[Vec<usize>],
+    ) -> Result<(usize, usize, usize), Box<dyn Error>> {
+        self.dummy_layout_with_reports(run_args, input_shapes, &mut None)
+    }
+
+    /// Reports the gates, advice cells and columns, lookup rows, constants and rows used by each
+    /// (top level) node of the model, subgraphs are reported as a single layer.
+    pub fn layer_reports(&self, run_args: &RunArgs) -> Result<Vec<LayerReport>, Box<dyn Error>> {
+        let mut reports = Some(vec![]);
+        self.dummy_layout_with_reports(run_args, &self.graph.input_shapes(), &mut reports)?;
+        Ok(reports.unwrap_or_default())
+    }
+
+    /// Formats the per layer reports of [Model::layer_reports] into a table
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn table_layer_reports(&self, run_args: &RunArgs) -> Result<String, Box<dyn Error>> {
+        let reports = self.layer_reports(run_args)?;
+        let mut table = Table::new(reports.iter());
+        table.with(tabled::settings::Style::modern());
+        Ok(table.to_string())
+    }
+
+    fn dummy_layout_with_reports(
+        &self,
+        run_args: &RunArgs,
+        input_shapes: &[Vec<usize>],
+        reports: &mut Option<Vec<LayerReport>>,
     ) -> Result<(usize, usize, usize), Box<dyn Error>> {
         info!("calculating num of constraints using dummy model layout...");
 
@@ -1368,19 +2079,25 @@ impl Model {
 
         let mut region = RegionCtx::new_dummy(0, run_args.num_inner_cols);
 
-        let outputs = self.layout_nodes(&mut model_config, &mut region, &mut results)?;
+        let outputs = self.layout_nodes(&mut model_config, &mut region, &mut results, reports)?;
+
+        let output_scales = self.graph.get_output_scales();
+        for assertion in &run_args.output_assertions {
+            assertion.layout(&dummy_config, &mut region, &outputs, &output_scales)?;
+        }
 
         if run_args.output_visibility == Visibility::Public
             || run_args.output_visibility == Visibility::Fixed
         {
             let _ = outputs
                 .into_iter()
-                .map(|output| {
+                .zip(output_scales)
+                .map(|(output, scale)| {
                     dummy_config
                         .layout(
                             &mut region,
                             &[output.clone(), output],
-                            Box::new(HybridOp::RangeCheck(run_args.tolerance)),
+                            Box::new(output_check(run_args, scale)),
                         )
                         .unwrap()
                 })
@@ -1415,6 +2132,61 @@ impl Model {
         params
     }
 
+    /// Lays out a single forward pass of the model on (private) `inputs` in `region`, returns the
+    /// assigned inputs and the outputs. Several passes can be laid out in the same region.
+    pub(crate) fn layout_pass(
+        &self,
+        config: &mut ModelConfig,
+        region: &mut RegionCtx<Fp>,
+        inputs: &[ValTensor<Fp>],
+    ) -> Result<(Vec<ValTensor<Fp>>, Vec<ValTensor<Fp>>), Box<dyn Error>> {
+        if inputs.len() != self.graph.inputs.len() {
+            return Err(format!(
+                "model has {} inputs, got {}",
+                self.graph.inputs.len(),
+                inputs.len()
+            )
+            .into());
+        }
+
+        let mut results = BTreeMap::<usize, Vec<ValTensor<Fp>>>::new();
+        let input_shapes = self.graph.input_shapes();
+        for (i, input_idx) in self.graph.inputs.iter().enumerate() {
+            let mut input = inputs[i].clone();
+            input.reshape(&input_shapes[i])?;
+            results.insert(*input_idx, vec![input]);
+        }
+
+        let outputs = self.layout_nodes(config, region, &mut results, &mut None)?;
+        // the input nodes have been replaced by their assigned values
+        let inputs = self
+            .graph
+            .inputs
+            .iter()
+            .map(|idx| results[idx][0].clone())
+            .collect_vec();
+
+        Ok((inputs, outputs))
+    }
+
+    /// The quantized values of the computational graph's constants
+    pub(crate) fn const_values(&self) -> Vec<Tensor<Fp>> {
+        let mut consts = vec![];
+        for node in self.graph.nodes.values() {
+            match node {
+                NodeType::Node(_) => {
+                    if let Some(constant) = extract_const_quantized_values(node.opkind()) {
+                        consts.push(constant);
+                    };
+                }
+                NodeType::SubGraph { model, .. } => {
+                    consts.extend(model.const_values());
+                }
+            }
+        }
+        consts
+    }
+
     /// Shapes of the computational graph's constants
     pub fn const_shapes(&self) -> Vec<Vec<usize>> {
         let mut const_shapes = vec![];
@@ -1433,6 +2205,22 @@ impl Model {
         const_shapes
     }
 
+    /// A copy of the model with the values of its weights zeroed and its nodes unnamed, so that models
+    /// which only differ in their weights (or the names of their layers) are the same.
+    pub fn without_weights(&self) -> Model {
+        let mut model = self.clone();
+        for node in model.graph.nodes.values_mut() {
+            match node {
+                NodeType::Node(n) => {
+                    zero_weights(&mut n.opkind);
+                    n.name = String::new();
+                }
+                NodeType::SubGraph { model, .. } => *model = model.without_weights(),
+            }
+        }
+        model
+    }
+
     /// Replaces all constants in the model with the provided values (in order of indexing), returns the number of consts
     pub fn replace_consts(&mut self, consts: &[ValTensor<Fp>]) -> usize {
         let mut const_idx = 0;
@@ -1459,6 +2247,235 @@ impl Model {
         const_idx
     }
 
+    /// Replaces the constants of the model with the named arrays of a .npz or .safetensors file, see
+    /// [Model::load_npz] and [Model::load_safetensors]
+    pub fn load_weights(&mut self, path: &std::path::Path) -> Result<usize, Box<dyn Error>> {
+        let names = BTreeMap::new();
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("npz") => self.load_npz(path, &names),
+            Some("safetensors") => self.load_safetensors(path, &names),
+            _ => Err(format!(
+                "unsupported weights file {}: expected .npz or .safetensors",
+                path.display()
+            )
+            .into()),
+        }
+    }
+
+    /// Replaces the constants of the model with the named arrays of a .npz archive (as written by `np.savez`)
+    /// and requantizes them, returns the number of replaced consts. Constants are matched to arrays as in
+    /// [Model::load_safetensors].
+    pub fn load_npz(
+        &mut self,
+        path: &std::path::Path,
+        names: &BTreeMap<String, String>,
+    ) -> Result<usize, Box<dyn Error>> {
+        let arrays = crate::io::load_npz(path)?.into_iter().collect();
+        self.replace_named_consts(&arrays, names)
+    }
+
+    /// Replaces the constants of the model with the named tensors of a safetensors file and requantizes them,
+    /// returns the number of replaced consts. Each constant is matched to a tensor through `names`, which maps
+    /// layer names to tensor names, or failing that to the tensor of the same name up to case and separators
+    /// (eg. `fc1.weight` matches `FC1_weight`). Tensors must be laid out as the constants of the onnx graph.
+    pub fn load_safetensors(
+        &mut self,
+        path: &std::path::Path,
+        names: &BTreeMap<String, String>,
+    ) -> Result<usize, Box<dyn Error>> {
+        let tensors = crate::io::load_safetensors(path)?;
+        self.replace_named_consts(&tensors, names)
+    }
+
+    fn replace_named_consts(
+        &mut self,
+        tensors: &BTreeMap<String, crate::io::NpyArray>,
+        names: &BTreeMap<String, String>,
+    ) -> Result<usize, Box<dyn Error>> {
+        let normalize = |name: &str| name.to_lowercase().replace(['.', '/', ':', '-'], "_");
+        let mut replaced = 0;
+        for node in self.graph.nodes.values_mut() {
+            match node {
+                NodeType::Node(n) => {
+                    let (name, out_scale) = (&n.name, n.out_scale);
+                    if let SupportedOp::Constant(c) = &mut n.opkind {
+                        let tensor = match names.get(name) {
+                            Some(t) => Some(
+                                tensors
+                                    .get(t)
+                                    .ok_or_else(|| format!("no tensor named {} found", t))?,
+                            ),
+                            None => tensors
+                                .iter()
+                                .find(|(k, _)| normalize(k) == normalize(name))
+                                .map(|(_, v)| v),
+                        };
+                        if let Some(tensor) = tensor {
+                            if tensor.data.len() != c.quantized_values.len() {
+                                return Err(format!(
+                                    "tensor for layer {} has {} elements but the constant has {}",
+                                    name,
+                                    tensor.data.len(),
+                                    c.quantized_values.len()
+                                )
+                                .into());
+                            }
+                            let dims = c.quantized_values.dims().to_vec();
+                            let mut raw_values: Tensor<f32> =
+                                Tensor::from(tensor.data.iter().map(|x| *x as f32));
+                            raw_values.reshape(&dims);
+                            c.raw_values = raw_values;
+                            c.rebase_scale(out_scale)?;
+                            replaced += 1;
+                        }
+                    }
+                }
+                NodeType::SubGraph { model, .. } => {
+                    replaced += model.replace_named_consts(tensors, names)?;
+                }
+            }
+        }
+        Ok(replaced)
+    }
+
+    /// Splits the model into two chunks at node `idx`: the first chunk holds the nodes up to and including
+    /// `idx`, the second the remaining nodes. The activations crossing the cut become the outputs of the first
+    /// chunk and the inputs of the second, and are committed to with a public poseidon hash in both so the
+    /// proofs of consecutive chunks can be chained. Returns each chunk with the [RunArgs] to compile it with.
+    pub fn split_at(
+        &self,
+        idx: usize,
+        run_args: &RunArgs,
+    ) -> Result<[(Model, RunArgs); 2], Box<dyn Error>> {
+        if self
+            .graph
+            .nodes
+            .values()
+            .any(|n| matches!(n, NodeType::SubGraph { .. }))
+        {
+            return Err("cannot split a model containing subgraphs".into());
+        }
+        if self.graph.inputs.iter().any(|i| *i > idx)
+            || self.graph.outputs.iter().any(|(i, _)| *i <= idx)
+        {
+            return Err(format!("cannot split the model at node {}", idx).into());
+        }
+
+        let (head, mut tail): (NodeGraph, NodeGraph) = self
+            .graph
+            .nodes
+            .clone()
+            .into_iter()
+            .partition(|(i, _)| *i <= idx);
+
+        // activations of the head consumed by the tail, constants are copied over instead
+        let mut boundary: Vec<Outlet> = vec![];
+        for (i, outlet) in tail.values().flat_map(|n| n.inputs()).collect_vec() {
+            if i > idx || boundary.contains(&(i, outlet)) {
+                continue;
+            }
+            let node = head.get(&i).ok_or(GraphError::MissingNode(i))?;
+            if node.is_constant() {
+                tail.insert(i, node.clone());
+            } else if outlet != 0 {
+                return Err(format!(
+                    "cannot split at node {}: outlet {} of node {} crosses the cut",
+                    idx, outlet, i
+                )
+                .into());
+            } else {
+                boundary.push((i, outlet));
+            }
+        }
+        if boundary.is_empty() {
+            return Err(format!("no activations cross the cut at node {}", idx).into());
+        }
+
+        for (i, _) in &boundary {
+            let node = &head[i];
+            let scale = node.out_scales()[0];
+            tail.insert(
+                *i,
+                NodeType::Node(Node {
+                    opkind: SupportedOp::Input(Input {
+                        scale,
+                        datum_type: InputType::F64,
+                    }),
+                    out_scale: scale,
+                    inputs: vec![],
+                    out_dims: node.out_dims()[0].clone(),
+                    idx: *i,
+                    num_uses: 1,
+                    name: node.name(),
+                }),
+            );
+        }
+
+        let commitment = Visibility::Hashed {
+            hash_is_public: true,
+            outlets: vec![],
+        };
+        let head_args = RunArgs {
+            output_visibility: commitment.clone(),
+            ..run_args.clone()
+        };
+        let tail_args = RunArgs {
+            input_visibility: commitment,
+            ..run_args.clone()
+        };
+
+        let mut head = ParsedNodes {
+            nodes: head,
+            inputs: self.graph.inputs.clone(),
+            outputs: boundary.clone(),
+        };
+        let mut tail = ParsedNodes {
+            nodes: tail,
+            inputs: boundary.iter().map(|(i, _)| *i).collect(),
+            outputs: self.graph.outputs.clone(),
+        };
+        Self::recount_uses(&mut head);
+        Self::recount_uses(&mut tail);
+
+        Ok([
+            (
+                Model {
+                    graph: head,
+                    visibility: VarVisibility::from_args(&head_args)?,
+                },
+                head_args,
+            ),
+            (
+                Model {
+                    graph: tail,
+                    visibility: VarVisibility::from_args(&tail_args)?,
+                },
+                tail_args,
+            ),
+        ])
+    }
+
+    /// Recomputes the number of uses of each node after the graph was cut, dropping nodes that are no longer used
+    fn recount_uses(graph: &mut ParsedNodes) {
+        let mut uses: BTreeMap<usize, usize> = BTreeMap::new();
+        for (i, _) in graph.nodes.values().flat_map(|n| n.inputs()) {
+            *uses.entry(i).or_default() += 1;
+        }
+        let outputs = graph
+            .outputs
+            .iter()
+            .map(|(i, _)| *i)
+            .collect::<HashSet<_>>();
+        graph
+            .nodes
+            .retain(|i, _| uses.contains_key(i) || outputs.contains(i) || graph.inputs.contains(i));
+        for (i, node) in graph.nodes.iter_mut() {
+            if let NodeType::Node(n) = node {
+                n.num_uses = std::cmp::max(*uses.get(i).unwrap_or(&0), 1);
+            }
+        }
+    }
+
     /// Shapes of the computational graph's public inputs (if any)
     pub fn instance_shapes(&self) -> Vec<Vec<usize>> {
         let mut instance_shapes = vec![];
@@ -1471,3 +2488,670 @@ impl Model {
         instance_shapes
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::circuit::modules::ModulePlanner;
+    use crate::circuit::ops::poly::PolyOp;
+    use crate::circuit::Constant;
+    use crate::fieldutils::i128_to_felt;
+    use crate::graph::quantize_tensor;
+    use crate::tensor::{ValType, VarTensor};
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::plonk::{Circuit, Column, Error as PlonkError, Instance};
+
+    fn node(idx: usize, opkind: SupportedOp, inputs: Vec<Outlet>, out_dims: &[usize]) -> NodeType {
+        NodeType::Node(Node {
+            opkind,
+            out_scale: 0,
+            inputs,
+            out_dims: out_dims.to_vec(),
+            idx,
+            num_uses: 1,
+            name: format!("node_{}", idx),
+        })
+    }
+
+    fn constant(values: &[f32]) -> SupportedOp {
+        let raw = Tensor::new(Some(values), &[values.len()]).unwrap();
+        let quantized = quantize_tensor(raw.clone(), 0, &Visibility::Private).unwrap();
+        SupportedOp::Constant(Constant::new(quantized, raw))
+    }
+
+    pub(crate) fn felts(values: &[i128]) -> Tensor<Fp> {
+        Tensor::new(
+            Some(&values.iter().map(|x| i128_to_felt(*x)).collect_vec()),
+            &[values.len()],
+        )
+        .unwrap()
+    }
+
+    /// input * weights
+    pub(crate) fn scaled_model(weights: &[f32]) -> Model {
+        let mut model = Model::default();
+        let input = SupportedOp::Input(Input {
+            scale: 0,
+            datum_type: InputType::F32,
+        });
+        let dims = [weights.len()];
+        model.graph.nodes.insert(0, node(0, input, vec![], &dims));
+        model
+            .graph
+            .nodes
+            .insert(1, node(1, constant(weights), vec![], &dims));
+        let mult = SupportedOp::Linear(PolyOp::Mult);
+        model
+            .graph
+            .nodes
+            .insert(2, node(2, mult, vec![(0, 0), (1, 0)], &dims));
+        model.graph.inputs = vec![0];
+        model.graph.outputs = vec![(2, 0)];
+        model
+    }
+
+    /// input * first * second
+    pub(crate) fn two_layer_model(first: &[f32], second: &[f32]) -> Model {
+        let mut model = scaled_model(first);
+        let dims = [first.len()];
+        model
+            .graph
+            .nodes
+            .insert(3, node(3, constant(second), vec![], &dims));
+        let mult = SupportedOp::Linear(PolyOp::Mult);
+        model
+            .graph
+            .nodes
+            .insert(4, node(4, mult, vec![(2, 0), (3, 0)], &dims));
+        model.graph.outputs = vec![(4, 0)];
+        model
+    }
+
+    #[test]
+    fn test_forward_overflow() {
+        let model = scaled_model(&[2f32.powi(30)]);
+        assert_eq!(
+            model.forward(&[felts(&[1 << 90])]).unwrap().outputs,
+            vec![felts(&[1 << 120])]
+        );
+        // 2^100 * 2^30 fits in the field but not in i128
+        let err = model.forward(&[felts(&[1 << 100])]).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<EzklError>(),
+            Some(EzklError::Overflow {
+                node: 2,
+                element: 0,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_split_at() {
+        let model = two_layer_model(&[2.0, -1.0], &[3.0, 3.0]);
+        let [(head, head_args), (tail, tail_args)] =
+            model.split_at(2, &RunArgs::default()).unwrap();
+        assert_eq!(head.graph.outputs, vec![(2, 0)]);
+        assert_eq!(tail.graph.inputs, vec![2]);
+        // the constant of the second layer stays in the tail
+        assert!(tail.graph.nodes[&3].is_constant());
+        assert!(head_args.output_visibility.is_hashed_public());
+        assert!(tail_args.input_visibility.is_hashed_public());
+
+        // chaining the chunks computes the model
+        let inputs = [felts(&[1, 2])];
+        let activations = head.forward(&inputs).unwrap().outputs;
+        assert_eq!(activations, vec![felts(&[2, -2])]);
+        assert_eq!(
+            tail.forward(&activations).unwrap().outputs,
+            model.forward(&inputs).unwrap().outputs
+        );
+
+        // the outputs of the model can't be in the head
+        assert!(model.split_at(4, &RunArgs::default()).is_err());
+    }
+
+    #[test]
+    fn test_layer_param_visibility() {
+        let run_args = RunArgs {
+            param_visibility: Visibility::Fixed,
+            layer_param_visibility: vec![("conv1".to_string(), Visibility::Private)],
+            ..Default::default()
+        };
+        assert_eq!(
+            Model::layer_param_visibility(&run_args, "conv1"),
+            &Visibility::Private
+        );
+        // layers are matched by their exact name
+        for name in ["conv10", "conv", "conv1.bias"] {
+            assert_eq!(
+                Model::layer_param_visibility(&run_args, name),
+                &Visibility::Fixed
+            );
+        }
+
+        assert!(crate::layer_visibility("conv1", "fixed").is_ok());
+        assert!(crate::layer_visibility("conv1", "public").is_err());
+        assert!(crate::layer_visibility("", "private").is_err());
+    }
+
+    #[test]
+    fn test_layer_divergence() {
+        let inputs = [felts(&[1, 2])];
+        let model = scaled_model(&[2.0, 3.0]);
+
+        let divergence = model.layer_divergence(&model, &inputs).unwrap();
+        assert_eq!(divergence.len(), 3);
+        assert!(divergence
+            .iter()
+            .all(|d| d.max_abs_error == 0.0 && d.mean_abs_error == 0.0));
+
+        // only the second weight changes, by 2
+        let updated = scaled_model(&[2.0, 5.0]);
+        let divergence = model.layer_divergence(&updated, &inputs).unwrap();
+        let errors = divergence
+            .iter()
+            .map(|d| (d.idx, d.max_abs_error, d.mean_abs_error))
+            .collect_vec();
+        assert_eq!(errors, vec![(0, 0.0, 0.0), (1, 2.0, 1.0), (2, 4.0, 2.0)]);
+    }
+
+    #[test]
+    fn test_replace_named_consts() {
+        let inputs = [felts(&[1, 2])];
+        let mut model = scaled_model(&[2.0, 3.0]);
+        let array = |data: Vec<f64>| crate::io::NpyArray {
+            shape: vec![data.len()],
+            data,
+        };
+
+        // names are matched up to case and separators
+        let weights = BTreeMap::from([("NODE.1".to_string(), array(vec![4.0, -1.0]))]);
+        let replaced = model.replace_named_consts(&weights, &BTreeMap::new());
+        assert_eq!(replaced.unwrap(), 1);
+        assert_eq!(
+            model.forward(&inputs).unwrap().outputs,
+            vec![felts(&[4, -2])]
+        );
+
+        // or through the names given
+        let weights = BTreeMap::from([("fc.weight".to_string(), array(vec![1.0, 1.0]))]);
+        let names = BTreeMap::from([("node_1".to_string(), "fc.weight".to_string())]);
+        assert_eq!(model.replace_named_consts(&weights, &names).unwrap(), 1);
+        assert_eq!(
+            model.forward(&inputs).unwrap().outputs,
+            vec![felts(&[1, 2])]
+        );
+
+        let weights = BTreeMap::from([("node_1".to_string(), array(vec![1.0]))]);
+        assert!(model
+            .replace_named_consts(&weights, &BTreeMap::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_load_weights() {
+        let header = r#"{"node_1":{"dtype":"F32","shape":[2],"data_offsets":[0,8]}}"#;
+        let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+        bytes.extend(header.as_bytes());
+        bytes.extend([-1.0f32, 5.0].iter().flat_map(|f| f.to_le_bytes()));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("weights.safetensors");
+        std::fs::write(&path, bytes).unwrap();
+
+        let mut model = scaled_model(&[2.0, 3.0]);
+        assert_eq!(model.load_weights(&path).unwrap(), 1);
+        let res = model.forward(&[felts(&[1, 2])]).unwrap();
+        assert_eq!(res.outputs, vec![felts(&[-1, 10])]);
+
+        assert!(model.load_weights(&dir.path().join("weights.bin")).is_err());
+    }
+
+    #[test]
+    fn test_layer_reports() {
+        let run_args = RunArgs {
+            num_inner_cols: 2,
+            ..Default::default()
+        };
+        let reports = scaled_model(&[2.0, 3.0]).layer_reports(&run_args).unwrap();
+        let counts = reports
+            .iter()
+            .map(|r| (r.idx, r.gates, r.rows))
+            .collect_vec();
+        // the input is copied without any gate, the constant is folded into the product and the
+        // products enable a gate per element, both on a single row
+        assert_eq!(counts, vec![(0, 0, 1), (1, 0, 0), (2, 2, 1)]);
+    }
+
+    /// lays out `x + x` and then `2x + 2x` in the model region, and exposes the latter
+    struct DoublingCircuit;
+
+    impl Circuit<Fp> for DoublingCircuit {
+        type Config = (PolyConfig<Fp>, Column<Instance>);
+        type FloorPlanner = ModulePlanner;
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            DoublingCircuit
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let a = VarTensor::new_advice(cs, 4, 1, 4);
+            let b = VarTensor::new_advice(cs, 4, 1, 4);
+            let output = VarTensor::new_advice(cs, 4, 1, 4);
+            let config = PolyConfig::configure(cs, &[a, b], &output, CheckMode::SAFE);
+            let instance = cs.instance_column();
+            cs.enable_equality(instance);
+            (config, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (mut config, instance): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), PlonkError> {
+            let output = layouter.assign_region(
+                || MODEL_REGION,
+                |region| {
+                    let mut region = RegionCtx::new(region, 0, 1);
+                    let x: ValTensor<Fp> =
+                        Tensor::from([1, 2].map(|i| Value::known(Fp::from(i))).into_iter()).into();
+                    let mut y = x;
+                    for _ in 0..2 {
+                        y = config
+                            .layout(&mut region, &[y.clone(), y], Box::new(PolyOp::Add))
+                            .map_err(|_| PlonkError::Synthesis)?
+                            .unwrap();
+                    }
+                    Ok(y)
+                },
+            )?;
+            for (i, v) in output.get_inner_tensor().unwrap().iter().enumerate() {
+                if let ValType::PrevAssigned(cell) = v {
+                    layouter.constrain_instance(cell.cell(), instance, i)?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_failure_layer() {
+        // each sum of two elements takes two rows
+        let reports = (0..2)
+            .map(|idx| LayerReport {
+                idx,
+                start_row: 2 * idx,
+                rows: 2,
+                ..Default::default()
+            })
+            .collect_vec();
+
+        let prover = MockProver::run(4, &DoublingCircuit, vec![felts(&[4, 8]).to_vec()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let prover = MockProver::run(4, &DoublingCircuit, vec![felts(&[4, 9]).to_vec()]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        let layers = failures
+            .iter()
+            .filter_map(|f| failure_layer(f, &reports))
+            .map(|r| r.idx)
+            .collect_vec();
+        // the forged output was laid out by the second sum
+        assert!(!layers.is_empty() && layers.iter().all(|idx| *idx == 1));
+    }
+
+    #[test]
+    fn test_without_weights() {
+        let model = scaled_model(&[2.0, 3.0]);
+        let mut renamed = scaled_model(&[4.0, -5.0]);
+        if let Some(NodeType::Node(n)) = renamed.graph.nodes.get_mut(&1) {
+            n.name = "weights".into();
+        }
+        assert_eq!(model.without_weights(), renamed.without_weights());
+        // but not the shapes of the weights
+        assert_ne!(
+            model.without_weights(),
+            scaled_model(&[2.0, 3.0, 4.0]).without_weights()
+        );
+
+        let weights = |values: &[f32]| match constant(values) {
+            SupportedOp::Constant(c) => c,
+            _ => unreachable!(),
+        };
+        let conv = |values: &[f32]| {
+            let mut op = SupportedOp::Linear(PolyOp::Conv {
+                kernel: weights(values).quantized_values,
+                bias: None,
+                padding: [(0, 0); 2],
+                stride: (1, 1),
+            });
+            zero_weights(&mut op);
+            op
+        };
+        assert_eq!(conv(&[1.0, 2.0]), conv(&[3.0, 4.0]));
+    }
+
+    #[test]
+    fn test_remove_identity_nodes() {
+        // input -> identity -> identity -> * weights -> identity, the last identity being the output
+        let mut model = Model::default();
+        let input = SupportedOp::Input(Input {
+            scale: 0,
+            datum_type: InputType::F32,
+        });
+        let dims = [2];
+        let identity = SupportedOp::Linear(PolyOp::Identity);
+        model.graph.nodes.insert(0, node(0, input, vec![], &dims));
+        model
+            .graph
+            .nodes
+            .insert(1, node(1, identity.clone(), vec![(0, 0)], &dims));
+        model
+            .graph
+            .nodes
+            .insert(2, node(2, identity.clone(), vec![(1, 0)], &dims));
+        model
+            .graph
+            .nodes
+            .insert(3, node(3, constant(&[2.0, 3.0]), vec![], &dims));
+        model.graph.nodes.insert(
+            4,
+            node(
+                4,
+                SupportedOp::Linear(PolyOp::Mult),
+                vec![(2, 0), (3, 0)],
+                &dims,
+            ),
+        );
+        model
+            .graph
+            .nodes
+            .insert(5, node(5, identity, vec![(4, 0)], &dims));
+        model.graph.inputs = vec![0];
+        model.graph.outputs = vec![(5, 0)];
+
+        let inputs = [felts(&[1, -2])];
+        let expected = model.forward(&inputs).unwrap().outputs;
+        assert_eq!(expected, vec![felts(&[2, -6])]);
+
+        Model::remove_identity_nodes(&mut model.graph.nodes, &HashSet::from([5]));
+        // the chain of identities is bypassed, the output is kept
+        assert_eq!(
+            model.graph.nodes.keys().cloned().collect_vec(),
+            vec![0, 3, 4, 5]
+        );
+        assert_eq!(model.graph.nodes[&4].inputs(), vec![(0, 0), (3, 0)]);
+        assert_eq!(model.graph.nodes[&0].num_uses(), 1);
+        assert_eq!(model.forward(&inputs).unwrap().outputs, expected);
+    }
+
+    #[test]
+    fn test_fold_constants() {
+        // input * (first * second), where the product of the weights only depends on constants
+        let mut model = Model::default();
+        let input = SupportedOp::Input(Input {
+            scale: 0,
+            datum_type: InputType::F32,
+        });
+        let dims = [2];
+        let mult = SupportedOp::Linear(PolyOp::Mult);
+        model.graph.nodes.insert(0, node(0, input, vec![], &dims));
+        model
+            .graph
+            .nodes
+            .insert(1, node(1, constant(&[2.0, -1.0]), vec![], &dims));
+        model
+            .graph
+            .nodes
+            .insert(2, node(2, constant(&[3.0, 3.0]), vec![], &dims));
+        model
+            .graph
+            .nodes
+            .insert(3, node(3, mult.clone(), vec![(1, 0), (2, 0)], &dims));
+        model
+            .graph
+            .nodes
+            .insert(4, node(4, mult, vec![(0, 0), (3, 0)], &dims));
+        model.graph.inputs = vec![0];
+        model.graph.outputs = vec![(4, 0)];
+
+        let inputs = [felts(&[1, 2])];
+        let expected = model.forward(&inputs).unwrap().outputs;
+        assert_eq!(expected, vec![felts(&[6, -6])]);
+
+        let nodes = &mut model.graph.nodes;
+        for idx in [3, 4] {
+            let mut n = match nodes.remove(&idx) {
+                Some(NodeType::Node(n)) => n,
+                _ => unreachable!(),
+            };
+            Model::fold_constants(&mut n, nodes);
+            nodes.insert(idx, NodeType::Node(n));
+        }
+        // the product of the weights is a constant, the product with the input is left as is
+        assert!(nodes[&3].is_constant());
+        assert!(nodes[&3].inputs().is_empty());
+        assert!(matches!(
+            nodes[&4].opkind(),
+            SupportedOp::Linear(PolyOp::Mult)
+        ));
+        assert_eq!(nodes[&1].num_uses(), 0);
+        assert_eq!(nodes[&2].num_uses(), 0);
+        assert_eq!(model.forward(&inputs).unwrap().outputs, expected);
+
+        // the folded weights are then dropped
+        Model::remove_unused_nodes(&mut model.graph.nodes);
+        assert_eq!(
+            model.graph.nodes.keys().cloned().collect_vec(),
+            vec![0, 3, 4]
+        );
+        assert_eq!(model.forward(&inputs).unwrap().outputs, expected);
+    }
+
+    #[test]
+    fn test_fuse_rebased_relus() {
+        // relu(input * weights / 4)
+        let mut model = scaled_model(&[3.0, 3.0, -2.0]);
+        let dims = [3];
+        if let Some(NodeType::Node(n)) = model.graph.nodes.get_mut(&2) {
+            n.opkind = RebaseScale::rebase(n.opkind.clone(), 0, 2, 1);
+        }
+        model.graph.nodes.insert(
+            3,
+            node(
+                3,
+                SupportedOp::Nonlinear(LookupOp::ReLU),
+                vec![(2, 0)],
+                &dims,
+            ),
+        );
+        model.graph.outputs = vec![(3, 0)];
+
+        let inputs = [felts(&[5, -7, 10])];
+        let expected = model.forward(&inputs).unwrap().outputs;
+        assert_eq!(expected, vec![felts(&[4, 0, 0])]);
+
+        // the rescale can't be fused when it is an output itself
+        let mut unfused = model.graph.nodes.clone();
+        Model::fuse_rebased_relus(&mut unfused, &HashSet::from([2, 3]));
+        assert!(unfused[&2].opkind().get_rebased().is_some());
+
+        Model::fuse_rebased_relus(&mut model.graph.nodes, &HashSet::from([3]));
+        assert!(matches!(
+            model.graph.nodes[&2].opkind(),
+            SupportedOp::Linear(PolyOp::Mult)
+        ));
+        assert!(matches!(
+            model.graph.nodes[&3].opkind(),
+            SupportedOp::Nonlinear(LookupOp::DivReLU { .. })
+        ));
+        assert_eq!(model.forward(&inputs).unwrap().outputs, expected);
+    }
+
+    /// a 2x2 convolution of a 1x`channels`x3x3 input by a `groups` grouped kernel
+    fn conv_model(channels: usize, groups: usize) -> Model {
+        let mut model = Model::default();
+        let input = SupportedOp::Input(Input {
+            scale: 0,
+            datum_type: InputType::F32,
+        });
+        let kernel = Tensor::new(
+            Some(
+                &(0..4 * channels / groups)
+                    .map(|x| i128_to_felt(x as i128 - 2))
+                    .collect_vec(),
+            ),
+            &[1, channels / groups, 2, 2],
+        )
+        .unwrap();
+        let conv = SupportedOp::Linear(PolyOp::Conv {
+            kernel,
+            bias: None,
+            padding: [(0, 0); 2],
+            stride: (1, 1),
+        });
+        model
+            .graph
+            .nodes
+            .insert(0, node(0, input, vec![], &[1, channels, 3, 3]));
+        model
+            .graph
+            .nodes
+            .insert(1, node(1, conv, vec![(0, 0)], &[1, 1, 2, 2]));
+        model.graph.inputs = vec![0];
+        model.graph.outputs = vec![(1, 0)];
+        model
+    }
+
+    #[test]
+    fn test_lower_convs_to_im2col() {
+        let mut model = conv_model(2, 1);
+        let inputs = [Tensor::new(
+            Some(&(0..18).map(|x| i128_to_felt(x as i128 - 9)).collect_vec()),
+            &[1, 2, 3, 3],
+        )
+        .unwrap()];
+        let expected = model.forward(&inputs).unwrap().outputs;
+
+        Model::lower_convs_to_im2col(&mut model.graph.nodes);
+        assert!(matches!(
+            model.graph.nodes[&1].opkind(),
+            SupportedOp::Linear(PolyOp::Im2ColConv { .. })
+        ));
+        assert_eq!(model.forward(&inputs).unwrap().outputs, expected);
+
+        // grouped convolutions are left as is
+        let mut grouped = conv_model(2, 2);
+        Model::lower_convs_to_im2col(&mut grouped.graph.nodes);
+        assert!(matches!(
+            grouped.graph.nodes[&1].opkind(),
+            SupportedOp::Linear(PolyOp::Conv { .. })
+        ));
+    }
+
+    #[test]
+    fn test_fuse_axis_moves() {
+        // input -> move axis 0 to 2 -> move axis 0 to 1, which reverses the axes
+        let mut model = Model::default();
+        let input = SupportedOp::Input(Input {
+            scale: 0,
+            datum_type: InputType::F32,
+        });
+        model
+            .graph
+            .nodes
+            .insert(0, node(0, input, vec![], &[2, 3, 4]));
+        let first = SupportedOp::Linear(PolyOp::MoveAxis {
+            source: 0,
+            destination: 2,
+        });
+        model
+            .graph
+            .nodes
+            .insert(1, node(1, first, vec![(0, 0)], &[3, 4, 2]));
+        let second = SupportedOp::Linear(PolyOp::MoveAxis {
+            source: 0,
+            destination: 1,
+        });
+        model
+            .graph
+            .nodes
+            .insert(2, node(2, second, vec![(1, 0)], &[4, 3, 2]));
+        model.graph.inputs = vec![0];
+        model.graph.outputs = vec![(2, 0)];
+
+        let inputs = [Tensor::new(
+            Some(&(0..24).map(|x| i128_to_felt(x as i128)).collect_vec()),
+            &[2, 3, 4],
+        )
+        .unwrap()];
+        let expected = model.forward(&inputs).unwrap().outputs;
+
+        Model::fuse_axis_moves(&mut model.graph.nodes, &HashSet::from([2]));
+        Model::remove_unused_nodes(&mut model.graph.nodes);
+        assert_eq!(model.graph.nodes.keys().cloned().collect_vec(), vec![0, 2]);
+        assert!(matches!(
+            model.graph.nodes[&2].opkind(),
+            SupportedOp::Linear(PolyOp::Permute { ref perm }) if *perm == vec![2, 1, 0]
+        ));
+        assert_eq!(model.graph.nodes[&2].inputs(), vec![(0, 0)]);
+        assert_eq!(model.forward(&inputs).unwrap().outputs, expected);
+    }
+
+    #[test]
+    fn test_round_public_outputs() {
+        // input * [2, 2] with the input and the output at scale 2
+        let mut model = scaled_model(&[2.0, 2.0]);
+        model.graph.nodes.insert(
+            0,
+            node(
+                0,
+                SupportedOp::Input(Input {
+                    scale: 2,
+                    datum_type: InputType::F32,
+                }),
+                vec![],
+                &[2],
+            ),
+        );
+        for idx in [0, 2] {
+            if let Some(NodeType::Node(n)) = model.graph.nodes.get_mut(&idx) {
+                n.out_scale = 2;
+            }
+        }
+
+        let outputs = HashSet::from([2]);
+        // rebasing up would only pad the output with zero bits
+        let mut finer = model.graph.nodes.clone();
+        assert!(Model::round_public_outputs(&mut finer, &outputs, 3).is_err());
+        Model::round_public_outputs(&mut model.graph.nodes, &outputs, 0).unwrap();
+        assert_eq!(model.graph.nodes[&2].out_scales(), vec![0]);
+
+        let logrows = 10;
+        let run_args = RunArgs {
+            logrows,
+            lookup_range: (-64, 64),
+            output_visibility: Visibility::Public,
+            ..Default::default()
+        };
+        let mut circuit = super::super::GraphCircuit::new(model, &run_args).unwrap();
+        // [1.5, -0.5] * 2 at scale 0
+        let witness = circuit.forward(&mut [felts(&[6, -2])], None, None).unwrap();
+        assert_eq!(witness.outputs, vec![felts(&[3, -1]).to_vec()]);
+        circuit.load_graph_witness(&witness).unwrap();
+        let instances = circuit.prepare_public_inputs(&witness).unwrap();
+        assert_eq!(instances, felts(&[3, -1]).to_vec());
+        MockProver::run(logrows, &circuit, vec![instances.clone()])
+            .unwrap()
+            .assert_satisfied();
+
+        // the instances have to be the rounded outputs
+        let mut tampered = instances;
+        tampered[0] = i128_to_felt(2);
+        assert!(MockProver::run(logrows, &circuit, vec![tampered])
+            .unwrap()
+            .verify()
+            .is_err());
+    }
+}
@@ -0,0 +1,402 @@
+use std::error::Error;
+use std::fmt::Debug;
+
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner},
+    plonk::{Circuit, ConstraintSystem, Error as PlonkError},
+};
+use halo2curves::bn256::Fr as Fp;
+use log::error;
+
+use super::{GraphSettings, Model, ModelConfig, ModelVars, Visibility};
+use crate::circuit::lookup::LookupOp;
+use crate::circuit::ops::region::RegionCtx;
+use crate::circuit::BaseConfig;
+use crate::tensor::{Tensor, ValTensor};
+
+// The passes are laid out one after the other in a single region. The circuit has no instances, so the
+// weights have to be fixed: they are then part of the verifying key, and a proof made with any other
+// weights doesn't verify against it.
+
+/// Constraints laid out across the forward passes of a [MultiPassCircuit]
+pub trait PassConstraints: Clone + Debug {
+    /// The lookup tables the constraints need
+    fn required_lookups(&self) -> Vec<LookupOp>;
+
+    /// Constrains the assigned `inputs` and `outputs` of the passes, indexed by pass then by model input
+    /// (resp. output)
+    fn layout(
+        &self,
+        config: &BaseConfig<Fp>,
+        region: &mut RegionCtx<Fp>,
+        inputs: &[Vec<ValTensor<Fp>>],
+        outputs: &[Vec<ValTensor<Fp>>],
+    ) -> Result<(), Box<dyn Error>>;
+}
+
+/// A circuit laying out several forward passes of a model that share one assignment of its weights,
+/// with [PassConstraints] across them. The settings are those generated for a single forward pass of
+/// the model, with private inputs and outputs and fixed params.
+#[derive(Clone, Debug)]
+pub struct MultiPassCircuit<C: PassConstraints> {
+    /// The model
+    pub model: Model,
+    /// The settings of the circuit, sized for all the passes
+    pub settings: GraphSettings,
+    /// The constraints across the passes
+    pub constraints: C,
+    /// The quantized inputs of each pass
+    pub inputs: Vec<Vec<Tensor<Fp>>>,
+}
+
+impl<C: PassConstraints> MultiPassCircuit<C> {
+    /// Creates the circuit from the `settings` of a single forward pass of `model`, with one pass per
+    /// element of `inputs`
+    pub fn new(
+        model: Model,
+        settings: &GraphSettings,
+        constraints: C,
+        inputs: Vec<Vec<Tensor<Fp>>>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let run_args = &settings.run_args;
+        if run_args.input_visibility != Visibility::Private
+            || run_args.output_visibility != Visibility::Private
+        {
+            return Err("multi pass circuits only support private inputs and outputs".into());
+        }
+        // per layer visibilities can make some of the constants private
+        if run_args.param_visibility != Visibility::Fixed
+            || model
+                .const_values()
+                .iter()
+                .any(|c| c.visibility() != Some(Visibility::Fixed))
+        {
+            return Err("multi pass circuits only support fixed params".into());
+        }
+        if inputs.is_empty() {
+            return Err("multi pass circuits need at least one pass".into());
+        }
+        let num_inputs = model.graph.inputs.len();
+        if inputs.iter().any(|i| i.len() != num_inputs) {
+            return Err(format!("each pass needs {} inputs", num_inputs).into());
+        }
+
+        let mut settings = settings.clone();
+        let passes = inputs.len();
+        // every pass, and a few cells per element for the constraints between them
+        let input_len: usize = inputs[0].iter().map(|i| i.len()).sum();
+        let output_len: usize = model
+            .graph
+            .output_shapes()
+            .iter()
+            .map(|s| s.iter().product::<usize>())
+            .sum();
+        let len = passes * (input_len + output_len);
+        settings.total_assignments = passes * settings.total_assignments + 4 * len;
+        settings.total_const_size = passes * settings.total_const_size + len;
+        for lookup in constraints.required_lookups() {
+            if !settings.required_lookups.contains(&lookup) {
+                settings.required_lookups.push(lookup);
+            }
+        }
+
+        Ok(MultiPassCircuit {
+            model,
+            settings,
+            constraints,
+            inputs,
+        })
+    }
+
+    /// The number of forward passes
+    pub fn num_passes(&self) -> usize {
+        self.inputs.len()
+    }
+
+    fn pass_inputs(&self, pass: usize) -> Vec<ValTensor<Fp>> {
+        self.inputs[pass]
+            .iter()
+            .map(|i| {
+                let mut i = i.clone();
+                i.set_visibility(&Visibility::Private);
+                ValTensor::from(i)
+            })
+            .collect()
+    }
+}
+
+impl<C: PassConstraints> Circuit<Fp> for MultiPassCircuit<C> {
+    type Config = ModelConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+    type Params = GraphSettings;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn params(&self) -> Self::Params {
+        self.settings.clone()
+    }
+
+    fn configure_with_params(cs: &mut ConstraintSystem<Fp>, params: Self::Params) -> Self::Config {
+        let run_args = &params.run_args;
+        let vars = ModelVars::new(
+            cs,
+            run_args.logrows as usize,
+            params.total_assignments,
+            run_args.num_inner_cols,
+            params.total_const_size,
+            false,
+            run_args.unblinded_advice,
+        );
+
+        let base = Model::configure(
+            cs,
+            &vars,
+            run_args.lookup_range,
+            run_args.logrows as usize,
+            params.required_lookups,
+            params.check_mode,
+        )
+        .unwrap();
+
+        ModelConfig { base, vars }
+    }
+
+    fn configure(_: &mut ConstraintSystem<Fp>) -> Self::Config {
+        unimplemented!("you should call configure_with_params instead")
+    }
+
+    fn synthesize(
+        &self,
+        mut config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), PlonkError> {
+        config.base.layout_tables(&mut layouter)?;
+
+        layouter.assign_region(
+            || "passes",
+            |region| {
+                let mut region = RegionCtx::new(region, 0, self.settings.run_args.num_inner_cols);
+                let mut config = config.clone();
+
+                let mut layout = || -> Result<(), Box<dyn Error>> {
+                    let mut inputs = vec![];
+                    let mut outputs = vec![];
+                    for pass in 0..self.num_passes() {
+                        let (i, o) = self.model.layout_pass(
+                            &mut config,
+                            &mut region,
+                            &self.pass_inputs(pass),
+                        )?;
+                        inputs.push(i);
+                        outputs.push(o);
+                    }
+                    self.constraints
+                        .layout(&config.base, &mut region, &inputs, &outputs)
+                };
+
+                layout().map_err(|e| {
+                    error!("{}", e);
+                    PlonkError::Synthesis
+                })
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::circuit::CheckMode;
+    use crate::graph::model::tests::{felts, scaled_model};
+    use crate::graph::node::{NodeType, SupportedOp};
+    use crate::graph::{ModelProperty, PropertyCircuit, PropertyRelation};
+    use crate::pfsys::{Snark, TranscriptType};
+    use crate::RunArgs;
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::plonk::{ProvingKey, VerifyingKey};
+    use halo2_proofs::poly::commitment::ParamsProver;
+    use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+    use halo2_proofs::poly::kzg::strategy::SingleStrategy;
+    use halo2curves::bn256::{Bn256, G1Affine};
+
+    /// The circuit of the passes of `input * weights` (with fixed weights) on `inputs`
+    fn circuit<C: PassConstraints>(
+        weights: &[f32],
+        constraints: C,
+        inputs: &[&[i128]],
+    ) -> MultiPassCircuit<C> {
+        let mut model = scaled_model(weights);
+        for node in model.graph.nodes.values_mut() {
+            if let NodeType::Node(n) = node {
+                if let SupportedOp::Constant(c) = &mut n.opkind {
+                    c.quantized_values.set_visibility(&Visibility::Fixed);
+                }
+            }
+        }
+        let run_args = RunArgs {
+            input_visibility: Visibility::Private,
+            output_visibility: Visibility::Private,
+            param_visibility: Visibility::Fixed,
+            lookup_range: (-64, 64),
+            logrows: 10,
+            ..Default::default()
+        };
+        let settings = model.gen_params(&run_args, CheckMode::SAFE).unwrap();
+        let inputs = inputs.iter().map(|i| vec![felts(i)]).collect();
+        MultiPassCircuit::new(model, &settings, constraints, inputs).unwrap()
+    }
+
+    /// Checks that the passes of `input * weights` (with fixed weights) on `inputs` satisfy `constraints`
+    pub(crate) fn passes_satisfy<C: PassConstraints>(
+        weights: &[f32],
+        constraints: C,
+        inputs: &[&[i128]],
+    ) -> bool {
+        let circuit = circuit(weights, constraints, inputs);
+        MockProver::run(circuit.settings.run_args.logrows, &circuit, vec![])
+            .unwrap()
+            .verify()
+            .is_ok()
+    }
+
+    /// Checks the assigned outputs of every pass
+    #[derive(Clone, Debug)]
+    struct ExpectedOutputs(Vec<Vec<i128>>);
+
+    impl PassConstraints for ExpectedOutputs {
+        fn required_lookups(&self) -> Vec<LookupOp> {
+            vec![]
+        }
+
+        fn layout(
+            &self,
+            _: &BaseConfig<Fp>,
+            _: &mut RegionCtx<Fp>,
+            _: &[Vec<ValTensor<Fp>>],
+            outputs: &[Vec<ValTensor<Fp>>],
+        ) -> Result<(), Box<dyn Error>> {
+            assert_eq!(outputs.len(), self.0.len());
+            for (output, expected) in outputs.iter().zip(&self.0) {
+                assert!(output[0].all_prev_assigned());
+                if let Ok(evals) = output[0].get_int_evals() {
+                    assert_eq!(&evals.to_vec(), expected);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_multi_pass_circuit() {
+        let expected = ExpectedOutputs(vec![vec![2, -6], vec![6, -12], vec![0, 3]]);
+        assert!(passes_satisfy(
+            &[2.0, -3.0],
+            expected,
+            &[&[1, 2], &[3, 4], &[0, -1]]
+        ));
+    }
+
+    #[test]
+    fn test_multi_pass_swapped_weights() {
+        let property = ModelProperty {
+            input: 0,
+            feature: 0,
+            relation: PropertyRelation::NonDecreasing,
+        };
+        let inputs: &[&[i128]] = &[&[1, 2], &[3, 2]];
+        // a prover swapping in all zero weights satisfies any property
+        let deployed = circuit(&[2.0, -3.0], property.clone(), inputs);
+        let swapped = circuit(&[0.0, 0.0], property, inputs);
+
+        let params = crate::pfsys::srs::gen_srs::<KZGCommitmentScheme<Bn256>>(
+            deployed.settings.run_args.logrows,
+        );
+        let keys = |circuit: &PropertyCircuit| {
+            crate::pfsys::create_keys::<KZGCommitmentScheme<Bn256>, Fp, _>(circuit, &params)
+                .unwrap()
+        };
+        let prove = |circuit: &PropertyCircuit, pk: &ProvingKey<G1Affine>| {
+            crate::pfsys::create_proof_circuit_kzg(
+                circuit.clone(),
+                &params,
+                None,
+                pk,
+                TranscriptType::EVM,
+                SingleStrategy::new(&params),
+                CheckMode::SAFE,
+                None,
+            )
+            .unwrap()
+        };
+        let verify = |proof: Snark<Fp, G1Affine>, vk: &VerifyingKey<G1Affine>| {
+            crate::pfsys::verify_proof_circuit_kzg(
+                params.verifier_params(),
+                proof,
+                vk,
+                SingleStrategy::new(params.verifier_params()),
+            )
+            .is_ok()
+        };
+
+        let deployed_pk = keys(&deployed);
+        assert!(verify(prove(&deployed, &deployed_pk), deployed_pk.get_vk()));
+        // the weights are fixed in the keys, so a proof with other weights is for another vk
+        let swapped_pk = keys(&swapped);
+        assert!(!verify(prove(&swapped, &swapped_pk), deployed_pk.get_vk()));
+    }
+
+    #[test]
+    fn test_multi_pass_circuit_visibility() {
+        let property = ModelProperty {
+            input: 0,
+            feature: 0,
+            relation: PropertyRelation::NonDecreasing,
+        };
+        let mut settings = GraphSettings::default();
+        settings.run_args.input_visibility = Visibility::Public;
+        let res = MultiPassCircuit::new(
+            Model::default(),
+            &settings,
+            property.clone(),
+            vec![vec![], vec![]],
+        );
+        assert!(res.is_err());
+
+        // private weights aren't committed to by the circuit
+        settings.run_args.input_visibility = Visibility::Private;
+        settings.run_args.output_visibility = Visibility::Private;
+        settings.run_args.param_visibility = Visibility::Private;
+        let res = MultiPassCircuit::new(
+            Model::default(),
+            &settings,
+            property.clone(),
+            vec![vec![], vec![]],
+        );
+        assert!(res.is_err());
+        let res = MultiPassCircuit::new(
+            scaled_model(&[1.0]),
+            &GraphSettings {
+                run_args: RunArgs {
+                    param_visibility: Visibility::Fixed,
+                    ..settings.run_args.clone()
+                },
+                ..settings.clone()
+            },
+            property.clone(),
+            vec![vec![], vec![]],
+        );
+        assert!(res.is_err());
+
+        settings.run_args.param_visibility = Visibility::Fixed;
+        let circuit =
+            MultiPassCircuit::new(Model::default(), &settings, property, vec![vec![], vec![]])
+                .unwrap();
+        assert_eq!(circuit.num_passes(), 2);
+        assert_eq!(
+            circuit.settings.required_lookups,
+            vec![crate::circuit::ops::cmp::gte_lookup()]
+        );
+    }
+}
@@ -132,6 +132,10 @@ pub struct RebaseScale {
     pub target_scale: i32,
     /// The original scale of the operation's inputs.
     pub original_scale: i32,
+    /// The axis and the per channel constants the output is multiplied by before the division, which bring
+    /// the channels of an op with per channel weight scales to a common scale (see [HybridOp::channel_rescale])
+    #[serde(default)]
+    pub channel_multipliers: Option<(usize, Vec<u64>)>,
 }
 
 impl RebaseScale {
@@ -151,9 +155,10 @@ impl RebaseScale {
             if let Some(op) = inner.get_rebased() {
                 SupportedOp::RebaseScale(RebaseScale {
                     inner: op.inner.clone(),
-                    target_scale: op.target_scale,
+                    target_scale: global_scale * scale_rebase_multiplier as i32,
                     multiplier: op.multiplier * multiplier,
                     original_scale: op.original_scale,
+                    channel_multipliers: op.channel_multipliers.clone(),
                 })
             } else {
                 SupportedOp::RebaseScale(RebaseScale {
@@ -161,6 +166,7 @@ impl RebaseScale {
                     target_scale: global_scale * scale_rebase_multiplier as i32,
                     multiplier,
                     original_scale: op_out_scale,
+                    channel_multipliers: None,
                 })
             }
         } else {
@@ -179,9 +185,10 @@ impl RebaseScale {
             if let Some(op) = inner.get_rebased() {
                 SupportedOp::RebaseScale(RebaseScale {
                     inner: op.inner.clone(),
-                    target_scale: op.target_scale,
+                    target_scale,
                     multiplier: op.multiplier * multiplier,
                     original_scale: op.original_scale,
+                    channel_multipliers: op.channel_multipliers.clone(),
                 })
             } else {
                 SupportedOp::RebaseScale(RebaseScale {
@@ -189,6 +196,7 @@ impl RebaseScale {
                     target_scale,
                     multiplier,
                     original_scale: op_out_scale,
+                    channel_multipliers: None,
                 })
             }
         } else {
@@ -202,9 +210,27 @@ impl Op<Fp> for RebaseScale {
         self
     }
     fn f(&self, x: &[Tensor<Fp>]) -> Result<crate::circuit::ForwardResult<Fp>, TensorError> {
-        let mut res = Op::<Fp>::f(&*self.inner, x)?;
-
-        let ri = res.output.map(felt_to_i128);
+        // rescale the exact, widened, output of the inner op when there is one
+        let (mut res, ri) = match self.inner.f_int(x)? {
+            Some(ri) => (
+                crate::circuit::ForwardResult {
+                    output: ri.map(i128_to_felt),
+                    intermediate_lookups: vec![],
+                },
+                ri,
+            ),
+            None => {
+                let res = Op::<Fp>::f(&*self.inner, x)?;
+                let ri = res.output.map(felt_to_i128);
+                (res, ri)
+            }
+        };
+        let ri = match &self.channel_multipliers {
+            Some((axis, multipliers)) => {
+                crate::tensor::ops::nonlinearities::channel_mult(&ri, *axis, multipliers)?
+            }
+            None => ri,
+        };
         let rescaled = crate::tensor::ops::nonlinearities::const_div(&ri, self.multiplier);
         res.output = rescaled.map(i128_to_felt);
 
@@ -214,11 +240,20 @@ impl Op<Fp> for RebaseScale {
     }
 
     fn as_string(&self) -> String {
-        format!(
-            "REBASED (div={:?}) ({})",
-            self.multiplier,
-            self.inner.as_string()
-        )
+        match &self.channel_multipliers {
+            Some((axis, multipliers)) => format!(
+                "REBASED (axis={}, multipliers={:?}, div={:?}) ({})",
+                axis,
+                multipliers,
+                self.multiplier,
+                self.inner.as_string()
+            ),
+            None => format!(
+                "REBASED (div={:?}) ({})",
+                self.multiplier,
+                self.inner.as_string()
+            ),
+        }
     }
 
     fn out_scale(&self, _: Vec<crate::Scale>) -> crate::Scale {
@@ -241,14 +276,23 @@ impl Op<Fp> for RebaseScale {
     ) -> Result<Option<crate::tensor::ValTensor<Fp>>, Box<dyn Error>> {
         let original_res = self.inner.layout(config, region, values)?.unwrap();
 
-        Ok(Some(crate::circuit::layouts::nonlinearity(
-            config,
-            region,
-            &[original_res],
-            &LookupOp::Div {
-                denom: crate::circuit::utils::F32(self.multiplier as f32),
-            },
-        )?))
+        let denom = crate::circuit::utils::F32(self.multiplier as f32);
+        Ok(Some(match &self.channel_multipliers {
+            Some((axis, multipliers)) => crate::circuit::layouts::channel_rescale(
+                config,
+                region,
+                &[original_res],
+                *axis,
+                multipliers,
+                denom,
+            )?,
+            None => crate::circuit::layouts::nonlinearity(
+                config,
+                region,
+                &[original_res],
+                &LookupOp::Div { denom },
+            )?,
+        }))
     }
 
     fn clone_dyn(&self) -> Box<dyn Op<Fp>> {
@@ -294,6 +338,14 @@ impl SupportedOp {
         }
     }
 
+    /// Evaluates the op over widened i128 integers, see [PolyOp::f_int]
+    pub fn f_int(&self, inputs: &[Tensor<Fp>]) -> Result<Option<Tensor<i128>>, TensorError> {
+        match self {
+            SupportedOp::Linear(op) => op.f_int(inputs),
+            _ => Ok(None),
+        }
+    }
+
     ///
     pub fn get_rebased(&self) -> Option<&RebaseScale> {
         match self {
@@ -514,6 +566,9 @@ pub struct Node {
     pub idx: usize,
     /// The node's num of uses
     pub num_uses: usize,
+    /// The name of the layer the node was created from in the onnx graph
+    #[serde(default)]
+    pub name: String,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -699,6 +754,7 @@ impl Node {
             out_dims,
             out_scale,
             num_uses,
+            name: node.name.clone(),
         })
     }
 }
@@ -0,0 +1,182 @@
+use std::error::Error;
+
+use halo2curves::bn256::Fr as Fp;
+use serde::{Deserialize, Serialize};
+
+use super::{MultiPassCircuit, PassConstraints};
+use crate::circuit::lookup::LookupOp;
+use crate::circuit::ops::{cmp, layouts::enforce_equality, region::RegionCtx};
+use crate::circuit::BaseConfig;
+use crate::fieldutils::i128_to_felt;
+use crate::tensor::{Tensor, ValTensor, ValType};
+
+// A property circuit runs the model twice, on inputs that only differ in one feature, with the same
+// fixed weights. Its proof attests the property of the model of its verifying key for that pair of
+// inputs, which both stay private.
+
+/// The relation a [ModelProperty] constrains between the outputs of the two forward passes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PropertyRelation {
+    /// The outputs are equal, eg. the model is fair with respect to the feature
+    Equal,
+    /// The outputs of the second pass are >= those of the first, whose feature is <= that of the second
+    NonDecreasing,
+    /// The outputs of the second pass are <= those of the first, whose feature is <= that of the second
+    NonIncreasing,
+}
+
+/// A property of a model, proven over two forward passes whose inputs only differ in one feature
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelProperty {
+    /// the index of the model input the feature belongs to
+    pub input: usize,
+    /// the index of the feature in the (flattened) input
+    pub feature: usize,
+    /// the relation constrained between every element of the outputs of the two passes
+    pub relation: PropertyRelation,
+}
+
+impl PassConstraints for ModelProperty {
+    fn required_lookups(&self) -> Vec<LookupOp> {
+        match self.relation {
+            PropertyRelation::Equal => vec![],
+            PropertyRelation::NonDecreasing | PropertyRelation::NonIncreasing => {
+                vec![cmp::gte_lookup()]
+            }
+        }
+    }
+
+    /// Constrains the inputs of the two passes to only differ in the feature, and the relation between
+    /// their outputs
+    fn layout(
+        &self,
+        config: &BaseConfig<Fp>,
+        region: &mut RegionCtx<Fp>,
+        inputs: &[Vec<ValTensor<Fp>>],
+        outputs: &[Vec<ValTensor<Fp>>],
+    ) -> Result<(), Box<dyn Error>> {
+        let [a, b] = match inputs {
+            [a, b] => [a, b],
+            _ => return Err("a property is proven over two passes".into()),
+        };
+        if self.input >= a.len() || self.feature >= a[self.input].len() {
+            return Err(format!("no feature {} in input {}", self.feature, self.input).into());
+        }
+        for (i, (a, b)) in a.iter().zip(b).enumerate() {
+            if i != self.input {
+                enforce_equality(config, region, &[a.clone(), b.clone()])?;
+                continue;
+            }
+            let (mut a, mut b) = (a.clone(), b.clone());
+            a.flatten();
+            b.flatten();
+            for range in [0..self.feature, self.feature + 1..a.len()] {
+                if !range.is_empty() {
+                    let (a, b) = (a.get_slice(&[range.clone()])?, b.get_slice(&[range])?);
+                    enforce_equality(config, region, &[a, b])?;
+                }
+            }
+            if self.relation != PropertyRelation::Equal {
+                let feature = self.feature..self.feature + 1;
+                let (a, b) = (a.get_slice(&[feature.clone()])?, b.get_slice(&[feature])?);
+                let ordered = cmp::lte(config, region, &a, &b)?;
+                enforce_equality(config, region, &[ones(1), ordered])?;
+            }
+        }
+
+        let [a, b] = match outputs {
+            [a, b] => [a, b],
+            _ => return Err("a property is proven over two passes".into()),
+        };
+        for (a, b) in a.iter().zip(b) {
+            let holds = match self.relation {
+                PropertyRelation::Equal => {
+                    enforce_equality(config, region, &[a.clone(), b.clone()])?;
+                    continue;
+                }
+                PropertyRelation::NonDecreasing => cmp::gte(config, region, b, a)?,
+                PropertyRelation::NonIncreasing => cmp::lte(config, region, b, a)?,
+            };
+            let mut expected = ones(holds.len());
+            expected.reshape(holds.dims())?;
+            enforce_equality(config, region, &[expected, holds])?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A tensor of `len` constant ones
+fn ones(len: usize) -> ValTensor<Fp> {
+    Tensor::from(vec![ValType::Constant(i128_to_felt(1)); len].into_iter()).into()
+}
+
+/// A circuit proving a [ModelProperty] on a pair of private inputs, see [MultiPassCircuit]
+pub type PropertyCircuit = MultiPassCircuit<ModelProperty>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::multipass::tests::passes_satisfy;
+
+    fn property(feature: usize, relation: PropertyRelation) -> ModelProperty {
+        ModelProperty {
+            input: 0,
+            feature,
+            relation,
+        }
+    }
+
+    #[test]
+    fn test_monotonic_property() {
+        // input * [2, -3] increases with the first feature and decreases with the second
+        let weights = [2.0, -3.0];
+        let non_decreasing = |feature| property(feature, PropertyRelation::NonDecreasing);
+        let non_increasing = |feature| property(feature, PropertyRelation::NonIncreasing);
+        assert!(passes_satisfy(
+            &weights,
+            non_decreasing(0),
+            &[&[1, 2], &[3, 2]]
+        ));
+        assert!(!passes_satisfy(
+            &weights,
+            non_decreasing(1),
+            &[&[1, 2], &[1, 4]]
+        ));
+        assert!(passes_satisfy(
+            &weights,
+            non_increasing(1),
+            &[&[1, 2], &[1, 4]]
+        ));
+
+        // the inputs can only differ in the feature
+        assert!(!passes_satisfy(
+            &weights,
+            non_decreasing(0),
+            &[&[1, 2], &[3, 1]]
+        ));
+        // whose first value is the smallest
+        assert!(!passes_satisfy(
+            &weights,
+            non_increasing(0),
+            &[&[3, 2], &[1, 2]]
+        ));
+    }
+
+    #[test]
+    fn test_equal_property() {
+        let equal = property(0, PropertyRelation::Equal);
+        // the first feature has no weight
+        assert!(passes_satisfy(
+            &[0.0, 3.0],
+            equal.clone(),
+            &[&[1, 2], &[5, 2]]
+        ));
+        assert!(!passes_satisfy(
+            &[1.0, 3.0],
+            equal.clone(),
+            &[&[1, 2], &[5, 2]]
+        ));
+        assert!(!passes_satisfy(&[0.0, 3.0], equal, &[&[1, 2], &[5, 3]]));
+    }
+}
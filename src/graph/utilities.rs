@@ -73,6 +73,80 @@ pub fn multiplier_to_scale(mult: f64) -> crate::Scale {
     mult.log2().round() as crate::Scale
 }
 
+/// Per-element comparison of a floating point reference output against the fixed point output of a circuit.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AccuracyReport {
+    /// The absolute error of each (dequantized) output element
+    pub element_errors: Vec<f64>,
+    /// The largest absolute error
+    pub max_error: f64,
+    /// The mean absolute error
+    pub mean_error: f64,
+    /// Whether the reference and the circuit output agree on the index of the largest element
+    pub argmax_agreement: bool,
+}
+
+/// Compares a floating point `reference` output (eg. from the original framework) to the `quantized` output of a circuit
+/// at a given `scale`, so users can tune scales before committing to a proof.
+pub fn accuracy_report(
+    reference: &[f32],
+    quantized: &Tensor<i128>,
+    scale: crate::Scale,
+) -> Result<AccuracyReport, Box<dyn Error>> {
+    if reference.len() != quantized.len() {
+        return Err(format!(
+            "reference output has {} elements but the circuit output has {}",
+            reference.len(),
+            quantized.len()
+        )
+        .into());
+    }
+
+    let mult = scale_to_multiplier(scale);
+    let dequantized = quantized.iter().map(|x| *x as f64 / mult).collect_vec();
+
+    let element_errors = reference
+        .iter()
+        .zip(dequantized.iter())
+        .map(|(r, q)| (*r as f64 - q).abs())
+        .collect_vec();
+
+    let max_error = element_errors.iter().cloned().fold(0.0, f64::max);
+    let mean_error = if element_errors.is_empty() {
+        0.0
+    } else {
+        element_errors.iter().sum::<f64>() / element_errors.len() as f64
+    };
+
+    let argmax = |v: &[f64]| {
+        v.iter()
+            .enumerate()
+            .fold(None, |acc: Option<(usize, f64)>, (i, x)| match acc {
+                Some((_, max)) if max >= *x => acc,
+                _ => Some((i, *x)),
+            })
+            .map(|(i, _)| i)
+    };
+    let reference = reference.iter().map(|x| *x as f64).collect_vec();
+    let argmax_agreement = argmax(&reference) == argmax(&dequantized);
+
+    Ok(AccuracyReport {
+        element_errors,
+        max_error,
+        mean_error,
+        argmax_agreement,
+    })
+}
+
+/// The coordinate transformation (eg. `HalfPixel`) of a tract Resize op, from its debug representation
+#[cfg(not(target_arch = "wasm32"))]
+fn resize_coord_transformer(resize_node: &str) -> Option<&str> {
+    resize_node
+        .split("coord_transformer: ")
+        .nth(1)
+        .and_then(|s| s.split([',', ' ', '}']).next())
+}
+
 /// Gets the shape of a onnx node's outlets.
 #[cfg(not(target_arch = "wasm32"))]
 pub fn node_output_shapes(
@@ -197,6 +271,114 @@ fn extract_tensor_value(
     Ok(const_value)
 }
 
+/// Computes the zero padding, as `[(top, left), (bottom, right)]`, of a 'same' convolution or pooling,
+/// i.e one whose output has `ceil(input / stride)` rows and columns.
+/// If `upper` is true any odd padding is placed at the end (ONNX `SAME_UPPER`, TF `SAME`), else at the start (`SAME_LOWER`).
+pub fn same_padding(
+    image_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    stride: (usize, usize),
+    upper: bool,
+) -> [(usize, usize); 2] {
+    let total = |input: usize, kernel: usize, stride: usize| {
+        let output = (input + stride - 1) / stride;
+        ((output - 1) * stride + kernel).saturating_sub(input)
+    };
+    let total_h = total(image_dims.0, kernel_dims.0, stride.0);
+    let total_w = total(image_dims.1, kernel_dims.1, stride.1);
+
+    let (before_h, before_w) = if upper {
+        (total_h / 2, total_w / 2)
+    } else {
+        (total_h - total_h / 2, total_w - total_w / 2)
+    };
+
+    [
+        (before_h, before_w),
+        (total_h - before_h, total_w - before_w),
+    ]
+}
+
+/// Extracts the padding of a convolution or pooling node, resolving 'same' and 'valid' padding against the input dims
+#[cfg(not(target_arch = "wasm32"))]
+fn extract_padding(
+    pool_spec: &PoolSpec,
+    image_dims: &[usize],
+) -> Result<[(usize, usize); 2], Box<dyn std::error::Error>> {
+    let padding = match &pool_spec.padding {
+        PaddingSpec::Explicit(b, a) | PaddingSpec::ExplicitOnnxPool(b, a, _) => {
+            if b.len() == 2 && a.len() == 2 {
+                [(b[0], b[1]), (a[0], a[1])]
+            } else if b.len() == 1 && a.len() == 1 {
+                [(b[0], b[0]), (a[0], a[0])]
+            } else if b.len() == 1 && a.len() == 2 {
+                [(b[0], b[0]), (a[0], a[1])]
+            } else if b.len() == 2 && a.len() == 1 {
+                [(b[0], b[1]), (a[0], a[0])]
+            } else {
+                return Err(Box::new(GraphError::MissingParams("padding".to_string())));
+            }
+        }
+        PaddingSpec::Valid => [(0, 0); 2],
+        PaddingSpec::SameUpper | PaddingSpec::SameLower => {
+            let kernel_shape = &pool_spec.kernel_shape;
+            if image_dims.len() < 2 || kernel_shape.len() != 2 {
+                return Err(Box::new(GraphError::MissingParams("padding".to_string())));
+            }
+            let stride = match &pool_spec.strides {
+                Some(s) if s.len() == 2 => (s[0], s[1]),
+                Some(s) if s.len() == 1 => (s[0], s[0]),
+                None => (1, 1),
+                _ => {
+                    return Err(Box::new(GraphError::MissingParams("strides".to_string())));
+                }
+            };
+            let image_dims = &image_dims[image_dims.len() - 2..];
+            same_padding(
+                (image_dims[0], image_dims[1]),
+                (kernel_shape[0], kernel_shape[1]),
+                stride,
+                matches!(pool_spec.padding, PaddingSpec::SameUpper),
+            )
+        }
+    };
+    Ok(padding)
+}
+
+/// Resolves the (constant) indices of a ScatterND node into `data` of shape `dims`. ONNX indices can be
+/// negative, counting back from the end of their dim.
+#[cfg(not(target_arch = "wasm32"))]
+fn scatter_nd_indices(
+    indices: &Tensor<f32>,
+    dims: &[usize],
+) -> Result<Tensor<usize>, Box<dyn std::error::Error>> {
+    let k = match indices.dims().last() {
+        Some(k) if *k > 0 && *k <= dims.len() => *k,
+        _ => {
+            return Err(Box::new(GraphError::MisformedParams(
+                "scatter nd indices of the wrong rank".to_string(),
+            )))
+        }
+    };
+    let resolved = indices
+        .iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let dim = dims[i % k] as i64;
+            let index = *x as i64;
+            let index = if index < 0 { index + dim } else { index };
+            if index < 0 || index >= dim {
+                return Err(GraphError::MisformedParams(format!(
+                    "scatter nd index {} out of bounds for a dim of size {}",
+                    x, dim
+                )));
+            }
+            Ok(index as usize)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Tensor::new(Some(&resolved), indices.dims())?)
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn load_op<C: tract_onnx::prelude::Op + Clone>(
     op: &dyn tract_onnx::prelude::Op,
@@ -214,6 +396,35 @@ fn load_op<C: tract_onnx::prelude::Op + Clone>(
     Ok(op.clone())
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+/// The [PolyOp::StridedSlice] ranges of a tract downsample, which keeps the elements `modulo`,
+/// `modulo + stride`, ... of `axis` for a positive stride, and `d - 1 - modulo`, `d - 1 - modulo + stride`,
+/// ... (where `d` is the dimension of `axis`) for a negative one.
+fn downsample_ranges(
+    dims: &[usize],
+    axis: usize,
+    stride: isize,
+    modulo: usize,
+) -> Result<Vec<(usize, usize, isize)>, Box<dyn std::error::Error>> {
+    if axis >= dims.len() || stride == 0 {
+        return Err(format!(
+            "invalid downsample of axis {} with stride {} for dims {:?}",
+            axis, stride, dims
+        )
+        .into());
+    }
+    let modulo = modulo.min(dims[axis]);
+    Ok(dims
+        .iter()
+        .enumerate()
+        .map(|(i, d)| match i == axis {
+            true if stride > 0 => (modulo, *d, stride),
+            true => (0, *d - modulo, stride),
+            false => (0, *d, 1),
+        })
+        .collect())
+}
+
 /// Matches an onnx node to a [crate::circuit::Op].
 /// Arguments
 /// * `idx` - the index of the node in the graph.
@@ -333,6 +544,27 @@ pub fn new_op_from_onnx(
 
             // Extract the max value
         }
+        "ScatterNd" => {
+            if inputs.len() != 3 {
+                return Err(Box::new(GraphError::InvalidDims(
+                    idx,
+                    "scatter nd".to_string(),
+                )));
+            };
+            // only constant indices are supported, runtime indices would need a lookup per coordinate
+            let indices = match inputs[1].opkind().get_mutable_constant() {
+                Some(c) => scatter_nd_indices(&c.raw_values, &inputs[0].out_dims()[0])?,
+                None => {
+                    return Err(Box::new(GraphError::MisformedParams(
+                        "scatter nd with non-constant indices".to_string(),
+                    )))
+                }
+            };
+            inputs[1].decrement_use();
+            deleted_indices.push(1);
+
+            SupportedOp::Hybrid(crate::circuit::ops::hybrid::HybridOp::ScatterND { indices })
+        }
         "GatherElements" => {
             if inputs.len() != 2 {
                 return Err(Box::new(GraphError::InvalidDims(
@@ -613,6 +845,8 @@ pub fn new_op_from_onnx(
             panic!("should never reach here")
         }
         "QuantizeLinearU8" | "DequantizeLinearF32" => SupportedOp::Linear(PolyOp::Identity),
+        // no-ops at inference time, these are bypassed once the graph is parsed
+        "Dropout" | "Identity" => SupportedOp::Linear(PolyOp::Identity),
         "Abs" => SupportedOp::Nonlinear(LookupOp::Abs),
         "Neg" => SupportedOp::Linear(PolyOp::Neg),
         "Sigmoid" => SupportedOp::Nonlinear(LookupOp::Sigmoid {
@@ -669,6 +903,15 @@ pub fn new_op_from_onnx(
         "Erf" => SupportedOp::Nonlinear(LookupOp::Erf {
             scale: scale_to_multiplier(inputs[0].out_scales()[0]).into(),
         }),
+        "Gelu" => SupportedOp::Nonlinear(LookupOp::Gelu {
+            scale: scale_to_multiplier(inputs[0].out_scales()[0]).into(),
+        }),
+        "Silu" | "Swish" => SupportedOp::Nonlinear(LookupOp::SiLU {
+            scale: scale_to_multiplier(inputs[0].out_scales()[0]).into(),
+        }),
+        "HardSwish" => SupportedOp::Hybrid(HybridOp::HardSwish {
+            scale: scale_to_multiplier(inputs[0].out_scales()[0]).into(),
+        }),
         "Source" => {
             let (scale, datum_type) = match node.outputs[0].fact.datum_type {
                 DatumType::Bool => (0, InputType::Bool),
@@ -813,10 +1056,22 @@ pub fn new_op_from_onnx(
                 }
             };
 
-            let axes = &op.axes;
-            SupportedOp::Linear(PolyOp::Einsum {
-                equation: axes.to_string(),
-            })
+            let equation = op.axes.to_string();
+            let einsum = SupportedOp::Linear(PolyOp::Einsum {
+                equation: equation.clone(),
+            });
+            match scales.per_channel {
+                true => match quantize_einsum_per_channel(&equation, inputs, scales.params)? {
+                    Some((axis, extra)) => rebase_channels(
+                        einsum,
+                        inputs.iter().map(|i| i.out_scales()[0]).sum(),
+                        axis,
+                        &extra,
+                    ),
+                    None => einsum,
+                },
+                false => einsum,
+            }
         }
         "Softmax" => {
             // Extract the slope layer hyperparams
@@ -852,13 +1107,7 @@ pub fn new_op_from_onnx(
             }
 
             let stride = pool_spec.strides.clone().unwrap();
-            let padding = match &pool_spec.padding {
-                PaddingSpec::Explicit(b, a) => [(b[0], b[1]), (a[0], a[1])],
-                PaddingSpec::ExplicitOnnxPool(b, a, _) => [(b[0], b[1]), (a[0], a[1])],
-                _ => {
-                    return Err(Box::new(GraphError::MissingParams("padding".to_string())));
-                }
-            };
+            let padding = extract_padding(pool_spec, &inputs[0].out_dims()[0])?;
             let kernel_shape = &pool_spec.kernel_shape;
 
             let (stride_h, stride_w) = (stride[0], stride[1]);
@@ -893,10 +1142,7 @@ pub fn new_op_from_onnx(
                 if c.raw_values.len() > 1 {
                     unimplemented!("only support scalar pow")
                 }
-                SupportedOp::Nonlinear(LookupOp::Pow {
-                    scale: scale_to_multiplier(inputs[0].out_scales()[0]).into(),
-                    a: crate::circuit::utils::F32(c.raw_values[0]),
-                })
+                pow_op(c.raw_values[0], inputs[0].out_scales()[0])
             } else {
                 unimplemented!("only support constant pow for now")
             }
@@ -943,35 +1189,27 @@ pub fn new_op_from_onnx(
                 }
             };
 
-            let padding = match &conv_node.pool_spec.padding {
-                PaddingSpec::Explicit(b, a) | PaddingSpec::ExplicitOnnxPool(b, a, _) => {
-                    if b.len() == 2 && a.len() == 2 {
-                        [(b[0], b[1]), (a[0], a[1])]
-                    } else if b.len() == 1 && a.len() == 1 {
-                        [(b[0], b[0]), (a[0], a[0])]
-                    } else if b.len() == 1 && a.len() == 2 {
-                        [(b[0], b[0]), (a[0], a[1])]
-                    } else if b.len() == 2 && a.len() == 1 {
-                        [(b[0], b[1]), (a[0], a[0])]
-                    } else {
-                        return Err(Box::new(GraphError::MissingParams("padding".to_string())));
-                    }
-                }
-                _ => {
-                    return Err(Box::new(GraphError::MissingParams("padding".to_string())));
-                }
-            };
+            let padding = extract_padding(&conv_node.pool_spec, &inputs[0].out_dims()[0])?;
 
             let kernel = extract_tensor_value(conv_node.kernel.clone(), symbol_values)?;
-            let kernel = quantize_tensor(kernel, scales.params, param_visibility)?;
+            // the output channels of the OIHW kernel
+            let extra = match scales.per_channel {
+                true => channel_extra_scales(&kernel, 0)?,
+                false => vec![],
+            };
+            let kernel =
+                quantize_tensor_per_channel(kernel, scales.params, 0, &extra, param_visibility)?;
 
+            let out_scale = scales.params + inputs[0].out_scales()[0];
             let bias = match conv_node.bias.clone() {
                 Some(b) => {
                     let const_value = extract_tensor_value(b, symbol_values)?;
 
-                    let val = quantize_tensor(
+                    let val = quantize_tensor_per_channel(
                         const_value,
-                        scales.params + inputs[0].out_scales()[0],
+                        out_scale,
+                        0,
+                        &extra,
                         param_visibility,
                     )?;
                     Some(val)
@@ -979,12 +1217,21 @@ pub fn new_op_from_onnx(
                 None => None,
             };
 
-            SupportedOp::Linear(PolyOp::Conv {
-                kernel,
-                bias,
-                padding,
-                stride,
-            })
+            let channel_axis = match conv_node.pool_spec.data_format {
+                DataFormat::NCHW => 1,
+                _ => 0,
+            };
+            rebase_channels(
+                SupportedOp::Linear(PolyOp::Conv {
+                    kernel,
+                    bias,
+                    padding,
+                    stride,
+                }),
+                out_scale,
+                channel_axis,
+                &extra,
+            )
         }
         "Not" => SupportedOp::Linear(PolyOp::Not),
         "And" => SupportedOp::Linear(PolyOp::And),
@@ -1068,11 +1315,18 @@ pub fn new_op_from_onnx(
                 }
             };
 
-            SupportedOp::Linear(PolyOp::Downsample {
-                axis: downsample_node.axis,
-                stride: downsample_node.stride as usize,
-                modulo: downsample_node.modulo,
-            })
+            // a stride of -1 is how tract lowers a reversing slice
+            if downsample_node.stride == -1 && downsample_node.modulo == 0 {
+                SupportedOp::Linear(PolyOp::Flip {
+                    axis: downsample_node.axis,
+                })
+            } else {
+                SupportedOp::Linear(PolyOp::Downsample {
+                    axis: downsample_node.axis,
+                    stride: downsample_node.stride as usize,
+                    modulo: downsample_node.modulo,
+                })
+            }
         }
 
         "Resize" => {
@@ -1082,10 +1336,24 @@ pub fn new_op_from_onnx(
 
             let resize_node = format!("{:?}", node);
 
-            if !resize_node.contains("interpolator: Nearest")
+            let is_bilinear = resize_node.contains("interpolator: Linear");
+            if !is_bilinear
+                && !resize_node.contains("interpolator: Nearest")
                 && !resize_node.contains("nearest: Floor")
             {
-                unimplemented!("Only nearest neighbor interpolation is supported")
+                unimplemented!("Only nearest neighbor and bilinear interpolation are supported")
+            }
+            // the bilinear weights are those of the (default) half_pixel coordinate transformation
+            if is_bilinear {
+                match resize_coord_transformer(&resize_node) {
+                    Some("HalfPixel") => {}
+                    mode => {
+                        return Err(Box::new(GraphError::MisformedParams(format!(
+                            "bilinear Resize (node {}) only supports the half_pixel coordinate_transformation_mode, not {:?}",
+                            idx, mode
+                        ))))
+                    }
+                }
             }
             // check if optional scale factor is present
             if inputs.len() != 2 && inputs.len() != 3 {
@@ -1126,7 +1394,11 @@ pub fn new_op_from_onnx(
                 }
             }
 
-            SupportedOp::Linear(PolyOp::Resize { scale_factor })
+            if is_bilinear {
+                SupportedOp::Hybrid(HybridOp::ResizeBilinear { scale_factor })
+            } else {
+                SupportedOp::Linear(PolyOp::Resize { scale_factor })
+            }
         }
 
         "SumPool" => {
@@ -1149,13 +1421,7 @@ pub fn new_op_from_onnx(
             }
 
             let stride = pool_spec.strides.clone().unwrap();
-            let padding = match &pool_spec.padding {
-                PaddingSpec::Explicit(b, a) => [(b[0], b[1]), (a[0], a[1])],
-                PaddingSpec::ExplicitOnnxPool(b, a, _) => [(b[0], b[1]), (a[0], a[1])],
-                _ => {
-                    return Err(Box::new(GraphError::MissingParams("padding".to_string())));
-                }
-            };
+            let padding = extract_padding(pool_spec, &inputs[0].out_dims()[0])?;
             let kernel_shape = &pool_spec.kernel_shape;
 
             let (stride_h, stride_w) = (stride[0], stride[1]);
@@ -1179,40 +1445,44 @@ pub fn new_op_from_onnx(
                     return Err(Box::new(GraphError::OpMismatch(idx, "pad".to_string())));
                 }
             };
-            // we only support constant 0 padding
-            if pad_node.mode
-                != PadMode::Constant(tract_onnx::prelude::Arc::new(
-                    tract_onnx::prelude::Tensor::zero::<f32>(&[])?,
-                ))
-            {
-                return Err(Box::new(GraphError::MisformedParams(
-                    "pad mode or pad type".to_string(),
-                )));
-            }
-
-            let padding_len = pad_node.pads.len();
-
-            // we only support symmetrical padding that affects the last 2 dims (height and width params)
-            for (i, pad_params) in pad_node.pads.iter().enumerate() {
-                if (i < padding_len - 2) && ((pad_params.0 != 0) || (pad_params.1 != 0)) {
+            // we only support constant padding
+            let value = match &pad_node.mode {
+                PadMode::Constant(v) => v.cast_to_scalar::<f32>()?,
+                _ => {
                     return Err(Box::new(GraphError::MisformedParams(
-                        "ezkl currently only supports padding height and width dimensions"
-                            .to_string(),
+                        "pad mode or pad type".to_string(),
                     )));
                 }
-            }
+            };
+
+            let padding_len = pad_node.pads.len();
 
-            let padding = [
-                (
-                    pad_node.pads[padding_len - 2].0,
-                    pad_node.pads[padding_len - 1].0,
-                ),
-                (
-                    pad_node.pads[padding_len - 2].1,
-                    pad_node.pads[padding_len - 1].1,
-                ),
-            ];
-            SupportedOp::Linear(PolyOp::Pad(padding))
+            // symmetrical zero padding of the height and width of an image goes through the same
+            // path as conv padding, anything else is laid out as an explicit pad layer
+            let is_image_padding = padding_len == 4
+                && pad_node.pads[..padding_len - 2]
+                    .iter()
+                    .all(|pad_params| pad_params.0 == 0 && pad_params.1 == 0);
+
+            if value == 0.0 && is_image_padding {
+                let padding = [
+                    (
+                        pad_node.pads[padding_len - 2].0,
+                        pad_node.pads[padding_len - 1].0,
+                    ),
+                    (
+                        pad_node.pads[padding_len - 2].1,
+                        pad_node.pads[padding_len - 1].1,
+                    ),
+                ];
+                SupportedOp::Linear(PolyOp::Pad(padding))
+            } else {
+                SupportedOp::Linear(PolyOp::ConstantPad {
+                    pads: pad_node.pads.clone(),
+                    value: value.into(),
+                    scale: inputs[0].out_scales()[0],
+                })
+            }
         }
         "RmAxis" | "Reshape" | "AddAxis" => {
             // Extract the slope layer hyperparams
@@ -1224,6 +1494,15 @@ pub fn new_op_from_onnx(
 
             SupportedOp::Linear(PolyOp::Reshape(output_shape))
         }
+        // onnx Expand, like the reshapes above this only rearranges (and repeats) cells
+        "MultiBroadcastTo" => {
+            let shapes = node_output_shapes(&node)?;
+            let shape = match shapes[0].as_ref() {
+                Some(s) => s.clone(),
+                None => return Err(Box::new(GraphError::InvalidDims(idx, "expand".to_string()))),
+            };
+            SupportedOp::Linear(PolyOp::MultiBroadcastTo { shape })
+        }
         "Flatten" => {
             let new_dims: Vec<usize> = vec![inputs[0].out_dims()[0].iter().product::<usize>()];
             SupportedOp::Linear(PolyOp::Flatten(new_dims))
@@ -1261,7 +1540,7 @@ pub fn extract_conv_values(boxed_op: Box<dyn crate::circuit::Op<Fp>>) -> [Option
         .as_any()
         .downcast_ref::<crate::circuit::ops::poly::PolyOp<Fp>>();
 
-    if let Some(PolyOp::Conv { kernel, bias, .. }) = op {
+    if let Some(PolyOp::Conv { kernel, bias, .. } | PolyOp::Im2ColConv { kernel, bias, .. }) = op {
         return [Some(kernel.clone()), bias.clone()];
     }
     [None, None]
@@ -1355,11 +1634,293 @@ pub fn homogenize_input_scales(
     }
 }
 
+/// A constant power of a tensor at `in_scale`. Positive integer powers are laid out with multiplication gates
+/// instead of a lookup, and rescaled back to `in_scale` (the output scale of the lookup) so the scales of the
+/// rest of the model don't change.
+pub(crate) fn pow_op(exponent: f32, in_scale: crate::Scale) -> SupportedOp {
+    if exponent >= 1.0 && exponent.fract() == 0.0 && in_scale >= 0 {
+        super::RebaseScale::rebase(
+            SupportedOp::Linear(PolyOp::Pow(exponent as u32)),
+            in_scale,
+            in_scale * exponent as i32,
+            1,
+        )
+    } else {
+        SupportedOp::Nonlinear(LookupOp::Pow {
+            scale: scale_to_multiplier(in_scale).into(),
+            a: crate::circuit::utils::F32(exponent),
+        })
+    }
+}
+
+/// The most extra bits of precision a channel gets over the param scale, see [channel_extra_scales]
+const MAX_CHANNEL_EXTRA_SCALE: crate::Scale = 8;
+
+/// The extra scale each slice of `value` along `axis` can be quantized at: as many bits as the range of the
+/// slice leaves free within the range of the whole tensor, up to [MAX_CHANNEL_EXTRA_SCALE].
+pub(crate) fn channel_extra_scales(
+    value: &Tensor<f32>,
+    axis: usize,
+) -> Result<Vec<crate::Scale>, TensorError> {
+    let dims = value.dims();
+    if axis >= dims.len() {
+        return Err(TensorError::DimMismatch("per channel scales".to_string()));
+    }
+    let stride = dims[axis + 1..].iter().product::<usize>();
+    let mut maxes = vec![0f32; dims[axis]];
+    for (i, x) in value.iter().enumerate() {
+        let c = (i / stride) % dims[axis];
+        maxes[c] = maxes[c].max(x.abs());
+    }
+    let max = maxes.iter().cloned().fold(0f32, f32::max);
+    Ok(maxes
+        .iter()
+        .map(|m| match *m > 0.0 {
+            true => ((max / m).log2().floor() as crate::Scale).min(MAX_CHANNEL_EXTRA_SCALE),
+            false => 0,
+        })
+        .collect())
+}
+
+/// Quantizes each slice of `const_value` along `axis` at `scale` plus its `extra` scale. The tensor keeps the
+/// nominal `scale`, the op it feeds has to be wrapped with [rebase_channels]. Without any extra scale this is
+/// [quantize_tensor].
+pub(crate) fn quantize_tensor_per_channel<F: PrimeField + TensorType + PartialOrd>(
+    const_value: Tensor<f32>,
+    scale: crate::Scale,
+    axis: usize,
+    extra: &[crate::Scale],
+    visibility: &Visibility,
+) -> Result<Tensor<F>, Box<dyn std::error::Error>> {
+    if extra.iter().all(|e| *e == 0) {
+        return quantize_tensor(const_value, scale, visibility);
+    }
+    let dims = const_value.dims().to_vec();
+    if axis >= dims.len() || dims[axis] != extra.len() {
+        return Err(Box::new(TensorError::DimMismatch(
+            "per channel quantization".to_string(),
+        )));
+    }
+    let stride = dims[axis + 1..].iter().product::<usize>();
+    let mut value: Tensor<F> = const_value.par_enum_map(|i, x| {
+        Ok::<_, TensorError>(crate::fieldutils::i128_to_felt::<F>(quantize_float(
+            &(x).into(),
+            0.0,
+            scale + extra[(i / stride) % extra.len()],
+        )?))
+    })?;
+
+    value.set_scale(scale);
+    value.set_visibility(visibility);
+    Ok(value)
+}
+
+/// Brings the channels (along `axis`) of the output of `op`, whose weights were quantized at the `extra` scales
+/// of [quantize_tensor_per_channel], back to its nominal `scale`: each channel is multiplied up to the finest
+/// scale and the whole output divided once.
+pub(crate) fn rebase_channels(
+    op: SupportedOp,
+    scale: crate::Scale,
+    axis: usize,
+    extra: &[crate::Scale],
+) -> SupportedOp {
+    let max = extra.iter().cloned().max().unwrap_or(0);
+    if max == 0 {
+        return op;
+    }
+    SupportedOp::RebaseScale(super::RebaseScale {
+        inner: Box::new(op),
+        multiplier: scale_to_multiplier(max),
+        target_scale: scale,
+        original_scale: scale + max,
+        channel_multipliers: Some((axis, extra.iter().map(|e| 1u64 << (max - e)).collect())),
+    })
+}
+
+/// Requantizes the 2D constant weights of an einsum per output channel. Returns the output axis of the
+/// channels and their extra scales, or `None` when the einsum has no such weights.
+pub(crate) fn quantize_einsum_per_channel(
+    equation: &str,
+    inputs: &mut [super::NodeType],
+    scale: crate::Scale,
+) -> Result<Option<(usize, Vec<crate::Scale>)>, Box<dyn std::error::Error>> {
+    let (in_eqs, out_eq) = match equation.split_once("->") {
+        Some(eqs) => eqs,
+        None => return Ok(None),
+    };
+    for (letters, input) in in_eqs.split(',').zip(inputs.iter_mut()) {
+        // the weights have one axis that is summed over and one that is kept
+        let kept = letters
+            .chars()
+            .enumerate()
+            .filter(|(_, l)| out_eq.contains(*l))
+            .collect_vec();
+        let n = match input {
+            super::NodeType::Node(n) if n.num_uses == 1 && letters.len() == 2 => n,
+            _ => continue,
+        };
+        let (axis, out_axis) = match kept[..] {
+            [(axis, l)] => (axis, out_eq.chars().position(|o| o == l).unwrap()),
+            _ => continue,
+        };
+        if let Some(c) = n.opkind.get_mutable_constant() {
+            let extra = channel_extra_scales(&c.raw_values, axis)?;
+            let visibility = c.quantized_values.visibility().unwrap();
+            c.quantized_values = quantize_tensor_per_channel(
+                c.raw_values.clone(),
+                scale,
+                axis,
+                &extra,
+                &visibility,
+            )?;
+            return Ok(Some((out_axis, extra)));
+        }
+    }
+    Ok(None)
+}
+
 #[cfg(test)]
 pub mod tests {
 
     use super::*;
 
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_downsample_ranges() {
+        let x = Tensor::<i128>::new(Some(&[0, 1, 2, 3, 4, 5, 6]), &[1, 7]).unwrap();
+        let downsample = |stride, modulo| {
+            let ranges = downsample_ranges(x.dims(), 1, stride, modulo).unwrap();
+            crate::tensor::ops::strided_slice(&x, &ranges)
+                .unwrap()
+                .to_vec()
+        };
+        assert_eq!(downsample(2, 1), vec![1, 3, 5]);
+        assert_eq!(downsample(-2, 1), vec![5, 3, 1]);
+        assert_eq!(downsample(-3, 0), vec![6, 3, 0]);
+        assert_eq!(downsample(1, 0), x.to_vec());
+        assert!(downsample_ranges(x.dims(), 2, 1, 0).is_err());
+        assert!(downsample_ranges(x.dims(), 1, 0, 0).is_err());
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_scatter_nd_indices() {
+        let indices = Tensor::new(Some(&[1.0, -1.0, -3.0, 0.0]), &[2, 2]).unwrap();
+        assert_eq!(
+            scatter_nd_indices(&indices, &[3, 2]).unwrap(),
+            Tensor::new(Some(&[1, 1, 0, 0]), &[2, 2]).unwrap()
+        );
+        // out of bounds, from either end
+        for x in [2.0, -3.0] {
+            let indices = Tensor::new(Some(&[0.0, x]), &[1, 2]).unwrap();
+            assert!(scatter_nd_indices(&indices, &[3, 2]).is_err());
+        }
+        let indices = Tensor::new(Some(&[0.0, 0.0, 0.0]), &[1, 3]).unwrap();
+        assert!(scatter_nd_indices(&indices, &[3, 2]).is_err());
+    }
+
+    #[test]
+    fn test_pow_op() {
+        // integer powers keep the scale of the lookup they replace
+        assert!(matches!(pow_op(2.0, 1), SupportedOp::RebaseScale(_)));
+        assert!(matches!(pow_op(0.5, 1), SupportedOp::Nonlinear(_)));
+        for exponent in [1.0, 2.0, 3.0, 0.5] {
+            assert_eq!(pow_op(exponent, 1).out_scale(vec![1]), 1);
+        }
+
+        // 2^2 and (-1.5)^3 at scale 1
+        let x = Tensor::new(Some(&[4, -3].map(crate::fieldutils::i128_to_felt)), &[2]).unwrap();
+        let squared = Op::<Fp>::f(&pow_op(2.0, 1), &[x.clone()]).unwrap().output;
+        assert_eq!(
+            squared.map(crate::fieldutils::felt_to_i128),
+            Tensor::new(Some(&[8, 5]), &[2]).unwrap()
+        );
+        let cubed = Op::<Fp>::f(&pow_op(3.0, 1), &[x]).unwrap().output;
+        assert_eq!(
+            cubed.map(crate::fieldutils::felt_to_i128),
+            Tensor::new(Some(&[16, -7]), &[2]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_per_channel_scales() {
+        // the second column of the weights is 50 times smaller than the first, the third is all zeros
+        let raw = Tensor::new(Some(&[1.0, 0.01, 0.0, 0.5, 0.02, 0.0]), &[2, 3]).unwrap();
+        let extra = channel_extra_scales(&raw, 1).unwrap();
+        assert_eq!(extra, vec![0, 5, 0]);
+        let tiny = Tensor::new(Some(&[1.0, 1e-6]), &[2]).unwrap();
+        assert_eq!(
+            channel_extra_scales(&tiny, 0).unwrap(),
+            vec![0, MAX_CHANNEL_EXTRA_SCALE]
+        );
+
+        let weights: Tensor<Fp> =
+            quantize_tensor_per_channel(raw, 2, 1, &extra, &Visibility::Fixed).unwrap();
+        assert_eq!(weights.scale(), Some(2));
+        assert_eq!(
+            weights.map(crate::fieldutils::felt_to_i128),
+            Tensor::new(Some(&[4, 1, 0, 2, 3, 0]), &[2, 3]).unwrap()
+        );
+
+        // [1, 2] at scale 3 times the weights, with the second channel brought back to scale 5
+        let einsum = SupportedOp::Linear(PolyOp::Einsum {
+            equation: "mk,kn->mn".to_string(),
+        });
+        assert!(matches!(
+            rebase_channels(einsum.clone(), 2, 1, &[0, 0, 0]),
+            SupportedOp::Linear(_)
+        ));
+        let op = rebase_channels(einsum, 5, 1, &extra);
+        assert_eq!(op.out_scale(vec![3, 2]), 5);
+        let x = Tensor::new(Some(&[8, 16].map(crate::fieldutils::i128_to_felt)), &[1, 2]).unwrap();
+        let res = Op::<Fp>::f(&op, &[x, weights]).unwrap().output;
+        // 1 + 2 * 0.5 = 2 and 0.01 + 2 * 0.02 = 0.05, which the weights at scale 2 alone would round to 0
+        assert_eq!(
+            res.map(crate::fieldutils::felt_to_i128),
+            Tensor::new(Some(&[64, 2, 0]), &[1, 3]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_same_padding() {
+        // 5x5 image, 3x3 kernel, stride 1: one pixel on each side
+        assert_eq!(same_padding((5, 5), (3, 3), (1, 1), true), [(1, 1), (1, 1)]);
+        // even kernels need asymmetric padding
+        assert_eq!(same_padding((4, 4), (2, 2), (1, 1), true), [(0, 0), (1, 1)]);
+        assert_eq!(
+            same_padding((4, 4), (2, 2), (1, 1), false),
+            [(1, 1), (0, 0)]
+        );
+        // 5 rows with stride 2 give 3 output rows
+        assert_eq!(same_padding((5, 6), (3, 3), (2, 2), true), [(1, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn test_resize_coord_transformer() {
+        let node = "Resize { axes: None, coord_transformer: HalfPixel, interpolator: Linear, nearest: Floor }";
+        assert_eq!(resize_coord_transformer(node), Some("HalfPixel"));
+        let node = "Resize { axes: None, coord_transformer: AlignCorners, interpolator: Linear }";
+        assert_eq!(resize_coord_transformer(node), Some("AlignCorners"));
+        assert_eq!(resize_coord_transformer("Resize { axes: None }"), None);
+    }
+
+    #[test]
+    fn test_accuracy_report() {
+        // scale 1 => multiplier of 2
+        let quantized = Tensor::<i128>::new(Some(&[2, 5, -1]), &[3]).unwrap();
+        let report = accuracy_report(&[1.0, 2.0, -0.5], &quantized, 1).unwrap();
+
+        assert_eq!(report.element_errors, vec![0.0, 0.5, 0.0]);
+        assert_eq!(report.max_error, 0.5);
+        assert_eq!(report.mean_error, 0.5 / 3.0);
+        assert!(report.argmax_agreement);
+
+        let report = accuracy_report(&[3.0, 2.0, -0.5], &quantized, 1).unwrap();
+        assert!(!report.argmax_agreement);
+
+        assert!(accuracy_report(&[1.0], &quantized, 1).is_err());
+    }
+
     #[test]
     fn test_flatten_valtensors() {
         let tensor1: Tensor<Fp> = (0..10).map(|x| x.into()).into();
@@ -44,31 +44,40 @@ pub enum Visibility {
 
 impl<'a> From<&'a str> for Visibility {
     fn from(s: &'a str) -> Self {
+        s.parse().unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl std::str::FromStr for Visibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.contains("hashed/private") {
             // split on last occurence of '/'
             let (_, outlets) = s.split_at(s.rfind('/').unwrap());
             let outlets = outlets
                 .trim_start_matches('/')
                 .split(',')
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect_vec();
+                .map(|o| o.parse::<usize>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| format!("Invalid hashed outlets in visibility string `{}`", s))?;
 
-            return Visibility::Hashed {
+            return Ok(Visibility::Hashed {
                 hash_is_public: false,
                 outlets,
-            };
+            });
         }
         match s {
-            "private" => Visibility::Private,
-            "public" => Visibility::Public,
-            "kzgcommit" => Visibility::KZGCommit,
-            "fixed" => Visibility::Fixed,
-            "hashed" | "hashed/public" => Visibility::Hashed {
+            "private" => Ok(Visibility::Private),
+            "public" => Ok(Visibility::Public),
+            "kzgcommit" => Ok(Visibility::KZGCommit),
+            "fixed" => Ok(Visibility::Fixed),
+            "hashed" | "hashed/public" => Ok(Visibility::Hashed {
                 hash_is_public: true,
                 outlets: vec![],
-            },
-            "encrypted" => Visibility::Encrypted,
-            _ => panic!("Invalid visibility string"),
+            }),
+            "encrypted" => Ok(Visibility::Encrypted),
+            _ => Err(format!("Invalid visibility string `{}`", s)),
         }
     }
 }
@@ -230,6 +239,8 @@ pub struct VarScales {
     pub params: crate::Scale,
     ///
     pub rebase_multiplier: u32,
+    /// whether conv and matmul weights are quantized at per channel scales
+    pub per_channel: bool,
 }
 
 impl std::fmt::Display for VarScales {
@@ -250,6 +261,7 @@ impl VarScales {
             input: args.input_scale,
             params: args.param_scale,
             rebase_multiplier: args.scale_rebase_multiplier,
+            per_channel: args.per_channel_scales,
         })
     }
 }
@@ -300,6 +312,19 @@ impl VarVisibility {
         {
             return Err(Box::new(GraphError::Visibility));
         }
+        // the params of a layer can only be swapped between fixed and advice columns, params that are
+        // processed by a module (hashed, committed to...) are processed all at once
+        let layer_vis_supported =
+            |v: &Visibility| matches!(v, Visibility::Private | Visibility::Fixed);
+        if !args.layer_param_visibility.is_empty()
+            && (!layer_vis_supported(params_vis)
+                || !args
+                    .layer_param_visibility
+                    .iter()
+                    .all(|(_, v)| layer_vis_supported(v)))
+        {
+            return Err("per layer param visibility can only mix private and fixed params".into());
+        }
         Ok(Self {
             input: input_vis.clone(),
             params: params_vis.clone(),
@@ -398,11 +423,23 @@ impl<F: PrimeField + TensorType + PartialOrd> ModelVars<F> {
         num_inner_cols: usize,
         num_constants: usize,
         uses_modules: bool,
+        unblinded: bool,
     ) -> Self {
         info!("number of blinding factors: {}", cs.blinding_factors());
+        if unblinded {
+            log::warn!(
+                "the model's advice columns are unblinded, proofs will not be zero knowledge"
+            );
+        }
 
         let advices = (0..3)
-            .map(|_| VarTensor::new_advice(cs, logrows, num_inner_cols, var_len))
+            .map(|_| {
+                if unblinded {
+                    VarTensor::new_unblinded_advice(cs, logrows, num_inner_cols, var_len)
+                } else {
+                    VarTensor::new_advice(cs, logrows, num_inner_cols, var_len)
+                }
+            })
             .collect_vec();
 
         debug!(
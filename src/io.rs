@@ -0,0 +1,707 @@
+use crate::circuit::region::TracedAdvice;
+use crate::tensor::{Tensor, TensorError};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use thiserror::Error;
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+const ZIP_LOCAL_HEADER: u32 = 0x04034b50;
+const ZIP_CENTRAL_HEADER: u32 = 0x02014b50;
+const ZIP_END_OF_CENTRAL_DIR: u32 = 0x06054b50;
+const TRACE_MAGIC: &[u8] = b"EZKLTRC\x01";
+/// numpy refuses to read headers longer than this by default
+const NPY_MAX_HEADER_LEN: usize = 10000;
+
+/// Errors raised when reading .npy / .npz / .safetensors / witness trace files
+#[derive(Debug, Error)]
+pub enum NpyError {
+    /// The file could not be read
+    #[error("failed to read file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The file does not start with the npy magic string
+    #[error("not an npy file")]
+    Magic,
+    /// The npy header could not be parsed
+    #[error("malformed npy header: {0}")]
+    Header(String),
+    /// The array's dtype is not supported
+    #[error("unsupported npy dtype {0}")]
+    Dtype(String),
+    /// The data doesn't match the shape of the array
+    #[error("expected {0} bytes of data but found {1}")]
+    Truncated(usize, usize),
+    /// The npz archive could not be parsed
+    #[error("malformed npz archive: {0}")]
+    Archive(String),
+    /// The safetensors file could not be parsed
+    #[error("malformed safetensors file: {0}")]
+    SafeTensors(String),
+    /// The witness trace could not be parsed
+    #[error("malformed witness trace: {0}")]
+    Trace(String),
+}
+
+/// A numeric array read from a .npy, .npz or .safetensors file, stored in row major (C) order
+#[derive(Clone, Debug, PartialEq)]
+pub struct NpyArray {
+    /// The shape of the array
+    pub shape: Vec<usize>,
+    /// The (row major) elements of the array, cast to f64
+    pub data: Vec<f64>,
+}
+
+impl NpyArray {
+    /// Converts the array into a [Tensor]
+    pub fn to_tensor(&self) -> Result<Tensor<f64>, TensorError> {
+        let dims = if self.shape.is_empty() {
+            vec![1]
+        } else {
+            self.shape.clone()
+        };
+        Tensor::new(Some(&self.data[..]), &dims)
+    }
+}
+
+/// Parses the value of `key` from a npy header dict, eg. `{'descr': '<f4', 'fortran_order': False, 'shape': (3, 4), }`
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, NpyError> {
+    let start = header
+        .find(&format!("'{}'", key))
+        .ok_or_else(|| NpyError::Header(format!("missing {}", key)))?;
+    let rest = header[start + key.len() + 2..].trim_start();
+    let rest = rest
+        .strip_prefix(':')
+        .ok_or_else(|| NpyError::Header(format!("missing value for {}", key)))?
+        .trim_start();
+    let end = if rest.starts_with('(') {
+        rest.find(')').map(|i| i + 1)
+    } else {
+        rest.find(|c| c == ',' || c == '}')
+    }
+    .ok_or_else(|| NpyError::Header(format!("unterminated value for {}", key)))?;
+    Ok(rest[..end].trim())
+}
+
+fn parse_shape(shape: &str) -> Result<Vec<usize>, NpyError> {
+    shape
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(|d| d.trim())
+        .filter(|d| !d.is_empty())
+        .map(|d| {
+            d.parse::<usize>()
+                .map_err(|_| NpyError::Header(format!("invalid shape {}", shape)))
+        })
+        .collect()
+}
+
+/// Decodes the raw bytes of a single element
+fn decode(descr: &str, bytes: &[u8]) -> f64 {
+    macro_rules! num {
+        ($t:ty) => {{
+            let b = bytes.try_into().unwrap();
+            if descr.starts_with('>') {
+                <$t>::from_be_bytes(b) as f64
+            } else {
+                <$t>::from_le_bytes(b) as f64
+            }
+        }};
+    }
+    match &descr[1..] {
+        "f4" => num!(f32),
+        "f8" => num!(f64),
+        "i1" => num!(i8),
+        "i2" => num!(i16),
+        "i4" => num!(i32),
+        "i8" => num!(i64),
+        "u1" => num!(u8),
+        "u2" => num!(u16),
+        "u4" => num!(u32),
+        "u8" => num!(u64),
+        "b1" => (bytes[0] != 0) as u8 as f64,
+        _ => unreachable!(),
+    }
+}
+
+/// The number of elements and of bytes of an array, or None if either overflows
+fn sizes(shape: &[usize], elem_size: usize) -> Option<(usize, usize)> {
+    let len = shape
+        .iter()
+        .try_fold(1usize, |acc, d| acc.checked_mul(*d))?;
+    Some((len, len.checked_mul(elem_size)?))
+}
+
+/// Reads a numpy array (as written by `np.save`) from a reader
+pub fn read_npy<R: Read>(mut reader: R) -> Result<NpyArray, NpyError> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic[..6] != NPY_MAGIC {
+        return Err(NpyError::Magic);
+    }
+    // version 1 uses a 2 byte header length, versions 2 and 3 a 4 byte one
+    let header_len = if magic[6] == 1 {
+        let mut len = [0u8; 2];
+        reader.read_exact(&mut len)?;
+        u16::from_le_bytes(len) as usize
+    } else {
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        u32::from_le_bytes(len) as usize
+    };
+    if header_len > NPY_MAX_HEADER_LEN {
+        return Err(NpyError::Header(format!(
+            "header length {} exceeds {}",
+            header_len, NPY_MAX_HEADER_LEN
+        )));
+    }
+    let mut header = vec![0u8; header_len];
+    reader.read_exact(&mut header)?;
+    let header = String::from_utf8_lossy(&header);
+
+    let descr = header_value(&header, "descr")?.trim_matches(|c| c == '\'' || c == '"');
+    let fortran_order = header_value(&header, "fortran_order")? == "True";
+    let shape = parse_shape(header_value(&header, "shape")?)?;
+
+    let elem_size = match descr.get(1..) {
+        Some("f4") | Some("i4") | Some("u4") => 4,
+        Some("f8") | Some("i8") | Some("u8") => 8,
+        Some("i2") | Some("u2") => 2,
+        Some("i1") | Some("u1") | Some("b1") => 1,
+        _ => return Err(NpyError::Dtype(descr.to_string())),
+    };
+
+    let (len, byte_len) = sizes(&shape, elem_size)
+        .ok_or_else(|| NpyError::Header(format!("shape {:?} is too large", shape)))?;
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+    if bytes.len() < byte_len {
+        return Err(NpyError::Truncated(byte_len, bytes.len()));
+    }
+    let mut data: Vec<f64> = bytes
+        .chunks_exact(elem_size)
+        .take(len)
+        .map(|b| decode(descr, b))
+        .collect();
+
+    if fortran_order && shape.len() > 1 {
+        // element at row major index i sits at the column major index of the same coordinate
+        let mut row_major = vec![0.0; len];
+        for (i, v) in row_major.iter_mut().enumerate() {
+            let (mut rem, mut col_major, mut stride) = (i, 0, 1);
+            let mut coord = vec![0; shape.len()];
+            for (d, c) in shape.iter().zip(coord.iter_mut()).rev() {
+                *c = rem % d;
+                rem /= d;
+            }
+            for (d, c) in shape.iter().zip(coord) {
+                col_major += c * stride;
+                stride *= d;
+            }
+            *v = data[col_major];
+        }
+        data = row_major;
+    }
+
+    Ok(NpyArray { shape, data })
+}
+
+/// Loads a numpy array from a .npy file
+pub fn load_npy(path: impl AsRef<Path>) -> Result<NpyArray, NpyError> {
+    read_npy(std::io::BufReader::new(std::fs::File::open(path)?))
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Result<u16, NpyError> {
+    bytes
+        .get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes(b.try_into().unwrap()))
+        .ok_or_else(|| NpyError::Archive("unexpected end of archive".into()))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Result<u32, NpyError> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+        .ok_or_else(|| NpyError::Archive("unexpected end of archive".into()))
+}
+
+/// Reads the (named) numpy arrays of a .npz archive (as written by `np.savez`), in archive order: the
+/// positional arrays (`arr_0`, `arr_1`, ...) in the order they were passed, then the keyword ones.
+/// Only uncompressed archives are supported, arrays saved with `np.savez_compressed` are rejected.
+pub fn read_npz(bytes: &[u8]) -> Result<Vec<(String, NpyArray)>, NpyError> {
+    // the end of central directory record sits at the end of the archive, followed by an optional comment
+    let eocd = (0..bytes.len().saturating_sub(21))
+        .rev()
+        .find(|i| u32_at(bytes, *i).ok() == Some(ZIP_END_OF_CENTRAL_DIR))
+        .ok_or_else(|| NpyError::Archive("missing end of central directory".into()))?;
+    let num_entries = u16_at(bytes, eocd + 10)? as usize;
+    let mut offset = u32_at(bytes, eocd + 16)? as usize;
+
+    let mut arrays = vec![];
+    for _ in 0..num_entries {
+        if u32_at(bytes, offset)? != ZIP_CENTRAL_HEADER {
+            return Err(NpyError::Archive("malformed central directory".into()));
+        }
+        let method = u16_at(bytes, offset + 10)?;
+        let mut size = u32_at(bytes, offset + 20)? as u64;
+        let name_len = u16_at(bytes, offset + 28)? as usize;
+        let extra_len = u16_at(bytes, offset + 30)? as usize;
+        let comment_len = u16_at(bytes, offset + 32)? as usize;
+        let mut local_offset = u32_at(bytes, offset + 42)? as u64;
+        let name_start = offset + 46;
+        let name = String::from_utf8_lossy(
+            bytes
+                .get(name_start..name_start + name_len)
+                .ok_or_else(|| NpyError::Archive("unexpected end of archive".into()))?,
+        )
+        .to_string();
+
+        // zip64 archives store (only) the saturated fields in an extra field
+        let mut extra = name_start + name_len;
+        let extra_end = extra + extra_len;
+        while extra + 4 <= extra_end {
+            let id = u16_at(bytes, extra)?;
+            let len = u16_at(bytes, extra + 2)? as usize;
+            if id == 0x0001 {
+                let mut field = extra + 4;
+                let mut next = || -> Result<u64, NpyError> {
+                    let lo = u32_at(bytes, field)? as u64;
+                    let hi = u32_at(bytes, field + 4)? as u64;
+                    field += 8;
+                    Ok(lo | (hi << 32))
+                };
+                if u32_at(bytes, offset + 24)? == u32::MAX {
+                    next()?;
+                }
+                if size == u32::MAX as u64 {
+                    size = next()?;
+                }
+                if local_offset == u32::MAX as u64 {
+                    local_offset = next()?;
+                }
+            }
+            extra += 4 + len;
+        }
+
+        if method != 0 {
+            return Err(NpyError::Archive(format!(
+                "{} is compressed, only archives written with np.savez are supported",
+                name
+            )));
+        }
+
+        let local_offset = local_offset as usize;
+        if u32_at(bytes, local_offset)? != ZIP_LOCAL_HEADER {
+            return Err(NpyError::Archive("malformed local file header".into()));
+        }
+        let data_start = local_offset
+            + 30
+            + u16_at(bytes, local_offset + 26)? as usize
+            + u16_at(bytes, local_offset + 28)? as usize;
+        let data = bytes
+            .get(data_start..data_start + size as usize)
+            .ok_or_else(|| NpyError::Archive("unexpected end of archive".into()))?;
+
+        let key = name.strip_suffix(".npy").unwrap_or(&name).to_string();
+        arrays.push((key, read_npy(data)?));
+
+        offset = extra_end + comment_len;
+    }
+
+    Ok(arrays)
+}
+
+/// Loads the numpy arrays of a .npz archive, see [read_npz]
+pub fn load_npz(path: impl AsRef<Path>) -> Result<Vec<(String, NpyArray)>, NpyError> {
+    read_npz(&std::fs::read(path)?)
+}
+
+/// Converts an IEEE half precision float to f32
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits >> 15 == 1 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+/// Reads the named tensors of a safetensors file.
+/// The file starts with the (little endian u64) length of a json header mapping each tensor name to its
+/// dtype, shape and byte offsets into the data which follows the header.
+pub fn read_safetensors(bytes: &[u8]) -> Result<BTreeMap<String, NpyArray>, NpyError> {
+    let header_len = bytes
+        .get(..8)
+        .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+        .ok_or_else(|| NpyError::SafeTensors("missing header length".into()))?;
+    let header_end = usize::try_from(header_len)
+        .ok()
+        .and_then(|n| n.checked_add(8))
+        .ok_or_else(|| NpyError::SafeTensors(format!("invalid header length {}", header_len)))?;
+    let header = bytes
+        .get(8..header_end)
+        .ok_or_else(|| NpyError::SafeTensors("truncated header".into()))?;
+    let header: BTreeMap<String, serde_json::Value> = serde_json::from_slice(header)
+        .map_err(|e| NpyError::SafeTensors(format!("invalid header: {}", e)))?;
+    let data = &bytes[header_end..];
+
+    let mut tensors = BTreeMap::new();
+    for (name, info) in header {
+        if name == "__metadata__" {
+            continue;
+        }
+        let field = |key: &str| {
+            info.get(key)
+                .ok_or_else(|| NpyError::SafeTensors(format!("{} is missing {}", name, key)))
+        };
+        let dtype = field("dtype")?.as_str().unwrap_or_default().to_string();
+        let usizes = |key: &str| -> Result<Vec<usize>, NpyError> {
+            field(key)?
+                .as_array()
+                .and_then(|v| {
+                    v.iter()
+                        .map(|d| d.as_u64().and_then(|d| usize::try_from(d).ok()))
+                        .collect()
+                })
+                .ok_or_else(|| NpyError::SafeTensors(format!("invalid {} for {}", key, name)))
+        };
+        let shape = usizes("shape")?;
+        let offsets = usizes("data_offsets")?;
+        let (start, end) = match offsets[..] {
+            [start, end] if start <= end => (start, end),
+            _ => {
+                return Err(NpyError::SafeTensors(format!(
+                    "invalid offsets for {}",
+                    name
+                )))
+            }
+        };
+        let raw = data
+            .get(start..end)
+            .ok_or_else(|| NpyError::Truncated(end, data.len()))?;
+
+        let elem_size = match dtype.as_str() {
+            "F64" | "I64" => 8,
+            "F32" | "I32" => 4,
+            "F16" | "BF16" | "I16" => 2,
+            "I8" | "U8" | "BOOL" => 1,
+            _ => return Err(NpyError::Dtype(dtype.clone())),
+        };
+        let (_, byte_len) = sizes(&shape, elem_size).ok_or_else(|| {
+            NpyError::SafeTensors(format!("shape {:?} of {} is too large", shape, name))
+        })?;
+        if raw.len() != byte_len {
+            return Err(NpyError::Truncated(byte_len, raw.len()));
+        }
+        let data = raw
+            .chunks_exact(elem_size)
+            .map(|b| match dtype.as_str() {
+                "F64" => f64::from_le_bytes(b.try_into().unwrap()),
+                "F32" => f32::from_le_bytes(b.try_into().unwrap()) as f64,
+                "F16" => f16_to_f32(u16::from_le_bytes(b.try_into().unwrap())) as f64,
+                // bf16 is the upper half of an f32
+                "BF16" => {
+                    f32::from_bits((u16::from_le_bytes(b.try_into().unwrap()) as u32) << 16) as f64
+                }
+                "I64" => i64::from_le_bytes(b.try_into().unwrap()) as f64,
+                "I32" => i32::from_le_bytes(b.try_into().unwrap()) as f64,
+                "I16" => i16::from_le_bytes(b.try_into().unwrap()) as f64,
+                "I8" => b[0] as i8 as f64,
+                _ => b[0] as f64,
+            })
+            .collect();
+        tensors.insert(name, NpyArray { shape, data });
+    }
+
+    Ok(tensors)
+}
+
+/// Loads the named tensors of a safetensors file
+pub fn load_safetensors(path: impl AsRef<Path>) -> Result<BTreeMap<String, NpyArray>, NpyError> {
+    read_safetensors(&std::fs::read(path)?)
+}
+
+/// Writes a witness trace as a compact binary file: the magic string, the byte length of a value (u32)
+/// and the number of values (u64), followed by a (layer: u32, column: u32, row: u32, value) record per
+/// value, all little endian. The same witness always produces the same bytes.
+pub fn write_witness_trace<W: Write>(
+    mut writer: W,
+    trace: &[TracedAdvice],
+) -> Result<(), NpyError> {
+    let value_len = trace.first().map(|t| t.value.len()).unwrap_or(0);
+    writer.write_all(TRACE_MAGIC)?;
+    writer.write_all(&(value_len as u32).to_le_bytes())?;
+    writer.write_all(&(trace.len() as u64).to_le_bytes())?;
+    for t in trace {
+        if t.value.len() != value_len {
+            return Err(NpyError::Trace("values of differing lengths".to_string()));
+        }
+        for x in [t.layer, t.column, t.row] {
+            let x =
+                u32::try_from(x).map_err(|_| NpyError::Trace(format!("{} overflows u32", x)))?;
+            writer.write_all(&x.to_le_bytes())?;
+        }
+        writer.write_all(&t.value)?;
+    }
+    Ok(())
+}
+
+/// Saves a witness trace to `path`, see [write_witness_trace]
+pub fn save_witness_trace(path: impl AsRef<Path>, trace: &[TracedAdvice]) -> Result<(), NpyError> {
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+    write_witness_trace(&mut writer, trace)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a witness trace written by [write_witness_trace]
+pub fn read_witness_trace(bytes: &[u8]) -> Result<Vec<TracedAdvice>, NpyError> {
+    if !bytes.starts_with(TRACE_MAGIC) {
+        return Err(NpyError::Trace("missing magic string".to_string()));
+    }
+    let header_len = TRACE_MAGIC.len() + 12;
+    if bytes.len() < header_len {
+        return Err(NpyError::Trace("truncated header".to_string()));
+    }
+    let value_len = u32_at(bytes, TRACE_MAGIC.len())? as usize;
+    let count = u64::from_le_bytes(bytes[TRACE_MAGIC.len() + 4..header_len].try_into().unwrap());
+    let record_len = 12 + value_len;
+    let expected = (count as usize)
+        .checked_mul(record_len)
+        .and_then(|n| n.checked_add(header_len))
+        .ok_or_else(|| NpyError::Trace(format!("invalid number of values {}", count)))?;
+    if bytes.len() != expected {
+        return Err(NpyError::Truncated(expected, bytes.len()));
+    }
+    bytes[header_len..]
+        .chunks_exact(record_len)
+        .map(|r| {
+            Ok(TracedAdvice {
+                layer: u32_at(r, 0)? as usize,
+                column: u32_at(r, 4)? as usize,
+                row: u32_at(r, 8)? as usize,
+                value: r[12..].to_vec(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn npy(descr: &str, fortran_order: bool, shape: &str, data: &[u8]) -> Vec<u8> {
+        let header = format!(
+            "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}\n",
+            descr,
+            if fortran_order { "True" } else { "False" },
+            shape
+        );
+        let mut bytes = NPY_MAGIC.to_vec();
+        bytes.extend([1, 0]);
+        bytes.extend((header.len() as u16).to_le_bytes());
+        bytes.extend(header.as_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    /// writes an uncompressed zip archive, (crcs are left empty as they aren't checked)
+    fn npz(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let (mut archive, mut central) = (vec![], vec![]);
+        for (name, data) in entries {
+            let offset = archive.len() as u32;
+            archive.extend(ZIP_LOCAL_HEADER.to_le_bytes());
+            archive.extend([0u8; 14]);
+            archive.extend((data.len() as u32).to_le_bytes());
+            archive.extend((data.len() as u32).to_le_bytes());
+            archive.extend((name.len() as u16).to_le_bytes());
+            archive.extend([0u8; 2]);
+            archive.extend(name.as_bytes());
+            archive.extend(data);
+
+            central.extend(ZIP_CENTRAL_HEADER.to_le_bytes());
+            central.extend([0u8; 16]);
+            central.extend((data.len() as u32).to_le_bytes());
+            central.extend((data.len() as u32).to_le_bytes());
+            central.extend((name.len() as u16).to_le_bytes());
+            central.extend([0u8; 12]);
+            central.extend(offset.to_le_bytes());
+            central.extend(name.as_bytes());
+        }
+        let central_offset = archive.len() as u32;
+        let central_len = central.len() as u32;
+        archive.extend(central);
+        archive.extend(ZIP_END_OF_CENTRAL_DIR.to_le_bytes());
+        archive.extend([0u8; 4]);
+        archive.extend((entries.len() as u16).to_le_bytes());
+        archive.extend((entries.len() as u16).to_le_bytes());
+        archive.extend(central_len.to_le_bytes());
+        archive.extend(central_offset.to_le_bytes());
+        archive.extend([0u8; 2]);
+        archive
+    }
+
+    #[test]
+    fn test_read_npy() {
+        let data: Vec<u8> = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0]
+            .iter()
+            .flat_map(|f| f.to_le_bytes())
+            .collect();
+        let array = read_npy(&npy("<f4", false, "(2, 3)", &data)[..]).unwrap();
+        assert_eq!(array.shape, vec![2, 3]);
+        assert_eq!(array.data, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(array.to_tensor().unwrap().dims(), &[2, 3]);
+
+        // the same array stored in column major order
+        let data: Vec<u8> = [1i64, 4, 2, 5, 3, 6]
+            .iter()
+            .flat_map(|i| i.to_le_bytes())
+            .collect();
+        let array = read_npy(&npy("<i8", true, "(2, 3)", &data)[..]).unwrap();
+        assert_eq!(array.data, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let array = read_npy(&npy(">i2", false, "(2,)", &[255, 254, 0, 7])[..]).unwrap();
+        assert_eq!(array.data, vec![-2.0, 7.0]);
+
+        assert!(matches!(
+            read_npy(&npy("<c8", false, "(1,)", &[0; 8])[..]),
+            Err(NpyError::Dtype(_))
+        ));
+        assert!(matches!(
+            read_npy(&npy("<f8", false, "(4,)", &[0; 8])[..]),
+            Err(NpyError::Truncated(32, 8))
+        ));
+        // a shape whose byte length overflows
+        let shape = format!("({}, 2)", usize::MAX);
+        assert!(matches!(
+            read_npy(&npy("<f8", false, &shape, &[0; 8])[..]),
+            Err(NpyError::Header(_))
+        ));
+        // a version 2 header length that is too long to allocate
+        let mut bytes = NPY_MAGIC.to_vec();
+        bytes.extend([2, 0]);
+        bytes.extend(u32::MAX.to_le_bytes());
+        assert!(matches!(read_npy(&bytes[..]), Err(NpyError::Header(_))));
+    }
+
+    #[test]
+    fn test_read_npz() {
+        let weights: Vec<u8> = [0.5f64, -1.5]
+            .iter()
+            .flat_map(|f| f.to_le_bytes())
+            .collect();
+        let archive = npz(&[
+            ("weight.npy", npy("<f8", false, "(2,)", &weights)),
+            ("bias.npy", npy("|u1", false, "()", &[3])),
+        ]);
+        let arrays = read_npz(&archive).unwrap();
+        assert_eq!(arrays.len(), 2);
+        assert_eq!(arrays[0].0, "weight");
+        assert_eq!(arrays[0].1.data, vec![0.5, -1.5]);
+        assert_eq!(arrays[1].0, "bias");
+        assert_eq!(arrays[1].1.shape, Vec::<usize>::new());
+        assert_eq!(arrays[1].1.data, vec![3.0]);
+
+        // positional arrays keep their order past arr_9
+        let entries = (0..12)
+            .map(|i| {
+                let name = format!("arr_{}.npy", i);
+                (name, npy("|u1", false, "(1,)", &[i as u8]))
+            })
+            .collect::<Vec<_>>();
+        let entries = entries
+            .iter()
+            .map(|(name, data)| (name.as_str(), data.clone()))
+            .collect::<Vec<_>>();
+        let arrays = read_npz(&npz(&entries)).unwrap();
+        assert_eq!(
+            arrays.iter().map(|(_, a)| a.data[0]).collect::<Vec<_>>(),
+            (0..12).map(|i| i as f64).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_read_safetensors() {
+        let header = r#"{"__metadata__":{"format":"pt"},"fc.weight":{"dtype":"F32","shape":[2,2],"data_offsets":[0,16]},"fc.bias":{"dtype":"BF16","shape":[2],"data_offsets":[16,20]},"scale":{"dtype":"F16","shape":[],"data_offsets":[20,22]}}"#;
+        let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+        bytes.extend(header.as_bytes());
+        bytes.extend(
+            [1.0f32, -2.0, 3.0, 0.25]
+                .iter()
+                .flat_map(|f| f.to_le_bytes()),
+        );
+        bytes.extend([0x3f80u16, 0xc000].iter().flat_map(|f| f.to_le_bytes()));
+        bytes.extend(0x3c00u16.to_le_bytes());
+
+        let tensors = read_safetensors(&bytes).unwrap();
+        assert_eq!(tensors.len(), 3);
+        assert_eq!(tensors["fc.weight"].shape, vec![2, 2]);
+        assert_eq!(tensors["fc.weight"].data, vec![1.0, -2.0, 3.0, 0.25]);
+        assert_eq!(tensors["fc.bias"].data, vec![1.0, -2.0]);
+        assert_eq!(tensors["scale"].data, vec![1.0]);
+
+        // a header length that overflows rather than indexing past the end
+        let mut bytes = u64::MAX.to_le_bytes().to_vec();
+        bytes.extend(header.as_bytes());
+        assert!(matches!(
+            read_safetensors(&bytes),
+            Err(NpyError::SafeTensors(_))
+        ));
+        let mut bytes = (header.len() as u64 + 1).to_le_bytes().to_vec();
+        bytes.extend(header.as_bytes());
+        assert!(matches!(
+            read_safetensors(&bytes),
+            Err(NpyError::SafeTensors(_))
+        ));
+
+        // malformed and overflowing shapes are rejected rather than dropped or wrapped
+        let safetensors = |shape: &str| {
+            let header = format!(
+                r#"{{"x":{{"dtype":"F32","shape":{},"data_offsets":[0,4]}}}}"#,
+                shape
+            );
+            let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+            bytes.extend(header.as_bytes());
+            bytes.extend(1.0f32.to_le_bytes());
+            read_safetensors(&bytes)
+        };
+        assert!(safetensors("[1]").is_ok());
+        assert!(matches!(
+            safetensors("[1,-1]"),
+            Err(NpyError::SafeTensors(_))
+        ));
+        assert!(matches!(
+            safetensors(&format!("[{},2]", u64::MAX)),
+            Err(NpyError::SafeTensors(_))
+        ));
+    }
+
+    #[test]
+    fn test_witness_trace_roundtrip() {
+        let trace = vec![
+            TracedAdvice {
+                layer: 0,
+                column: 1,
+                row: 2,
+                value: vec![3; 32],
+            },
+            TracedAdvice {
+                layer: 4,
+                column: 0,
+                row: 70000,
+                value: vec![5; 32],
+            },
+        ];
+        let mut bytes = vec![];
+        write_witness_trace(&mut bytes, &trace).unwrap();
+        assert_eq!(bytes.len(), TRACE_MAGIC.len() + 12 + 2 * 44);
+        assert_eq!(read_witness_trace(&bytes).unwrap(), trace);
+        assert!(matches!(
+            read_witness_trace(&bytes[..bytes.len() - 1]),
+            Err(NpyError::Truncated(..))
+        ));
+    }
+}
@@ -29,7 +29,7 @@
 
 use circuit::Tolerance;
 use clap::Args;
-use graph::Visibility;
+use graph::{OutputAssertion, Visibility};
 use serde::{Deserialize, Serialize};
 
 /// Methods for configuring tensor operations and assigning values to them in a Halo2 circuit.
@@ -37,6 +37,8 @@ pub mod circuit;
 /// CLI commands.
 #[cfg(not(target_arch = "wasm32"))]
 pub mod commands;
+/// Errors carrying the node and op they originated from
+pub mod error;
 #[cfg(not(target_arch = "wasm32"))]
 // abigen doesn't generate docs for this module
 #[allow(missing_docs)]
@@ -46,6 +48,9 @@ pub mod eth;
 ///
 #[cfg(not(target_arch = "wasm32"))]
 pub mod execute;
+/// C api for embedding the prover in other runtimes
+#[cfg(all(feature = "ffi", not(target_arch = "wasm32")))]
+pub mod ffi;
 /// Utilities for converting from Halo2 Field types to integers (and vice-versa).
 pub mod fieldutils;
 /// Methods for loading onnx format models and automatically laying them out in
@@ -55,11 +60,16 @@ pub mod graph;
 /// Methods for deploying and interacting with the ezkl hub
 #[cfg(not(target_arch = "wasm32"))]
 pub mod hub;
+/// Readers for arrays exported from numpy / pytorch (.npy, .npz and .safetensors files)
+pub mod io;
 /// beautiful logging
 #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 pub mod logger;
 /// Tools for proofs and verification used by cli
 pub mod pfsys;
+/// Runs the full pipeline from an `ezkl.toml` configuration file
+#[cfg(not(target_arch = "wasm32"))]
+pub mod pipeline;
 /// Python bindings
 #[cfg(feature = "python-bindings")]
 pub mod python;
@@ -73,11 +83,16 @@ pub mod wasm;
 pub type Scale = i32;
 
 /// Parameters specific to a proving run
-#[derive(Debug, Args, Deserialize, Serialize, Clone, Default, PartialEq, PartialOrd)]
+#[derive(Debug, Args, Deserialize, Serialize, Clone, PartialEq, PartialOrd)]
 pub struct RunArgs {
     /// The tolerance for error on model outputs
     #[arg(short = 'T', long, default_value = "0")]
     pub tolerance: Tolerance,
+    /// The absolute tolerance for error on public or fixed model outputs, in the units of the (float) outputs.
+    /// Absorbs small rounding differences between a reference runtime and the circuit, cannot be combined with `tolerance`
+    #[arg(long, default_value = "0")]
+    #[serde(default)]
+    pub abs_tolerance: f32,
     /// The denominator in the fixed point representation used when quantizing inputs
     #[arg(short = 'S', long, default_value = "7", allow_hyphen_values = true)]
     pub input_scale: Scale,
@@ -108,6 +123,81 @@ pub struct RunArgs {
     /// Flags whether params are public, private, hashed
     #[arg(long, default_value = "private")]
     pub param_visibility: Visibility,
+    /// If set and outputs are public, outputs are rounded to this (lower) fixed point scale before
+    /// being exposed in the instance column
+    #[arg(long, allow_hyphen_values = true)]
+    #[serde(default)]
+    pub public_output_scale: Option<Scale>,
+    /// Fuses the rescaling of a layer's output (eg. of a conv) into the relu that follows it,
+    /// so that both are computed by a single lookup
+    #[arg(long)]
+    #[serde(default)]
+    pub fuse_ops: bool,
+    /// Leaves the model's advice columns unblinded. Proofs are still sound but no longer zero knowledge
+    /// (the witness can leak through the proof), for verifiable inference where privacy isn't needed.
+    /// It doesn't make proving measurably faster, as the circuit keeps the same number of blinding rows.
+    #[arg(long)]
+    #[serde(default)]
+    pub unblinded_advice: bool,
+    /// Lowers (ungrouped) convolutions to a single im2col matmul rather than one dot product per output
+    #[arg(long)]
+    #[serde(default)]
+    pub im2col_conv: bool,
+    /// Collapses the chains of axis moves that onnx transposes are lowered to (eg. the NHWC <-> NCHW transposes
+    /// around the convs of models exported from TensorFlow) into a single permute per transpose
+    #[arg(long)]
+    #[serde(default)]
+    pub fuse_transposes: bool,
+    /// Quantizes the kernels of convs and the weights of matmuls at one scale per output channel (up to 2^8 times
+    /// finer than the param scale), for models whose channels have very different ranges
+    #[arg(long)]
+    #[serde(default)]
+    pub per_channel_scales: bool,
+    /// Overrides the param visibility of single layers, given as `name=visibility` where `name` is the exact name of
+    /// an onnx node, eg. `head=private` keeps a fine-tuned head private on top of a fixed backbone.
+    /// Only private and fixed params can be mixed this way.
+    #[arg(long, value_parser = parse_layer_visibility, value_delimiter = ',')]
+    #[serde(default)]
+    pub layer_param_visibility: Vec<(String, Visibility)>,
+    /// Claims about single elements of the model outputs that are constrained in the circuit, written
+    /// `output[index]>threshold` (or `>=`, `<`, `<=`), eg. `0[3]>0.9`. They hold whatever the output visibility.
+    #[arg(long, value_delimiter = ',')]
+    #[serde(default)]
+    pub output_assertions: Vec<OutputAssertion>,
+    /// A .npz or .safetensors file whose named arrays replace the constants of the model (matched by name,
+    /// up to case and separators), eg. to prove with fine-tuned weights without re-exporting the onnx file
+    #[arg(long)]
+    #[serde(default)]
+    pub weights: Option<std::path::PathBuf>,
+}
+
+impl Default for RunArgs {
+    /// The cli defaults
+    fn default() -> Self {
+        Self {
+            tolerance: Tolerance::from(0.0),
+            abs_tolerance: 0.0,
+            input_scale: 7,
+            param_scale: 7,
+            scale_rebase_multiplier: 1,
+            lookup_range: (-32768, 32768),
+            logrows: 17,
+            num_inner_cols: 1,
+            variables: vec![("batch_size".to_string(), 1)],
+            input_visibility: Visibility::Private,
+            output_visibility: Visibility::Public,
+            param_visibility: Visibility::Private,
+            public_output_scale: None,
+            fuse_ops: false,
+            unblinded_advice: false,
+            im2col_conv: false,
+            fuse_transposes: false,
+            per_channel_scales: false,
+            layer_param_visibility: vec![],
+            output_assertions: vec![],
+            weights: None,
+        }
+    }
 }
 
 impl RunArgs {
@@ -143,6 +233,32 @@ where
     Ok((s[..pos].parse()?, s[pos + 1..].parse()?))
 }
 
+/// Parse a layer=visibility pair, where the visibility is private or fixed
+fn parse_layer_visibility(
+    s: &str,
+) -> Result<(String, Visibility), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let pos = s
+        .rfind('=')
+        .ok_or_else(|| format!("invalid LAYER=visibility: no `=` found in `{s}`"))?;
+    layer_visibility(&s[..pos], &s[pos + 1..])
+}
+
+/// Checks the param visibility of the layer `name`, which can only be private or fixed
+pub(crate) fn layer_visibility(
+    name: &str,
+    visibility: &str,
+) -> Result<(String, Visibility), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    if name.is_empty() {
+        return Err("the layer of a param visibility must be named".into());
+    }
+    let visibility = match visibility {
+        "private" => Visibility::Private,
+        "fixed" => Visibility::Fixed,
+        v => return Err(format!("layer params can only be private or fixed, not `{v}`").into()),
+    };
+    Ok((name.to_string(), visibility))
+}
+
 /// Parse a tuple
 fn parse_tuple<T>(s: &str) -> Result<(T, T), Box<dyn std::error::Error + Send + Sync + 'static>>
 where
@@ -0,0 +1,382 @@
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+use halo2_proofs::plonk::{Circuit, ConstraintSystem, VerifyingKey};
+use halo2curves::ff::{Field, PrimeField};
+use halo2curves::CurveAffine;
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+// Keys are written as `EZKL`, the length of the header (u32, little endian), the json header and then
+// the raw halo2 key. Reading a halo2 key with the params of another circuit reads garbage (or panics),
+// so the header is checked against the circuit the key is read for beforehand, and the key that was read
+// against the fingerprint of the header afterwards. Keys written before headers existed are still read,
+// unchecked. Proofs carry the header as a json field instead.
+
+/// Marks the start of the keys written by ezkl
+const MAGIC: &[u8; 4] = b"EZKL";
+
+/// The largest header read, so a corrupt length can't allocate arbitrarily much
+const MAX_HEADER_LEN: usize = 1 << 16;
+
+/// The version of the key and proof formats, bumped whenever a change to ezkl changes the layout of its
+/// circuits (and so invalidates the artifacts written before)
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The shape of a constraint system: everything that determines the layout of its keys
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstraintShape {
+    /// number of advice columns
+    pub advice: usize,
+    /// number of instance columns
+    pub instance: usize,
+    /// number of fixed columns
+    pub fixed: usize,
+    /// number of selectors
+    pub selectors: usize,
+    /// number of gates
+    pub gates: usize,
+    /// number of lookup arguments
+    pub lookups: usize,
+    /// number of columns in the permutation
+    pub permutation: usize,
+    /// degree of the constraint system
+    pub degree: usize,
+}
+
+impl ConstraintShape {
+    /// The shape of `cs`
+    pub fn new<F: Field>(cs: &ConstraintSystem<F>) -> Self {
+        ConstraintShape {
+            advice: cs.num_advice_columns(),
+            instance: cs.num_instance_columns(),
+            fixed: cs.num_fixed_columns(),
+            selectors: cs.num_selectors(),
+            gates: cs.gates().len(),
+            lookups: cs.lookups().len(),
+            permutation: cs.permutation().get_columns().len(),
+            degree: cs.degree(),
+        }
+    }
+
+    /// Whether a key with this shape is a key of a circuit `configured` with the given shape. Keygen
+    /// compresses the selectors of the circuit into new fixed columns (at most one per selector), so the
+    /// key can have that many more fixed columns than the configured circuit.
+    pub fn matches(&self, configured: &Self) -> bool {
+        let fixed =
+            configured.fixed <= self.fixed && self.fixed <= configured.fixed + configured.selectors;
+        fixed
+            && ConstraintShape {
+                fixed: configured.fixed,
+                ..self.clone()
+            } == *configured
+    }
+}
+
+/// The header of the keys (and proofs) written by ezkl, checked when they are read
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactHeader {
+    /// the [FORMAT_VERSION] the artifact was written with
+    pub format: u32,
+    /// the version of ezkl the artifact was written with
+    pub version: String,
+    /// the shape of the constraint system of the circuit
+    pub shape: ConstraintShape,
+    /// the fingerprint of the verifying key, see [vk_fingerprint]
+    pub fingerprint: String,
+    /// the log_2 number of rows of the circuit
+    pub k: u32,
+    /// the curve of the commitments
+    pub curve: String,
+}
+
+/// A fingerprint of `vk` (hex encoded): its transcript repr, which hashes its whole constraint system (gates,
+/// lookups and queries included), its domain, and the commitments to its fixed columns and permutation
+pub fn vk_fingerprint<C: CurveAffine>(vk: &VerifyingKey<C>) -> String {
+    vk.transcript_repr()
+        .to_repr()
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+impl ArtifactHeader {
+    /// The header of an artifact of the circuit with constraint system `cs`, `2^k` rows and a verifying key
+    /// with the given `fingerprint`, written with this version of ezkl
+    pub fn new<C: CurveAffine>(
+        cs: &ConstraintSystem<C::Scalar>,
+        k: u32,
+        fingerprint: String,
+    ) -> Self {
+        ArtifactHeader {
+            format: FORMAT_VERSION,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            shape: ConstraintShape::new(cs),
+            fingerprint,
+            k,
+            curve: std::any::type_name::<C>().to_string(),
+        }
+    }
+
+    /// The header of the keys of `vk`, and of the proofs created with them
+    pub fn from_vk<C: CurveAffine>(vk: &VerifyingKey<C>) -> Self {
+        Self::new::<C>(vk.cs(), vk.get_domain().k(), vk_fingerprint(vk))
+    }
+
+    /// Writes the magic bytes and the header
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let header =
+            serde_json::to_vec(self).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        writer.write_all(MAGIC)?;
+        writer.write_all(&(header.len() as u32).to_le_bytes())?;
+        writer.write_all(&header)
+    }
+
+    /// Reads the magic bytes and the header, or nothing (and `None`) if the artifact was written without a
+    /// header
+    pub fn read<R: BufRead>(reader: &mut R) -> Result<Option<Self>, Box<dyn Error>> {
+        if !reader.fill_buf()?.starts_with(MAGIC) {
+            warn!("artifact has no header, it can't be checked against the circuit");
+            return Ok(None);
+        }
+        reader.consume(MAGIC.len());
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        let len = u32::from_le_bytes(len) as usize;
+        if len > MAX_HEADER_LEN {
+            return Err(format!(
+                "artifact header is {} bytes, at most {} are read",
+                len, MAX_HEADER_LEN
+            )
+            .into());
+        }
+        let mut header = vec![0u8; len];
+        reader.read_exact(&mut header)?;
+        serde_json::from_slice(&header)
+            .map(Some)
+            .map_err(|e| format!("invalid artifact header: {}", e).into())
+    }
+
+    /// Checks that the artifact is in the current [FORMAT_VERSION], for the curve `C`. Artifacts written by
+    /// another version of ezkl in the same format are read, with a warning.
+    pub fn check_version<C: CurveAffine>(&self) -> Result<(), Box<dyn Error>> {
+        if self.format != FORMAT_VERSION {
+            return Err(format!(
+                "artifact has format version {} (ezkl {}), this ezkl reads version {}: regenerate it",
+                self.format, self.version, FORMAT_VERSION
+            )
+            .into());
+        }
+        let version = env!("CARGO_PKG_VERSION");
+        if self.version != version {
+            warn!(
+                "artifact was written by ezkl {}, this is ezkl {}",
+                self.version, version
+            );
+        }
+        let curve = std::any::type_name::<C>();
+        if self.curve != curve {
+            return Err(format!("artifact is for curve {}, not {}", self.curve, curve).into());
+        }
+        Ok(())
+    }
+
+    /// Checks that the artifact can be read as a key of the circuit `Circ` configured with `params`
+    pub fn check_circuit<C: CurveAffine, Circ: Circuit<C::Scalar>>(
+        &self,
+        params: Circ::Params,
+    ) -> Result<(), Box<dyn Error>> {
+        self.check_version::<C>()?;
+        let mut cs = ConstraintSystem::<C::Scalar>::default();
+        let _ = Circ::configure_with_params(&mut cs, params);
+        self.check_shape(&ConstraintShape::new(&cs))
+    }
+
+    /// Checks that the artifact is for a circuit configured with the given [ConstraintShape]
+    pub fn check_shape(&self, configured: &ConstraintShape) -> Result<(), Box<dyn Error>> {
+        if !self.shape.matches(configured) {
+            return Err(format!(
+                "artifact is for another circuit ({:?}), not this one ({:?}): check the settings or regenerate the keys",
+                self.shape, configured
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Checks that the artifact is for `vk`: the key read after the header, or the key a proof is verified
+    /// with
+    pub fn check_vk<C: CurveAffine>(&self, vk: &VerifyingKey<C>) -> Result<(), Box<dyn Error>> {
+        let expected = Self::from_vk(vk);
+        self.check_shape(&expected.shape)?;
+        if self.k != expected.k {
+            return Err(format!(
+                "artifact is for a circuit with 2^{} rows, the key has 2^{}",
+                self.k, expected.k
+            )
+            .into());
+        }
+        if self.fingerprint != expected.fingerprint {
+            return Err(format!(
+                "artifact is for the key with fingerprint {}, not {}: check the settings or regenerate the keys",
+                self.fingerprint, expected.fingerprint
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pfsys::{vk_from_bytes, vk_to_bytes};
+    use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+    use halo2_proofs::plonk::{keygen_vk, Advice, Column, Error as PlonkError, Fixed};
+    use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+    use halo2_proofs::poly::Rotation;
+    use halo2curves::bn256::{Bn256, Fr, G1Affine};
+
+    /// A single gate, `advice * advice` if `SQUARE` and `advice * fixed` otherwise: both circuits have the
+    /// same constraint shape
+    #[derive(Clone, Default)]
+    struct GateCircuit<const SQUARE: bool>;
+
+    impl<const SQUARE: bool> Circuit<Fr> for GateCircuit<SQUARE> {
+        type Config = (Column<Advice>, Column<Fixed>);
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let advice = cs.advice_column();
+            let fixed = cs.fixed_column();
+            cs.create_gate("gate", |cs| {
+                let a = cs.query_advice(advice, Rotation::cur());
+                let f = cs.query_fixed(fixed, Rotation::cur());
+                vec![if SQUARE { a.clone() * a } else { a * f }]
+            });
+            (advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            (advice, fixed): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), PlonkError> {
+            layouter.assign_region(
+                || "gate",
+                |mut region| {
+                    region.assign_advice(|| "a", advice, 0, || Value::known(Fr::ZERO))?;
+                    region.assign_fixed(|| "f", fixed, 0, || Value::known(Fr::ZERO))?;
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn test_header_roundtrip() {
+        let mut cs = ConstraintSystem::<Fr>::default();
+        let advice = cs.advice_column();
+        cs.enable_equality(advice);
+        cs.fixed_column();
+        cs.selector();
+        let header = ArtifactHeader::new::<G1Affine>(&cs, 10, "00".into());
+        assert_eq!(header.format, FORMAT_VERSION);
+        assert_eq!(header.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            (
+                header.shape.advice,
+                header.shape.fixed,
+                header.shape.selectors
+            ),
+            (1, 1, 1)
+        );
+        assert_eq!(header.shape.permutation, 1);
+
+        let mut bytes = vec![];
+        header.write(&mut bytes).unwrap();
+        bytes.extend([1, 2, 3]);
+        let mut reader = &bytes[..];
+        assert_eq!(
+            ArtifactHeader::read(&mut reader).unwrap(),
+            Some(header.clone())
+        );
+        // the key follows the header
+        assert_eq!(reader, &[1, 2, 3]);
+        assert!(header.check_version::<G1Affine>().is_ok());
+        // another version of ezkl in the same format is read
+        let upgraded = ArtifactHeader {
+            version: "0.0.0".into(),
+            ..header.clone()
+        };
+        assert!(upgraded.check_version::<G1Affine>().is_ok());
+        let reformatted = ArtifactHeader {
+            format: FORMAT_VERSION + 1,
+            ..header.clone()
+        };
+        assert!(reformatted.check_version::<G1Affine>().is_err());
+
+        let mut other = ConstraintSystem::<Fr>::default();
+        other.advice_column();
+        assert!(header.check_shape(&ConstraintShape::new(&other)).is_err());
+
+        // keygen may compress the selector into one more fixed column, but not two
+        let configured = header.shape.clone();
+        let compressed = ConstraintShape {
+            fixed: 2,
+            ..configured.clone()
+        };
+        assert!(compressed.matches(&configured));
+        assert!(!ConstraintShape {
+            fixed: 3,
+            ..configured.clone()
+        }
+        .matches(&configured));
+        assert!(!ConstraintShape {
+            fixed: 0,
+            ..configured.clone()
+        }
+        .matches(&configured));
+        assert!(!ConstraintShape {
+            lookups: 1,
+            ..compressed
+        }
+        .matches(&configured));
+
+        // keys without a header are read from the start
+        let legacy = [0u8; 8];
+        let mut reader = &legacy[..];
+        assert_eq!(ArtifactHeader::read(&mut reader).unwrap(), None);
+        assert_eq!(reader, &legacy);
+
+        let mut huge = MAGIC.to_vec();
+        huge.extend(u32::MAX.to_le_bytes());
+        assert!(ArtifactHeader::read(&mut &huge[..]).is_err());
+    }
+
+    #[test]
+    fn test_header_fingerprint() {
+        let params = crate::pfsys::srs::gen_srs::<KZGCommitmentScheme<Bn256>>(4);
+        let vk = keygen_vk(&params, &GateCircuit::<false>).unwrap();
+        let square_vk = keygen_vk(&params, &GateCircuit::<true>).unwrap();
+        // the shapes can't tell the circuits apart, their fingerprints do
+        let header = ArtifactHeader::from_vk(&vk);
+        assert_eq!(header.shape, ArtifactHeader::from_vk(&square_vk).shape);
+        assert!(header.check_vk(&vk).is_ok());
+        assert!(header.check_vk(&square_vk).is_err());
+
+        let bytes = vk_to_bytes::<KZGCommitmentScheme<Bn256>>(&vk).unwrap();
+        assert!(
+            vk_from_bytes::<KZGCommitmentScheme<Bn256>, Fr, GateCircuit<false>>(&bytes, ()).is_ok()
+        );
+        assert!(
+            vk_from_bytes::<KZGCommitmentScheme<Bn256>, Fr, GateCircuit<true>>(&bytes, ()).is_err()
+        );
+    }
+}
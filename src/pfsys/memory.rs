@@ -0,0 +1,85 @@
+use log::{info, warn};
+
+// Peak resident memory is read from procfs, so on other platforms (and in wasm) every function
+// here reports nothing rather than failing.
+
+/// The environment variable which turns on the peak memory reports of keygen and proving
+pub const PROFILE_MEMORY_ENV: &str = "EZKL_PROFILE_MEMORY";
+
+/// Whether the peak memory of keygen and proving should be reported, which is only the case when
+/// [PROFILE_MEMORY_ENV] is set: measuring a phase resets the peak of the whole process (see [reset_peak])
+pub fn profiling() -> bool {
+    std::env::var(PROFILE_MEMORY_ENV).is_ok()
+}
+
+/// Reads a `kB` valued field (eg. `VmHWM`) of a procfs status file, returned in bytes
+fn read_kb_field(path: &str, field: &str) -> Option<u64> {
+    let status = std::fs::read_to_string(path).ok()?;
+    status
+        .lines()
+        .find(|l| l.starts_with(field) && l[field.len()..].starts_with(':'))
+        .and_then(|l| {
+            l[field.len() + 1..]
+                .trim()
+                .trim_end_matches("kB")
+                .trim()
+                .parse::<u64>()
+                .ok()
+        })
+        .map(|kb| kb * 1024)
+}
+
+/// The peak resident memory of the process in bytes, since startup or the last [reset_peak]
+pub fn peak() -> Option<u64> {
+    read_kb_field("/proc/self/status", "VmHWM")
+}
+
+/// The memory the system can still hand out in bytes
+pub fn available() -> Option<u64> {
+    read_kb_field("/proc/meminfo", "MemAvailable")
+}
+
+/// Resets the peak reported by [peak] to the current resident memory, so that the peak of a
+/// single phase (eg. keygen) can be measured
+pub fn reset_peak() {
+    // writing 5 to clear_refs resets VmHWM, this is a no-op for kernels that don't support it
+    let _ = std::fs::write("/proc/self/clear_refs", "5");
+}
+
+fn mib(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+/// Logs the peak memory of `phase` (measured since the last [reset_peak]) and returns it in bytes.
+/// Warns when the system is close to running out, as the process is then likely to be killed
+/// by the OOM killer in a later phase.
+pub fn report(phase: &str) -> Option<u64> {
+    let peak = peak()?;
+    info!("{} peak memory: {:.1} MiB", phase, mib(peak));
+    if let Some(available) = available() {
+        if available < peak / 4 {
+            warn!(
+                "only {:.1} MiB of memory left after {}, larger circuits will likely be killed for running out of memory",
+                mib(available),
+                phase
+            );
+        }
+    }
+    Some(peak)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_kb_field() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("status");
+        std::fs::write(&path, "VmPeak:\t  2048 kB\nVmHWM:\t  1024 kB\n").unwrap();
+        let path = path.to_str().unwrap();
+        assert_eq!(read_kb_field(path, "VmHWM"), Some(1024 * 1024));
+        assert_eq!(read_kb_field(path, "VmPeak"), Some(2048 * 1024));
+        assert_eq!(read_kb_field(path, "VmRSS"), None);
+    }
+}
@@ -4,9 +4,22 @@ pub mod evm;
 /// SRS generation, processing, verification and downloading
 pub mod srs;
 
+/// Sessions of several related proofs with domain-separated transcripts
+pub mod session;
+
+/// Peak memory accounting for keygen and proving
+pub mod memory;
+
+/// Progress reporting for keygen and proving
+pub mod progress;
+
+/// Versioned headers of the keys and proofs, checked when they are loaded
+pub mod header;
+
 use crate::circuit::CheckMode;
 use crate::graph::GraphWitness;
 use crate::pfsys::evm::aggregation::PoseidonTranscript;
+use crate::pfsys::header::ArtifactHeader;
 use crate::tensor::TensorType;
 use clap::ValueEnum;
 use halo2_proofs::circuit::Value;
@@ -16,8 +29,12 @@ use halo2_proofs::plonk::{
 use halo2_proofs::poly::commitment::{CommitmentScheme, Params, ParamsProver, Prover, Verifier};
 use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
 use halo2_proofs::poly::kzg::multiopen::{ProverSHPLONK, VerifierSHPLONK};
+use halo2_proofs::poly::kzg::strategy::AccumulatorStrategy;
 use halo2_proofs::poly::VerificationStrategy;
-use halo2_proofs::transcript::{EncodedChallenge, TranscriptReadBuffer, TranscriptWriterBuffer};
+use halo2_proofs::transcript::{
+    Blake2bRead, Blake2bWrite, Challenge255, EncodedChallenge, TranscriptReadBuffer,
+    TranscriptWriterBuffer,
+};
 use halo2curves::ff::{FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
 use halo2curves::serde::SerdeObject;
 use halo2curves::CurveAffine;
@@ -35,7 +52,7 @@ use snark_verifier::system::halo2::{compile, Config};
 use snark_verifier::verifier::plonk::PlonkProtocol;
 use std::error::Error;
 use std::fs::File;
-use std::io::{self, BufReader, BufWriter, Cursor, Write};
+use std::io::{self, BufRead, BufReader, BufWriter, Cursor, Write};
 use std::ops::Deref;
 use std::path::PathBuf;
 use thiserror::Error as thisError;
@@ -141,11 +158,15 @@ pub enum PfSysError {
     PackingExponent,
 }
 
+/// The hash used for the Fiat-Shamir transcript of a proof. EVM (keccak) proofs are cheap to verify
+/// on chain, Poseidon proofs are cheap to verify in circuit (ie. to aggregate) and Blake2b is halo2's
+/// native transcript, for proofs that are only ever verified natively.
 #[allow(missing_docs)]
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum TranscriptType {
     Poseidon,
     EVM,
+    Blake2b,
 }
 
 #[cfg(feature = "python-bindings")]
@@ -154,6 +175,7 @@ impl ToPyObject for TranscriptType {
         match self {
             TranscriptType::Poseidon => "Poseidon".to_object(py),
             TranscriptType::EVM => "EVM".to_object(py),
+            TranscriptType::Blake2b => "Blake2b".to_object(py),
         }
     }
 }
@@ -213,6 +235,9 @@ where
     pub transcript_type: TranscriptType,
     /// the split proof
     pub split: Option<ProofSplitCommit>,
+    /// the header of the proof, identifying the version of ezkl and the circuit it was created with
+    #[serde(default)]
+    pub header: Option<ArtifactHeader>,
 }
 
 #[cfg(feature = "python-bindings")]
@@ -261,6 +286,7 @@ where
             proof,
             transcript_type,
             split,
+            header: None,
         }
     }
 
@@ -281,7 +307,19 @@ where
     {
         trace!("reading proof");
         let data = std::fs::read_to_string(proof_path)?;
-        serde_json::from_str(&data).map_err(|e| e.into())
+        let snark: Self = serde_json::from_str(&data)?;
+        if let Some(header) = &snark.header {
+            header.check_version::<C>()?;
+        }
+        Ok(snark)
+    }
+
+    /// Checks that the proof was created for the circuit of `vk`, if it has a header
+    pub fn check_vk(&self, vk: &VerifyingKey<C>) -> Result<(), Box<dyn Error>> {
+        if let Some(header) = &self.header {
+            header.check_vk(vk)?;
+        }
+        Ok(())
     }
 }
 
@@ -394,17 +432,27 @@ where
     let empty_circuit = <C as Circuit<F>>::without_witnesses(circuit);
 
     // Initialize verifying key
+    if memory::profiling() {
+        memory::reset_peak();
+    }
     let now = Instant::now();
     trace!("preparing VK");
+    progress::report(progress::Progress::Started("vk"));
     let vk = keygen_vk(params, &empty_circuit)?;
     let elapsed = now.elapsed();
+    progress::report(progress::Progress::Finished("vk", elapsed.as_millis()));
     info!("VK took {}.{}", elapsed.as_secs(), elapsed.subsec_millis());
 
     // Initialize the proving key
     let now = Instant::now();
+    progress::report(progress::Progress::Started("pk"));
     let pk = keygen_pk(params, vk, &empty_circuit)?;
     let elapsed = now.elapsed();
+    progress::report(progress::Progress::Finished("pk", elapsed.as_millis()));
     info!("PK took {}.{}", elapsed.as_secs(), elapsed.subsec_millis());
+    if memory::profiling() {
+        memory::report("keygen");
+    }
     Ok(pk)
 }
 
@@ -442,7 +490,69 @@ where
         + Ord,
     Scheme::Curve: Serialize + DeserializeOwned,
 {
-    let mut transcript = TranscriptWriterBuffer::<_, Scheme::Curve, _>::init(vec![]);
+    create_proof_circuit_with_domain::<Scheme, F, C, P, V, Strategy, E, TW, TR>(
+        circuit,
+        instances,
+        params,
+        pk,
+        strategy,
+        check_mode,
+        transcript_type,
+        split,
+        None,
+    )
+}
+
+/// a wrapper around halo2's create_proof which, if a `domain` is provided, absorbs it into the transcript before any of the proof is written
+pub(crate) fn create_proof_circuit_with_domain<
+    'params,
+    Scheme: CommitmentScheme,
+    F: PrimeField + TensorType,
+    C: Circuit<F>,
+    P: Prover<'params, Scheme>,
+    V: Verifier<'params, Scheme>,
+    Strategy: VerificationStrategy<'params, Scheme, V>,
+    E: EncodedChallenge<Scheme::Curve>,
+    TW: TranscriptWriterBuffer<Vec<u8>, Scheme::Curve, E>,
+    TR: TranscriptReadBuffer<Cursor<Vec<u8>>, Scheme::Curve, E>,
+>(
+    circuit: C,
+    instances: Vec<Vec<Scheme::Scalar>>,
+    params: &'params Scheme::ParamsProver,
+    pk: &ProvingKey<Scheme::Curve>,
+    strategy: Strategy,
+    check_mode: CheckMode,
+    transcript_type: TranscriptType,
+    split: Option<ProofSplitCommit>,
+    domain: Option<Scheme::Scalar>,
+) -> Result<Snark<Scheme::Scalar, Scheme::Curve>, Box<dyn Error>>
+where
+    C: Circuit<Scheme::Scalar>,
+    Scheme::ParamsVerifier: 'params,
+    Scheme::Scalar: Serialize
+        + DeserializeOwned
+        + SerdeObject
+        + PrimeField
+        + FromUniformBytes<64>
+        + WithSmallOrderMulGroup<3>
+        + Ord,
+    Scheme::Curve: Serialize + DeserializeOwned,
+{
+    let k = pk.get_vk().get_domain().k();
+    if params.k() != k {
+        return Err(format!(
+            "the srs has 2^{} rows, the proving key is for a circuit with 2^{}: use an srs of that size",
+            params.k(),
+            k
+        )
+        .into());
+    }
+    let mut transcript: TW = TranscriptWriterBuffer::<_, Scheme::Curve, _>::init(vec![]);
+    if let Some(domain) = domain {
+        transcript
+            .common_scalar(domain)
+            .map_err(|_| "failed to absorb transcript domain")?;
+    }
     #[cfg(feature = "det-prove")]
     let mut rng = <StdRng as rand::SeedableRng>::from_seed([0u8; 32]);
     #[cfg(not(feature = "det-prove"))]
@@ -467,8 +577,12 @@ where
     );
 
     info!("proof started...");
+    if memory::profiling() {
+        memory::reset_peak();
+    }
     // not wasm32 unknown
     let now = Instant::now();
+    progress::report(progress::Progress::Started("proof"));
 
     create_proof::<Scheme, P, _, _, TW, _>(
         params,
@@ -479,18 +593,27 @@ where
         &mut transcript,
     )?;
     let proof = transcript.finalize();
+    progress::report(progress::Progress::Finished(
+        "proof",
+        now.elapsed().as_millis(),
+    ));
+    if memory::profiling() {
+        memory::report("proof");
+    }
 
-    let checkable_pf = Snark::new(protocol, instances, proof, transcript_type, split);
+    let mut checkable_pf = Snark::new(protocol, instances, proof, transcript_type, split);
+    checkable_pf.header = Some(ArtifactHeader::from_vk(pk.get_vk()));
 
     // sanity check that the generated proof is valid
     if check_mode == CheckMode::SAFE {
         debug!("verifying generated proof");
         let verifier_params = params.verifier_params();
-        verify_proof_circuit::<F, V, Scheme, Strategy, E, TR>(
+        verify_proof_circuit_with_domain::<F, V, Scheme, Strategy, E, TR>(
             &checkable_pf,
             verifier_params,
             pk.get_vk(),
             strategy,
+            domain,
         )?;
     }
     let elapsed = now.elapsed();
@@ -560,6 +683,12 @@ pub fn swap_proof_commitments_kzg(
             _,
             PoseidonTranscript<NativeLoader, _>,
         >(snark, commitments)?,
+        TranscriptType::Blake2b => swap_proof_commitments::<
+            Fr,
+            KZGCommitmentScheme<Bn256>,
+            _,
+            Blake2bWrite<_, _, Challenge255<_>>,
+        >(snark, commitments)?,
     };
     Ok(proof)
 }
@@ -579,6 +708,37 @@ pub fn verify_proof_circuit<
     vk: &VerifyingKey<Scheme::Curve>,
     strategy: Strategy,
 ) -> Result<Strategy::Output, halo2_proofs::plonk::Error>
+where
+    Scheme::Scalar: SerdeObject
+        + PrimeField
+        + FromUniformBytes<64>
+        + WithSmallOrderMulGroup<3>
+        + Ord
+        + Serialize
+        + DeserializeOwned,
+    Scheme::Curve: Serialize + DeserializeOwned,
+{
+    verify_proof_circuit_with_domain::<F, V, Scheme, Strategy, E, TR>(
+        snark, params, vk, strategy, None,
+    )
+}
+
+/// A wrapper around halo2's verify_proof which absorbs the (optional) `domain` the proof was created with
+pub(crate) fn verify_proof_circuit_with_domain<
+    'params,
+    F: PrimeField,
+    V: Verifier<'params, Scheme>,
+    Scheme: CommitmentScheme,
+    Strategy: VerificationStrategy<'params, Scheme, V>,
+    E: EncodedChallenge<Scheme::Curve>,
+    TR: TranscriptReadBuffer<Cursor<Vec<u8>>, Scheme::Curve, E>,
+>(
+    snark: &Snark<Scheme::Scalar, Scheme::Curve>,
+    params: &'params Scheme::ParamsVerifier,
+    vk: &VerifyingKey<Scheme::Curve>,
+    strategy: Strategy,
+    domain: Option<Scheme::Scalar>,
+) -> Result<Strategy::Output, halo2_proofs::plonk::Error>
 where
     Scheme::Scalar: SerdeObject
         + PrimeField
@@ -597,7 +757,12 @@ where
     let instances: &[&[&[Scheme::Scalar]]] = &[&pi_inner];
     trace!("instances {:?}", instances);
 
-    let mut transcript = TranscriptReadBuffer::init(Cursor::new(snark.proof.clone()));
+    let mut transcript: TR = TranscriptReadBuffer::init(Cursor::new(snark.proof.clone()));
+    if let Some(domain) = domain {
+        transcript
+            .common_scalar(domain)
+            .map_err(halo2_proofs::plonk::Error::Transcript)?;
+    }
     verify_proof::<Scheme, V, _, TR, _>(params, vk, strategy, instances, &mut transcript)
 }
 
@@ -610,17 +775,14 @@ where
     C: Circuit<Scheme::Scalar>,
     Scheme::Curve: SerdeObject + CurveAffine,
     Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
+    <C as Circuit<Scheme::Scalar>>::Params: Clone,
 {
     info!("loading verification key from {:?}", path);
     let f =
         File::open(path.clone()).map_err(|_| format!("failed to load vk at {}", path.display()))?;
     let mut reader = BufReader::new(f);
-    VerifyingKey::<Scheme::Curve>::read::<_, C>(
-        &mut reader,
-        halo2_proofs::SerdeFormat::RawBytes,
-        params,
-    )
-    .map_err(Box::<dyn Error>::from)
+    read_vk::<Scheme, C, _>(&mut reader, params)
+        .map_err(|e| format!("failed to load vk at {}: {}", path.display(), e).into())
 }
 
 /// Loads a [ProvingKey] at `path`.
@@ -632,17 +794,66 @@ where
     C: Circuit<Scheme::Scalar>,
     Scheme::Curve: SerdeObject + CurveAffine,
     Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
+    <C as Circuit<Scheme::Scalar>>::Params: Clone,
 {
     info!("loading proving key from {:?}", path);
     let f =
         File::open(path.clone()).map_err(|_| format!("failed to load pk at {}", path.display()))?;
     let mut reader = BufReader::new(f);
-    ProvingKey::<Scheme::Curve>::read::<_, C>(
-        &mut reader,
+    read_pk::<Scheme, C, _>(&mut reader, params)
+        .map_err(|e| format!("failed to load pk at {}: {}", path.display(), e).into())
+}
+
+/// Reads a [VerifyingKey], checking its header (if it was written with one) against the circuit before the key
+/// itself, and against the key that was read afterwards
+fn read_vk<Scheme: CommitmentScheme, C: Circuit<Scheme::Scalar>, R: BufRead>(
+    reader: &mut R,
+    params: C::Params,
+) -> Result<VerifyingKey<Scheme::Curve>, Box<dyn Error>>
+where
+    Scheme::Curve: SerdeObject + CurveAffine,
+    Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
+    C::Params: Clone,
+{
+    let header = ArtifactHeader::read(reader)?;
+    if let Some(header) = &header {
+        header.check_circuit::<Scheme::Curve, C>(params.clone())?;
+    }
+    let vk = VerifyingKey::<Scheme::Curve>::read::<_, C>(
+        reader,
         halo2_proofs::SerdeFormat::RawBytes,
         params,
-    )
-    .map_err(Box::<dyn Error>::from)
+    )?;
+    if let Some(header) = &header {
+        header.check_vk(&vk)?;
+    }
+    Ok(vk)
+}
+
+/// Reads a [ProvingKey], checking its header (if it was written with one) against the circuit before the key
+/// itself, and against the key that was read afterwards
+fn read_pk<Scheme: CommitmentScheme, C: Circuit<Scheme::Scalar>, R: BufRead>(
+    reader: &mut R,
+    params: C::Params,
+) -> Result<ProvingKey<Scheme::Curve>, Box<dyn Error>>
+where
+    Scheme::Curve: SerdeObject + CurveAffine,
+    Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
+    C::Params: Clone,
+{
+    let header = ArtifactHeader::read(reader)?;
+    if let Some(header) = &header {
+        header.check_circuit::<Scheme::Curve, C>(params.clone())?;
+    }
+    let pk = ProvingKey::<Scheme::Curve>::read::<_, C>(
+        reader,
+        halo2_proofs::SerdeFormat::RawBytes,
+        params,
+    )?;
+    if let Some(header) = &header {
+        header.check_vk(pk.get_vk())?;
+    }
+    Ok(pk)
 }
 
 /// Saves a [ProvingKey] to `path`.
@@ -657,6 +868,7 @@ where
     info!("saving proving key 💾");
     let f = File::create(path)?;
     let mut writer = BufWriter::new(f);
+    ArtifactHeader::from_vk(vk.get_vk()).write(&mut writer)?;
     vk.write(&mut writer, halo2_proofs::SerdeFormat::RawBytes)?;
     writer.flush()?;
     Ok(())
@@ -674,6 +886,7 @@ where
     info!("saving verification key 💾");
     let f = File::create(path)?;
     let mut writer = BufWriter::new(f);
+    ArtifactHeader::from_vk(vk).write(&mut writer)?;
     vk.write(&mut writer, halo2_proofs::SerdeFormat::RawBytes)?;
     writer.flush()?;
     Ok(())
@@ -692,6 +905,73 @@ pub fn save_params<Scheme: CommitmentScheme>(
     Ok(())
 }
 
+/// Serializes a [VerifyingKey] to headered raw bytes, so it can be shared with verifiers without going through the filesystem.
+pub fn vk_to_bytes<Scheme: CommitmentScheme>(
+    vk: &VerifyingKey<Scheme::Curve>,
+) -> Result<Vec<u8>, io::Error>
+where
+    Scheme::Curve: SerdeObject + CurveAffine,
+    Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
+{
+    let mut bytes = vec![];
+    ArtifactHeader::from_vk(vk).write(&mut bytes)?;
+    vk.write(&mut bytes, halo2_proofs::SerdeFormat::RawBytes)?;
+    Ok(bytes)
+}
+
+/// Deserializes a [VerifyingKey] from raw bytes (as produced by [vk_to_bytes]).
+pub fn vk_from_bytes<Scheme: CommitmentScheme, F: PrimeField + TensorType, C: Circuit<F>>(
+    bytes: &[u8],
+    params: <C as Circuit<Scheme::Scalar>>::Params,
+) -> Result<VerifyingKey<Scheme::Curve>, Box<dyn Error>>
+where
+    C: Circuit<Scheme::Scalar>,
+    Scheme::Curve: SerdeObject + CurveAffine,
+    Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
+    <C as Circuit<Scheme::Scalar>>::Params: Clone,
+{
+    let mut reader = BufReader::new(bytes);
+    read_vk::<Scheme, C, _>(&mut reader, params)
+}
+
+/// Serializes a [ProvingKey] to headered raw bytes, so keys generated once can be reused across runs.
+pub fn pk_to_bytes<Scheme: CommitmentScheme>(
+    pk: &ProvingKey<Scheme::Curve>,
+) -> Result<Vec<u8>, io::Error>
+where
+    Scheme::Curve: SerdeObject + CurveAffine,
+    Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
+{
+    let mut bytes = vec![];
+    ArtifactHeader::from_vk(pk.get_vk()).write(&mut bytes)?;
+    pk.write(&mut bytes, halo2_proofs::SerdeFormat::RawBytes)?;
+    Ok(bytes)
+}
+
+/// Deserializes a [ProvingKey] from raw bytes (as produced by [pk_to_bytes]).
+pub fn pk_from_bytes<Scheme: CommitmentScheme, F: PrimeField + TensorType, C: Circuit<F>>(
+    bytes: &[u8],
+    params: <C as Circuit<Scheme::Scalar>>::Params,
+) -> Result<ProvingKey<Scheme::Curve>, Box<dyn Error>>
+where
+    C: Circuit<Scheme::Scalar>,
+    Scheme::Curve: SerdeObject + CurveAffine,
+    Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
+    <C as Circuit<Scheme::Scalar>>::Params: Clone,
+{
+    let mut reader = BufReader::new(bytes);
+    read_pk::<Scheme, C, _>(&mut reader, params)
+}
+
+/// Serializes [CommitmentScheme] parameters to raw bytes.
+pub fn params_to_bytes<Scheme: CommitmentScheme>(
+    params: &'_ Scheme::ParamsVerifier,
+) -> Result<Vec<u8>, io::Error> {
+    let mut bytes = vec![];
+    params.write(&mut bytes)?;
+    Ok(bytes)
+}
+
 /// helper function
 pub fn create_proof_circuit_kzg<
     'params,
@@ -760,6 +1040,27 @@ pub fn create_proof_circuit_kzg<
             split,
         )
         .map_err(Box::<dyn Error>::from),
+        TranscriptType::Blake2b => create_proof_circuit::<
+            KZGCommitmentScheme<_>,
+            Fr,
+            _,
+            ProverSHPLONK<_>,
+            VerifierSHPLONK<_>,
+            _,
+            _,
+            Blake2bWrite<_, _, Challenge255<_>>,
+            Blake2bRead<_, _, Challenge255<_>>,
+        >(
+            circuit,
+            public_inputs,
+            params,
+            pk,
+            strategy,
+            check_mode,
+            transcript,
+            split,
+        )
+        .map_err(Box::<dyn Error>::from),
     }
 }
 
@@ -791,9 +1092,47 @@ pub(crate) fn verify_proof_circuit_kzg<
             _,
             PoseidonTranscript<NativeLoader, _>,
         >(&proof, params, vk, strategy),
+        TranscriptType::Blake2b => verify_proof_circuit::<
+            Fr,
+            VerifierSHPLONK<'_, Bn256>,
+            _,
+            _,
+            _,
+            Blake2bRead<_, _, Challenge255<_>>,
+        >(&proof, params, vk, strategy),
     }
 }
 
+/// Verifies `snarks` created with the same `vk`. Each proof is checked against its own instances, but
+/// the final pairing checks of all the proofs are folded (with random coefficients) into a single one,
+/// so verifying many proofs costs little more than verifying one.
+pub fn verify_batch(
+    snarks: &[Snark<Fr, G1Affine>],
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+) -> Result<(), Box<dyn Error>> {
+    if snarks.is_empty() {
+        return Err("no proofs to verify".into());
+    }
+    let mut strategy = AccumulatorStrategy::new(params);
+    for (i, snark) in snarks.iter().enumerate() {
+        snark
+            .check_vk(vk)
+            .map_err(|e| format!("failed to verify proof {}: {}", i, e))?;
+        strategy = verify_proof_circuit_kzg(params, snark.clone(), vk, strategy)
+            .map_err(|e| format!("failed to verify proof {}: {}", i, e))?;
+    }
+    let verified = VerificationStrategy::<
+        '_,
+        KZGCommitmentScheme<Bn256>,
+        VerifierSHPLONK<'_, Bn256>,
+    >::finalize(strategy);
+    if !verified {
+        return Err("batch verification failed".into());
+    }
+    Ok(())
+}
+
 ////////////////////////
 
 #[cfg(test)]
@@ -841,6 +1180,29 @@ mod tests {
         assert!(res.is_ok())
     }
 
+    #[test]
+    fn test_params_bytes_roundtrip() {
+        let srs = srs::gen_srs::<KZGCommitmentScheme<Bn256>>(1);
+        let bytes = params_to_bytes::<KZGCommitmentScheme<Bn256>>(&srs).unwrap();
+        let mut reader = BufReader::new(&bytes[..]);
+        let srs2: ParamsKZG<Bn256> = Params::<'_, G1Affine>::read(&mut reader).unwrap();
+        assert_eq!(
+            bytes,
+            params_to_bytes::<KZGCommitmentScheme<Bn256>>(&srs2).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_session_proof_domains_are_distinct() {
+        let a = session::proof_domain(Fr::from(1), 0).unwrap();
+        let b = session::proof_domain(Fr::from(1), 1).unwrap();
+        let c = session::proof_domain(Fr::from(2), 0).unwrap();
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(b, c);
+        assert_eq!(a, session::proof_domain(Fr::from(1), 0).unwrap());
+    }
+
     #[test]
     fn test_snark_serialization_roundtrip() {
         let snark = Snark::<Fr, G1Affine> {
@@ -849,6 +1211,7 @@ mod tests {
             transcript_type: TranscriptType::EVM,
             protocol: None,
             split: None,
+            header: None,
         };
 
         snark
@@ -0,0 +1,68 @@
+use log::trace;
+use std::sync::RwLock;
+
+// halo2 doesn't expose its FFTs and MSMs, so the finest grained steps reported here are the
+// layers of the model as they are synthesized (at keygen and at proving).
+
+/// A step of keygen or proving, passed to the callback registered with [set_callback]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Progress {
+    /// A phase ("vk", "pk" or "proof") started
+    Started(&'static str),
+    /// A phase finished, after the given number of milliseconds
+    Finished(&'static str, u128),
+    /// Layer `layer` (out of `total`) of the model was synthesized
+    Layer {
+        /// the index of the layer in the (sub)graph
+        layer: usize,
+        /// the number of layers in the (sub)graph
+        total: usize,
+    },
+}
+
+type Callback = Box<dyn Fn(&Progress) + Send + Sync>;
+
+lazy_static::lazy_static! {
+    static ref CALLBACK: RwLock<Option<Callback>> = RwLock::new(None);
+}
+
+/// Registers `callback` to be called at every [Progress] step of keygen and proving, eg. to drive a
+/// progress bar. Replaces any previously registered callback.
+pub fn set_callback(callback: impl Fn(&Progress) + Send + Sync + 'static) {
+    *CALLBACK.write().unwrap() = Some(Box::new(callback));
+}
+
+/// Removes the callback registered with [set_callback]
+pub fn clear_callback() {
+    *CALLBACK.write().unwrap() = None;
+}
+
+/// Reports `progress` to the registered callback (if any)
+pub fn report(progress: Progress) {
+    trace!("progress: {:?}", progress);
+    if let Some(callback) = CALLBACK.read().unwrap().as_ref() {
+        callback(&progress);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_progress_callback() {
+        let seen = Arc::new(Mutex::new(vec![]));
+        let recorder = seen.clone();
+        set_callback(move |p| recorder.lock().unwrap().push(p.clone()));
+        report(Progress::Started("proof"));
+        report(Progress::Layer { layer: 1, total: 2 });
+        clear_callback();
+        report(Progress::Finished("proof", 10));
+
+        let seen = seen.lock().unwrap();
+        assert!(seen.contains(&Progress::Started("proof")));
+        assert!(seen.contains(&Progress::Layer { layer: 1, total: 2 }));
+        assert!(!seen.contains(&Progress::Finished("proof", 10)));
+    }
+}
@@ -0,0 +1,231 @@
+use super::{
+    create_proof_circuit_with_domain, verify_proof_circuit_with_domain, ProofSplitCommit, Snark,
+    TranscriptType,
+};
+use crate::circuit::modules::poseidon::spec::{PoseidonSpec, POSEIDON_RATE, POSEIDON_WIDTH};
+use crate::circuit::modules::poseidon::PoseidonChip;
+use crate::circuit::modules::Module;
+use crate::circuit::CheckMode;
+use crate::pfsys::evm::aggregation::PoseidonTranscript;
+use halo2_proofs::plonk::{Circuit, ProvingKey, VerifyingKey};
+use halo2_proofs::poly::commitment::ParamsProver;
+use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
+use halo2_proofs::poly::kzg::multiopen::{ProverSHPLONK, VerifierSHPLONK};
+use halo2_proofs::poly::kzg::strategy::SingleStrategy;
+use halo2_proofs::transcript::{Blake2bRead, Blake2bWrite, Challenge255};
+use halo2curves::bn256::{Bn256, Fr, G1Affine};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use snark_verifier::loader::native::NativeLoader;
+use snark_verifier::system::halo2::transcript::evm::EvmTranscript;
+use std::error::Error;
+use std::io::BufWriter;
+use std::path::PathBuf;
+
+/// Derives the transcript domain separator of the `idx`-th proof in the session `session_id`.
+/// Both the prover and the verifier absorb this value before anything else, so a proof
+/// can't be replayed in another session or at another position within the same session.
+pub fn proof_domain(session_id: Fr, idx: usize) -> Result<Fr, Box<dyn Error>> {
+    let hash = PoseidonChip::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 2>::run(vec![
+        session_id,
+        Fr::from(idx as u64),
+    ])?;
+    Ok(hash[0][0])
+}
+
+/// A set of related proofs (e.g. per-layer chunks of a model) created with domain-separated transcripts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofSession {
+    /// the identifier all proof domains are derived from
+    pub session_id: Fr,
+    /// the transcript used for every proof in the session
+    pub transcript_type: TranscriptType,
+    /// the proofs, in the order they were created
+    pub proofs: Vec<Snark<Fr, G1Affine>>,
+}
+
+impl ProofSession {
+    /// Create a new, empty, session
+    pub fn new(session_id: Fr, transcript_type: TranscriptType) -> Self {
+        Self {
+            session_id,
+            transcript_type,
+            proofs: vec![],
+        }
+    }
+
+    /// Number of proofs in the session
+    pub fn len(&self) -> usize {
+        self.proofs.len()
+    }
+
+    /// Whether the session has no proofs yet
+    pub fn is_empty(&self) -> bool {
+        self.proofs.is_empty()
+    }
+
+    /// Proves `circuit` as the next proof of the session and returns it
+    pub fn prove<C: Circuit<Fr>>(
+        &mut self,
+        circuit: C,
+        public_inputs: Vec<Vec<Fr>>,
+        params: &ParamsKZG<Bn256>,
+        pk: &ProvingKey<G1Affine>,
+        check_mode: CheckMode,
+        split: Option<ProofSplitCommit>,
+    ) -> Result<&Snark<Fr, G1Affine>, Box<dyn Error>> {
+        let domain = proof_domain(self.session_id, self.proofs.len())?;
+        debug!("proof {} domain: {:?}", self.proofs.len(), domain);
+        let strategy = SingleStrategy::new(params);
+
+        let snark = match self.transcript_type {
+            TranscriptType::EVM => create_proof_circuit_with_domain::<
+                KZGCommitmentScheme<_>,
+                Fr,
+                _,
+                ProverSHPLONK<_>,
+                VerifierSHPLONK<_>,
+                _,
+                _,
+                EvmTranscript<G1Affine, _, _, _>,
+                EvmTranscript<G1Affine, _, _, _>,
+            >(
+                circuit,
+                public_inputs,
+                params,
+                pk,
+                strategy,
+                check_mode,
+                self.transcript_type,
+                split,
+                Some(domain),
+            )?,
+            TranscriptType::Poseidon => create_proof_circuit_with_domain::<
+                KZGCommitmentScheme<_>,
+                Fr,
+                _,
+                ProverSHPLONK<_>,
+                VerifierSHPLONK<_>,
+                _,
+                _,
+                PoseidonTranscript<NativeLoader, _>,
+                PoseidonTranscript<NativeLoader, _>,
+            >(
+                circuit,
+                public_inputs,
+                params,
+                pk,
+                strategy,
+                check_mode,
+                self.transcript_type,
+                split,
+                Some(domain),
+            )?,
+            TranscriptType::Blake2b => create_proof_circuit_with_domain::<
+                KZGCommitmentScheme<_>,
+                Fr,
+                _,
+                ProverSHPLONK<_>,
+                VerifierSHPLONK<_>,
+                _,
+                _,
+                Blake2bWrite<_, _, Challenge255<_>>,
+                Blake2bRead<_, _, Challenge255<_>>,
+            >(
+                circuit,
+                public_inputs,
+                params,
+                pk,
+                strategy,
+                check_mode,
+                self.transcript_type,
+                split,
+                Some(domain),
+            )?,
+        };
+
+        self.proofs.push(snark);
+        Ok(&self.proofs[self.proofs.len() - 1])
+    }
+
+    /// Verifies every proof of the session against its own domain. `vks` holds either a
+    /// single verifying key shared by all the proofs, or one verifying key per proof.
+    pub fn verify(
+        &self,
+        params: &ParamsKZG<Bn256>,
+        vks: &[VerifyingKey<G1Affine>],
+    ) -> Result<(), Box<dyn Error>> {
+        if vks.len() != 1 && vks.len() != self.proofs.len() {
+            return Err(format!(
+                "expected 1 or {} verifying keys, got {}",
+                self.proofs.len(),
+                vks.len()
+            )
+            .into());
+        }
+
+        for (idx, snark) in self.proofs.iter().enumerate() {
+            if snark.transcript_type != self.transcript_type {
+                return Err(format!("proof {} uses a different transcript type", idx).into());
+            }
+            let vk = if vks.len() == 1 { &vks[0] } else { &vks[idx] };
+            let domain = proof_domain(self.session_id, idx)?;
+            let strategy = SingleStrategy::new(params.verifier_params());
+
+            let res = match self.transcript_type {
+                TranscriptType::EVM => {
+                    verify_proof_circuit_with_domain::<
+                        Fr,
+                        VerifierSHPLONK<'_, Bn256>,
+                        _,
+                        _,
+                        _,
+                        EvmTranscript<G1Affine, _, _, _>,
+                    >(
+                        snark, params.verifier_params(), vk, strategy, Some(domain)
+                    )
+                }
+                TranscriptType::Poseidon => {
+                    verify_proof_circuit_with_domain::<
+                        Fr,
+                        VerifierSHPLONK<'_, Bn256>,
+                        _,
+                        _,
+                        _,
+                        PoseidonTranscript<NativeLoader, _>,
+                    >(
+                        snark, params.verifier_params(), vk, strategy, Some(domain)
+                    )
+                }
+                TranscriptType::Blake2b => {
+                    verify_proof_circuit_with_domain::<
+                        Fr,
+                        VerifierSHPLONK<'_, Bn256>,
+                        _,
+                        _,
+                        _,
+                        Blake2bRead<_, _, Challenge255<_>>,
+                    >(
+                        snark, params.verifier_params(), vk, strategy, Some(domain)
+                    )
+                }
+            };
+            res.map_err(|e| format!("proof {} failed to verify: {:?}", idx, e))?;
+        }
+        Ok(())
+    }
+
+    /// Saves the session to a specified `path`.
+    pub fn save(&self, path: &PathBuf) -> Result<(), Box<dyn Error>> {
+        let file = std::fs::File::create(path)?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, &self)?;
+        Ok(())
+    }
+
+    /// Load a json serialized session from the provided path.
+    pub fn load(path: &PathBuf) -> Result<Self, Box<dyn Error>> {
+        let data = std::fs::read_to_string(path)?;
+        serde_json::from_str(&data).map_err(|e| e.into())
+    }
+}
@@ -7,6 +7,9 @@ use std::fs::File;
 use std::io::BufReader;
 use std::path::PathBuf;
 
+#[cfg(not(target_arch = "wasm32"))]
+use ethers::utils::keccak256;
+
 /// for now we use the urls of the powers of tau ceremony from <https://github.com/han0110/halo2-kzg-srs>
 pub const PUBLIC_SRS_URL: &str =
     "https://trusted-setup-halo2kzg.s3.eu-central-1.amazonaws.com/perpetual-powers-of-tau-raw-";
@@ -26,3 +29,54 @@ pub fn load_srs<Scheme: CommitmentScheme>(
     let mut reader = BufReader::new(f);
     Params::<'_, Scheme::Curve>::read(&mut reader).map_err(Box::<dyn Error>::from)
 }
+
+/// The directory downloaded SRS are cached in, `$EZKL_SRS_DIR` if set and `~/.ezkl/srs` otherwise
+pub fn srs_cache_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var("EZKL_SRS_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(".ezkl").join("srs"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// The cache file of the SRS with `2^k` rows downloaded from `url`, srs from other urls than
+/// [PUBLIC_SRS_URL] are cached separately so they never shadow the public ones
+pub fn cached_srs_path(k: u32, url: Option<&str>) -> Option<PathBuf> {
+    let name = match url {
+        Some(url) => format!("kzg{}-{}.srs", k, &hex::encode(keccak256(url))[..16]),
+        None => format!("kzg{}.srs", k),
+    };
+    srs_cache_dir().map(|dir| dir.join(name))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Checks that the keccak256 hash of the raw `srs` bytes is `expected` (hex encoded, with or without 0x)
+pub fn check_srs_hash(srs: &[u8], expected: &str) -> Result<(), Box<dyn Error>> {
+    let hash = hex::encode(keccak256(srs));
+    if hash != expected.trim_start_matches("0x").to_lowercase() {
+        return Err(format!("srs hash mismatch: expected {}, got 0x{}", expected, hash).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_srs_hash() {
+        let hash = hex::encode(keccak256(b"srs"));
+        assert!(check_srs_hash(b"srs", &hash).is_ok());
+        assert!(check_srs_hash(b"srs", &format!("0x{}", hash.to_uppercase())).is_ok());
+        assert!(check_srs_hash(b"other", &hash).is_err());
+
+        assert_ne!(
+            cached_srs_path(17, None),
+            cached_srs_path(17, Some("https://srs"))
+        );
+        assert_ne!(cached_srs_path(17, None), cached_srs_path(18, None));
+    }
+}
@@ -0,0 +1,372 @@
+use crate::circuit::{CheckMode, Tolerance};
+use crate::execute::{
+    compile_circuit, gen_circuit_settings, gen_srs_cmd, gen_witness, prove, setup, verify,
+};
+use crate::graph::{OutputAssertion, Visibility};
+use crate::pfsys::{ProofType, Snark};
+use crate::{RunArgs, Scale};
+use halo2curves::bn256::{Fr, G1Affine};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::PathBuf;
+
+/// Quantization parameters of a pipeline, any field left out falls back to the cli default
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct QuantizationProfile {
+    /// The denominator in the fixed point representation used when quantizing inputs
+    pub input_scale: Option<Scale>,
+    /// The denominator in the fixed point representation used when quantizing parameters
+    pub param_scale: Option<Scale>,
+    /// if the scale is ever > scale_rebase_multiplier * input_scale then the scale is rebased to input_scale
+    pub scale_rebase_multiplier: Option<u32>,
+    /// The min and max elements in the lookup table input column
+    pub lookup_range: Option<(i128, i128)>,
+    /// The tolerance for error on model outputs (in percent)
+    pub tolerance: Option<f32>,
+    /// The absolute tolerance for error on model outputs (in the units of the outputs)
+    pub abs_tolerance: Option<f32>,
+    /// The fixed point scale public outputs are rounded to
+    pub public_output_scale: Option<Scale>,
+    /// Whether to fuse rescales into the relus that follow them
+    pub fuse_ops: Option<bool>,
+    /// Whether to leave the model's advice columns unblinded (no zero knowledge)
+    pub unblinded_advice: Option<bool>,
+    /// Whether to lower convolutions to im2col matmuls
+    pub im2col_conv: Option<bool>,
+    /// Whether to fuse the axis moves of onnx transposes into single permutes
+    pub fuse_transposes: Option<bool>,
+    /// Whether to quantize conv and matmul weights at per channel scales
+    pub per_channel_scales: Option<bool>,
+}
+
+/// Visibility of the model inputs, outputs and params, written as on the cli (eg. "hashed/private")
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct VisibilityProfile {
+    /// Flags whether inputs are public, private, hashed
+    pub input: Option<String>,
+    /// Flags whether outputs are public, private, hashed
+    pub output: Option<String>,
+    /// Flags whether params are public, private, hashed, fixed
+    pub param: Option<String>,
+    /// Overrides the (private or fixed) param visibility of the layers whose onnx names are keys
+    pub layers: BTreeMap<String, String>,
+}
+
+/// Where each artifact of the pipeline is written to
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OutputPaths {
+    /// Path to the circuit settings
+    pub settings: PathBuf,
+    /// Path to the compiled circuit
+    pub compiled_circuit: PathBuf,
+    /// Path to the witness
+    pub witness: PathBuf,
+    /// Path to the srs
+    pub srs: PathBuf,
+    /// Path to the verification key
+    pub vk: PathBuf,
+    /// Path to the proving key
+    pub pk: PathBuf,
+    /// Path to the proof
+    pub proof: PathBuf,
+}
+
+impl Default for OutputPaths {
+    fn default() -> Self {
+        Self {
+            settings: "settings.json".into(),
+            compiled_circuit: "network.compiled".into(),
+            witness: "witness.json".into(),
+            srs: "kzg.srs".into(),
+            vk: "vk.key".into(),
+            pk: "pk.key".into(),
+            proof: "proof.json".into(),
+        }
+    }
+}
+
+/// The contents of an `ezkl.toml` file
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PipelineConfig {
+    /// The path to the .onnx model file
+    pub model: PathBuf,
+    /// The path to the .json input data file
+    pub data: PathBuf,
+    /// A .npz or .safetensors file overriding the weights of the model
+    #[serde(default)]
+    pub weights: Option<PathBuf>,
+    /// The proving backend ("Single" or "ForAggr")
+    #[serde(default = "default_backend")]
+    pub backend: ProofType,
+    /// Overrides the log_2 number of rows
+    #[serde(default)]
+    pub k: Option<u32>,
+    /// Generates an (insecure, for testing purposes only) srs if there is none at the srs path
+    #[serde(default)]
+    pub gen_srs: bool,
+    /// Quantization parameters
+    #[serde(default)]
+    pub quantization: QuantizationProfile,
+    /// Visibility of inputs, outputs and params
+    #[serde(default)]
+    pub visibility: VisibilityProfile,
+    /// Where the artifacts are written to
+    #[serde(default)]
+    pub output: OutputPaths,
+    /// Claims about the model outputs constrained in the circuit
+    #[serde(default)]
+    pub assertions: Vec<OutputAssertion>,
+}
+
+fn default_backend() -> ProofType {
+    ProofType::Single
+}
+
+impl PipelineConfig {
+    /// Parse a pipeline configuration from a toml string
+    pub fn from_toml(config: &str) -> Result<Self, Box<dyn Error>> {
+        let config: Self = toml::from_str(config)?;
+        // rejects invalid visibilities when parsed rather than when run
+        config.run_args()?;
+        Ok(config)
+    }
+
+    /// Export the pipeline configuration as toml
+    pub fn as_toml(&self) -> Result<String, Box<dyn Error>> {
+        toml::to_string(&self).map_err(|e| e.into())
+    }
+
+    /// The run args described by the configuration
+    pub fn run_args(&self) -> Result<RunArgs, Box<dyn Error>> {
+        let q = &self.quantization;
+        let defaults = RunArgs::default();
+        let visibility = |v: &Option<String>, default: Visibility| match v {
+            Some(v) => v.parse::<Visibility>(),
+            None => Ok(default),
+        };
+        let layer_param_visibility = self
+            .visibility
+            .layers
+            .iter()
+            .map(|(layer, v)| crate::layer_visibility(layer, v).map_err(|e| e.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(RunArgs {
+            tolerance: q
+                .tolerance
+                .map(Tolerance::from)
+                .unwrap_or(defaults.tolerance),
+            abs_tolerance: q.abs_tolerance.unwrap_or(defaults.abs_tolerance),
+            input_scale: q.input_scale.unwrap_or(defaults.input_scale),
+            param_scale: q.param_scale.unwrap_or(defaults.param_scale),
+            scale_rebase_multiplier: q
+                .scale_rebase_multiplier
+                .unwrap_or(defaults.scale_rebase_multiplier),
+            lookup_range: q.lookup_range.unwrap_or(defaults.lookup_range),
+            logrows: self.k.unwrap_or(defaults.logrows),
+            input_visibility: visibility(&self.visibility.input, defaults.input_visibility)?,
+            output_visibility: visibility(&self.visibility.output, defaults.output_visibility)?,
+            param_visibility: visibility(&self.visibility.param, defaults.param_visibility)?,
+            public_output_scale: q.public_output_scale.or(defaults.public_output_scale),
+            fuse_ops: q.fuse_ops.unwrap_or(defaults.fuse_ops),
+            unblinded_advice: q.unblinded_advice.unwrap_or(defaults.unblinded_advice),
+            im2col_conv: q.im2col_conv.unwrap_or(defaults.im2col_conv),
+            fuse_transposes: q.fuse_transposes.unwrap_or(defaults.fuse_transposes),
+            per_channel_scales: q.per_channel_scales.unwrap_or(defaults.per_channel_scales),
+            layer_param_visibility,
+            output_assertions: self.assertions.clone(),
+            weights: self.weights.clone(),
+            ..defaults
+        })
+    }
+}
+
+/// Runs settings generation, compilation, setup, proving and verification from a single
+/// configuration, so that a run can be reproduced from its `ezkl.toml`.
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+    /// the configuration driving the pipeline
+    pub config: PipelineConfig,
+}
+
+impl Pipeline {
+    /// Load a pipeline from an `ezkl.toml` file
+    pub fn from_config(path: &PathBuf) -> Result<Self, Box<dyn Error>> {
+        let config = std::fs::read_to_string(path)?;
+        Ok(Self {
+            config: PipelineConfig::from_toml(&config)?,
+        })
+    }
+
+    /// Run the full pipeline, returning the verified proof
+    pub async fn run(&self) -> Result<Snark<Fr, G1Affine>, Box<dyn Error>> {
+        let config = &self.config;
+        let out = &config.output;
+        let run_args = config.run_args()?;
+        let logrows = run_args.logrows;
+
+        // before spending any time on the settings and the compilation
+        if !out.srs.exists() {
+            if !config.gen_srs {
+                return Err(format!(
+                    "no srs found at {:?}: fetch one with get-srs, or set gen_srs = true to generate one for testing purposes only",
+                    out.srs
+                )
+                .into());
+            }
+            warn!(
+                "no srs found at {:?}, generating an insecure one for testing purposes only",
+                out.srs
+            );
+            gen_srs_cmd(out.srs.clone(), logrows)?;
+        }
+
+        info!("generating settings for {:?}", config.model);
+        gen_circuit_settings(config.model.clone(), out.settings.clone(), run_args)?;
+        compile_circuit(
+            config.model.clone(),
+            out.compiled_circuit.clone(),
+            out.settings.clone(),
+        )?;
+
+        gen_witness(
+            out.compiled_circuit.clone(),
+            config.data.clone(),
+            Some(out.witness.clone()),
+            None,
+            None,
+        )
+        .await?;
+
+        setup(
+            out.compiled_circuit.clone(),
+            out.srs.clone(),
+            out.vk.clone(),
+            out.pk.clone(),
+            Some(out.witness.clone()),
+        )?;
+
+        let snark = prove(
+            out.witness.clone(),
+            out.compiled_circuit.clone(),
+            out.pk.clone(),
+            Some(out.proof.clone()),
+            out.srs.clone(),
+            config.backend,
+            CheckMode::UNSAFE,
+        )
+        .await?;
+
+        verify(
+            out.proof.clone(),
+            out.settings.clone(),
+            out.vk.clone(),
+            out.srs.clone(),
+        )?;
+
+        Ok(snark)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_config_defaults() {
+        let config = PipelineConfig::from_toml(
+            r#"
+            model = "network.onnx"
+            data = "input.json"
+            k = 12
+
+            [quantization]
+            input_scale = 5
+            public_output_scale = 2
+
+            [visibility]
+            param = "fixed"
+
+            [visibility.layers]
+            head = "private"
+
+            [[assertions]]
+            output = 0
+            index = 3
+            cmp = "Greater"
+            threshold = 0.9
+            "#,
+        )
+        .unwrap();
+
+        let run_args = config.run_args().unwrap();
+        let defaults = RunArgs::default();
+        assert_eq!(run_args.input_scale, 5);
+        assert_eq!(run_args.param_scale, defaults.param_scale);
+        assert_eq!(run_args.lookup_range, defaults.lookup_range);
+        assert_eq!(run_args.variables, defaults.variables);
+        assert_eq!(run_args.public_output_scale, Some(2));
+        assert_eq!(run_args.logrows, 12);
+        assert_eq!(run_args.param_visibility, Visibility::Fixed);
+        assert_eq!(
+            run_args.layer_param_visibility,
+            vec![("head".to_string(), Visibility::Private)]
+        );
+        assert!(crate::graph::VarVisibility::from_args(&run_args).is_ok());
+        assert_eq!(run_args.output_visibility, Visibility::Public);
+        assert_eq!(
+            run_args.output_assertions,
+            vec!["0[3]>0.9".parse().unwrap()]
+        );
+        assert_eq!(config.backend, ProofType::Single);
+        assert_eq!(config.output, OutputPaths::default());
+        assert!(!config.gen_srs);
+
+        let roundtrip = PipelineConfig::from_toml(&config.as_toml().unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+
+        // layer visibilities are checked when parsed
+        assert!(PipelineConfig::from_toml(
+            r#"
+            model = "network.onnx"
+            data = "input.json"
+
+            [visibility.layers]
+            head = "public"
+            "#,
+        )
+        .is_err());
+
+        // as are the input, output and param visibilities
+        assert!(PipelineConfig::from_toml(
+            r#"
+            model = "network.onnx"
+            data = "input.json"
+
+            [visibility]
+            param = "fixd"
+            "#,
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_requires_srs() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = PipelineConfig::from_toml(
+            r#"
+            model = "network.onnx"
+            data = "input.json"
+            "#,
+        )
+        .unwrap();
+        config.output.srs = dir.path().join("kzg.srs");
+
+        let err = Pipeline { config }.run().await.unwrap_err();
+        assert!(err.to_string().contains("no srs found"));
+    }
+}
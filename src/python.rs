@@ -9,7 +9,7 @@ use crate::commands::CalibrationTarget;
 use crate::fieldutils::{felt_to_i128, i128_to_felt};
 use crate::graph::modules::POSEIDON_LEN_GRAPH;
 use crate::graph::{
-    quantize_float, scale_to_multiplier, GraphCircuit, GraphSettings, Model, Visibility,
+    quantize_float, scale_to_multiplier, GraphCircuit, GraphSettings, Model, OutputAssertion, Visibility,
 };
 use crate::pfsys::evm::aggregation::AggregationCircuit;
 use crate::pfsys::{
@@ -242,6 +242,8 @@ struct PyRunArgs {
     #[pyo3(get, set)]
     pub tolerance: f32,
     #[pyo3(get, set)]
+    pub abs_tolerance: f32,
+    #[pyo3(get, set)]
     pub input_scale: crate::Scale,
     #[pyo3(get, set)]
     pub param_scale: crate::Scale,
@@ -261,6 +263,24 @@ struct PyRunArgs {
     pub param_visibility: Visibility,
     #[pyo3(get, set)]
     pub variables: Vec<(String, usize)>,
+    #[pyo3(get, set)]
+    pub public_output_scale: Option<crate::Scale>,
+    #[pyo3(get, set)]
+    pub fuse_ops: bool,
+    #[pyo3(get, set)]
+    pub unblinded_advice: bool,
+    #[pyo3(get, set)]
+    pub im2col_conv: bool,
+    #[pyo3(get, set)]
+    pub fuse_transposes: bool,
+    #[pyo3(get, set)]
+    pub per_channel_scales: bool,
+    #[pyo3(get, set)]
+    pub layer_param_visibility: Vec<(String, Visibility)>,
+    #[pyo3(get, set)]
+    pub output_assertions: Vec<OutputAssertion>,
+    #[pyo3(get, set)]
+    pub weights: Option<PathBuf>,
 }
 
 /// default instantiation of PyRunArgs
@@ -270,6 +290,7 @@ impl PyRunArgs {
     fn new() -> Self {
         PyRunArgs {
             tolerance: 0.0,
+            abs_tolerance: 0.0,
             input_scale: 7,
             param_scale: 7,
             scale_rebase_multiplier: 1,
@@ -280,6 +301,15 @@ impl PyRunArgs {
             output_visibility: Visibility::Public,
             param_visibility: Visibility::Private,
             variables: vec![("batch_size".to_string(), 1)],
+            public_output_scale: None,
+            fuse_ops: false,
+            unblinded_advice: false,
+            im2col_conv: false,
+            fuse_transposes: false,
+            per_channel_scales: false,
+            layer_param_visibility: vec![],
+            output_assertions: vec![],
+            weights: None,
         }
     }
 }
@@ -289,6 +319,7 @@ impl From<PyRunArgs> for RunArgs {
     fn from(py_run_args: PyRunArgs) -> Self {
         RunArgs {
             tolerance: Tolerance::from(py_run_args.tolerance),
+            abs_tolerance: py_run_args.abs_tolerance,
             input_scale: py_run_args.input_scale,
             param_scale: py_run_args.param_scale,
             num_inner_cols: py_run_args.num_inner_cols,
@@ -299,6 +330,15 @@ impl From<PyRunArgs> for RunArgs {
             output_visibility: py_run_args.output_visibility,
             param_visibility: py_run_args.param_visibility,
             variables: py_run_args.variables,
+            public_output_scale: py_run_args.public_output_scale,
+            fuse_ops: py_run_args.fuse_ops,
+            unblinded_advice: py_run_args.unblinded_advice,
+            im2col_conv: py_run_args.im2col_conv,
+            fuse_transposes: py_run_args.fuse_transposes,
+            per_channel_scales: py_run_args.per_channel_scales,
+            layer_param_visibility: py_run_args.layer_param_visibility,
+            output_assertions: py_run_args.output_assertions,
+            weights: py_run_args.weights,
         }
     }
 }
@@ -307,6 +347,7 @@ impl Into<PyRunArgs> for RunArgs {
     fn into(self) -> PyRunArgs {
         PyRunArgs {
             tolerance: self.tolerance.val.into(),
+            abs_tolerance: self.abs_tolerance,
             input_scale: self.input_scale,
             param_scale: self.param_scale,
             num_inner_cols: self.num_inner_cols,
@@ -317,6 +358,15 @@ impl Into<PyRunArgs> for RunArgs {
             output_visibility: self.output_visibility,
             param_visibility: self.param_visibility,
             variables: self.variables,
+            public_output_scale: self.public_output_scale,
+            fuse_ops: self.fuse_ops,
+            unblinded_advice: self.unblinded_advice,
+            im2col_conv: self.im2col_conv,
+            fuse_transposes: self.fuse_transposes,
+            per_channel_scales: self.per_channel_scales,
+            layer_param_visibility: self.layer_param_visibility,
+            output_assertions: self.output_assertions,
+            weights: self.weights,
         }
     }
 }
@@ -625,11 +675,15 @@ fn gen_srs(srs_path: PathBuf, logrows: usize) -> PyResult<()> {
     srs_path,
     settings_path=None,
     logrows=None,
+    srs_url=None,
+    srs_hash=None,
 ))]
 fn get_srs(
     srs_path: PathBuf,
     settings_path: Option<PathBuf>,
     logrows: Option<u32>,
+    srs_url: Option<String>,
+    srs_hash: Option<String>,
 ) -> PyResult<bool> {
     Runtime::new()
         .unwrap()
@@ -638,6 +692,8 @@ fn get_srs(
             settings_path,
             logrows,
             CheckMode::SAFE,
+            srs_url,
+            srs_hash,
         ))
         .map_err(|e| {
             let err_str = format!("Failed to get srs: {}", e);
@@ -831,6 +887,27 @@ fn verify(
     Ok(true)
 }
 
+/// verifies several proofs of the same circuit at once
+#[pyfunction(signature = (
+    proof_paths,
+    settings_path,
+    vk_path,
+    srs_path,
+))]
+fn verify_batch(
+    proof_paths: Vec<PathBuf>,
+    settings_path: PathBuf,
+    vk_path: PathBuf,
+    srs_path: PathBuf,
+) -> Result<bool, PyErr> {
+    crate::execute::verify_batch(proof_paths, settings_path, vk_path, srs_path).map_err(|e| {
+        let err_str = format!("Failed to run verify_batch: {}", e);
+        PyRuntimeError::new_err(err_str)
+    })?;
+
+    Ok(true)
+}
+
 #[pyfunction(signature = (
     sample_snarks,
     vk_path,
@@ -1246,6 +1323,7 @@ fn ezkl(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(setup, m)?)?;
     m.add_function(wrap_pyfunction!(prove, m)?)?;
     m.add_function(wrap_pyfunction!(verify, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_batch, m)?)?;
     m.add_function(wrap_pyfunction!(gen_srs, m)?)?;
     m.add_function(wrap_pyfunction!(get_srs, m)?)?;
     m.add_function(wrap_pyfunction!(gen_witness, m)?)?;
@@ -54,6 +54,9 @@ pub enum TensorError {
     /// Failed to convert to field element tensor
     #[error("Failed to convert to field element tensor")]
     FeltError,
+    /// An integer op overflowed i128
+    #[error("integer overflow at element {0}")]
+    Overflow(usize),
 }
 
 /// The (inner) type of tensor elements.
@@ -141,6 +144,12 @@ tensor_type!(i32, Int32, 0, 1);
 tensor_type!(usize, USize, 0, 1);
 tensor_type!((), Empty, (), ());
 tensor_type!(utils::F32, F32, utils::F32(0.0), utils::F32(1.0));
+tensor_type!(
+    utils::CheckedI128,
+    CheckedI128,
+    utils::CheckedI128(Some(0)),
+    utils::CheckedI128(Some(1))
+);
 
 impl<T: TensorType> TensorType for Tensor<T> {
     fn zero() -> Option<Self> {
@@ -680,6 +689,22 @@ impl<T: Clone + TensorType> Tensor<T> {
         index
     }
 
+    /// Get the rows / columns indices from an array index, the inverse of [Tensor::get_index].
+    ///
+    /// ```
+    /// use ezkl::tensor::Tensor;
+    /// let a = Tensor::<f32>::new(None, &[3, 3, 3]).unwrap();
+    ///
+    /// assert_eq!(a.get_coord(26), vec![2, 2, 2]);
+    /// assert_eq!(a.get_coord(17), vec![1, 2, 2]);
+    /// assert_eq!(a.get_coord(15), vec![1, 2, 0]);
+    /// assert_eq!(a.get_index(&a.get_coord(10)), 10);
+    /// ```
+    pub fn get_coord(&self, index: usize) -> Vec<usize> {
+        assert!(index < self.len());
+        coord_from_index(index, &self.dims)
+    }
+
     /// Duplicates every nth element
     ///
     /// ```
@@ -886,6 +911,82 @@ impl<T: Clone + TensorType> Tensor<T> {
         Ok(output)
     }
 
+    /// Permutes the axes of the tensor, axis `i` of the output is axis `perm[i]` of the input
+    /// ```
+    /// use ezkl::tensor::Tensor;
+    /// let a = Tensor::<i32>::new(Some(&[1, 2, 3, 4, 5, 6]), &[2, 3]).unwrap();
+    /// let expected = Tensor::<i32>::new(Some(&[1, 4, 2, 5, 3, 6]), &[3, 2]).unwrap();
+    /// let b = a.permute(&[1, 0]).unwrap();
+    /// assert_eq!(b, expected);
+    ///
+    /// // NHWC -> NCHW
+    /// let a = Tensor::<i32>::new(Some(&[1, 2, 3, 4, 5, 6, 7, 8]), &[1, 2, 2, 2]).unwrap();
+    /// let expected = Tensor::<i32>::new(Some(&[1, 3, 5, 7, 2, 4, 6, 8]), &[1, 2, 2, 2]).unwrap();
+    /// let b = a.permute(&[0, 3, 1, 2]).unwrap();
+    /// assert_eq!(b, expected);
+    /// ```
+    pub fn permute(&self, perm: &[usize]) -> Result<Self, TensorError> {
+        let mut sorted = perm.to_vec();
+        sorted.sort();
+        if sorted != (0..self.dims.len()).collect::<Vec<_>>() {
+            return Err(TensorError::DimMismatch("permute".to_string()));
+        }
+        let new_dims = perm.iter().map(|p| self.dims[*p]).collect::<Vec<_>>();
+
+        let cartesian_coords = new_dims
+            .iter()
+            .map(|d| 0..*d)
+            .multi_cartesian_product()
+            .collect::<Vec<Vec<usize>>>();
+
+        let mut output = Tensor::new(None, &new_dims)?;
+
+        for coord in cartesian_coords {
+            let mut old_coord = vec![0; self.dims.len()];
+            for (i, c) in coord.iter().enumerate() {
+                old_coord[perm[i]] = *c;
+            }
+            output.set(&coord, self.get(&old_coord));
+        }
+
+        Ok(output)
+    }
+
+    /// Reverses the order of the elements of the tensor along an axis
+    /// ```
+    /// use ezkl::tensor::Tensor;
+    /// let mut a = Tensor::<i32>::new(Some(&[1, 2, 3, 4, 5, 6]), &[2, 3]).unwrap();
+    /// let expected = Tensor::<i32>::new(Some(&[3, 2, 1, 6, 5, 4]), &[2, 3]).unwrap();
+    /// let b = a.flip(1).unwrap();
+    /// assert_eq!(b, expected);
+    ///
+    /// let expected = Tensor::<i32>::new(Some(&[4, 5, 6, 1, 2, 3]), &[2, 3]).unwrap();
+    /// let b = a.flip(0).unwrap();
+    /// assert_eq!(b, expected);
+    /// ```
+    pub fn flip(&self, axis: usize) -> Result<Self, TensorError> {
+        if axis >= self.dims.len() {
+            return Err(TensorError::DimError);
+        }
+
+        let cartesian_coords = self
+            .dims
+            .iter()
+            .map(|d| 0..*d)
+            .multi_cartesian_product()
+            .collect::<Vec<Vec<usize>>>();
+
+        let mut output = Tensor::new(None, &self.dims)?;
+
+        for coord in cartesian_coords {
+            let mut old_coord = coord.clone();
+            old_coord[axis] = self.dims[axis] - 1 - coord[axis];
+            output.set(&coord, self.get(&old_coord));
+        }
+
+        Ok(output)
+    }
+
     /// Swap axes of the tensor
     /// ```
     /// use ezkl::tensor::Tensor;
@@ -948,7 +1049,10 @@ impl<T: Clone + TensorType> Tensor<T> {
         Ok(output)
     }
 
-    /// Broadcasts the tensor to a given shape
+    /// Broadcasts the tensor to a given shape. As in numpy, dims are aligned from the trailing
+    /// end and a dim of 1 is repeated along the corresponding dim of `shape` (so a `[C, 1, 1]`
+    /// bias expands over a `[N, C, H, W]` feature map). Shapes whose trailing dims don't line up
+    /// can't be broadcast, eg. a `[2]` tensor against `[2, 3]` has to be reshaped to `[2, 1]`.
     /// ```
     /// use ezkl::tensor::Tensor;
     /// let mut a = Tensor::<i32>::new(Some(&[1, 2, 3]), &[3, 1]).unwrap();
@@ -956,17 +1060,29 @@ impl<T: Clone + TensorType> Tensor<T> {
     /// let mut expected = Tensor::<i32>::new(Some(&[1, 1, 1, 2, 2, 2, 3, 3, 3]), &[3, 3]).unwrap();
     /// assert_eq!(a.expand(&[3, 3]).unwrap(), expected);
     ///
+    /// // trailing dims are aligned
+    /// let a = Tensor::<i32>::new(Some(&[1, 2, 3]), &[3]).unwrap();
+    /// let expected = Tensor::<i32>::new(Some(&[1, 2, 3, 1, 2, 3]), &[2, 3]).unwrap();
+    /// assert_eq!(a.expand(&[2, 3]).unwrap(), expected);
+    ///
+    /// // a per channel bias
+    /// let a = Tensor::<i32>::new(Some(&[1, 2]), &[2, 1, 1]).unwrap();
+    /// let expected = Tensor::<i32>::new(Some(&[1, 1, 1, 1, 2, 2, 2, 2]), &[2, 2, 2]).unwrap();
+    /// assert_eq!(a.expand(&[2, 2, 2]).unwrap(), expected);
+    ///
+    /// // incompatible shapes
+    /// let a = Tensor::<i32>::new(Some(&[1, 2]), &[2]).unwrap();
+    /// assert!(a.expand(&[2, 3]).is_err());
+    /// assert!(a.expand(&[3, 3]).is_err());
     /// ```
     pub fn expand(&self, shape: &[usize]) -> Result<Self, TensorError> {
-        assert!(self.dims().len() <= shape.len());
-
         if shape == self.dims() {
             return Ok(self.clone());
         }
 
-        for d in self.dims() {
-            assert!(shape.contains(d) || *d == 1);
-        }
+        let offset = broadcast_offset(self.dims(), shape).ok_or_else(|| {
+            TensorError::DimMismatch(format!("cannot broadcast {:?} to {:?}", self.dims(), shape))
+        })?;
 
         let cartesian_coords = shape
             .iter()
@@ -977,16 +1093,12 @@ impl<T: Clone + TensorType> Tensor<T> {
         let mut output = Tensor::new(None, shape)?;
 
         for coord in cartesian_coords {
-            let mut new_coord = Vec::with_capacity(self.dims().len());
-            for (i, c) in coord.iter().enumerate() {
-                if i < self.dims().len() && self.dims()[i] == 1 {
-                    new_coord.push(0);
-                } else if i >= self.dims().len() {
-                    // do nothing at this point does not exist in the original tensor
-                } else {
-                    new_coord.push(*c);
-                }
-            }
+            let new_coord = self
+                .dims()
+                .iter()
+                .enumerate()
+                .map(|(i, d)| if *d == 1 { 0 } else { coord[i + offset] })
+                .collect::<Vec<_>>();
             output.set(&coord, self.get(&new_coord));
         }
 
@@ -1019,6 +1131,30 @@ impl<T: Clone + TensorType> Tensor<T> {
         t
     }
 
+    /// Creates a tensor of shape `dims` by calling `f` on the coordinates of each element, for
+    /// any number of dims.
+    /// ```
+    /// use ezkl::tensor::{Tensor, TensorError};
+    /// let a = Tensor::<usize>::ndmap::<_, TensorError>(&[2, 3], |c| Ok(10 * c[0] + c[1])).unwrap();
+    /// let expected = Tensor::<usize>::new(Some(&[0, 1, 2, 10, 11, 12]), &[2, 3]).unwrap();
+    /// assert_eq!(a, expected);
+    ///
+    /// // eg. a [N, C, H, W] image
+    /// let a = Tensor::<usize>::ndmap::<_, TensorError>(&[1, 2, 2, 2], |c| Ok(c.iter().sum())).unwrap();
+    /// let expected = Tensor::<usize>::new(Some(&[0, 1, 1, 2, 1, 2, 2, 3]), &[1, 2, 2, 2]).unwrap();
+    /// assert_eq!(a, expected);
+    /// ```
+    pub fn ndmap<F: FnMut(&[usize]) -> Result<T, E>, E: Error>(
+        dims: &[usize],
+        mut f: F,
+    ) -> Result<Tensor<T>, E> {
+        let len = dims.iter().product::<usize>();
+        let vec: Result<Vec<T>, E> = (0..len).map(|i| f(&coord_from_index(i, dims))).collect();
+        let mut t = Tensor::from(vec?.into_iter());
+        t.reshape(dims);
+        Ok(t)
+    }
+
     /// Maps a function to tensors and enumerates
     /// ```
     /// use ezkl::tensor::{Tensor, TensorError};
@@ -1161,15 +1297,16 @@ impl<T: TensorType + Add<Output = T> + std::marker::Send + std::marker::Sync> Ad
     /// ).unwrap();
     /// let k = Tensor::<i32>::new(
     ///     Some(&[2, 3]),
-    ///     &[2]).unwrap();
+    ///     &[2, 1]).unwrap();
     /// let result = x.add(k).unwrap();
     /// let expected = Tensor::<i32>::new(Some(&[4, 3, 4, 4, 4, 4]), &[2, 3]).unwrap();
     /// assert_eq!(result, expected);
     /// ```
     fn add(self, rhs: Self) -> Self::Output {
-        let broadcasted_shape = get_broadcasted_shape(self.dims(), rhs.dims()).unwrap();
-        let mut lhs = self.expand(&broadcasted_shape).unwrap();
-        let rhs = rhs.expand(&broadcasted_shape).unwrap();
+        let broadcasted_shape = get_broadcasted_shape(self.dims(), rhs.dims())
+            .map_err(|e| TensorError::DimMismatch(e.to_string()))?;
+        let mut lhs = self.expand(&broadcasted_shape)?;
+        let rhs = rhs.expand(&broadcasted_shape)?;
 
         lhs.par_iter_mut().zip(rhs).for_each(|(o, r)| {
             *o = o.clone() + r;
@@ -1248,16 +1385,17 @@ impl<T: TensorType + Sub<Output = T> + std::marker::Send + std::marker::Sync> Su
     /// ).unwrap();
     /// let k = Tensor::<i32>::new(
     ///     Some(&[2, 3]),
-    ///     &[2],
+    ///     &[2, 1],
     /// ).unwrap();
     /// let result = x.sub(k).unwrap();
     /// let expected = Tensor::<i32>::new(Some(&[0, -1, 0, -2, -2, -2]), &[2, 3]).unwrap();
     /// assert_eq!(result, expected);
     /// ```
     fn sub(self, rhs: Self) -> Self::Output {
-        let broadcasted_shape = get_broadcasted_shape(self.dims(), rhs.dims()).unwrap();
-        let mut lhs = self.expand(&broadcasted_shape).unwrap();
-        let rhs = rhs.expand(&broadcasted_shape).unwrap();
+        let broadcasted_shape = get_broadcasted_shape(self.dims(), rhs.dims())
+            .map_err(|e| TensorError::DimMismatch(e.to_string()))?;
+        let mut lhs = self.expand(&broadcasted_shape)?;
+        let rhs = rhs.expand(&broadcasted_shape)?;
 
         lhs.par_iter_mut().zip(rhs).for_each(|(o, r)| {
             *o = o.clone() - r;
@@ -1309,15 +1447,16 @@ impl<T: TensorType + Mul<Output = T> + std::marker::Send + std::marker::Sync> Mu
     /// ).unwrap();
     /// let k = Tensor::<i32>::new(
     ///     Some(&[2, 2]),
-    ///     &[2]).unwrap();
+    ///     &[2, 1]).unwrap();
     /// let result = x.mul(k).unwrap();
     /// let expected = Tensor::<i32>::new(Some(&[4, 2, 4, 2, 2, 2]), &[2, 3]).unwrap();
     /// assert_eq!(result, expected);
     /// ```
     fn mul(self, rhs: Self) -> Self::Output {
-        let broadcasted_shape = get_broadcasted_shape(self.dims(), rhs.dims()).unwrap();
-        let mut lhs = self.expand(&broadcasted_shape).unwrap();
-        let rhs = rhs.expand(&broadcasted_shape).unwrap();
+        let broadcasted_shape = get_broadcasted_shape(self.dims(), rhs.dims())
+            .map_err(|e| TensorError::DimMismatch(e.to_string()))?;
+        let mut lhs = self.expand(&broadcasted_shape)?;
+        let rhs = rhs.expand(&broadcasted_shape)?;
 
         lhs.par_iter_mut().zip(rhs).for_each(|(o, r)| {
             *o = o.clone() * r;
@@ -1403,9 +1542,10 @@ impl<T: TensorType + Div<Output = T> + std::marker::Send + std::marker::Sync> Di
     /// assert_eq!(result, expected);
     /// ```
     fn div(self, rhs: Self) -> Self::Output {
-        let broadcasted_shape = get_broadcasted_shape(self.dims(), rhs.dims()).unwrap();
-        let mut lhs = self.expand(&broadcasted_shape).unwrap();
-        let rhs = rhs.expand(&broadcasted_shape).unwrap();
+        let broadcasted_shape = get_broadcasted_shape(self.dims(), rhs.dims())
+            .map_err(|e| TensorError::DimMismatch(e.to_string()))?;
+        let mut lhs = self.expand(&broadcasted_shape)?;
+        let rhs = rhs.expand(&broadcasted_shape)?;
 
         lhs.par_iter_mut().zip(rhs).for_each(|(o, r)| {
             *o = o.clone() / r;
@@ -1443,28 +1583,77 @@ impl<T: TensorType + Div<Output = T> + std::marker::Send + std::marker::Sync> Di
 /// let c = get_broadcasted_shape(&a, &b).unwrap();
 /// assert_eq!(c, vec![2, 3]);
 ///
+/// // trailing dims are aligned as in numpy
+/// let a = vec![3, 1];
+/// let b = vec![2, 1, 4];
+/// let c = get_broadcasted_shape(&a, &b).unwrap();
+/// assert_eq!(c, vec![2, 3, 4]);
+///
+/// let a = vec![4, 1, 1];
+/// let b = vec![4, 8, 8];
+/// let c = get_broadcasted_shape(&a, &b).unwrap();
+/// assert_eq!(c, vec![4, 8, 8]);
+///
+/// // the trailing dims must match or be 1, whatever the leading ones
+/// let a = vec![2, 3];
+/// let b = vec![2];
+/// assert!(get_broadcasted_shape(&a, &b).is_err());
+///
+/// let a = vec![2, 3];
+/// let b = vec![4, 3];
+/// assert!(get_broadcasted_shape(&a, &b).is_err());
 /// ```
 
 pub fn get_broadcasted_shape(
     shape_a: &[usize],
     shape_b: &[usize],
 ) -> Result<Vec<usize>, Box<dyn Error>> {
-    let num_dims_a = shape_a.len();
-    let num_dims_b = shape_b.len();
-
-    // reewrite the below using match
-    if num_dims_a == num_dims_b {
-        let mut broadcasted_shape = Vec::with_capacity(num_dims_a);
-        for (dim_a, dim_b) in shape_a.iter().zip(shape_b.iter()) {
-            let max_dim = dim_a.max(dim_b);
-            broadcasted_shape.push(*max_dim);
-        }
-        Ok(broadcasted_shape)
-    } else if num_dims_a < num_dims_b {
-        Ok(shape_b.to_vec())
+    let (longer, shorter) = if shape_a.len() >= shape_b.len() {
+        (shape_a, shape_b)
     } else {
-        Ok(shape_a.to_vec())
+        (shape_b, shape_a)
+    };
+    let offset = longer.len() - shorter.len();
+
+    // numpy broadcasting: trailing dims are aligned and must match or be 1
+    let trailing = longer
+        .iter()
+        .enumerate()
+        .map(|(i, d)| {
+            if i < offset {
+                return Some(*d);
+            }
+            let other = shorter[i - offset];
+            match (*d, other) {
+                (a, b) if a == b => Some(a),
+                (1, b) => Some(b),
+                (a, 1) => Some(a),
+                _ => None,
+            }
+        })
+        .collect::<Option<Vec<usize>>>();
+
+    trailing.ok_or_else(|| format!("cannot broadcast {:?} and {:?}", shape_a, shape_b).into())
+}
+
+/// The coordinates of the element at `index` of a flattened tensor of shape `dims`
+fn coord_from_index(mut index: usize, dims: &[usize]) -> Vec<usize> {
+    let mut coord = vec![0; dims.len()];
+    for (c, d) in coord.iter_mut().zip(dims).rev() {
+        *c = index % d;
+        index /= d;
     }
+    coord
+}
+
+/// The position of the first dim of `dims` within `shape` when broadcasting `dims` to `shape`,
+/// with the trailing dims aligned as in numpy. None if `dims` can't be broadcast to `shape`.
+fn broadcast_offset(dims: &[usize], shape: &[usize]) -> Option<usize> {
+    let offset = shape.len().checked_sub(dims.len())?;
+    dims.iter()
+        .enumerate()
+        .all(|(i, d)| *d == 1 || *d == shape[i + offset])
+        .then_some(offset)
 }
 ////////////////////////
 
@@ -1502,4 +1691,27 @@ mod tests {
         let b = Tensor::<i32>::new(Some(&[1, 4]), &[2, 1]).unwrap();
         assert_eq!(a.get_slice(&[0..2, 0..1]).unwrap(), b);
     }
+
+    #[test]
+    fn tensor_coord_roundtrip() {
+        let a = Tensor::<i32>::new(None, &[2, 3, 4, 5]).unwrap();
+        for i in 0..a.len() {
+            let coord = a.get_coord(i);
+            assert!(coord.iter().zip(a.dims()).all(|(c, d)| c < d));
+            assert_eq!(a.get_index(&coord), i);
+        }
+    }
+
+    #[test]
+    fn valtensor_reshape() {
+        use halo2curves::bn256::Fr;
+        let a = Tensor::from((0..12).map(|i| Value::known(Fr::from(i as u64))));
+        let mut a = ValTensor::from(a);
+        // [C, H, W] -> [C * H * W]
+        a.reshape(&[3, 2, 2]).unwrap();
+        a.reshape(&[12]).unwrap();
+        assert_eq!(a.dims(), &[12]);
+        assert!(a.reshape(&[5, 2]).is_err());
+        assert_eq!(a.dims(), &[12]);
+    }
 }
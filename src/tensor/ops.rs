@@ -506,6 +506,97 @@ pub fn resize<T: TensorType + Send + Sync>(
     Ok(output)
 }
 
+/// The interpolation coefficients of a bilinear (half pixel) upsampling by integer `scales`.
+/// Only the last two axes may be upsampled. Returns the output shape, the common denominator of the weights,
+/// and for each output element the flat indices of the 4 input elements it interpolates alongside their integer weights.
+pub fn bilinear_resize_coeffs(
+    dims: &[usize],
+    scales: &[usize],
+) -> Result<(Vec<usize>, i128, Vec<[(usize, i128); 4]>), TensorError> {
+    let rank = dims.len();
+    if rank < 2
+        || scales.len() != rank
+        || scales.iter().any(|s| *s == 0)
+        || scales[..rank - 2].iter().any(|s| *s != 1)
+    {
+        return Err(TensorError::DimMismatch("bilinear resize".to_string()));
+    }
+
+    // coefficients of the two input elements the output element `o` falls in between along an axis
+    let axis_coeffs = |o: usize, len: usize, s: usize| -> [(usize, i128); 2] {
+        if s == 1 {
+            return [(o, 1), (o, 0)];
+        }
+        let den = 2 * s as i128;
+        // the source coordinate is (o + 0.5) / s - 0.5 = num / den
+        let num = (2 * o + 1) as i128 - s as i128;
+        if num <= 0 {
+            return [(0, den), (0, 0)];
+        }
+        let i0 = (num / den) as usize;
+        if i0 >= len - 1 {
+            return [(len - 1, den), (len - 1, 0)];
+        }
+        let frac = num % den;
+        [(i0, den - frac), (i0 + 1, frac)]
+    };
+    let axis_denom = |s: usize| if s == 1 { 1 } else { 2 * s as i128 };
+
+    let (h, w) = (dims[rank - 2], dims[rank - 1]);
+    let (sh, sw) = (scales[rank - 2], scales[rank - 1]);
+    let denom = axis_denom(sh) * axis_denom(sw);
+
+    let mut new_shape = dims.to_vec();
+    new_shape[rank - 2] *= sh;
+    new_shape[rank - 1] *= sw;
+    let batch = dims[..rank - 2].iter().product::<usize>();
+
+    let mut coeffs = Vec::with_capacity(batch * h * sh * w * sw);
+    for b in 0..batch {
+        for y in 0..h * sh {
+            let ch = axis_coeffs(y, h, sh);
+            for x in 0..w * sw {
+                let cw = axis_coeffs(x, w, sw);
+                let mut c = [(0, 0); 4];
+                for (i, (ih, wh)) in ch.iter().enumerate() {
+                    for (j, (iw, ww)) in cw.iter().enumerate() {
+                        c[2 * i + j] = ((b * h + ih) * w + iw, wh * ww);
+                    }
+                }
+                coeffs.push(c);
+            }
+        }
+    }
+
+    Ok((new_shape, denom, coeffs))
+}
+
+/// Bilinear (half pixel) upsampling of the last two axes of a tensor by integer `scales`.
+/// The interpolated values are rounded to the nearest integer.
+/// # Examples
+/// ```
+/// use ezkl::tensor::Tensor;
+/// use ezkl::tensor::ops::resize_bilinear;
+/// let a = Tensor::<i128>::new(Some(&[0, 8, 16, 24]), &[1, 1, 2, 2]).unwrap();
+/// let result = resize_bilinear(&a, &[1, 1, 2, 2]).unwrap();
+/// let expected = Tensor::<i128>::new(
+///     Some(&[0, 2, 6, 8, 4, 6, 10, 12, 12, 14, 18, 20, 16, 18, 22, 24]),
+///     &[1, 1, 4, 4],
+/// ).unwrap();
+/// assert_eq!(result, expected);
+/// ```
+pub fn resize_bilinear(a: &Tensor<i128>, scales: &[usize]) -> Result<Tensor<i128>, TensorError> {
+    let (new_shape, denom, coeffs) = bilinear_resize_coeffs(a.dims(), scales)?;
+    let numerators = Tensor::from(
+        coeffs
+            .iter()
+            .map(|c| c.iter().map(|(idx, w)| a[*idx] * w).sum::<i128>()),
+    );
+    let mut output = nonlinearities::const_div(&numerators, denom as f64);
+    output.reshape(&new_shape);
+    Ok(output)
+}
+
 /// Computes the einstein sum of a set of tensors.
 /// # Arguments
 /// * `equation` - Einstein summation equation
@@ -1029,6 +1120,45 @@ pub fn sum<T: TensorType + Add<Output = T>>(a: &Tensor<T>) -> Result<Tensor<T>,
     Tensor::new(Some(&[res]), &[1])
 }
 
+/// Cumulative sum of a tensor along an axis.
+/// # Arguments
+///
+/// * `a` - Tensor
+/// * `axis` - Axis to sum along
+/// # Examples
+/// ```
+/// use ezkl::tensor::Tensor;
+/// use ezkl::tensor::ops::cumsum;
+/// let x = Tensor::<i128>::new(
+///     Some(&[2, 15, 2, 1, 1, 0]),
+///     &[2, 3],
+/// ).unwrap();
+/// let result = cumsum(&x, 1).unwrap();
+/// let expected = Tensor::<i128>::new(Some(&[2, 17, 19, 1, 2, 2]), &[2, 3]).unwrap();
+/// assert_eq!(result, expected);
+///
+/// let result = cumsum(&x, 0).unwrap();
+/// let expected = Tensor::<i128>::new(Some(&[2, 15, 2, 3, 16, 2]), &[2, 3]).unwrap();
+/// assert_eq!(result, expected);
+/// ```
+pub fn cumsum<T: TensorType + Add<Output = T>>(
+    a: &Tensor<T>,
+    axis: usize,
+) -> Result<Tensor<T>, TensorError> {
+    if axis >= a.dims().len() {
+        return Err(TensorError::DimMismatch("cumsum".to_string()));
+    }
+    // the previous element along the axis is `stride` elements earlier in the flattened tensor
+    let stride = a.dims()[axis + 1..].iter().product::<usize>();
+    let mut output = a.clone();
+    for i in 0..output.len() {
+        if a.get_coord(i)[axis] > 0 {
+            output[i] = output[i - stride].clone() + output[i].clone();
+        }
+    }
+    Ok(output)
+}
+
 /// Takes prod of tensor's elements.
 /// # Arguments
 ///
@@ -1121,6 +1251,67 @@ pub fn downsample<T: TensorType + Send + Sync>(
     Ok(output)
 }
 
+/// Scatters slices of `updates` into a copy of `input` at the positions in `indices`, as in ONNX
+/// ScatterND. The last dim of `indices` (of size k) holds the coordinates of a slice of `input`
+/// over its first k dims, so `updates` has shape `indices.dims()[..-1] ++ input.dims()[k..]`.
+/// # Arguments
+/// * `input` - Tensor
+/// * `indices` - Tensor of coordinates to scatter to
+/// * `updates` - Tensor of values to scatter
+/// # Examples
+/// ```
+/// use ezkl::tensor::Tensor;
+/// use ezkl::tensor::ops::scatter_nd;
+/// let x = Tensor::<i128>::new(Some(&[1, 2, 3, 4, 5, 6, 7, 8]), &[8]).unwrap();
+/// let indices = Tensor::<usize>::new(Some(&[4, 3, 1, 7]), &[4, 1]).unwrap();
+/// let updates = Tensor::<i128>::new(Some(&[9, 10, 11, 12]), &[4]).unwrap();
+/// let result = scatter_nd(&x, &indices, &updates).unwrap();
+/// let expected = Tensor::<i128>::new(Some(&[1, 11, 3, 10, 9, 6, 7, 12]), &[8]).unwrap();
+/// assert_eq!(result, expected);
+///
+/// // scatter whole rows
+/// let x = Tensor::<i128>::new(Some(&[1, 2, 3, 4, 5, 6]), &[3, 2]).unwrap();
+/// let indices = Tensor::<usize>::new(Some(&[2, 0]), &[2, 1]).unwrap();
+/// let updates = Tensor::<i128>::new(Some(&[7, 8, 9, 10]), &[2, 2]).unwrap();
+/// let result = scatter_nd(&x, &indices, &updates).unwrap();
+/// let expected = Tensor::<i128>::new(Some(&[9, 10, 3, 4, 7, 8]), &[3, 2]).unwrap();
+/// assert_eq!(result, expected);
+/// ```
+pub fn scatter_nd<T: TensorType + Send + Sync>(
+    input: &Tensor<T>,
+    indices: &Tensor<usize>,
+    updates: &Tensor<T>,
+) -> Result<Tensor<T>, TensorError> {
+    let (k, batch_dims) = match indices.dims().split_last() {
+        Some((k, batch_dims)) if *k > 0 && *k <= input.dims().len() => (*k, batch_dims),
+        _ => return Err(TensorError::DimMismatch("scatter_nd".to_string())),
+    };
+    let slice_dims = &input.dims()[k..];
+    if updates.dims() != [batch_dims, slice_dims].concat() {
+        return Err(TensorError::DimMismatch("scatter_nd".to_string()));
+    }
+    let slice_len = slice_dims.iter().product::<usize>();
+
+    let mut output = input.clone();
+    if slice_len == 0 {
+        return Ok(output);
+    }
+
+    for (i, coord) in indices.chunks(k).enumerate() {
+        if coord.iter().zip(input.dims()).any(|(c, d)| c >= d) {
+            return Err(TensorError::DimMismatch("scatter_nd".to_string()));
+        }
+        // the slice at coord is contiguous in the flattened input
+        let padded = [coord.to_vec(), vec![0; input.dims().len() - k]].concat();
+        let start = output.get_index(&padded);
+        for j in 0..slice_len {
+            output[start + j] = updates[i * slice_len + j].clone();
+        }
+    }
+
+    Ok(output)
+}
+
 /// Gathers a tensor along a dimension.
 /// # Arguments
 /// * `input` - Tensor
@@ -2507,6 +2698,59 @@ pub fn pad<T: TensorType>(
     Ok(output)
 }
 
+/// Pads each dim of a tensor with `value`, `pads[i]` being the number of elements added before
+/// and after dim `i`.
+/// # Arguments
+///
+/// * `input` - Tensor.
+/// * `pads` - (before, after) padding of each dim.
+/// * `value` - the value to pad with.
+/// # Examples
+/// ```
+/// use ezkl::tensor::Tensor;
+/// use ezkl::tensor::ops::pad_nd;
+///
+/// let x = Tensor::<i128>::new(Some(&[1, 2, 3, 4]), &[2, 2]).unwrap();
+/// let result = pad_nd::<i128>(&x, &[(1, 0), (0, 2)], 7).unwrap();
+/// let expected = Tensor::<i128>::new(
+///     Some(&[7, 7, 7, 7, 1, 2, 7, 7, 3, 4, 7, 7]),
+///     &[3, 4],
+/// ).unwrap();
+/// assert_eq!(result, expected);
+/// ```
+pub fn pad_nd<T: TensorType>(
+    input: &Tensor<T>,
+    pads: &[(usize, usize)],
+    value: T,
+) -> Result<Tensor<T>, TensorError> {
+    if pads.len() != input.dims().len() {
+        return Err(TensorError::DimMismatch("pad".to_string()));
+    }
+    let padded_dims = input
+        .dims()
+        .iter()
+        .zip(pads)
+        .map(|(d, (before, after))| d + before + after)
+        .collect::<Vec<_>>();
+
+    let mut output = Tensor::<T>::new(
+        Some(&vec![value; padded_dims.iter().product()]),
+        &padded_dims,
+    )?;
+
+    for (i, v) in input.iter().enumerate() {
+        let coord = input
+            .get_coord(i)
+            .iter()
+            .zip(pads)
+            .map(|(c, (before, _))| c + before)
+            .collect::<Vec<_>>();
+        output.set(&coord, v.clone());
+    }
+
+    Ok(output)
+}
+
 /// Packs a multi-dim tensor into a single elem tensor
 /// # Arguments
 ///
@@ -2693,6 +2937,73 @@ pub fn slice<T: TensorType + Send + Sync>(
     t.get_slice(&slice)
 }
 
+/// Selects every `step`-th element from `start` (inclusive) to `end` (exclusive) along each axis,
+/// `ranges` holds a `(start, end, step)` triple per axis. A negative step walks the range backwards from
+/// `end - 1`, as numpy does for `x[end - 1:start - 1:step]`.
+///
+/// # Examples
+/// ```
+/// use ezkl::tensor::Tensor;
+/// use ezkl::tensor::ops::strided_slice;
+/// let x = Tensor::<i128>::new(Some(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]), &[2, 6]).unwrap();
+/// let result = strided_slice(&x, &[(0, 2, 1), (0, 6, 2)]).unwrap();
+/// let expected = Tensor::<i128>::new(Some(&[1, 3, 5, 7, 9, 11]), &[2, 3]).unwrap();
+/// assert_eq!(result, expected);
+///
+/// // crop and take every other element
+/// let result = strided_slice(&x, &[(1, 2, 1), (1, 6, 2)]).unwrap();
+/// let expected = Tensor::<i128>::new(Some(&[8, 10, 12]), &[1, 3]).unwrap();
+/// assert_eq!(result, expected);
+///
+/// // split the last axis in half (e.g for a GLU)
+/// let result = strided_slice(&x, &[(0, 2, 1), (3, 6, 1)]).unwrap();
+/// let expected = Tensor::<i128>::new(Some(&[4, 5, 6, 10, 11, 12]), &[2, 3]).unwrap();
+/// assert_eq!(result, expected);
+///
+/// // walk the last axis backwards
+/// let result = strided_slice(&x, &[(0, 2, 1), (0, 5, -2)]).unwrap();
+/// let expected = Tensor::<i128>::new(Some(&[5, 3, 1, 11, 9, 7]), &[2, 3]).unwrap();
+/// assert_eq!(result, expected);
+/// ```
+pub fn strided_slice<T: TensorType + Send + Sync>(
+    t: &Tensor<T>,
+    ranges: &[(usize, usize, isize)],
+) -> Result<Tensor<T>, TensorError> {
+    if ranges.len() != t.dims().len() {
+        return Err(TensorError::DimMismatch("strided_slice".to_string()));
+    }
+    for ((start, end, step), d) in ranges.iter().zip(t.dims()) {
+        if *step == 0 || start > end || end > d {
+            return Err(TensorError::DimMismatch("strided_slice".to_string()));
+        }
+    }
+
+    let indices = ranges
+        .iter()
+        .map(|(start, end, step)| {
+            let range = *start..*end;
+            if *step < 0 {
+                range.rev().step_by(step.unsigned_abs()).collect::<Vec<_>>()
+            } else {
+                range.step_by(step.unsigned_abs()).collect::<Vec<_>>()
+            }
+        })
+        .collect::<Vec<_>>();
+    let new_dims = indices.iter().map(|i| i.len()).collect::<Vec<_>>();
+
+    let mut output = Tensor::new(None, &new_dims)?;
+    for (flat, coord) in indices
+        .iter()
+        .map(|i| i.iter().cloned())
+        .multi_cartesian_product()
+        .enumerate()
+    {
+        output[flat] = t.get(&coord);
+    }
+
+    Ok(output)
+}
+
 // ---------------------------------------------------------------------------------------------------------
 // -- nonlinear Functions ---------------------------------------------------------------------------------
 // ---------------------------------------------------------------------------------------------------------
@@ -3129,6 +3440,36 @@ pub mod nonlinearities {
         add(&[upper_bound, lower_bound]).unwrap()
     }
 
+    /// Applies range_check_abs, ie. counts the bounds of `|a - b| <= tol` that are violated
+    /// # Arguments
+    ///
+    /// * `a` - Tensor
+    /// * `b` - Tensor
+    /// * `tol` - Single value, in fixed point
+    /// # Examples
+    /// ```
+    /// use ezkl::tensor::Tensor;
+    /// use ezkl::tensor::ops::nonlinearities::range_check_abs;
+    /// let x = Tensor::<i128>::new(
+    ///     Some(&[100, 200, 300, 400]),
+    ///     &[2, 2],
+    /// ).unwrap();
+    /// let y = Tensor::<i128>::new(
+    ///    Some(&[102, 197, 303, 400]),
+    ///   &[2, 2],
+    /// ).unwrap();
+    /// let result = range_check_abs(&[x, y], 2);
+    /// let expected = Tensor::<i128>::new(Some(&[0, 1, 1, 0]), &[2, 2]).unwrap();
+    /// assert_eq!(result, expected);
+    /// ```
+    pub fn range_check_abs(t: &[Tensor<i128>], tol: i128) -> Tensor<i128> {
+        let diff: Tensor<i128> = sub(t).unwrap();
+        let upper_bound = greater_than(&diff, tol as f64);
+        let lower_bound = less_than(&diff, -tol as f64);
+
+        add(&[upper_bound, lower_bound]).unwrap()
+    }
+
     /// Elementwise applies square root to a tensor of integers.
     /// # Arguments
     ///
@@ -3513,6 +3854,63 @@ pub mod nonlinearities {
         .unwrap()
     }
 
+    const NCOEF: usize = 28;
+    const COF: [f64; 28] = [
+        -1.3026537197817094,
+        6.419_697_923_564_902e-1,
+        1.9476473204185836e-2,
+        -9.561_514_786_808_63e-3,
+        -9.46595344482036e-4,
+        3.66839497852761e-4,
+        4.2523324806907e-5,
+        -2.0278578112534e-5,
+        -1.624290004647e-6,
+        1.303655835580e-6,
+        1.5626441722e-8,
+        -8.5238095915e-8,
+        6.529054439e-9,
+        5.059343495e-9,
+        -9.91364156e-10,
+        -2.27365122e-10,
+        9.6467911e-11,
+        2.394038e-12,
+        -6.886027e-12,
+        8.94487e-13,
+        3.13092e-13,
+        -1.12708e-13,
+        3.81e-16,
+        7.106e-15,
+        -1.523e-15,
+        -9.4e-17,
+        1.21e-16,
+        -2.8e-17,
+    ];
+
+    /// Chebyshev coefficients
+    fn erfccheb(z: f64) -> f64 {
+        let mut d = 0f64;
+        let mut dd = 0f64;
+
+        assert!(z >= 0f64, "erfccheb requires nonnegative argument");
+        let t = 2f64 / (2f64 + z);
+        let ty = 4f64 * t - 2f64;
+        for j in (1..NCOEF - 1).rev() {
+            let tmp = d;
+            d = ty * d - dd + COF[j];
+            dd = tmp;
+        }
+        t * (-z.powi(2) + 0.5 * (COF[0] + ty * d) - dd).exp()
+    }
+
+    /// Error function of a float
+    fn erf(x: f64) -> f64 {
+        if x >= 0f64 {
+            1.0 - erfccheb(x)
+        } else {
+            erfccheb(-x) - 1f64
+        }
+    }
+
     /// Applies error function (erf) on a tensor of integers.
     /// # Arguments
     ///
@@ -3532,65 +3930,63 @@ pub mod nonlinearities {
     /// assert_eq!(result, expected);
     /// ```
     pub fn erffunc(a: &Tensor<i128>, scale_input: f64) -> Tensor<i128> {
-        const NCOEF: usize = 28;
-        const COF: [f64; 28] = [
-            -1.3026537197817094,
-            6.419_697_923_564_902e-1,
-            1.9476473204185836e-2,
-            -9.561_514_786_808_63e-3,
-            -9.46595344482036e-4,
-            3.66839497852761e-4,
-            4.2523324806907e-5,
-            -2.0278578112534e-5,
-            -1.624290004647e-6,
-            1.303655835580e-6,
-            1.5626441722e-8,
-            -8.5238095915e-8,
-            6.529054439e-9,
-            5.059343495e-9,
-            -9.91364156e-10,
-            -2.27365122e-10,
-            9.6467911e-11,
-            2.394038e-12,
-            -6.886027e-12,
-            8.94487e-13,
-            3.13092e-13,
-            -1.12708e-13,
-            3.81e-16,
-            7.106e-15,
-            -1.523e-15,
-            -9.4e-17,
-            1.21e-16,
-            -2.8e-17,
-        ];
-
-        /// Chebyshev coefficients
-        fn erfccheb(z: f64) -> f64 {
-            let mut d = 0f64;
-            let mut dd = 0f64;
-
-            assert!(z >= 0f64, "erfccheb requires nonnegative argument");
-            let t = 2f64 / (2f64 + z);
-            let ty = 4f64 * t - 2f64;
-            for j in (1..NCOEF - 1).rev() {
-                let tmp = d;
-                d = ty * d - dd + COF[j];
-                dd = tmp;
-            }
-            t * (-z.powi(2) + 0.5 * (COF[0] + ty * d) - dd).exp()
-        }
+        a.par_enum_map(|_, a_i| {
+            let kix = (a_i as f64) / scale_input;
+            let fout = scale_input * erf(kix);
+            let rounded = fout.round();
+            Ok::<_, TensorError>(rounded as i128)
+        })
+        .unwrap()
+    }
 
-        pub fn erf(x: f64) -> f64 {
-            if x >= 0f64 {
-                1.0 - erfccheb(x)
-            } else {
-                erfccheb(-x) - 1f64
-            }
-        }
+    /// Elementwise applies the gaussian error linear unit, `x * (1 + erf(x / sqrt(2))) / 2`, to a tensor of integers.
+    /// # Arguments
+    ///
+    /// * `a` - Tensor
+    /// * `scale_input` - Single value
+    /// # Examples
+    /// ```
+    /// use ezkl::tensor::Tensor;
+    /// use ezkl::tensor::ops::nonlinearities::gelu;
+    /// let x = Tensor::<i128>::new(
+    ///     Some(&[-256, -128, 0, 64, 128, 512]),
+    ///     &[2, 3],
+    /// ).unwrap();
+    /// let result = gelu(&x, 128.0);
+    /// let expected = Tensor::<i128>::new(Some(&[-6, -20, 0, 44, 108, 512]), &[2, 3]).unwrap();
+    /// assert_eq!(result, expected);
+    /// ```
+    pub fn gelu(a: &Tensor<i128>, scale_input: f64) -> Tensor<i128> {
+        a.par_enum_map(|_, a_i| {
+            let kix = (a_i as f64) / scale_input;
+            let fout = scale_input * kix * 0.5 * (1.0 + erf(kix / std::f64::consts::SQRT_2));
+            let rounded = fout.round();
+            Ok::<_, TensorError>(rounded as i128)
+        })
+        .unwrap()
+    }
 
+    /// Elementwise applies the sigmoid linear unit (swish), `x * sigmoid(x)`, to a tensor of integers.
+    /// # Arguments
+    ///
+    /// * `a` - Tensor
+    /// * `scale_input` - Single value
+    /// # Examples
+    /// ```
+    /// use ezkl::tensor::Tensor;
+    /// use ezkl::tensor::ops::nonlinearities::silu;
+    /// let x = Tensor::<i128>::new(
+    ///     Some(&[-256, -128, 0, 64, 128, 512]),
+    ///     &[2, 3],
+    /// ).unwrap();
+    /// let result = silu(&x, 128.0);
+    /// let expected = Tensor::<i128>::new(Some(&[-31, -34, 0, 40, 94, 503]), &[2, 3]).unwrap();
+    /// assert_eq!(result, expected);
+    /// ```
+    pub fn silu(a: &Tensor<i128>, scale_input: f64) -> Tensor<i128> {
         a.par_enum_map(|_, a_i| {
             let kix = (a_i as f64) / scale_input;
-            let fout = scale_input * erf(kix);
+            let fout = scale_input * kix / (1.0 + (-kix).exp());
             let rounded = fout.round();
             Ok::<_, TensorError>(rounded as i128)
         })
@@ -3629,6 +4025,114 @@ pub mod nonlinearities {
         .unwrap()
     }
 
+    /// Elementwise applies hard sigmoid, ie. `clip(alpha * x + beta, 0, 1)`, to a tensor of integers.
+    /// The output is at the square of the input scale, so that neither `alpha` nor `beta` need a division.
+    /// # Arguments
+    ///
+    /// * `a` - Tensor
+    /// * `scale` - Single value
+    /// * `alpha` - Single value
+    /// * `beta` - Single value
+    /// # Examples
+    /// ```
+    /// use ezkl::tensor::Tensor;
+    /// use ezkl::tensor::ops::nonlinearities::hard_sigmoid;
+    /// let x = Tensor::<i128>::new(
+    ///     Some(&[-16, -4, 0, 4, 16]),
+    ///     &[5],
+    /// ).unwrap();
+    /// let result = hard_sigmoid(&x, 4.0, 0.2, 0.5);
+    /// let expected = Tensor::<i128>::new(Some(&[0, 4, 8, 12, 16]), &[5]).unwrap();
+    /// assert_eq!(result, expected);
+    /// ```
+    pub fn hard_sigmoid(a: &Tensor<i128>, scale: f64, alpha: f64, beta: f64) -> Tensor<i128> {
+        let slope = (alpha * scale).round() as i128;
+        let offset = (beta * scale * scale).round() as i128;
+        let one = (scale * scale).round() as i128;
+        a.par_enum_map(|_, a_i| Ok::<_, TensorError>((slope * a_i + offset).max(0).min(one)))
+            .unwrap()
+    }
+
+    /// Elementwise applies hard swish, ie. `x * hard_sigmoid(x)` with `alpha = 1 / 6` and `beta = 0.5`,
+    /// to a tensor of integers. The output is at the cube of the input scale.
+    /// # Arguments
+    ///
+    /// * `a` - Tensor
+    /// * `scale` - Single value
+    /// # Examples
+    /// ```
+    /// use ezkl::tensor::Tensor;
+    /// use ezkl::tensor::ops::nonlinearities::hard_swish;
+    /// let x = Tensor::<i128>::new(
+    ///     Some(&[-16, -4, 0, 4, 16]),
+    ///     &[5],
+    /// ).unwrap();
+    /// let result = hard_swish(&x, 4.0);
+    /// let expected = Tensor::<i128>::new(Some(&[0, -16, 0, 48, 256]), &[5]).unwrap();
+    /// assert_eq!(result, expected);
+    /// ```
+    pub fn hard_swish(a: &Tensor<i128>, scale: f64) -> Tensor<i128> {
+        // matches the f32 alpha used by the circuit layout
+        let gate = hard_sigmoid(a, scale, (1.0_f32 / 6.0) as f64, 0.5);
+        (a.clone() * gate).unwrap()
+    }
+
+    /// Elementwise applies a piecewise linear function to a tensor of integers. Segment `i` covers the
+    /// inputs in `(breakpoints[i - 1], breakpoints[i]]` and maps them to `slopes[i] * x + intercepts[i]`.
+    /// The output is at the square of the input scale, so that neither slopes nor intercepts need a division.
+    /// # Arguments
+    ///
+    /// * `a` - Tensor
+    /// * `scale` - Single value
+    /// * `breakpoints` - Ascending values, one fewer than the number of segments
+    /// * `slopes` - One value per segment
+    /// * `intercepts` - One value per segment
+    /// # Examples
+    /// ```
+    /// use ezkl::tensor::Tensor;
+    /// use ezkl::tensor::ops::nonlinearities::piecewise_linear;
+    /// let x = Tensor::<i128>::new(
+    ///     Some(&[-8, -4, 0, 4, 8]),
+    ///     &[5],
+    /// ).unwrap();
+    /// // hardtanh
+    /// let result = piecewise_linear(&x, 4.0, &[-1.0, 1.0], &[0.0, 1.0, 0.0], &[-1.0, 0.0, 1.0]).unwrap();
+    /// let expected = Tensor::<i128>::new(Some(&[-16, -16, 0, 16, 16]), &[5]).unwrap();
+    /// assert_eq!(result, expected);
+    /// ```
+    pub fn piecewise_linear(
+        a: &Tensor<i128>,
+        scale: f64,
+        breakpoints: &[f64],
+        slopes: &[f64],
+        intercepts: &[f64],
+    ) -> Result<Tensor<i128>, TensorError> {
+        if slopes.len() != breakpoints.len() + 1 || intercepts.len() != slopes.len() {
+            return Err(TensorError::DimMismatch("piecewise_linear".to_string()));
+        }
+        let breakpoints = breakpoints
+            .iter()
+            .map(|b| (b * scale).round() as i128)
+            .collect::<Vec<_>>();
+        if breakpoints.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(TensorError::DimMismatch(
+                "piecewise_linear breakpoints must be strictly ascending".to_string(),
+            ));
+        }
+        let slopes = slopes
+            .iter()
+            .map(|m| (m * scale).round() as i128)
+            .collect::<Vec<_>>();
+        let intercepts = intercepts
+            .iter()
+            .map(|c| (c * scale * scale).round() as i128)
+            .collect::<Vec<_>>();
+        a.par_enum_map(|_, a_i| {
+            let segment = breakpoints.iter().filter(|b| a_i > **b).count();
+            Ok::<_, TensorError>(slopes[segment] * a_i + intercepts[segment])
+        })
+    }
+
     /// Elementwise applies max to a tensor of integers.
     /// # Arguments
     /// * `a` - Tensor
@@ -3699,6 +4203,47 @@ pub mod nonlinearities {
         .unwrap()
     }
 
+    /// Elementwise clamps a tensor of integers to the range [`lower`, `upper`].
+    /// # Arguments
+    /// * `a` - Tensor
+    /// * `in_scale` - multiplier of the input
+    /// * `out_scale` - multiplier of the output
+    /// * `lower` - lower bound (unscaled)
+    /// * `upper` - upper bound (unscaled)
+    /// # Examples
+    /// ```
+    /// use ezkl::tensor::Tensor;
+    /// use ezkl::tensor::ops::nonlinearities::clip;
+    /// let x = Tensor::<i128>::new(
+    ///    Some(&[2, 15, 2, 1, 1, -5]),
+    ///   &[2, 3],
+    /// ).unwrap();
+    /// let result = clip(&x, 1, 1, 0.0, 6.0);
+    /// let expected = Tensor::<i128>::new(Some(&[2, 6, 2, 1, 1, 0]), &[2, 3]).unwrap();
+    /// assert_eq!(result, expected);
+    ///
+    /// // the bounds are in the unscaled domain
+    /// let result = clip(&x, 2, 2, 0.0, 6.0);
+    /// let expected = Tensor::<i128>::new(Some(&[2, 12, 2, 1, 1, 0]), &[2, 3]).unwrap();
+    /// assert_eq!(result, expected);
+    /// ```
+    pub fn clip(
+        a: &Tensor<i128>,
+        in_scale: usize,
+        out_scale: usize,
+        lower: f64,
+        upper: f64,
+    ) -> Tensor<i128> {
+        // calculate value of output
+        a.par_enum_map(|_, a_i| {
+            let d_inv_x = (a_i as f64) / (in_scale as f64);
+            let clamped = d_inv_x.max(lower).min(upper);
+            let rounded = (clamped * (out_scale as f64)).round() as i128;
+            Ok::<_, TensorError>(rounded)
+        })
+        .unwrap()
+    }
+
     /// Elementwise divides a tensor with a const integer element.
     /// # Arguments
     ///
@@ -3725,6 +4270,40 @@ pub mod nonlinearities {
         .unwrap()
     }
 
+    /// Multiplies each slice of a tensor along `axis` by its own const element, eg. to bring the channels
+    /// of a tensor whose weights were quantized at per channel scales to a common scale.
+    /// # Arguments
+    ///
+    /// * `a` - Tensor
+    /// * `axis` - The axis the slices are taken along
+    /// * `multipliers` - One value per slice
+    /// # Examples
+    /// ```
+    /// use ezkl::tensor::Tensor;
+    /// use ezkl::tensor::ops::nonlinearities::channel_mult;
+    /// let x = Tensor::<i128>::new(
+    ///     Some(&[4, 6, 8, 10, 12, 14]),
+    ///     &[2, 3],
+    /// ).unwrap();
+    /// let result = channel_mult(&x, 0, &[2, 1]).unwrap();
+    /// let expected = Tensor::<i128>::new(Some(&[8, 12, 16, 10, 12, 14]), &[2, 3]).unwrap();
+    /// assert_eq!(result, expected);
+    /// ```
+    pub fn channel_mult(
+        a: &Tensor<i128>,
+        axis: usize,
+        multipliers: &[u64],
+    ) -> Result<Tensor<i128>, TensorError> {
+        let dims = a.dims();
+        if axis >= dims.len() || dims[axis] != multipliers.len() {
+            return Err(TensorError::DimMismatch("channel_mult".to_string()));
+        }
+        let stride = dims[axis + 1..].iter().product::<usize>();
+        a.par_enum_map(|i, a_i| {
+            Ok::<_, TensorError>(a_i * multipliers[(i / stride) % multipliers.len()] as i128)
+        })
+    }
+
     /// Elementwise inverse.
     /// # Arguments
     ///
@@ -560,12 +560,55 @@ impl<F: PrimeField + TensorType + PartialOrd> ValTensor<F> {
         Ok(())
     }
 
+    /// Permutes the axes of the [ValTensor].
+    pub fn permute(&mut self, perm: &[usize]) -> Result<(), Box<dyn Error>> {
+        match self {
+            ValTensor::Value {
+                inner: v, dims: d, ..
+            } => {
+                *v = v.permute(perm)?;
+                *d = v.dims().to_vec();
+            }
+            ValTensor::Instance { .. } => {
+                return Err(Box::new(TensorError::WrongMethod));
+            }
+        };
+        Ok(())
+    }
+
+    /// Reverses the order of the [ValTensor]'s elements along an axis.
+    pub fn flip(&mut self, axis: usize) -> Result<(), Box<dyn Error>> {
+        match self {
+            ValTensor::Value {
+                inner: v, dims: d, ..
+            } => {
+                *v = v.flip(axis)?;
+                *d = v.dims().to_vec();
+            }
+            ValTensor::Instance { .. } => {
+                return Err(Box::new(TensorError::WrongMethod));
+            }
+        };
+        Ok(())
+    }
+
     /// Sets the [ValTensor]'s shape.
     pub fn reshape(&mut self, new_dims: &[usize]) -> Result<(), Box<dyn Error>> {
         match self {
             ValTensor::Value {
                 inner: v, dims: d, ..
             } => {
+                // reshaping is a pure reindexing of the cells, so the element count must be preserved
+                let matches = if new_dims.is_empty() {
+                    v.len() <= 1
+                } else if new_dims == [0] {
+                    v.is_empty()
+                } else {
+                    v.len() == new_dims.iter().product::<usize>()
+                };
+                if !matches {
+                    return Err(Box::new(TensorError::DimError));
+                }
                 v.reshape(new_dims);
                 *d = v.dims().to_vec();
             }
@@ -600,6 +643,26 @@ impl<F: PrimeField + TensorType + PartialOrd> ValTensor<F> {
         Ok(())
     }
 
+    /// Selects every `step`-th element from `start` to `end` along each axis of the [ValTensor], backwards
+    /// for a negative step.
+    pub fn strided_slice(
+        &mut self,
+        ranges: &[(usize, usize, isize)],
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            ValTensor::Value {
+                inner: v, dims: d, ..
+            } => {
+                *v = crate::tensor::ops::strided_slice(v, ranges)?;
+                *d = v.dims().to_vec();
+            }
+            ValTensor::Instance { .. } => {
+                return Err(Box::new(TensorError::WrongMethod));
+            }
+        };
+        Ok(())
+    }
+
     /// Calls `flatten` on the inner [Tensor].
     pub fn flatten(&mut self) {
         match self {
@@ -41,7 +41,10 @@ pub fn init_panic_hook() {
 }
 
 use crate::graph::{GraphCircuit, GraphSettings};
-use crate::pfsys::{create_proof_circuit_kzg, verify_proof_circuit_kzg};
+use crate::pfsys::{
+    create_proof_circuit_kzg, pk_from_bytes, pk_to_bytes, verify_proof_circuit_kzg, vk_from_bytes,
+    vk_to_bytes,
+};
 
 /// Wrapper around the halo2 encode call data method
 #[wasm_bindgen]
@@ -299,8 +302,7 @@ pub fn genVk(
         .map_err(Box::<dyn std::error::Error>::from)
         .map_err(|e| JsError::new(&format!("Failed to create verifying key: {}", e)))?;
 
-    let mut serialized_vk = Vec::new();
-    vk.write(&mut serialized_vk, halo2_proofs::SerdeFormat::RawBytes)
+    let serialized_vk = vk_to_bytes::<KZGCommitmentScheme<Bn256>>(&vk)
         .map_err(|e| JsError::new(&format!("Failed to serialize vk: {}", e)))?;
 
     Ok(serialized_vk)
@@ -324,10 +326,8 @@ pub fn genPk(
         .map_err(|e| JsError::new(&format!("Failed to deserialize compiled model: {}", e)))?;
 
     // Read in verifying key
-    let mut reader = std::io::BufReader::new(&vk[..]);
-    let vk = VerifyingKey::<G1Affine>::read::<_, GraphCircuit>(
-        &mut reader,
-        halo2_proofs::SerdeFormat::RawBytes,
+    let vk = vk_from_bytes::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(
+        &vk[..],
         circuit.settings().clone(),
     )
     .map_err(|e| JsError::new(&format!("Failed to deserialize verifying key: {}", e)))?;
@@ -336,8 +336,7 @@ pub fn genPk(
         .map_err(Box::<dyn std::error::Error>::from)
         .map_err(|e| JsError::new(&format!("Failed to create proving key: {}", e)))?;
 
-    let mut serialized_pk = Vec::new();
-    pk.write(&mut serialized_pk, halo2_proofs::SerdeFormat::RawBytes)
+    let serialized_pk = pk_to_bytes::<KZGCommitmentScheme<Bn256>>(&pk)
         .map_err(|e| JsError::new(&format!("Failed to serialize pk: {}", e)))?;
 
     Ok(serialized_pk)
@@ -362,13 +361,13 @@ pub fn verify(
     let snark: crate::pfsys::Snark<Fr, G1Affine> = serde_json::from_slice(&proof_js[..])
         .map_err(|e| JsError::new(&format!("Failed to deserialize proof: {}", e)))?;
 
-    let mut reader = std::io::BufReader::new(&vk[..]);
-    let vk = VerifyingKey::<G1Affine>::read::<_, GraphCircuit>(
-        &mut reader,
-        halo2_proofs::SerdeFormat::RawBytes,
-        circuit_settings,
-    )
-    .map_err(|e| JsError::new(&format!("Failed to deserialize vk: {}", e)))?;
+    let vk =
+        vk_from_bytes::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(&vk[..], circuit_settings)
+            .map_err(|e| JsError::new(&format!("Failed to deserialize vk: {}", e)))?;
+
+    snark
+        .check_vk(&vk)
+        .map_err(|e| JsError::new(&format!("{}", e)))?;
 
     let strategy = KZGSingleStrategy::new(params.verifier_params());
 
@@ -407,10 +406,8 @@ pub fn prove(
         .map_err(|e| JsError::new(&format!("Failed to deserialize witness: {}", e)))?;
 
     // read in proving key
-    let mut reader = std::io::BufReader::new(&pk[..]);
-    let pk = ProvingKey::<G1Affine>::read::<_, GraphCircuit>(
-        &mut reader,
-        halo2_proofs::SerdeFormat::RawBytes,
+    let pk = pk_from_bytes::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(
+        &pk[..],
         circuit.settings().clone(),
     )
     .map_err(|e| JsError::new(&format!("Failed to deserialize proving key: {}", e)))?;
@@ -491,13 +488,9 @@ pub fn vkValidation(
 ) -> Result<bool, JsError> {
     let circuit_settings: GraphSettings = serde_json::from_slice(&settings[..])
         .map_err(|e| JsError::new(&format!("Failed to deserialize settings: {}", e)))?;
-    let mut reader = std::io::BufReader::new(&vk[..]);
-    let _ = VerifyingKey::<G1Affine>::read::<_, GraphCircuit>(
-        &mut reader,
-        halo2_proofs::SerdeFormat::RawBytes,
-        circuit_settings,
-    )
-    .map_err(|e| JsError::new(&format!("Failed to deserialize vk: {}", e)))?;
+    let _ =
+        vk_from_bytes::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(&vk[..], circuit_settings)
+            .map_err(|e| JsError::new(&format!("Failed to deserialize vk: {}", e)))?;
 
     Ok(true)
 }
@@ -510,13 +503,9 @@ pub fn pkValidation(
 ) -> Result<bool, JsError> {
     let circuit_settings: GraphSettings = serde_json::from_slice(&settings[..])
         .map_err(|e| JsError::new(&format!("Failed to deserialize settings: {}", e)))?;
-    let mut reader = std::io::BufReader::new(&pk[..]);
-    let _ = ProvingKey::<G1Affine>::read::<_, GraphCircuit>(
-        &mut reader,
-        halo2_proofs::SerdeFormat::RawBytes,
-        circuit_settings,
-    )
-    .map_err(|e| JsError::new(&format!("Failed to deserialize proving key: {}", e)))?;
+    let _ =
+        pk_from_bytes::<KZGCommitmentScheme<Bn256>, Fr, GraphCircuit>(&pk[..], circuit_settings)
+            .map_err(|e| JsError::new(&format!("Failed to deserialize proving key: {}", e)))?;
 
     Ok(true)
 }
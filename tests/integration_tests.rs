@@ -47,6 +47,17 @@ mod native_tests {
         child
     }
 
+    // runs the examples/onchain_oracle.sh walkthrough, on a port the other evm tests don't use
+    fn run_onchain_oracle_script(example: &str) {
+        let status = Command::new("bash")
+            .args(["examples/onchain_oracle.sh", example])
+            .env("EZKL", format!("{}/release/ezkl", *CARGO_TARGET_DIR))
+            .env("ANVIL_PORT", "3040")
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success());
+    }
+
     fn init_binary() {
         COMPILE.call_once(|| {
             println!("using cargo target dir: {}", *CARGO_TARGET_DIR);
@@ -1142,6 +1153,12 @@ mod native_tests {
 
                 }
             });
+
+            #[test]
+            fn onchain_oracle_script_() {
+                crate::native_tests::init_binary();
+                crate::native_tests::run_onchain_oracle_script("1l_mlp");
+            }
     }
     };
 }
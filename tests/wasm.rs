@@ -349,24 +349,49 @@ mod wasm32 {
         // );
         // assert!(vk.is_err());
 
-        // Run vk validation on vk (should pass)
+        // Run vk validation on a vk written without a header (should pass)
         let vk = vkValidation(
             wasm_bindgen::Clamped(VK.to_vec()),
             wasm_bindgen::Clamped(SETTINGS.to_vec()),
         );
         assert!(vk.is_ok());
+        // Run vk validation on a headered vk (should pass)
+        let vk = genVk(
+            wasm_bindgen::Clamped(NETWORK_COMPILED.to_vec()),
+            wasm_bindgen::Clamped(SRS.to_vec()),
+        )
+        .map_err(|_| "failed")
+        .unwrap();
+        let vk_valid = vkValidation(
+            wasm_bindgen::Clamped(vk.clone()),
+            wasm_bindgen::Clamped(SETTINGS.to_vec()),
+        );
+        assert!(vk_valid.is_ok());
         // // Run pk validation on vk (should fail)
         // let pk = pkValidation(
         //     wasm_bindgen::Clamped(VK.to_vec()),
         //     wasm_bindgen::Clamped(SETTINGS.to_vec())
         // );
         // assert!(pk.is_err());
-        // Run pk validation on pk (should pass)
+        // Run pk validation on a pk written without a header (should pass)
         let pk = pkValidation(
             wasm_bindgen::Clamped(PK.to_vec()),
             wasm_bindgen::Clamped(SETTINGS.to_vec()),
         );
         assert!(pk.is_ok());
+        // Run pk validation on a headered pk (should pass)
+        let pk = genPk(
+            wasm_bindgen::Clamped(vk),
+            wasm_bindgen::Clamped(NETWORK_COMPILED.to_vec()),
+            wasm_bindgen::Clamped(SRS.to_vec()),
+        )
+        .map_err(|_| "failed")
+        .unwrap();
+        let pk = pkValidation(
+            wasm_bindgen::Clamped(pk),
+            wasm_bindgen::Clamped(SETTINGS.to_vec()),
+        );
+        assert!(pk.is_ok());
         // Run settings validation on proof (should fail)
         let settings = settingsValidation(wasm_bindgen::Clamped(PROOF.to_vec()));
         assert!(settings.is_err());